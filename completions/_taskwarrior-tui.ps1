@@ -29,10 +29,67 @@ Register-ArgumentCompleter -Native -CommandName 'taskwarrior-tui' -ScriptBlock {
             [CompletionResult]::new('--taskrc', 'taskrc', [CompletionResultType]::ParameterName, 'Sets the .taskrc file using the TASKRC environment variable for taskwarrior')
             [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'Sets default report')
             [CompletionResult]::new('--report', 'report', [CompletionResultType]::ParameterName, 'Sets default report')
+            [CompletionResult]::new('--uuid', 'uuid', [CompletionResultType]::ParameterName, 'Selects a task on start, given its uuid or a link to it such as `task://<uuid>`')
+            [CompletionResult]::new('--context', 'context', [CompletionResultType]::ParameterName, 'Uses a taskwarrior context while running, `none` for no context, without changing the taskrc')
+            [CompletionResult]::new('--record', 'record', [CompletionResultType]::ParameterName, 'Records keys, resizes and taskwarrior commands of the session to a file')
+            [CompletionResult]::new('--replay', 'replay', [CompletionResultType]::ParameterName, 'Replays a session recorded with --record, use with --taskdata to replay against a copy of the tasks')
+            [CompletionResult]::new('--trace-perf', 'trace-perf', [CompletionResultType]::ParameterName, 'Records draw, export and event timings, viewable from the command palette and written to the data folder on exit')
+            [CompletionResult]::new('--demo', 'demo', [CompletionResultType]::ParameterName, 'Runs against a temporary copy of bundled example tasks, leaving your own tasks and settings untouched')
             [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('-V', 'V ', [CompletionResultType]::ParameterName, 'Print version')
             [CompletionResult]::new('--version', 'version', [CompletionResultType]::ParameterName, 'Print version')
+            [CompletionResult]::new('completions', 'completions', [CompletionResultType]::ParameterValue, 'Prints shell completions, including the reports and contexts of the taskrc')
+            [CompletionResult]::new('man', 'man', [CompletionResultType]::ParameterValue, 'Prints the man page, including the reports and contexts of the taskrc')
+            [CompletionResult]::new('cheatsheet', 'cheatsheet', [CompletionResultType]::ParameterValue, 'Writes the effective key bindings and settings to a cheatsheet, to share a setup')
+            [CompletionResult]::new('settings', 'settings', [CompletionResultType]::ParameterValue, 'Exports or imports the settings, key bindings, styles, named filters and layouts as one bundle file')
+            [CompletionResult]::new('sample-data', 'sample-data', [CompletionResultType]::ParameterValue, 'Launches against generated tasks in a temporary data folder, to evaluate performance settings')
+            break
+        }
+        'taskwarrior-tui;completions' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'taskwarrior-tui;man' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'taskwarrior-tui;cheatsheet' {
+            [CompletionResult]::new('--format', 'format', [CompletionResultType]::ParameterName, 'Layout of the cheatsheet, guessed from the extension of the file by default')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'taskwarrior-tui;settings' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('export', 'export', [CompletionResultType]::ParameterValue, 'Writes the bundle of the current settings')
+            [CompletionResult]::new('import', 'import', [CompletionResultType]::ParameterValue, 'Adds the settings and layouts of a bundle, asking before replacing ones set differently')
+            break
+        }
+        'taskwarrior-tui;settings;export' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'taskwarrior-tui;settings;import' {
+            [CompletionResult]::new('-y', 'y', [CompletionResultType]::ParameterName, 'Adds new settings and layouts and replaces conflicting ones without asking, except settings running a command')
+            [CompletionResult]::new('--yes', 'yes', [CompletionResultType]::ParameterName, 'Adds new settings and layouts and replaces conflicting ones without asking, except settings running a command')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'taskwarrior-tui;sample-data' {
+            [CompletionResult]::new('-n', 'n', [CompletionResultType]::ParameterName, 'Number of tasks to generate')
+            [CompletionResult]::new('--tasks', 'tasks', [CompletionResultType]::ParameterName, 'Number of tasks to generate')
+            [CompletionResult]::new('--projects', 'projects', [CompletionResultType]::ParameterName, 'Number of projects the tasks are spread over')
+            [CompletionResult]::new('--tags', 'tags', [CompletionResultType]::ParameterName, 'Number of tags the tasks are tagged with')
+            [CompletionResult]::new('--days', 'days', [CompletionResultType]::ParameterName, 'Entry and due dates are spread over this many days around today')
+            [CompletionResult]::new('--seed', 'seed', [CompletionResultType]::ParameterName, 'Seed of the generator, the same seed gives the same tasks')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
     })
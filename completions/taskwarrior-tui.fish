@@ -1,7 +1,34 @@
-complete -c taskwarrior-tui -s d -l data -d 'Sets the data folder for taskwarrior-tui' -r
-complete -c taskwarrior-tui -s c -l config -d 'Sets the config folder for taskwarrior-tui (currently not used)' -r
-complete -c taskwarrior-tui -l taskdata -d 'Sets the .task folder using the TASKDATA environment variable for taskwarrior' -r
-complete -c taskwarrior-tui -l taskrc -d 'Sets the .taskrc file using the TASKRC environment variable for taskwarrior' -r
-complete -c taskwarrior-tui -s r -l report -d 'Sets default report' -r
-complete -c taskwarrior-tui -s h -l help -d 'Print help'
-complete -c taskwarrior-tui -s V -l version -d 'Print version'
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -s d -l data -d 'Sets the data folder for taskwarrior-tui' -r
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -s c -l config -d 'Sets the config folder for taskwarrior-tui (currently not used)' -r
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -l taskdata -d 'Sets the .task folder using the TASKDATA environment variable for taskwarrior' -r
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -l taskrc -d 'Sets the .taskrc file using the TASKRC environment variable for taskwarrior' -r
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -s r -l report -d 'Sets default report' -r
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -l uuid -d 'Selects a task on start, given its uuid or a link to it such as `task://<uuid>`' -r
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -l context -d 'Uses a taskwarrior context while running, `none` for no context, without changing the taskrc' -r
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -l record -d 'Records keys, resizes and taskwarrior commands of the session to a file' -r
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -l replay -d 'Replays a session recorded with --record, use with --taskdata to replay against a copy of the tasks' -r
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -l trace-perf -d 'Records draw, export and event timings, viewable from the command palette and written to the data folder on exit'
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -l demo -d 'Runs against a temporary copy of bundled example tasks, leaving your own tasks and settings untouched'
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -s h -l help -d 'Print help'
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -s V -l version -d 'Print version'
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -f -a "completions" -d 'Prints shell completions, including the reports and contexts of the taskrc'
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -f -a "man" -d 'Prints the man page, including the reports and contexts of the taskrc'
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -f -a "cheatsheet" -d 'Writes the effective key bindings and settings to a cheatsheet, to share a setup'
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -f -a "settings" -d 'Exports or imports the settings, key bindings, styles, named filters and layouts as one bundle file'
+complete -c taskwarrior-tui -n "__fish_use_subcommand" -f -a "sample-data" -d 'Launches against generated tasks in a temporary data folder, to evaluate performance settings'
+complete -c taskwarrior-tui -n "__fish_seen_subcommand_from completions" -s h -l help -d 'Print help'
+complete -c taskwarrior-tui -n "__fish_seen_subcommand_from man" -s h -l help -d 'Print help'
+complete -c taskwarrior-tui -n "__fish_seen_subcommand_from cheatsheet" -l format -d 'Layout of the cheatsheet, guessed from the extension of the file by default' -r -f -a "{markdown	,text	}"
+complete -c taskwarrior-tui -n "__fish_seen_subcommand_from cheatsheet" -s h -l help -d 'Print help'
+complete -c taskwarrior-tui -n "__fish_seen_subcommand_from settings; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import" -s h -l help -d 'Print help'
+complete -c taskwarrior-tui -n "__fish_seen_subcommand_from settings; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import" -f -a "export" -d 'Writes the bundle of the current settings'
+complete -c taskwarrior-tui -n "__fish_seen_subcommand_from settings; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import" -f -a "import" -d 'Adds the settings and layouts of a bundle, asking before replacing ones set differently'
+complete -c taskwarrior-tui -n "__fish_seen_subcommand_from settings; and __fish_seen_subcommand_from export" -s h -l help -d 'Print help'
+complete -c taskwarrior-tui -n "__fish_seen_subcommand_from settings; and __fish_seen_subcommand_from import" -s y -l yes -d 'Adds new settings and layouts and replaces conflicting ones without asking, except settings running a command'
+complete -c taskwarrior-tui -n "__fish_seen_subcommand_from settings; and __fish_seen_subcommand_from import" -s h -l help -d 'Print help'
+complete -c taskwarrior-tui -n "__fish_seen_subcommand_from sample-data" -s n -l tasks -d 'Number of tasks to generate' -r
+complete -c taskwarrior-tui -n "__fish_seen_subcommand_from sample-data" -l projects -d 'Number of projects the tasks are spread over' -r
+complete -c taskwarrior-tui -n "__fish_seen_subcommand_from sample-data" -l tags -d 'Number of tags the tasks are tagged with' -r
+complete -c taskwarrior-tui -n "__fish_seen_subcommand_from sample-data" -l days -d 'Entry and due dates are spread over this many days around today' -r
+complete -c taskwarrior-tui -n "__fish_seen_subcommand_from sample-data" -l seed -d 'Seed of the generator, the same seed gives the same tasks' -r
+complete -c taskwarrior-tui -n "__fish_seen_subcommand_from sample-data" -s h -l help -d 'Print help'
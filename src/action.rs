@@ -1,4 +1,4 @@
-#[derive(Clone, PartialEq, Eq, Debug, Copy)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Copy)]
 pub enum Action {
   Report,
   Filter,
@@ -13,5 +13,34 @@ pub enum Action {
   DeletePrompt,
   UndoPrompt,
   DonePrompt,
+  StartPrompt,
+  SnapshotDiff,
+  RecentActions,
+  Rules,
+  Journal,
+  HeaderFocus,
+  SpellSuggestions,
+  Palette,
+  SaveLayout,
+  PerfReport,
+  LogViewer,
+  UsageStats,
+  QuitPrompt,
+  TextEditor,
+  HintSelect,
+  Calculator,
+  StartMenu,
+  Output,
+  UrgencyTuner,
+  Goals,
+  Review,
+  StalledProjects,
+  ReplacePrompt,
+  ReplaceReview,
+  Backups,
+  Assign,
+  Activity,
+  Changelog,
+  Tour,
   Error,
 }
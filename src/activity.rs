@@ -0,0 +1,128 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Result;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use task_hookrs::{status::TaskStatus, task::Task};
+use uuid::Uuid;
+
+use crate::{snapshot::ChangeKind, team::Team, utils};
+
+/// The last change made to a task, as far as its timestamps tell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Activity {
+  pub uuid: Uuid,
+  pub description: String,
+  pub kind: ChangeKind,
+  /// Assignee of the task, the closest to who changed it a task keeps
+  pub who: Option<String>,
+  /// In UTC
+  pub modified: NaiveDateTime,
+  /// Changed since the feed was last looked at
+  pub new: bool,
+}
+
+/// What the last change to `task` was: a task modified when it was created was added, and one
+/// modified when it ended was completed or deleted then.
+fn kind(task: &Task, modified: NaiveDateTime) -> ChangeKind {
+  let at = |time: NaiveDateTime| (modified - time).num_seconds().abs() <= 5;
+  let ended = task.end().is_some_and(|end| at(**end));
+  match task.status() {
+    TaskStatus::Completed if ended => ChangeKind::Completed,
+    TaskStatus::Deleted if ended => ChangeKind::Deleted,
+    _ if at(**task.entry()) => ChangeKind::Added,
+    _ => ChangeKind::Modified,
+  }
+}
+
+/// Last changes of `tasks`, most recent first, new when made after `seen`.
+pub fn feed(tasks: &[Task], team: Option<&Team>, seen: Option<NaiveDateTime>) -> Vec<Activity> {
+  let mut feed: Vec<Activity> = tasks
+    .iter()
+    .map(|task| {
+      let modified = task.modified().map_or(**task.entry(), |m| **m);
+      Activity {
+        uuid: *task.uuid(),
+        description: task.description().clone(),
+        kind: kind(task, modified),
+        who: team.and_then(|t| t.assignee(task)).map(ToString::to_string),
+        modified,
+        new: seen.is_none_or(|seen| modified > seen),
+      }
+    })
+    .collect();
+  feed.sort_by_key(|a| std::cmp::Reverse(a.modified));
+  feed
+}
+
+/// When the feed was last looked at, to tell the changes made since.
+pub struct Seen {
+  /// In RFC 3339
+  pub time: Option<String>,
+  path: PathBuf,
+}
+
+impl Seen {
+  pub fn new(filename: &str) -> Self {
+    Self {
+      time: None,
+      path: utils::get_data_dir().join(filename),
+    }
+  }
+
+  pub fn load(&mut self) -> Result<()> {
+    if self.path.exists() {
+      self.time = serde_json::from_str(&fs::read_to_string(&self.path)?)?;
+    }
+    Ok(())
+  }
+
+  /// In UTC, as the timestamps of tasks.
+  pub fn time(&self) -> Option<NaiveDateTime> {
+    let time = DateTime::parse_from_rfc3339(self.time.as_ref()?).ok()?;
+    Some(time.with_timezone(&Utc).naive_utc())
+  }
+
+  pub fn mark(&mut self) -> Result<()> {
+    self.time = Some(Utc::now().to_rfc3339());
+    fs::write(&self.path, serde_json::to_string(&self.time)?)?;
+    Ok(())
+  }
+}
+
+/// Filter of the tasks changed in the last `days` days.
+pub fn filter(days: i64) -> String {
+  format!("modified.after:now-{}d", days.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::NaiveDate;
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  #[test]
+  fn test_feed() {
+    let tasks = import(
+      r#"[
+        {"uuid":"00000000-0000-0000-0000-000000000001","description":"New","status":"pending","entry":"20261015T090000Z","modified":"20261015T090000Z","assignee":"alice"},
+        {"uuid":"00000000-0000-0000-0000-000000000002","description":"Done","status":"completed","entry":"20261001T090000Z","end":"20261014T100000Z","modified":"20261014T100002Z"},
+        {"uuid":"00000000-0000-0000-0000-000000000003","description":"Changed","status":"pending","entry":"20261001T090000Z","modified":"20261013T110000Z"},
+        {"uuid":"00000000-0000-0000-0000-000000000004","description":"Gone","status":"deleted","entry":"20261001T090000Z","end":"20261012T090000Z","modified":"20261012T090000Z"}
+      ]"#
+      .as_bytes(),
+    )
+    .unwrap();
+    let team = Team::parse("assignee", "alice", "bob").unwrap();
+    let seen = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap().and_hms_opt(0, 0, 0);
+    let feed = feed(&tasks, Some(&team), seen);
+    let kinds: Vec<ChangeKind> = feed.iter().map(|a| a.kind).collect();
+    assert_eq!(
+      kinds,
+      [ChangeKind::Added, ChangeKind::Completed, ChangeKind::Modified, ChangeKind::Deleted]
+    );
+    assert_eq!(feed[0].who.as_deref(), Some("alice"));
+    assert_eq!(feed.iter().filter(|a| a.new).count(), 2);
+    assert_eq!(filter(7), "modified.after:now-7d");
+  }
+}
@@ -0,0 +1,67 @@
+use chrono::NaiveDateTime;
+use ratatui::style::Color;
+
+/// Colors of the `aging` column, from new tasks in green to the oldest in red.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Aging {
+  /// Ages in days where the color changes, in increasing order
+  pub breakpoints: Vec<i64>,
+  colors: Vec<Color>,
+}
+
+/// Ages in days from a comma separated list such as `7,30,90,365`, ignoring invalid ones.
+pub fn parse_breakpoints(s: &str) -> Vec<i64> {
+  let mut breakpoints: Vec<i64> = s.split(',').filter_map(|b| b.trim().parse().ok()).filter(|b| *b > 0).collect();
+  breakpoints.sort_unstable();
+  breakpoints.dedup();
+  breakpoints
+}
+
+/// `n` colors going from green through yellow to red.
+fn gradient(n: usize) -> Vec<Color> {
+  (0..n)
+    .map(|i| {
+      let t = if n > 1 { i as f64 / (n - 1) as f64 } else { 0.0 };
+      let (r, g) = if t < 0.5 { (510.0 * t, 200.0) } else { (255.0, 200.0 * (2.0 - 2.0 * t)) };
+      Color::Rgb(r.round() as u8, g.round() as u8, 60)
+    })
+    .collect()
+}
+
+impl Aging {
+  pub fn new(breakpoints: Vec<i64>) -> Self {
+    let colors = gradient(breakpoints.len() + 1);
+    Self { breakpoints, colors }
+  }
+
+  /// Color of a task created at `entry`: one step further along the gradient for each breakpoint
+  /// its age in days reached.
+  pub fn color(&self, entry: NaiveDateTime, now: NaiveDateTime) -> Color {
+    let days = (now - entry).num_days();
+    let step = self.breakpoints.iter().take_while(|b| days >= **b).count();
+    self.colors[step]
+  }
+}
+
+impl Default for Aging {
+  fn default() -> Self {
+    Self::new(vec![7, 30, 90, 365])
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Duration;
+
+  use super::*;
+
+  #[test]
+  fn test_aging() {
+    assert_eq!(parse_breakpoints("30, 7,x,-1,30"), [7, 30]);
+    let aging = Aging::new(parse_breakpoints("7,30"));
+    let now = chrono::NaiveDate::from_ymd_opt(2026, 10, 15).unwrap().and_hms_opt(12, 0, 0).unwrap();
+    assert_eq!(aging.color(now - Duration::days(2), now), Color::Rgb(0, 200, 60));
+    assert_eq!(aging.color(now - Duration::days(7), now), Color::Rgb(255, 200, 60));
+    assert_eq!(aging.color(now - Duration::days(400), now), Color::Rgb(255, 0, 60));
+  }
+}
@@ -6,6 +6,7 @@ use std::{
   fs, io,
   io::{Read, Write},
   path::Path,
+  process::{ExitStatus, Output},
   sync::{mpsc, Arc, Mutex},
   time::{Duration, Instant, SystemTime},
 };
@@ -28,12 +29,12 @@ use ratatui::{
   symbols::bar::FULL,
   terminal::Frame,
   text::{Line, Span, Text},
-  widgets::{Block, BorderType, Borders, Clear, Gauge, LineGauge, List, ListItem, Paragraph, Tabs, Wrap},
-  Terminal,
+  widgets::{Block, BorderType, Borders, Clear, Gauge, LineGauge, List, ListItem, ListState, Paragraph, Tabs, Wrap},
+  Terminal, TerminalOptions, Viewport,
 };
 use regex::Regex;
 use rustyline::{history::SearchDirection as HistoryDirection, line_buffer::LineBuffer, At, Editor, Word};
-use task_hookrs::{date::Date, import::import, project::Project, status::TaskStatus, task::Task};
+use task_hookrs::{date::Date, import::import, project::Project, status::TaskStatus, task::Task, uda::UDAValue};
 use unicode_segmentation::{Graphemes, UnicodeSegmentation};
 use unicode_width::UnicodeWidthStr;
 use uuid::Uuid;
@@ -41,30 +42,106 @@ use versions::Versioning;
 
 use crate::{
   action::Action,
+  activity::{self, Activity},
+  backup::{self, Backups},
+  badges::{self, Badges},
+  calc::Calculator,
   calendar::Calendar,
+  capabilities::{self, disable_kitty_keyboard, enable_kitty_keyboard, Capabilities},
+  capacity::DueLoad,
+  chips::Chips,
   completion::{get_start_word_under_cursor, CompletionList},
-  config,
-  config::Config,
+  compose, config,
+  config::{Config, CursorAfterRemoval},
+  cursor::PromptCursor,
+  datepreview::DatePreviews,
+  deeplink,
+  density::Density,
+  details, diff,
+  draft::Drafts,
+  editor::{EditorTarget, TextEditor},
   event::{Event, KeyCode},
+  filter::{self as task_filter, TokenKind},
+  focus::{self, Focus},
+  goals::{self, GoalProgress},
+  health,
   help::Help,
+  hints::{self, Hint},
   history::HistoryContext,
+  instances::{Instances, Message},
+  journal::{Journal, JournalKind},
   keyconfig::KeyConfig,
+  layout::{LayoutPreset, Layouts, View},
+  logging,
+  middleware::{self, Pipeline},
+  nextaction::{self, StalledProject},
+  notify::Subscriptions,
+  overrides,
+  palette::{Palette, PaletteCommand},
   pane::{
     context::{ContextDetails, ContextsState},
-    project::ProjectsState,
+    project::{self, ProjectsState},
+    scroll::{PaneAreas, ScrollPane, WHEEL_LINES},
     Pane,
   },
+  perf::{Metric, PerfTrace},
+  prefetch::Prefetch,
+  preview::{self, Graphics, ImagePreview},
+  quickadd::QuickAdd,
+  recent::{RecentActionKind, RecentActions},
+  recording::Recorder,
+  replace::{self, ReplaceReview, Replacement},
+  review::Review,
+  rules::{Evaluations, RuleAction, RuleMatches},
+  screenshot,
   scrollbar::Scrollbar,
+  snapshot::{ChangeKind, Snapshot},
+  sort,
+  spellcheck::{self, SpellChecker, SpellSuggestions},
+  startmenu::{self, RecentWorkspaces, StartItem, StartMenu},
+  status,
+  stream::OutputStream,
   table::{Row, Table, TableMode, TableState},
-  task_report::TaskReportTable,
-  ui, utils,
+  task_report::{vague_format_date_time, TaskReportTable},
+  ticks::{Component, Ticks},
+  tooltip::{self, Hover},
+  tour::{self, Tour},
+  ui, update, urgency,
+  usage::Usage,
+  utils, wip,
+  writequeue::{self, PendingRecord, WriteQueue, Written},
 };
 
-const MAX_LINE: usize = 4096;
+const RECURRENCE_PERIODS: [&str; 9] = [
+  "daily",
+  "weekdays",
+  "weekly",
+  "biweekly",
+  "monthly",
+  "quarterly",
+  "semiannual",
+  "annual",
+  "yearly",
+];
+/// How long to keep rendering without decorations after a frame went over budget.
+const DEGRADED_RETRY: Duration = Duration::from_secs(30);
+
+/// Tasks remembered on each side of the selected one, to fall back to when it is no longer in the report
+const STICKY_NEIGHBORS: usize = 5;
+
+/// Resuming the interface is attempted this many times, waiting twice as long after each failure.
+const RESUME_ATTEMPTS: u32 = 5;
+const RESUME_BACKOFF: Duration = Duration::from_millis(100);
+
+pub const MAX_LINE: usize = 4096;
+
+/// Date expressions with a time of day suggested for date attributes.
+const DATE_TIMES: [&str; 6] = ["now", "eod", "today+17h", "tomorrow+9h", "tomorrow+12h", "tomorrow+17h"];
 
 lazy_static! {
   static ref START_TIME: Instant = Instant::now();
   static ref TASKWARRIOR_VERSION_SUPPORTED: Versioning = Versioning::new("2.6.0").unwrap();
+  static ref DATE_ATTRIBUTE: Regex = Regex::new(r"^(due|scheduled|wait|until|start|end|entry)(\.[a-z]+)?:(?P<expression>.+)$").unwrap();
 }
 
 #[derive(Debug)]
@@ -131,7 +208,7 @@ fn get_formatted_datetime(date: &Date) -> String {
   )
 }
 
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
   let popup_layout = Layout::default()
     .direction(Direction::Vertical)
     .constraints(
@@ -176,11 +253,14 @@ pub struct TaskwarriorTui {
   pub tasks: Vec<Task>,
   pub all_tasks: Vec<Task>,
   pub task_details: HashMap<Uuid, String>,
+  pub prefetch: Prefetch,
   pub marked: HashSet<Uuid>,
   // stores index of current task that is highlighted
   pub current_selection: usize,
   pub current_selection_uuid: Option<Uuid>,
   pub current_selection_id: Option<u64>,
+  pub current_selection_neighbors: Vec<Uuid>,
+  pub current_selection_keep_index: bool,
   pub task_report_table: TaskReportTable,
   pub calendar_year: i32,
   pub mode: Mode,
@@ -188,7 +268,12 @@ pub struct TaskwarriorTui {
   pub config: Config,
   pub task_report_show_info: bool,
   pub task_report_height: u16,
+  pub task_report_header_focus: usize,
   pub task_details_scroll: u16,
+  /// Scrolling further down the details loads more annotations, when there are
+  task_details_scroll_max: u16,
+  /// Annotations shown in the details of a task with too many to show them all
+  pub details_pages: details::Pages,
   pub help_popup: Help,
   pub last_export: Option<SystemTime>,
   pub keyconfig: KeyConfig,
@@ -207,11 +292,127 @@ pub struct TaskwarriorTui {
   pub event_loop: crate::event::EventLoop,
   pub requires_redraw: bool,
   pub changes: utils::Changeset,
+  pub snapshot: Snapshot,
+  pub recent_actions: RecentActions,
+  pub journal: Journal,
+  pub drafts: Drafts,
+  pub spellchecker: Option<SpellChecker>,
+  pub spell_suggestions: Option<SpellSuggestions>,
+  pub calculator: Calculator,
+  pub date_preview: Option<(String, String)>,
+  pub date_previews: DatePreviews,
+  /// Tasks due on each day, exported when a due date is typed
+  pub due_load: Option<DueLoad>,
+  /// Filter chips above the report and where each was drawn, to toggle them with the mouse
+  pub chips: Chips,
+  chip_areas: Vec<(Rect, usize)>,
+  /// Description changes of a search and replace, reviewed one by one
+  pub replace_review: ReplaceReview,
+  pub layouts: Layouts,
+  pub palette: Palette,
+  pub perf: Option<PerfTrace>,
+  pub degraded_since: Option<Instant>,
+  pub recorder: Option<Recorder>,
+  pub replaying: bool,
+  recorded_size: Option<Rect>,
+  pub log_lines: Vec<String>,
+  pub log_scroll: usize,
+  pub usage: Usage,
+  pub usage_scroll: u16,
+  /// Process started by `uda.taskwarrior-tui.background_process` that is still running
+  pub background_job: Arc<Mutex<Option<std::process::Child>>>,
+  pub quit_when_idle: bool,
+  /// Set when the process was asked to terminate, the terminal may be gone
+  pub terminated: bool,
+  /// Error that made the interface unusable, reported once the terminal is restored
+  pub fatal_error: Option<String>,
+  pub instances: Instances,
+  pub capabilities: Capabilities,
+  pub text_editor: TextEditor,
+  pub text_editor_target: EditorTarget,
+  /// Decrypted secret of a task, shown in its details while it is selected
+  pub revealed_secret: Option<(Uuid, String)>,
+  pub image_preview: ImagePreview,
+  /// Only show tasks that are not blocked or waiting
+  pub ready_only: bool,
+  /// Only show tasks assigned to the current user
+  pub mine_only: bool,
+  /// User picked to assign the selected tasks to, the last item being nobody
+  pub assign_state: ListState,
+  /// Last changes of the tasks, most recent first, shown in the activity feed
+  pub activity: Vec<Activity>,
+  pub activity_state: ListState,
+  pub activity_seen: activity::Seen,
+  pub updates: update::Updates,
+  pub badges: Badges,
+  pub tour: Tour,
+  /// Keys typed so far while the hint labels are shown
+  pub hint_input: String,
+  pub task_report_rect: Rect,
+  /// Stages between the keys read from the terminal and the key handlers
+  pub middleware: Pipeline,
+  /// Name of the workspace switched to last
+  pub workspace: Option<String>,
+  pub ticks: Ticks,
+  /// The last event changed nothing on screen
+  pub skip_draw: bool,
+  pub cursor: PromptCursor,
+  /// Where the mouse rests, for the task tooltip
+  pub hover: Option<Hover>,
+  /// Index of the task whose tooltip was drawn last
+  pub tooltip: Option<usize>,
+  pub density: Density,
+  pub subscriptions: Subscriptions,
+  /// Named filters that tasks newly matched since the last key press, with how many tasks
+  pub notifications: Vec<(String, usize)>,
+  /// Number of tasks each work in progress limit counts, in the order of the config
+  pub wip_counts: wip::Counts,
+  pub focus: Focus,
+  /// Tasks each archive rule applies to, for the rules that apply to any
+  pub rule_matches: Vec<RuleMatches>,
+  pub rule_evaluations: Evaluations,
+  pub rules_state: ListState,
+  /// The selected rule purges or deletes, and waits for `y` to be applied
+  pub rule_confirmation: bool,
+  /// Changes to the tasks waiting for the lock of the task database
+  pub write_queue: WriteQueue,
+  /// What the pending `task undo` reverts, shown while it waits for confirmation
+  pub undo_diff: Vec<Line<'static>>,
+  /// Save the next frame as a screenshot
+  pub screenshot_requested: bool,
+  /// Message in the status bar until the next key
+  pub status_message: Option<String>,
+  pub start_menu: StartMenu,
+  pub recent_workspaces: RecentWorkspaces,
+  /// Where the panes were drawn, to route the mouse wheel
+  pub panes: PaneAreas,
+  /// Output of the slow command running in the background, or of the last one
+  pub output_stream: Option<OutputStream>,
+  /// Journal entry of the streamed command, recorded once it succeeds
+  stream_journal: Option<(JournalKind, String, Vec<Uuid>)>,
+  pub urgency_tuner: urgency::Tuner,
+  pub urgency_rankings: urgency::Rankings,
+  /// Goals with the progress of their tasks, shown in the goals view
+  pub goal_progress: Vec<GoalProgress>,
+  pub goals_state: ListState,
+  pub review: Review,
+  pub review_state: ListState,
+  /// Projects without a next action, shown in their own list
+  pub stalled_projects: Vec<StalledProject>,
+  pub stalled_state: ListState,
+  /// Backups of the task data, listed to restore one
+  pub backups: Backups,
+  pub scheduled_backup: backup::Scheduled,
 }
 
 impl TaskwarriorTui {
   pub async fn new(report: &str, init_event_loop: bool) -> Result<Self> {
-    let output = std::process::Command::new("task")
+    let issues = health::check();
+    if issues.iter().any(|i| i.fatal) {
+      return Err(anyhow!(health::summary(&issues)));
+    }
+
+    let output = overrides::task()
       .arg("rc.color=off")
       .arg("rc._forcecolor=off")
       .arg("rc.defaultwidth=0")
@@ -220,7 +421,7 @@ impl TaskwarriorTui {
       .context("Unable to run `task show`.")?;
 
     if !output.status.success() {
-      let output = std::process::Command::new("task")
+      let output = overrides::task()
         .arg("diagnostics")
         .output()
         .context("Unable to run `task diagnostics`.")?;
@@ -233,12 +434,12 @@ impl TaskwarriorTui {
 
     let data = String::from_utf8_lossy(&output.stdout);
     let c = Config::new(&data, report)?;
+    if let Some(level) = &c.uda_log_level {
+      logging::set_level(level);
+    }
     let kc = KeyConfig::new(&data)?;
 
-    let output = std::process::Command::new("task")
-      .arg("--version")
-      .output()
-      .context("Unable to run `task --version`")?;
+    let output = overrides::task().arg("--version").output().context("Unable to run `task --version`")?;
 
     let task_version = Versioning::new(String::from_utf8_lossy(&output.stdout).trim()).context("Unable to get version string")?;
 
@@ -250,6 +451,9 @@ impl TaskwarriorTui {
       None
     };
     let event_loop = crate::event::EventLoop::new(tick_rate, init_event_loop);
+    let ticks = Ticks::new(&c.uda_ticks);
+    let density = c.uda_density;
+    let middleware = Pipeline::new(&kc, Duration::from_millis(c.uda_chord_timeout));
 
     let mut app = Self {
       should_quit: false,
@@ -258,10 +462,13 @@ impl TaskwarriorTui {
       tasks: vec![],
       all_tasks: vec![],
       task_details: HashMap::new(),
+      prefetch: Prefetch::new(c.uda_task_detail_prefetch_concurrency),
       marked: HashSet::new(),
       current_selection: 0,
       current_selection_uuid: None,
       current_selection_id: None,
+      current_selection_neighbors: vec![],
+      current_selection_keep_index: false,
       current_context_filter: "".to_string(),
       current_context: "".to_string(),
       command: LineBuffer::with_capacity(MAX_LINE),
@@ -270,7 +477,10 @@ impl TaskwarriorTui {
       mode: Mode::Tasks(Action::Report),
       previous_mode: None,
       task_report_height: 0,
+      task_report_header_focus: 0,
       task_details_scroll: 0,
+      task_details_scroll_max: 0,
+      details_pages: details::Pages::default(),
       task_report_show_info: c.uda_task_report_show_info,
       config: c,
       task_report_table: TaskReportTable::new(&data, report)?,
@@ -293,6 +503,86 @@ impl TaskwarriorTui {
       event_loop,
       requires_redraw: false,
       changes: utils::Changeset::default(),
+      snapshot: Snapshot::new(),
+      recent_actions: RecentActions::new(),
+      journal: Journal::new("journal.json"),
+      drafts: Drafts::new(),
+      spellchecker: None,
+      spell_suggestions: None,
+      calculator: Calculator::default(),
+      date_preview: None,
+      date_previews: DatePreviews::new(),
+      due_load: None,
+      chips: Chips::default(),
+      chip_areas: vec![],
+      replace_review: ReplaceReview::default(),
+      layouts: Layouts::new("layouts.json"),
+      palette: Palette::new(),
+      perf: None,
+      degraded_since: None,
+      recorder: None,
+      replaying: false,
+      recorded_size: None,
+      log_lines: vec![],
+      log_scroll: 0,
+      usage: Usage::new("usage.json"),
+      usage_scroll: 0,
+      background_job: Arc::new(Mutex::new(None)),
+      quit_when_idle: false,
+      terminated: false,
+      fatal_error: None,
+      instances: Instances::new(),
+      capabilities: Capabilities::default(),
+      text_editor: TextEditor::default(),
+      text_editor_target: EditorTarget::Annotation,
+      revealed_secret: None,
+      image_preview: ImagePreview::new(Graphics::Off),
+      ready_only: false,
+      mine_only: false,
+      assign_state: ListState::default(),
+      activity: vec![],
+      activity_state: ListState::default(),
+      activity_seen: activity::Seen::new("activity-seen.json"),
+      updates: update::Updates::new("update-check.json"),
+      badges: Badges::new(),
+      tour: Tour::new("tour-taken"),
+      hint_input: String::new(),
+      task_report_rect: Rect::default(),
+      middleware,
+      workspace: None,
+      ticks,
+      cursor: PromptCursor::default(),
+      hover: None,
+      tooltip: None,
+      density,
+      subscriptions: Subscriptions::new(),
+      notifications: vec![],
+      wip_counts: wip::Counts::new(),
+      rule_matches: vec![],
+      rule_evaluations: Evaluations::new(),
+      rules_state: ListState::default(),
+      rule_confirmation: false,
+      write_queue: WriteQueue::default(),
+      undo_diff: vec![],
+      screenshot_requested: false,
+      status_message: None,
+      start_menu: StartMenu::default(),
+      recent_workspaces: RecentWorkspaces::new("recent-workspaces.json"),
+      panes: PaneAreas::default(),
+      output_stream: None,
+      stream_journal: None,
+      urgency_tuner: urgency::Tuner::default(),
+      urgency_rankings: urgency::Rankings::new(),
+      goal_progress: vec![],
+      goals_state: ListState::default(),
+      review: Review::new("review.json"),
+      review_state: ListState::default(),
+      stalled_projects: vec![],
+      stalled_state: ListState::default(),
+      backups: Backups::default(),
+      scheduled_backup: backup::Scheduled::new(),
+      focus: Focus::new("focus.json", focus::Source::default()),
+      skip_draw: false,
     };
 
     for c in app.config.filter.chars() {
@@ -300,20 +590,84 @@ impl TaskwarriorTui {
     }
 
     app.task_report_table.date_time_vague_precise = app.config.uda_task_report_date_time_vague_more_precise;
+    app.task_report_table.auto_columns = app.config.uda_task_report_auto_columns.clone();
+    app.task_report_table.show_tracked = app.config.uda_focus_column;
+    app.task_report_table.show_aging = app.config.uda_aging_column;
+    app.task_report_table.team = app.config.uda_team.clone();
+    app.chips = app.config.uda_chips.clone();
+    app.backups = Backups::new(app.backup_dir(), app.config.uda_backup_keep);
+    if let Err(e) = app.backups.load() {
+      warn!("Unable to load the backups: {}", e);
+    }
+    app.focus.source = app.config.uda_focus_source;
 
     app.update(true).await?;
 
     app.filter_history.load()?;
     app.filter_history.add(app.filter.as_str());
     app.command_history.load()?;
+    if let Err(e) = app.journal.load() {
+      warn!("Unable to load journal: {}", e);
+    }
+    if app.config.uda_usage_stats {
+      if let Err(e) = app.usage.load() {
+        warn!("Unable to load usage statistics: {}", e);
+      }
+    }
+    if let Err(e) = app.layouts.load() {
+      warn!("Unable to load layout presets: {}", e);
+    }
+    if let Err(e) = app.focus.load() {
+      warn!("Unable to load tracked time: {}", e);
+    }
+    if let Err(e) = app.recent_workspaces.load() {
+      warn!("Unable to load recent workspaces: {}", e);
+    }
+    if let Err(e) = app.review.load() {
+      warn!("Unable to load the weekly review: {}", e);
+    }
+    if let Err(e) = app.activity_seen.load() {
+      warn!("Unable to load when the activity feed was last seen: {}", e);
+    }
+    if let Err(e) = app.updates.load() {
+      warn!("Unable to load the last update check: {}", e);
+    }
+    if !app.config.uda_spellcheck_dictionary.is_empty() {
+      match SpellChecker::from_dic(&app.config.uda_spellcheck_dictionary) {
+        Ok(checker) => app.spellchecker = Some(checker),
+        Err(e) => warn!("Unable to load dictionary {}: {}", app.config.uda_spellcheck_dictionary, e),
+      }
+    }
     app.task_background();
 
+    if init_event_loop {
+      app.capabilities = Capabilities::detect(app.config.terminal_overrides());
+      app.config.apply_capabilities(&app.capabilities);
+      app.image_preview = ImagePreview::new(Graphics::detect(&app.config.uda_image_preview));
+      if !app.instances.others().is_empty() {
+        if app.config.uda_single_instance {
+          app.instances.notify(Message::Focus);
+          return Err(anyhow!(
+            "taskwarrior-tui is already running on {:?}, `uda.taskwarrior-tui.single-instance` is enabled.",
+            utils::get_data_dir()
+          ));
+        }
+        app.history_status = Some("another taskwarrior-tui is running on the same data".to_string());
+      }
+      if let Err(e) = app.instances.listen() {
+        warn!("Unable to listen for other instances: {}", e);
+      }
+    }
+
     if app.task_version < *TASKWARRIOR_VERSION_SUPPORTED {
       app.error = Some(format!(
         "Found taskwarrior version {} but taskwarrior-tui works with taskwarrior>={}",
         app.task_version, *TASKWARRIOR_VERSION_SUPPORTED
       ));
       app.mode = Mode::Tasks(Action::Error);
+    } else if !issues.is_empty() {
+      app.error = Some(health::summary(&issues));
+      app.mode = Mode::Tasks(Action::Error);
     }
 
     Ok(app)
@@ -323,17 +677,58 @@ impl TaskwarriorTui {
     enable_raw_mode()?;
     let mut stdout = std::io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    if self.capabilities.kitty_keyboard {
+      enable_kitty_keyboard()?;
+    }
     let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    let mut terminal = if self.replaying {
+      // the size is set by the recording, do not follow the size of the terminal
+      let viewport = Viewport::Fixed(backend.size()?);
+      Terminal::with_options(backend, TerminalOptions { viewport })?
+    } else {
+      Terminal::new(backend)?
+    };
     terminal.hide_cursor()?;
     Ok(terminal)
   }
 
+  /// Re-enter the interface after running an external command. The terminal may not be usable yet,
+  /// e.g. while it is being resized or restored, so retry with backoff before giving up, in which case
+  /// the terminal is left in its normal state and taskwarrior-tui quits with the error.
   pub async fn resume_tui(&mut self) -> Result<()> {
+    let mut delay = RESUME_BACKOFF;
+    let mut attempt = 1;
+    loop {
+      match self.try_resume_tui().await {
+        Ok(()) => return Ok(()),
+        Err(e) if attempt < RESUME_ATTEMPTS => {
+          warn!("Unable to resume the interface (attempt {}): {}", attempt, e);
+          tokio::time::sleep(delay).await;
+          delay *= 2;
+          attempt += 1;
+        }
+        Err(e) => {
+          error!("Unable to resume the interface: {}", e);
+          disable_raw_mode().ok();
+          disable_kitty_keyboard().ok();
+          execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, crossterm::cursor::Show).ok();
+          let message = format!("Unable to restore the terminal after {} attempts: {}", attempt, e);
+          self.fatal_error = Some(message.clone());
+          self.should_quit = true;
+          return Err(anyhow!(message));
+        }
+      }
+    }
+  }
+
+  async fn try_resume_tui(&mut self) -> Result<()> {
     self.resume_event_loop().await?;
     let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
     execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    if self.capabilities.kitty_keyboard {
+      enable_kitty_keyboard()?;
+    }
     enable_raw_mode()?;
     self.requires_redraw = true;
     terminal.hide_cursor()?;
@@ -365,7 +760,9 @@ impl TaskwarriorTui {
     let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
     disable_raw_mode()?;
+    disable_kitty_keyboard()?;
     execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+    self.cursor.reset()?;
     terminal.show_cursor()?;
     Ok(())
   }
@@ -378,44 +775,615 @@ impl TaskwarriorTui {
     loop {
       if self.requires_redraw {
         terminal.resize(terminal.size()?)?;
+        self.image_preview.invalidate();
         self.requires_redraw = false;
       }
-      terminal.draw(|f| self.draw(f))?;
+      if self.recorder.is_some() {
+        let size = terminal.size()?;
+        if self.recorded_size != Some(size) {
+          self.recorded_size = Some(size);
+          self.record(Event::Resize(size.width, size.height));
+        }
+      }
+      if !std::mem::take(&mut self.skip_draw) {
+        let started = Instant::now();
+        let frame = terminal.draw(|f| self.draw(f))?;
+        if std::mem::take(&mut self.screenshot_requested) {
+          self.status_message = Some(match screenshot::save(frame.buffer, self.screenshot_dir()) {
+            Ok(path) => format!("Saved {}", path.display()),
+            Err(e) => format!("Unable to save screenshot: {}", e),
+          });
+          terminal.draw(|f| self.draw(f))?;
+        }
+        self.cursor.finish_frame(self.config.uda_cursor_shape)?;
+        if self.image_preview.flush()? {
+          self.requires_redraw = true;
+        }
+        self.trace(Metric::Draw, started);
+        self.check_frame_budget(started.elapsed());
+      }
       // Handle input
       if let Some(event) = self.next().await {
+        let started = Instant::now();
+        if event != Event::Closed {
+          self.record(event);
+        }
+        let last_command = self.journal.entries.first().map(|e| e.time.clone());
+        let previous_mode = self.mode.clone();
+        if let Event::Input(input) = event {
+          self.count_key(input);
+        }
         match event {
           Event::Input(input) => {
             debug!("Received input = {:?}", input);
-            self.handle_input(input).await?;
+            self.hover = None;
+            self.notifications.clear();
+            self.status_message = None;
+            self.handle_key(input).await?;
           }
           Event::Tick => {
             debug!("Tick event");
-            self.update(false).await?;
+            let due = self.ticks.due(Instant::now());
+            self.skip_draw = due.is_empty() && self.middleware.deadline().is_none() && self.hovered_task() == self.tooltip;
+            self.expire_middleware().await?;
+            if due.contains(&Component::Report) {
+              let last_export = self.last_export;
+              self.update(false).await?;
+              // tasks changed on disk are drawn right away
+              if self.last_export != last_export {
+                self.skip_draw = false;
+              }
+            }
+            if due.contains(&Component::Notify) {
+              self.refresh_subscriptions();
+              // filters such as +OVERDUE match other tasks as time passes
+              self.badges.refresh(&self.config.uda_named_filters, &self.event_loop.tx);
+            }
+            if self.process_write_queue().await? {
+              self.skip_draw = false;
+            }
+            if self.poll_output_stream().await? {
+              self.skip_draw = false;
+            }
+            if self.poll_prefetch() {
+              self.skip_draw = false;
+            }
+            if self.updates.poll() {
+              self.skip_draw = false;
+            }
+            if self.badges.poll() {
+              self.skip_draw = false;
+            }
+            if self.poll_subscriptions()? {
+              self.skip_draw = false;
+            }
+            if self.wip_counts.poll() {
+              self.skip_draw = false;
+            }
+            if self.poll_scheduled_backup() {
+              self.skip_draw = false;
+            }
+            if self.poll_snapshot() {
+              self.skip_draw = false;
+            }
+            if self.poll_rules() {
+              self.skip_draw = false;
+            }
+            if let Some(ranking) = self.urgency_rankings.poll() {
+              self.urgency_tuner.preview = ranking;
+              self.skip_draw = false;
+            }
+            if self.date_previews.poll() {
+              self.update_date_preview();
+              self.skip_draw = false;
+            }
           }
           Event::Closed => {
             debug!("Event loop closed");
           }
+          Event::Resize(width, height) => {
+            terminal.resize(Rect::new(0, 0, width, height))?;
+          }
+          Event::Terminate => {
+            info!("Received termination signal, shutting down");
+            self.terminated = true;
+            self.should_quit = true;
+          }
+          Event::Hover(column, row) => self.hover(column, row),
+          Event::Scroll(column, row, notches) => self.scroll(column, row, notches),
+          Event::Click(column, row) => self.click(column, row).await?,
         }
+        self.trace(Metric::Event, started);
+        if self.journal.entries.first().map(|e| &e.time) != last_command.as_ref() {
+          self.instances.notify(Message::Refresh);
+        }
+        self.handle_instance_messages().await?;
+        self.record_commands(last_command.clone());
+        self.count_usage(&previous_mode, last_command);
       }
 
+      if self.quit_when_idle && !self.background_job_running() {
+        self.should_quit = true;
+      }
       if self.should_quit {
         break;
       }
     }
+    self.flush_state()?;
+    match self.fatal_error.take() {
+      Some(e) => Err(anyhow!(e)),
+      None => Ok(()),
+    }
+  }
+
+  /// Write everything kept in memory that should survive the session.
+  pub fn flush_state(&mut self) -> Result<()> {
+    self.flush_write_queue();
+    self.save_history()?;
+    if self.config.uda_usage_stats {
+      self.usage.write()?;
+    }
+    Ok(())
+  }
+
+  fn record(&mut self, event: Event<KeyCode>) {
+    if let Some(recorder) = self.recorder.as_mut() {
+      if let Err(e) = recorder.event(event) {
+        warn!("Unable to record {:?}: {}", event, e);
+      }
+    }
+  }
+
+  /// Add the commands journaled since the entry recorded at `last` to the recording.
+  fn record_commands(&mut self, last: Option<String>) {
+    if let Some(recorder) = self.recorder.as_mut() {
+      let commands: Vec<&str> = self
+        .journal
+        .entries
+        .iter()
+        .take_while(|e| Some(&e.time) != last.as_ref())
+        .map(|e| e.command.as_str())
+        .collect();
+      for command in commands.into_iter().rev() {
+        if let Err(e) = recorder.command(command) {
+          warn!("Unable to record command {}: {}", command, e);
+        }
+      }
+    }
+  }
+
+  /// Check the named filters with `notify` for tasks that newly match them, in the background.
+  fn refresh_subscriptions(&mut self) {
+    if cfg!(feature = "notifications") && self.config.uda_notifications {
+      self.subscriptions.refresh(&self.config.uda_named_filters, &self.event_loop.tx);
+    }
+  }
+
+  /// Take in the finished checks of the named filters, and ring the bell when tasks newly match
+  /// any. Returns whether there are new matches.
+  fn poll_subscriptions(&mut self) -> Result<bool> {
+    let new = self.subscriptions.poll();
+    for (name, count) in &new {
+      info!("{} tasks newly match filter {}", count, name);
+      match self.notifications.iter_mut().find(|(n, _)| n == name) {
+        Some((_, total)) => *total += count,
+        None => self.notifications.push((name.clone(), *count)),
+      }
+    }
+    if !new.is_empty() {
+      io::stdout().write_all(b"\x07")?;
+      io::stdout().flush()?;
+    }
+    Ok(!new.is_empty())
+  }
+
+  fn screenshot_dir(&self) -> std::path::PathBuf {
+    if self.config.uda_screenshot_dir.is_empty() {
+      utils::get_data_dir()
+    } else {
+      shellexpand::tilde(&self.config.uda_screenshot_dir).into_owned().into()
+    }
+  }
+
+  fn backup_dir(&self) -> std::path::PathBuf {
+    if self.config.uda_backup_dir.is_empty() {
+      utils::get_data_dir().join("backups")
+    } else {
+      shellexpand::tilde(&self.config.uda_backup_dir).into_owned().into()
+    }
+  }
+
+  /// Copy the files of the task data folder to a new backup.
+  fn back_up(&mut self, reason: backup::Reason) -> Result<(), String> {
+    let data_dir = shellexpand::tilde(&self.config.data_location).into_owned();
+    self
+      .backups
+      .create(Path::new(&data_dir), reason, Local::now().naive_local())
+      .map_err(|e| e.to_string())
+  }
+
+  /// Take in the backups once a scheduled backup is taken. Returns whether it finished.
+  fn poll_scheduled_backup(&mut self) -> bool {
+    match self.scheduled_backup.poll() {
+      Some(Ok(backups)) => self.backups.backups = backups.backups,
+      Some(Err(e)) => {
+        warn!("Unable to back up the task data: {}", e);
+        self.status_message = Some(format!("Unable to back up the task data: {}", e));
+      }
+      None => return false,
+    }
+    true
+  }
+
+  /// Back up the task data before changing `count` tasks at once, when they are enough.
+  fn back_up_before_bulk(&mut self, count: usize) -> Result<(), String> {
+    let threshold = self.config.uda_backup_bulk_threshold;
+    if threshold == 0 || count < threshold {
+      return Ok(());
+    }
+    self
+      .back_up(backup::Reason::Bulk)
+      .map_err(|e| format!("Not changing {} tasks, the task data could not be backed up. {}", count, e))
+  }
+
+  /// Run `command`, a change to the tasks. When the task database is locked, or earlier changes
+  /// still wait for it, the change is queued to be retried in order.
+  fn run_write(&mut self, command: &mut std::process::Command) -> io::Result<Written> {
+    let args: Vec<String> = command.get_args().map(|a| a.to_string_lossy().to_string()).collect();
+    if !self.write_queue.is_empty() {
+      self.write_queue.push(args, 0);
+      return Ok(Written::Queued);
+    }
+    let output = command.output()?;
+    if writequeue::is_lock_error(&output) {
+      warn!("Task database is locked, queueing `{}`", Self::command_to_string(command));
+      self.write_queue.push(args, 1);
+      self.wake_write_queue();
+      return Ok(Written::Queued);
+    }
+    Ok(Written::Ran(output))
+  }
+
+  /// Journal `command` once it changed the tasks, now if it ran or when it leaves the queue.
+  fn journal_write(
+    &mut self,
+    write: &Written,
+    kind: JournalKind,
+    command: &std::process::Command,
+    uuids: Vec<Uuid>,
+    compensation: Option<Vec<String>>,
+  ) {
+    match write {
+      Written::Ran(_) => self.journal.record(kind, Self::command_to_string(command), uuids, compensation),
+      Written::Queued => self.write_queue.record_last(PendingRecord { kind, uuids, compensation }),
+    }
+  }
+
+  /// Tick when the first queued change is due, whatever the tick rate is.
+  fn wake_write_queue(&self) {
+    if let Some(deadline) = self.write_queue.deadline() {
+      let tx = self.event_loop.tx.clone();
+      tokio::spawn(async move {
+        tokio::time::sleep_until(deadline.into()).await;
+        tx.send(Event::Tick).ok();
+      });
+    }
+  }
+
+  /// Retry the queued changes that are due. Returns whether any was tried.
+  async fn process_write_queue(&mut self) -> Result<bool> {
+    let mut tried = false;
+    while let Some(write) = self.write_queue.due(Instant::now()).cloned() {
+      tried = true;
+      let command = utils::command_line("task", &write.args);
+      let output = overrides::task().args(&write.args).output()?;
+      if writequeue::is_lock_error(&output) && write.attempts < self.config.uda_write_retry_limit {
+        debug!("Task database is still locked, retrying `{}` later", command);
+        self.write_queue.retry_later();
+        break;
+      }
+      self.write_queue.pending.pop_front();
+      let error = if writequeue::is_lock_error(&output) {
+        Some(format!(
+          "Gave up on `{}` after {} attempts, the task database stayed locked.",
+          command, write.attempts
+        ))
+      } else if !output.status.success() {
+        Some(format!("`{}` failed. {}", command, String::from_utf8_lossy(&output.stderr).trim()))
+      } else {
+        if let Some(record) = write.record {
+          self.journal.record(record.kind, command, record.uuids, record.compensation);
+        }
+        None
+      };
+      if let Some(e) = error {
+        warn!("{}", e);
+        self.status_message = Some(e.clone());
+        self.error = Some(e);
+        if self.mode == Mode::Tasks(Action::Report) {
+          self.previous_mode = Some(self.mode.clone());
+          self.mode = Mode::Tasks(Action::Error);
+        }
+      }
+    }
+    if tried {
+      if self.write_queue.is_empty() {
+        self.update(true).await?;
+      } else {
+        self.wake_write_queue();
+      }
+    }
+    Ok(tried)
+  }
+
+  /// Wait for the queued changes before quitting, rather than losing them. Each change is tried at
+  /// least once, and retried while the task database is locked for no longer than
+  /// `writequeue::FLUSH_LIMIT` overall and not at all when terminated by a signal.
+  fn flush_write_queue(&mut self) {
+    let deadline = Instant::now() + writequeue::FLUSH_LIMIT;
+    let mut dropped = 0;
+    while let Some(write) = self.write_queue.pending.pop_front() {
+      let command = utils::command_line("task", &write.args);
+      let mut attempt = write.attempts;
+      loop {
+        match overrides::task().args(&write.args).output() {
+          Ok(output) if writequeue::is_lock_error(&output) => {
+            if self.terminated || attempt >= self.config.uda_write_retry_limit || Instant::now() >= deadline {
+              warn!("Dropping `{}`, the task database is still locked", command);
+              dropped += 1;
+            } else {
+              std::thread::sleep(writequeue::backoff(attempt).min(deadline.saturating_duration_since(Instant::now())));
+              attempt += 1;
+              continue;
+            }
+          }
+          Ok(output) if !output.status.success() => error!("`{}` failed: {}", command, String::from_utf8_lossy(&output.stderr).trim()),
+          Ok(_) => {
+            if let Some(record) = write.record {
+              self.journal.record(record.kind, command, record.uuids, record.compensation);
+            }
+          }
+          Err(e) => error!("Unable to run `{}`: {}", command, e),
+        }
+        break;
+      }
+    }
+    if dropped > 0 {
+      self.status_message = Some(format!("Dropped {} changes, the task database stayed locked", dropped));
+    }
+  }
+
+  /// Work in progress limits that starting the selected tasks would go over, as `label count/max`.
+  fn wip_limits_exceeded_by_start(&self) -> Vec<String> {
+    let tasks: Vec<Task> = self.selected_task_uuids().iter().filter_map(|uuid| self.task_by_uuid(*uuid)).collect();
+    self
+      .config
+      .uda_wip_limits
+      .iter()
+      .zip(&self.wip_counts.counts)
+      .filter_map(|(limit, count)| {
+        let starting = tasks.iter().filter(|t| limit.counts_start(t)).count();
+        (starting > 0 && count + starting > limit.max).then(|| format!("{} {}/{}", limit.label(), count + starting, limit.max))
+      })
+      .collect()
+  }
+
+  /// Take in the tasks each archive rule applies to once they were found in the background.
+  fn poll_rules(&mut self) -> bool {
+    let matches = match self.rule_evaluations.poll() {
+      Some(matches) => matches,
+      None => return false,
+    };
+    self.rule_matches = matches;
+    let selected = self.rules_state.selected().unwrap_or_default();
+    self
+      .rules_state
+      .select((!self.rule_matches.is_empty()).then(|| selected.min(self.rule_matches.len() - 1)));
+    self.rule_confirmation = false;
+    true
+  }
+
+  /// Apply the rule selected in the rules screen to the tasks it matched. The filter of the rule is
+  /// run again first, so that tasks changed since the preview are left alone.
+  fn apply_selected_rule(&mut self) -> Result<(), String> {
+    let matches = match self.rules_state.selected().and_then(|i| self.rule_matches.get(i)) {
+      Some(m) => m.clone(),
+      None => return Ok(()),
+    };
+    let output = overrides::task()
+      .arg("rc.json.array=on")
+      .arg("rc.color=off")
+      .arg("rc.verbose=nothing")
+      .args(matches.rule.recheck_filter(&matches.uuids()))
+      .arg("export")
+      .output();
+    let current = match output {
+      Ok(o) if o.status.success() => import(o.stdout.as_slice()).map_err(|e| format!("Rule {}: {}", matches.rule.name, e))?,
+      Ok(o) => {
+        return Err(format!(
+          "Unable to find the tasks of rule {}: {}",
+          matches.rule.name,
+          String::from_utf8_lossy(&o.stderr).trim()
+        ))
+      }
+      Err(_) => return Err(format!("Cannot run `task` for rule {}.", matches.rule.name)),
+    };
+    let uuids = matches.still_matching(&current);
+    if uuids.is_empty() {
+      return Ok(());
+    }
+    for args in matches.rule.action.commands(&uuids) {
+      let mut cmd = overrides::task();
+      cmd
+        .arg("rc.bulk=0")
+        .arg("rc.confirmation=off")
+        .arg("rc.dependency.confirmation=off")
+        .arg("rc.recurrence.confirmation=off")
+        .args(&args);
+      let write = match self.run_write(&mut cmd) {
+        Ok(Written::Ran(o)) if !o.status.success() => {
+          return Err(format!(
+            "Rule {} failed: {}",
+            matches.rule.name,
+            String::from_utf8_lossy(&o.stderr).trim()
+          ))
+        }
+        Ok(write) => write,
+        Err(_) => return Err(format!("Cannot run `task` for rule {}.", matches.rule.name)),
+      };
+      let (kind, compensation) = match &matches.rule.action {
+        RuleAction::Done => (JournalKind::Done, Some(Self::restore_arguments(&uuids))),
+        RuleAction::Delete => (JournalKind::Delete, Some(Self::restore_arguments(&uuids))),
+        // purged tasks cannot be brought back
+        RuleAction::Purge => (JournalKind::Delete, None),
+        RuleAction::Modify(_) => (JournalKind::Modify, None),
+      };
+      self.journal_write(&write, kind, &cmd, uuids.clone(), compensation);
+    }
+    Ok(())
+  }
+
+  async fn handle_instance_messages(&mut self) -> Result<()> {
+    while let Ok(message) = self.instances.rx.try_recv() {
+      debug!("Received {:?} from another instance", message);
+      self.skip_draw = false;
+      match message {
+        Message::Refresh => {
+          // the other instance changed tasks and wrote its journal
+          self.journal.load().unwrap_or_else(|e| warn!("Unable to reload journal: {}", e));
+          self.update(true).await?;
+        }
+        Message::Focus => {
+          self.history_status = Some("taskwarrior-tui is already running here".to_string());
+          io::stdout().write_all(b"\x07")?;
+          io::stdout().flush()?;
+        }
+      }
+    }
     Ok(())
   }
 
+  fn count(&mut self, category: &str, name: &str) {
+    if self.config.uda_usage_stats {
+      if let Err(e) = self.usage.count(category, name) {
+        warn!("Unable to write usage statistics: {}", e);
+      }
+    }
+  }
+
+  /// Count the key binding `input` triggers in the task report.
+  fn count_key(&mut self, input: KeyCode) {
+    if !self.config.uda_usage_stats || self.mode != Mode::Tasks(Action::Report) {
+      return;
+    }
+    if let Some((name, _)) = self.keyconfig.bindings().into_iter().find(|(_, key)| *key == input) {
+      self.count("key", &name);
+    }
+  }
+
+  /// Count the view opened and the commands run while handling the last event.
+  fn count_usage(&mut self, previous_mode: &Mode, last_command: Option<String>) {
+    if !self.config.uda_usage_stats {
+      return;
+    }
+    if *previous_mode != self.mode {
+      let view = match self.mode {
+        Mode::Tasks(action) => format!("{:?}", action).to_lowercase(),
+        Mode::Projects => "projects".to_string(),
+        Mode::Calendar => "calendar".to_string(),
+      };
+      self.count("view", &view);
+    }
+    let kinds: Vec<&'static str> = self
+      .journal
+      .entries
+      .iter()
+      .take_while(|e| Some(&e.time) != last_command.as_ref())
+      .map(|e| e.kind.label())
+      .collect();
+    for kind in kinds {
+      self.count("command", &kind.to_lowercase());
+    }
+  }
+
+  /// Record the time elapsed since `started` when running with `--trace-perf`.
+  pub fn trace(&mut self, metric: Metric, started: Instant) {
+    if let Some(perf) = self.perf.as_mut() {
+      perf.record(metric, started.elapsed());
+    }
+  }
+
+  /// Skip expensive decorations after a frame took longer than `uda.taskwarrior-tui.frame-budget`,
+  /// and try drawing them again once `DEGRADED_RETRY` has passed.
+  fn check_frame_budget(&mut self, elapsed: Duration) {
+    self.degraded_since = Self::degraded_after_frame(self.degraded_since, elapsed, self.config.uda_frame_budget, Instant::now());
+  }
+
+  /// When decorations are skipped from, once a frame took `elapsed` at `now`.
+  fn degraded_after_frame(since: Option<Instant>, elapsed: Duration, budget: u64, now: Instant) -> Option<Instant> {
+    if budget == 0 {
+      return None;
+    }
+    match since {
+      Some(since) if now.duration_since(since) >= DEGRADED_RETRY => None,
+      Some(since) => Some(since),
+      None if elapsed > Duration::from_millis(budget) => {
+        debug!("Frame took {:?}, skipping decorations", elapsed);
+        Some(now)
+      }
+      None => None,
+    }
+  }
+
+  pub fn degraded(&self) -> bool {
+    self.degraded_since.is_some()
+  }
+
   pub fn reset_command(&mut self) {
     self.command.update("", 0, &mut self.changes)
   }
 
+  fn draft_task_uuids(&self, action: Action) -> Vec<Uuid> {
+    match action {
+      Action::Modify | Action::Annotate => self.selected_task_uuids(),
+      _ => vec![],
+    }
+  }
+
+  /// Keep the text of a prompt that is being dismissed so that it can be restored when it is opened again.
+  pub fn save_draft(&mut self, action: Action) {
+    let task_uuids = self.draft_task_uuids(action);
+    let text = if action == Action::Modify {
+      self.modify.as_str().to_string()
+    } else {
+      self.command.as_str().to_string()
+    };
+    self.drafts.save(action, task_uuids, &text);
+  }
+
+  pub fn restore_draft(&mut self, action: Action) {
+    let task_uuids = self.draft_task_uuids(action);
+    let buffer = if action == Action::Modify { &mut self.modify } else { &mut self.command };
+    if let Some(text) = self.drafts.take(action, &task_uuids, buffer.as_str()) {
+      buffer.update(&text, text.len(), &mut self.changes);
+      self.history_status = Some("draft restored, Ctrl-x to discard".to_string());
+    }
+  }
+
+  pub fn discard_draft(&mut self, action: Action) {
+    let prefill = self.drafts.discard(action);
+    let buffer = if action == Action::Modify { &mut self.modify } else { &mut self.command };
+    buffer.update(&prefill, prefill.len(), &mut self.changes);
+    self.history_status = None;
+  }
+
   pub fn get_context(&mut self) -> Result<()> {
-    let output = std::process::Command::new("task").arg("_get").arg("rc.context").output()?;
+    let output = overrides::task().arg("_get").arg("rc.context").output()?;
     self.current_context = String::from_utf8_lossy(&output.stdout).to_string();
     self.current_context = self.current_context.strip_suffix('\n').unwrap_or("").to_string();
 
     // support new format for context
-    let output = std::process::Command::new("task")
+    let output = overrides::task()
       .arg("_get")
       .arg(format!("rc.context.{}.read", self.current_context))
       .output()?;
@@ -424,7 +1392,7 @@ impl TaskwarriorTui {
 
     // If new format is not used, check if old format is used
     if self.current_context_filter.is_empty() {
-      let output = std::process::Command::new("task")
+      let output = overrides::task()
         .arg("_get")
         .arg(format!("rc.context.{}", self.current_context))
         .output()?;
@@ -447,75 +1415,465 @@ impl TaskwarriorTui {
     let tab_layout = chunks[0];
     let main_layout = chunks[1];
 
+    self.panes.clear();
     self.draw_tabs(f, tab_layout);
     match self.mode {
       Mode::Tasks(action) => self.draw_task(f, main_layout, action),
       Mode::Calendar => self.draw_calendar(f, main_layout),
       Mode::Projects => self.draw_projects(f, main_layout),
     }
+    self.tooltip = self.hovered_task();
+    if let Some(i) = self.tooltip {
+      self.draw_tooltip(f, i);
+    }
+    if self.mode == Mode::Tasks(Action::Tour) {
+      self.draw_tour(f);
+    }
   }
 
-  fn draw_tabs(&self, f: &mut Frame<impl Backend>, layout: Rect) {
-    let titles: Vec<&str> = vec!["Tasks", "Projects", "Calendar"];
-    let tab_names: Vec<_> = titles.into_iter().map(Line::from).collect();
-    let selected_tab = match self.mode {
-      Mode::Tasks(_) => 0,
-      Mode::Projects => 1,
-      Mode::Calendar => 2,
-    };
-    let navbar_block = Block::default().style(self.config.uda_style_navbar);
-    let context = Line::from(vec![
-      Span::from("["),
-      Span::from(if self.current_context.is_empty() {
-        "none"
-      } else {
-        &self.current_context
-      }),
-      Span::from("]"),
-    ]);
-    let tabs = Tabs::new(tab_names)
-      .block(navbar_block.clone())
-      .select(selected_tab)
-      .divider(" ")
-      .highlight_style(Style::default().add_modifier(Modifier::BOLD));
-    let rects = Layout::default()
-      .direction(Direction::Horizontal)
-      .constraints([Constraint::Min(0), Constraint::Length(context.width() as u16)])
-      .split(layout);
+  pub fn open_tour(&mut self) {
+    self.tour.step = 0;
+    self.mode = Mode::Tasks(Action::Tour);
+  }
 
-    f.render_widget(tabs, rects[0]);
-    f.render_widget(Paragraph::new(Text::from(context)).block(navbar_block), rects[1]);
+  fn end_tour(&mut self) {
+    self.mode = Mode::Tasks(Action::Report);
+    if let Err(e) = self.tour.mark() {
+      warn!("Unable to remember that the tour was taken: {}", e);
+    }
   }
 
-  pub fn draw_debug(&mut self, f: &mut Frame<impl Backend>) {
-    let area = centered_rect(50, 50, f.size());
+  /// Dim the screen but for the region the step of the tour points at, and explain it in a box
+  /// kept clear of that region.
+  fn draw_tour(&mut self, f: &mut Frame<impl Backend>) {
+    let steps = tour::steps(&self.keyconfig);
+    let step = match steps.get(self.tour.step) {
+      Some(step) => step,
+      None => return,
+    };
+    let size = f.size();
+    f.render_widget(
+      tour::Dim {
+        except: step.region.area(size),
+      },
+      size,
+    );
+    let width = size.width.min(60);
+    let height = (step.text.len() as u16 / width.saturating_sub(4).max(1) + 4).min(size.height);
+    let y = match step.region {
+      tour::Region::Tabs => size.height / 3,
+      tour::Region::CommandLine => size.height.saturating_sub(height + 3),
+      tour::Region::Report => size.height.saturating_sub(height) / 2,
+    };
+    let area = Rect::new(size.x + (size.width - width) / 2, size.y + y, width, height);
     f.render_widget(Clear, area);
-    let t = format!("{}", self.current_selection);
-    let p = Paragraph::new(Text::from(t)).block(Block::default().borders(Borders::ALL).border_type(BorderType::Rounded));
+    let p = Paragraph::new(step.text.clone()).wrap(Wrap { trim: true }).block(
+      Block::default()
+        .borders(Borders::ALL)
+        .border_type(self.config.border_type)
+        .title(Span::styled(
+          format!("{} ({}/{})", step.title, self.tour.step + 1, steps.len()),
+          Style::default().add_modifier(Modifier::BOLD),
+        )),
+    );
+    f.render_widget(p, area);
+  }
+
+  /// Remember where the mouse rests, and wake up when it rested long enough for a tooltip.
+  fn hover(&mut self, column: u16, row: u16) {
+    self.hover = Some(Hover::new(column, row));
+    if self.config.uda_tooltip_delay > 0 {
+      let tx = self.event_loop.tx.clone();
+      let delay = Duration::from_millis(self.config.uda_tooltip_delay);
+      tokio::spawn(async move {
+        tokio::time::sleep(delay).await;
+        tx.send(Event::Tick).ok();
+      });
+    }
+    // moving the mouse only changes the screen when it hides a tooltip
+    self.skip_draw = self.tooltip.is_none();
+  }
+
+  /// Toggle the filter chip under the mouse, clicks elsewhere are ignored.
+  async fn click(&mut self, column: u16, row: u16) -> Result<()> {
+    let chip = self
+      .chip_areas
+      .iter()
+      .find(|(area, _)| area.y == row && (area.x..area.x + area.width).contains(&column))
+      .map(|(_, i)| *i);
+    match chip.filter(|_| self.mode == Mode::Tasks(Action::Report)) {
+      Some(i) => self.toggle_chip(i).await?,
+      None => self.skip_draw = true,
+    }
+    Ok(())
+  }
+
+  async fn toggle_chip(&mut self, i: usize) -> Result<()> {
+    self.chips.toggle(i);
+    self.update(true).await
+  }
+
+  /// Chips of the filters above the report, remembering where each one is drawn.
+  fn chips_line(&mut self, area: Rect) -> Line<'static> {
+    self.chip_areas.clear();
+    let mut spans = vec![];
+    let mut x = area.x;
+    for i in 0..self.chips.filters.len() {
+      let label = self.chips.label(i);
+      let width = label.width() as u16;
+      let style = if self.chips.active[i] {
+        Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+      } else {
+        Style::default().add_modifier(Modifier::DIM)
+      };
+      self.chip_areas.push((Rect::new(x, area.y, width, 1), i));
+      spans.push(Span::styled(label, style));
+      spans.push(Span::raw(" "));
+      x += width + 1;
+    }
+    Line::from(spans)
+  }
+
+  /// Scroll the pane under the mouse by `notches` of the wheel. The report and the task details only
+  /// scroll while no popup is open.
+  fn scroll(&mut self, column: u16, row: u16, notches: i8) {
+    let pane = match self.panes.at(column, row) {
+      Some(pane) => pane,
+      None => {
+        self.skip_draw = true;
+        return;
+      }
+    };
+    let lines = WHEEL_LINES as usize * notches.unsigned_abs() as usize;
+    let down = notches > 0;
+    let step = |value: u16| {
+      if down {
+        value.saturating_add(lines as u16)
+      } else {
+        value.saturating_sub(lines as u16)
+      }
+    };
+    let on_report = self.mode == Mode::Tasks(Action::Report);
+    match pane {
+      ScrollPane::Report if on_report && !self.tasks.is_empty() => {
+        self.current_selection = if down {
+          (self.current_selection + lines).min(self.tasks.len() - 1)
+        } else {
+          self.current_selection.saturating_sub(lines)
+        };
+        self.current_selection_id = None;
+        self.current_selection_uuid = None;
+      }
+      ScrollPane::Details if on_report => {
+        if down && self.task_details_scroll >= self.task_details_scroll_max && self.details_pages.more {
+          self.details_pages.load_more();
+        }
+        self.task_details_scroll = step(self.task_details_scroll);
+      }
+      ScrollPane::Help => {
+        let last = (self.help_popup.text_height as u16).saturating_sub(1);
+        self.help_popup.scroll = step(self.help_popup.scroll).min(last);
+      }
+      ScrollPane::SnapshotDiff => self.snapshot.scroll = step(self.snapshot.scroll),
+      ScrollPane::UsageStats => self.usage_scroll = step(self.usage_scroll),
+      ScrollPane::LogViewer => {
+        let last = self.log_lines.len().saturating_sub(1);
+        self.log_scroll = if down {
+          (self.log_scroll + lines).min(last)
+        } else {
+          self.log_scroll.saturating_sub(lines)
+        };
+      }
+      _ => self.skip_draw = true,
+    }
+  }
+
+  /// Index of the task the mouse rested on long enough to show its tooltip.
+  fn hovered_task(&self) -> Option<usize> {
+    let hover = self.hover?;
+    let delay = Duration::from_millis(self.config.uda_tooltip_delay);
+    if delay.is_zero() || !hover.ready(delay, Instant::now()) || self.mode != Mode::Tasks(Action::Report) {
+      return None;
+    }
+    let rect = self.task_report_rect;
+    let top = rect.y + 1 + self.density.header_gap();
+    if hover.column < rect.x || hover.column >= rect.x + rect.width || hover.row < top {
+      return None;
+    }
+    let row = ((hover.row - top) / self.density.row_height()) as usize;
+    (row < self.visible_task_rows()).then(|| self.task_table_state.offset() + row)
+  }
+
+  /// Due date, tags and first annotation of a task next to the mouse, leaving the selection alone.
+  fn draw_tooltip(&self, f: &mut Frame<impl Backend>, task: usize) {
+    let (hover, task) = match (self.hover, self.tasks.get(task)) {
+      (Some(hover), Some(task)) => (hover, task),
+      _ => return,
+    };
+    let lines = tooltip::lines(task);
+    let size = f.size();
+    let width = (lines.iter().map(|l| l.width()).max().unwrap_or_default() as u16 + 2).min(size.width);
+    let height = (lines.len() as u16 + 2).min(size.height);
+    let x = hover.column.min(size.width - width);
+    let y = if hover.row + 1 + height <= size.height {
+      hover.row + 1
+    } else {
+      hover.row.saturating_sub(height)
+    };
+    let area = Rect::new(x, y, width, height);
+    let text: Vec<Line> = lines.into_iter().map(Line::from).collect();
+    f.render_widget(Clear, area);
+    f.render_widget(
+      Paragraph::new(text).block(Block::default().borders(Borders::ALL).border_type(self.config.border_type)),
+      area,
+    );
+  }
+
+  fn draw_tabs(&self, f: &mut Frame<impl Backend>, layout: Rect) {
+    let titles: Vec<&str> = vec!["Tasks", "Projects", "Calendar"];
+    let tab_names: Vec<_> = titles.into_iter().map(Line::from).collect();
+    let selected_tab = match self.mode {
+      Mode::Tasks(_) => 0,
+      Mode::Projects => 1,
+      Mode::Calendar => 2,
+    };
+    let navbar_block = Block::default().style(self.config.uda_style_navbar);
+    let mut context = Line::from(vec![
+      Span::from("["),
+      Span::from(if self.current_context.is_empty() {
+        "none"
+      } else {
+        &self.current_context
+      }),
+      Span::from("]"),
+    ]);
+    if let Some(workspace) = &self.workspace {
+      context.spans.insert(0, Span::from(format!("{} ", workspace)));
+    }
+    if self.config.uda_status_next_due {
+      let now = chrono::Utc::now().naive_utc();
+      if let Some(due) = status::next_due(&self.tasks, now) {
+        context.spans.insert(0, Span::from(format!("due in {} ", status::countdown(due - now))));
+      }
+    }
+    if self.config.uda_status_clock {
+      context.spans.push(Span::from(format!(" {}", Local::now().format("%H:%M:%S"))));
+    }
+    for status in self.middleware.status() {
+      context
+        .spans
+        .insert(0, Span::styled(format!("{} ", status), Style::default().add_modifier(Modifier::BOLD)));
+    }
+    for (limit, count) in self.config.uda_wip_limits.iter().zip(&self.wip_counts.counts) {
+      if *count >= limit.max {
+        let mut style = Style::default().add_modifier(Modifier::BOLD);
+        if *count > limit.max {
+          style = style.add_modifier(Modifier::REVERSED);
+        }
+        context
+          .spans
+          .insert(0, Span::styled(format!("{} {}/{} ", limit.label(), count, limit.max), style));
+      }
+    }
+    if let Some(status) = self.write_queue.status() {
+      context
+        .spans
+        .insert(0, Span::styled(format!("{} ", status), Style::default().add_modifier(Modifier::BOLD)));
+    }
+    let ruled: usize = self.rule_matches.iter().map(|m| m.tasks.len()).sum();
+    if ruled > 0 {
+      context.spans.insert(
+        0,
+        Span::styled(format!("rules {} ", ruled), Style::default().add_modifier(Modifier::BOLD)),
+      );
+    }
+    if let Some(stream) = self.output_stream.as_ref().filter(|_| self.mode != Mode::Tasks(Action::Output)) {
+      if stream.running() {
+        context.spans.insert(
+          0,
+          Span::styled(
+            format!("{} {} ", stream.title, stream.status()),
+            Style::default().add_modifier(Modifier::BOLD),
+          ),
+        );
+      }
+    }
+    if self.review.in_progress() {
+      let done = self.review.state.done.len();
+      let total = self.config.uda_review_steps.len();
+      context.spans.insert(
+        0,
+        Span::styled(format!("review {}/{} ", done, total), Style::default().add_modifier(Modifier::BOLD)),
+      );
+    }
+    if let Some(message) = &self.status_message {
+      context
+        .spans
+        .insert(0, Span::styled(format!("{} ", message), Style::default().add_modifier(Modifier::BOLD)));
+    }
+    if !self.badges.counts.is_empty() {
+      context.spans.insert(0, Span::from(format!("{} ", badges::label(&self.badges.counts))));
+    }
+    for (name, count) in &self.notifications {
+      context.spans.insert(
+        0,
+        Span::styled(format!("{} +{} ", name, count), Style::default().add_modifier(Modifier::BOLD)),
+      );
+    }
+    if self.ready_only {
+      context
+        .spans
+        .insert(0, Span::styled("READY ", Style::default().add_modifier(Modifier::BOLD)));
+    }
+    if self.mine_only {
+      context
+        .spans
+        .insert(0, Span::styled("MINE ", Style::default().add_modifier(Modifier::BOLD)));
+    }
+    if self.degraded() {
+      context.spans.insert(0, Span::styled("~ ", Style::default().add_modifier(Modifier::DIM)));
+    }
+    if let Some(version) = self.updates.available().filter(|_| self.config.uda_update_check) {
+      context.spans.insert(
+        0,
+        Span::styled(format!("v{} available ", version), Style::default().add_modifier(Modifier::DIM)),
+      );
+    }
+    let tabs = Tabs::new(tab_names)
+      .block(navbar_block.clone())
+      .select(selected_tab)
+      .divider(" ")
+      .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+    let rects = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints([Constraint::Min(0), Constraint::Length(context.width() as u16)])
+      .split(layout);
+
+    f.render_widget(tabs, rects[0]);
+    f.render_widget(Paragraph::new(Text::from(context)).block(navbar_block), rects[1]);
+  }
+
+  /// The projects the report is restricted to by the filter or the context, as `work › dev` in the
+  /// accent color of each project.
+  fn breadcrumb(&self) -> Option<Line<'static>> {
+    if !self.config.uda_task_report_breadcrumb || !self.density.breadcrumb() {
+      return None;
+    }
+    let mut projects = task_filter::projects(self.filter.as_str());
+    projects.extend(task_filter::projects(&self.current_context_filter));
+    if projects.is_empty() {
+      return None;
+    }
+    let separator = if self.capabilities.unicode { " \u{203a} " } else { " > " };
+    let mut spans = vec![];
+    for (i, project) in projects.iter().enumerate() {
+      if i > 0 {
+        spans.push(Span::raw(" | "));
+      }
+      let style = self.config.project_style(project).add_modifier(Modifier::BOLD);
+      let accent = style.fg.map(|c| Style::default().fg(c)).unwrap_or_default();
+      spans.push(Span::styled(if self.capabilities.unicode { "\u{258c}" } else { "|" }, accent));
+      spans.push(Span::styled(project.split('.').collect::<Vec<_>>().join(separator), style));
+    }
+    Some(Line::from(spans))
+  }
+
+  pub fn draw_debug(&mut self, f: &mut Frame<impl Backend>) {
+    let area = centered_rect(50, 50, f.size());
+    f.render_widget(Clear, area);
+    let t = format!("{}", self.current_selection);
+    let p = Paragraph::new(Text::from(t)).block(Block::default().borders(Borders::ALL).border_type(self.config.border_type));
     f.render_widget(p, area);
   }
 
   pub fn draw_projects(&mut self, f: &mut Frame<impl Backend>, rect: Rect) {
-    let data = self.projects.data.clone();
-    let p = Paragraph::new(Text::from(&data[..]));
-    f.render_widget(p, rect);
+    let selected = self.projects.lines.get(self.projects.current_selection).copied();
+    let lines: Vec<Line> = self
+      .projects
+      .data
+      .lines()
+      .enumerate()
+      .map(|(i, l)| {
+        if Some(i) == selected {
+          Line::from(Span::styled(l.to_string(), self.config.uda_style_report_selection))
+        } else {
+          Line::from(l.to_string())
+        }
+      })
+      .collect();
+
+    let notes = self.projects.selected().map(|project| (project.clone(), project::read_notes(project)));
+    let rect = match notes {
+      Some((project, notes)) => {
+        let split = Layout::default()
+          .direction(Direction::Vertical)
+          .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
+          .split(rect);
+        let (title, text) = match notes {
+          Some((owner, notes)) if owner == project => (project, notes),
+          Some((owner, notes)) => (format!("{} (notes of {})", project, owner), notes),
+          None => (
+            project.clone(),
+            format!(
+              "No notes. Press `{}` to describe the project and its goal.",
+              self.keyconfig.key_label(self.keyconfig.edit)
+            ),
+          ),
+        };
+        let p = Paragraph::new(Text::from(text)).wrap(Wrap { trim: false }).block(
+          Block::default()
+            .borders(Borders::ALL)
+            .border_type(self.config.border_type)
+            .title(Span::styled(title, Style::default().add_modifier(Modifier::BOLD))),
+        );
+        f.render_widget(p, split[1]);
+        split[0]
+      }
+      None => rect,
+    };
+    let scroll = selected.unwrap_or_default().saturating_sub(rect.height.saturating_sub(1) as usize) as u16;
+    f.render_widget(Paragraph::new(lines).scroll((scroll, 0)), rect);
+  }
+
+  /// Open the notes of the selected project in `$VISUAL` or `$EDITOR`.
+  pub async fn edit_project_notes(&mut self) -> Result<(), String> {
+    let project = match self.projects.selected() {
+      Some(project) => project.clone(),
+      None => return Ok(()),
+    };
+    let path = project::notes_path(&project);
+    if let Some(dir) = path.parent() {
+      std::fs::create_dir_all(dir).map_err(|e| format!("Unable to create {}: {}", dir.display(), e))?;
+    }
+    let editor = std::env::var("VISUAL")
+      .or_else(|_| std::env::var("EDITOR"))
+      .unwrap_or_else(|_| "vi".to_string());
+    let mut words = shlex::split(&editor).unwrap_or_default();
+    if words.is_empty() {
+      words.push("vi".to_string());
+    }
+    self.pause_tui().await.map_err(|e| e.to_string())?;
+    let status = std::process::Command::new(&words[0]).args(&words[1..]).arg(&path).status();
+    self.resume_tui().await.map_err(|e| e.to_string())?;
+    match status {
+      Ok(status) if status.success() => Ok(()),
+      Ok(status) => Err(format!("`{}` exited with {}", editor, status)),
+      Err(e) => Err(format!("Unable to run `{}`: {}", editor, e)),
+    }
   }
 
   fn style_for_project(&self, project: &[String]) -> Style {
     let virtual_tag_names_in_precedence = &self.config.rule_precedence_color;
     let mut style = Style::default();
     for tag_name in virtual_tag_names_in_precedence.iter().rev() {
-      match tag_name.as_str() {
-        "project." => {
-          let s = self
-            .config
-            .color
-            .get(&format!("color.project.{}", project[0]))
-            .copied()
-            .unwrap_or_default();
-          style = style.patch(s);
-        }
-        &_ => {}
+      if tag_name.as_str() == "project." {
+        let s = self
+          .config
+          .color
+          .get(&format!("color.project.{}", project[0]))
+          .copied()
+          .unwrap_or_default();
+        style = style.patch(s);
       }
     }
     style
@@ -533,11 +1891,31 @@ impl TaskwarriorTui {
   }
 
   pub fn draw_task(&mut self, f: &mut Frame<impl Backend>, layout: Rect, action: Action) {
-    let rects = Layout::default()
+    let mut rects = Layout::default()
       .direction(Direction::Vertical)
       .constraints([Constraint::Min(0), Constraint::Length(2)].as_ref())
       .split(layout);
 
+    let breadcrumb = self.breadcrumb();
+    let chips = !self.chips.is_empty();
+    let header = breadcrumb.is_some() as u16 + chips as u16;
+    if header > 0 {
+      let split = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(header), Constraint::Min(0), Constraint::Length(2)].as_ref())
+        .split(layout);
+      let mut lines = vec![];
+      if chips {
+        let area = Rect::new(split[0].x, split[0].y, split[0].width, 1);
+        lines.push(self.chips_line(area));
+      }
+      lines.extend(breadcrumb);
+      f.render_widget(Paragraph::new(lines), split[0]);
+      rects = vec![split[1], split[2]].into();
+    } else {
+      self.chip_areas.clear();
+    }
+
     // render task report and task details if required
     if self.task_report_show_info {
       let split_task_layout = Layout::default()
@@ -593,7 +1971,7 @@ impl TaskwarriorTui {
         let rect = centered_rect(90, 60, f.size());
         f.render_widget(Clear, rect);
         let p = Paragraph::new(Text::from(text))
-          .block(Block::default().borders(Borders::ALL).border_type(BorderType::Rounded).title(title))
+          .block(Block::default().borders(Borders::ALL).border_type(self.config.border_type).title(title))
           .wrap(Wrap { trim: true });
         f.render_widget(p, rect);
         // draw error pop up
@@ -617,6 +1995,92 @@ impl TaskwarriorTui {
           self.error.clone(),
         );
       }
+      Action::Palette => {
+        let query = self.palette.query.clone();
+        self.draw_command(
+          f,
+          rects[1],
+          &query,
+          (Span::styled("Command Palette", Style::default().add_modifier(Modifier::BOLD)), None),
+          query.width(),
+          true,
+          self.error.clone(),
+        );
+        self.draw_palette(f, 50, 50);
+      }
+      Action::ReplacePrompt => {
+        let position = Self::get_position(&self.command);
+        let count = self.replace_targets().len();
+        self.draw_command(
+          f,
+          rects[1],
+          self.command.as_str(),
+          (
+            Span::styled("Replace in Descriptions", Style::default().add_modifier(Modifier::BOLD)),
+            Some(Span::from(format!("/pattern/replacement/flags, {} tasks", count))),
+          ),
+          position,
+          true,
+          self.error.clone(),
+        );
+      }
+      Action::ReplaceReview => {
+        self.draw_command(
+          f,
+          rects[1],
+          "Press <y> to accept the change, <n> to skip it, <a> to accept it and all the next ones or <Esc> to cancel.",
+          (Span::styled("Replace", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+        self.draw_replace_review(f, 70, 40);
+      }
+      Action::SaveLayout => {
+        let position = Self::get_position(&self.command);
+        self.draw_command(
+          f,
+          rects[1],
+          self.command.as_str(),
+          (Span::styled("Save Layout As", Style::default().add_modifier(Modifier::BOLD)), None),
+          position,
+          true,
+          self.error.clone(),
+        );
+      }
+      Action::SpellSuggestions => {
+        if let Some(Mode::Tasks(action)) = self.previous_mode {
+          self.handle_task_mode_action(f, rects, task_ids, action);
+        }
+        self.draw_spell_suggestions(f, 30, 30);
+      }
+      Action::Calculator => {
+        if let Some(Mode::Tasks(action)) = self.previous_mode {
+          self.handle_task_mode_action(f, rects, task_ids, action);
+        }
+        self.draw_calculator(f);
+      }
+      Action::HeaderFocus => {
+        let column = self
+          .task_report_table
+          .visible_columns
+          .get(self.task_report_header_focus)
+          .cloned()
+          .unwrap_or_default();
+        self.draw_command(
+          f,
+          rects[1],
+          &format!(
+            "Press <h>/<l> to move between columns, <Enter> to cycle the sort of `{}`, <Esc> to leave. Sort: {}",
+            column,
+            self.task_report_table.sort_string()
+          ),
+          (Span::styled("Sort Columns", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+      }
       Action::Jump => {
         let position = Self::get_position(&self.command);
         self.draw_command(
@@ -634,17 +2098,21 @@ impl TaskwarriorTui {
         if self.show_completion_pane {
           self.draw_completion_pop_up(f, rects[1], position);
         }
+        let subtitle = match task_filter::validate(self.filter.as_str()) {
+          Err(e) => Some(Span::styled(
+            format!("{} at column {}", e, e.column(self.filter.as_str())),
+            Style::default().add_modifier(Modifier::BOLD),
+          )),
+          Ok(_) => self
+            .history_status
+            .as_ref()
+            .map(|s| Span::styled(s.clone(), Style::default().add_modifier(Modifier::BOLD))),
+        };
         self.draw_command(
           f,
           rects[1],
           self.filter.as_str(),
-          (
-            Span::styled("Filter Tasks", Style::default().add_modifier(Modifier::BOLD)),
-            self
-              .history_status
-              .as_ref()
-              .map(|s| Span::styled(s, Style::default().add_modifier(Modifier::BOLD))),
-          ),
+          (Span::styled("Filter Tasks", Style::default().add_modifier(Modifier::BOLD)), subtitle),
           position,
           true,
           self.error.clone(),
@@ -691,6 +2159,10 @@ impl TaskwarriorTui {
         if self.show_completion_pane {
           self.draw_completion_pop_up(f, rects[1], position);
         }
+        let preview = self.modify_preview();
+        if !preview.is_empty() {
+          self.draw_diff(f, rects[0], "Preview", preview);
+        }
         let label = if task_ids.len() > 1 {
           format!("Modify Tasks {}", task_ids.join(","))
         } else {
@@ -789,84 +2261,386 @@ impl TaskwarriorTui {
         );
         self.draw_context_menu(f, 80, 50);
       }
-      Action::DonePrompt => {
-        let label = if task_ids.len() > 1 {
-          format!("Done Tasks {}?", task_ids.join(","))
+      Action::SnapshotDiff => {
+        self.draw_command(
+          f,
+          rects[1],
+          self.filter.as_str(),
+          ("Filter Tasks".into(), None),
+          Self::get_position(&self.filter),
+          false,
+          self.error.clone(),
+        );
+        self.draw_snapshot_diff(f, 80, 70);
+      }
+      Action::QuitPrompt => {
+        let (title, text) = if self.background_job_running() {
+          (
+            "A background job is still running",
+            "Press <w> to quit when it finishes, <f> to stop it and quit now, or <Esc> to cancel.",
+          )
         } else {
-          format!("Done Task {}?", task_ids.join(","))
-        };
-        let x = match self.keyconfig.done {
-          KeyCode::Char(c) => c.to_string(),
-          _ => "Enter".to_string(),
-        };
-        let q = match self.keyconfig.quit {
-          KeyCode::Char(c) => c.to_string(),
-          _ => "Esc".to_string(),
+          ("Quit taskwarrior-tui?", "Press <y> to quit or <Esc> to cancel.")
         };
         self.draw_command(
           f,
           rects[1],
-          &format!("Press <{}> to confirm or <{}> to abort.", x, q),
-          (Span::styled(label, Style::default().add_modifier(Modifier::BOLD)), None),
+          text,
+          (Span::styled(title, Style::default().add_modifier(Modifier::BOLD)), None),
           0,
           false,
           self.error.clone(),
         );
       }
-      Action::DeletePrompt => {
-        let label = if task_ids.len() > 1 {
-          format!("Delete Tasks {}?", task_ids.join(","))
-        } else {
-          format!("Delete Task {}?", task_ids.join(","))
-        };
-        let x = match self.keyconfig.delete {
-          KeyCode::Char(c) => c.to_string(),
-          _ => "Enter".to_string(),
-        };
-        let q = match self.keyconfig.quit {
-          KeyCode::Char(c) => c.to_string(),
-          _ => "Esc".to_string(),
-        };
+      Action::HintSelect => {
         self.draw_command(
           f,
           rects[1],
-          &format!("Press <{}> to confirm or <{}> to abort.", x, q),
-          (Span::styled(label, Style::default().add_modifier(Modifier::BOLD)), None),
+          "Type the label of a task, <Esc> to cancel.",
+          (Span::styled("Select", Style::default().add_modifier(Modifier::BOLD)), None),
           0,
           false,
           self.error.clone(),
         );
+        self.draw_hints(f);
       }
-      Action::UndoPrompt => {
-        let label = "Run `task undo`?";
-        let k = match self.keyconfig.undo {
-          KeyCode::Char(c) => c.to_string(),
-          _ => "Enter".to_string(),
-        };
-        let q = match self.keyconfig.quit {
-          KeyCode::Char(c) => c.to_string(),
-          _ => "Esc".to_string(),
-        };
+      Action::TextEditor => {
         self.draw_command(
           f,
           rects[1],
-          &format!("Press <{}> to confirm or <{}> to abort.", k, q),
-          (Span::styled(label, Style::default().add_modifier(Modifier::BOLD)), None),
+          "Press <Ctrl-s> to save, <Esc> to cancel.",
+          (Span::styled("Editor", Style::default().add_modifier(Modifier::BOLD)), None),
           0,
           false,
           self.error.clone(),
         );
+        self.draw_text_editor(f, 60, 50);
       }
-    }
-  }
-
-  pub fn get_dates_with_styles(&self) -> Vec<(chrono::NaiveDate, Style)> {
-    if !self.tasks.is_empty() {
-      let tasks = &self.tasks;
-      tasks
-        .iter()
-        .filter_map(|t| t.due().map(|d| (d.clone(), self.style_for_task(t))))
-        .map(|(d, t)| {
+      Action::UsageStats => {
+        self.draw_command(
+          f,
+          rects[1],
+          "Press <Esc> to close.",
+          (Span::styled("Usage Statistics", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+        self.draw_usage_stats(f, 60, 80);
+      }
+      Action::LogViewer => {
+        self.draw_command(
+          f,
+          rects[1],
+          "Press <Esc> to close, <r> to reload.",
+          (Span::styled("Log", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+        self.draw_log_viewer(f, 90, 80);
+      }
+      Action::PerfReport => {
+        self.draw_command(
+          f,
+          rects[1],
+          "Press any key to continue.",
+          (Span::styled("Performance Report", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+        self.draw_perf_report(f, 80, 40);
+      }
+      Action::UrgencyTuner => {
+        self.draw_command(
+          f,
+          rects[1],
+          "<Left>/<Right> change by 0.5, <->/<+> by 0.1, <0> resets, <Enter> writes the changes to the taskrc, <Esc> discards them.",
+          (Span::styled("Urgency", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+        self.draw_urgency_tuner(f, 90, 70);
+      }
+      Action::Output => {
+        let running = self.output_stream.as_ref().is_some_and(|s| s.running());
+        let hint = if running {
+          "Press <Ctrl-c> to cancel or <Esc> to keep it running in the background."
+        } else {
+          "Press <Esc> to close."
+        };
+        self.draw_command(
+          f,
+          rects[1],
+          hint,
+          (Span::styled("Output", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+        self.draw_output_stream(f, 80, 60);
+      }
+      Action::StartMenu => {
+        self.draw_command(
+          f,
+          rects[1],
+          "Press <Enter> to start with the selected item or <Esc> for the default report.",
+          (Span::styled("Start", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+        self.draw_start_menu(f, 50, 60);
+      }
+      Action::Tour => {
+        self.draw_command(
+          f,
+          rects[1],
+          "Press <Enter>/<Right> for the next step, <Left> for the previous one or <Esc> to end the tour.",
+          (Span::styled("Tour", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+      }
+      Action::Changelog => {
+        self.draw_command(
+          f,
+          rects[1],
+          "Press <Up>/<Down> to scroll or <Esc> to close.",
+          (Span::styled("Changelog", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+        self.draw_changelog(f, 80, 80);
+      }
+      Action::Activity => {
+        self.draw_command(
+          f,
+          rects[1],
+          "Press <Enter> to show the selected task or <Esc> to close.",
+          (Span::styled("Activity", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+        self.draw_activity(f, 80, 70);
+      }
+      Action::Assign => {
+        self.draw_command(
+          f,
+          rects[1],
+          "Press <Enter> to assign the selected tasks or <Esc> to cancel.",
+          (Span::styled("Assign", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+        self.draw_assign(f, 40, 40);
+      }
+      Action::Backups => {
+        self.draw_command(
+          f,
+          rects[1],
+          "Press <Enter> to restore the selected backup or <Esc> to close. The current data is backed up first.",
+          (Span::styled("Backups", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+        self.draw_backups(f, 60, 50);
+      }
+      Action::StalledProjects => {
+        self.draw_command(
+          f,
+          rects[1],
+          "Press <Enter> to add a next action to the selected project or <Esc> to close.",
+          (Span::styled("Projects", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+        self.draw_stalled_projects(f, 60, 60);
+      }
+      Action::Review => {
+        self.draw_command(
+          f,
+          rects[1],
+          "Press <Enter> to show the tasks of a step, <Space> to check it off or <Esc> to close.",
+          (Span::styled("Weekly Review", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+        self.draw_review(f, 60, 50);
+      }
+      Action::Goals => {
+        self.draw_command(
+          f,
+          rects[1],
+          "Press <Enter> to show the tasks of the selected goal or <Esc> to close.",
+          (Span::styled("Goals", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+        self.draw_goals(f, 80, 70);
+      }
+      Action::Rules => {
+        let prompt = match self.rules_state.selected().and_then(|i| self.rule_matches.get(i)) {
+          Some(m) if self.rule_confirmation => format!(
+            "Press y to {} the {} tasks of rule {}, any other key to cancel.",
+            m.rule.action.label(),
+            m.tasks.len(),
+            m.rule.name
+          ),
+          _ => "Press <Enter> to apply the selected rule to its tasks or <Esc> to close.".to_string(),
+        };
+        self.draw_command(
+          f,
+          rects[1],
+          &prompt,
+          (Span::styled("Archive Rules", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+        self.draw_rules(f, 80, 60);
+      }
+      Action::RecentActions => {
+        self.draw_command(
+          f,
+          rects[1],
+          "Press <Enter> to restore the selected task or <Esc> to close.",
+          (Span::styled("Recent Actions", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+        self.draw_recent_actions(f, 80, 50);
+      }
+      Action::Journal => {
+        self.draw_command(
+          f,
+          rects[1],
+          "Press <y> to copy the command, <Enter> to run the compensating action or <Esc> to close.",
+          (Span::styled("Action History", Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+        self.draw_action_history(f, 90, 70);
+      }
+      Action::DonePrompt => {
+        let label = if task_ids.len() > 1 {
+          format!("Done Tasks {}?", task_ids.join(","))
+        } else {
+          format!("Done Task {}?", task_ids.join(","))
+        };
+        let x = match self.keyconfig.done {
+          KeyCode::Char(c) => c.to_string(),
+          _ => "Enter".to_string(),
+        };
+        let q = match self.keyconfig.quit {
+          KeyCode::Char(c) => c.to_string(),
+          _ => "Esc".to_string(),
+        };
+        self.draw_command(
+          f,
+          rects[1],
+          &format!("Press <{}> to confirm or <{}> to abort.", x, q),
+          (Span::styled(label, Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+      }
+      Action::StartPrompt => {
+        let label = format!(
+          "Start {} {} over the limit of {}?",
+          if task_ids.len() > 1 { "Tasks" } else { "Task" },
+          task_ids.join(","),
+          self.wip_limits_exceeded_by_start().join(", ")
+        );
+        let x = match self.keyconfig.start_stop {
+          KeyCode::Char(c) => c.to_string(),
+          _ => "Enter".to_string(),
+        };
+        let q = match self.keyconfig.quit {
+          KeyCode::Char(c) => c.to_string(),
+          _ => "Esc".to_string(),
+        };
+        self.draw_command(
+          f,
+          rects[1],
+          &format!("Press <{}> to confirm or <{}> to abort.", x, q),
+          (Span::styled(label, Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+      }
+      Action::DeletePrompt => {
+        let label = if task_ids.len() > 1 {
+          format!("Delete Tasks {}?", task_ids.join(","))
+        } else {
+          format!("Delete Task {}?", task_ids.join(","))
+        };
+        let x = match self.keyconfig.delete {
+          KeyCode::Char(c) => c.to_string(),
+          _ => "Enter".to_string(),
+        };
+        let q = match self.keyconfig.quit {
+          KeyCode::Char(c) => c.to_string(),
+          _ => "Esc".to_string(),
+        };
+        self.draw_command(
+          f,
+          rects[1],
+          &format!("Press <{}> to confirm or <{}> to abort.", x, q),
+          (Span::styled(label, Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+      }
+      Action::UndoPrompt => {
+        if !self.undo_diff.is_empty() {
+          let lines = self.undo_diff.clone();
+          self.draw_diff(f, rects[0], "Changes reverted by `task undo`", lines);
+        }
+        let label = "Run `task undo`?";
+        let k = match self.keyconfig.undo {
+          KeyCode::Char(c) => c.to_string(),
+          _ => "Enter".to_string(),
+        };
+        let q = match self.keyconfig.quit {
+          KeyCode::Char(c) => c.to_string(),
+          _ => "Esc".to_string(),
+        };
+        self.draw_command(
+          f,
+          rects[1],
+          &format!("Press <{}> to confirm or <{}> to abort.", k, q),
+          (Span::styled(label, Style::default().add_modifier(Modifier::BOLD)), None),
+          0,
+          false,
+          self.error.clone(),
+        );
+      }
+    }
+  }
+
+  pub fn get_dates_with_styles(&self) -> Vec<(chrono::NaiveDate, Style)> {
+    if !self.tasks.is_empty() {
+      let tasks = &self.tasks;
+      tasks
+        .iter()
+        .filter_map(|t| t.due().map(|d| (d.clone(), self.style_for_task(t))))
+        .map(|(d, t)| {
           let now = Local::now();
           let reference = TimeZone::from_utc_datetime(now.offset(), &d);
           (reference.date_naive(), t)
@@ -890,6 +2664,7 @@ impl TaskwarriorTui {
 
   fn draw_help_popup(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
     let area = centered_rect(percent_x, percent_y, f.size());
+    self.panes.register(ScrollPane::Help, area);
     f.render_widget(Clear, area);
 
     let chunks = Layout::default()
@@ -926,7 +2701,7 @@ impl TaskwarriorTui {
     let (contexts, headers) = self.get_all_contexts();
 
     let maximum_column_width = area.width;
-    let widths = self.calculate_widths(&contexts, &headers, maximum_column_width);
+    let widths = self.calculate_widths(&contexts, &headers, &[], maximum_column_width);
 
     let selected = self.contexts.table_state.current_selection().unwrap_or_default();
     let header = headers.iter();
@@ -953,7 +2728,7 @@ impl TaskwarriorTui {
       .block(
         Block::default()
           .borders(Borders::ALL)
-          .border_type(BorderType::Rounded)
+          .border_type(self.config.border_type)
           .title(Line::from(vec![Span::styled("Context", Style::default().add_modifier(Modifier::BOLD))])),
       )
       .header_style(
@@ -972,1449 +2747,3903 @@ impl TaskwarriorTui {
     f.render_stateful_widget(t, area, &mut self.contexts.table_state);
   }
 
-  fn draw_completion_pop_up(&mut self, f: &mut Frame<impl Backend>, rect: Rect, cursor_position: usize) {
-    if self.completion_list.candidates().is_empty() {
-      self.show_completion_pane = false;
-      return;
-    }
-    // Iterate through all elements in the `items` app and append some debug text to it.
-    let items: Vec<ListItem> = self
-      .completion_list
-      .candidates()
+  /// Read the end of the log file, scrolled to the most recent entry.
+  pub fn load_log(&mut self) {
+    const MAX_LOG_LINES: usize = 1000;
+    let log = std::fs::read_to_string(logging::log_file()).unwrap_or_else(|e| format!("Unable to read log file: {}", e));
+    let lines: Vec<&str> = log.lines().collect();
+    self.log_lines = lines[lines.len().saturating_sub(MAX_LOG_LINES)..].iter().map(|l| l.to_string()).collect();
+    self.log_scroll = self.log_lines.len().saturating_sub(1);
+  }
+
+  fn draw_log_viewer(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.size());
+    self.panes.register(ScrollPane::LogViewer, area);
+    f.render_widget(Clear, area);
+    let height = area.height.saturating_sub(2) as usize;
+    // keep the line at `log_scroll` at the bottom of the viewer
+    let start = (self.log_scroll + 1).saturating_sub(height);
+    let lines: Vec<Line> = self
+      .log_lines
       .iter()
-      .map(|p| {
-        let lines = vec![Line::from(vec![
-          Span::styled(p.3.clone(), Style::default().add_modifier(Modifier::BOLD)),
-          Span::from(p.4.clone()),
-        ])];
-        ListItem::new(lines)
+      .skip(start)
+      .take(height)
+      .map(|l| {
+        let style = match l.split(" | ").nth(1) {
+          Some("ERROR") => Style::default().fg(Color::Red),
+          Some("WARN") => Style::default().fg(Color::Yellow),
+          Some("DEBUG") | Some("TRACE") => Style::default().add_modifier(Modifier::DIM),
+          _ => Style::default(),
+        };
+        Line::from(Span::styled(l.as_str(), style))
       })
       .collect();
-
-    // Create a List from all list items and highlight the currently selected one
-    let items = List::new(items)
-      .block(Block::default().borders(Borders::NONE).title(""))
-      .style(self.config.uda_style_report_completion_pane)
-      .highlight_style(self.config.uda_style_report_completion_pane_highlight)
-      .highlight_symbol(&self.config.uda_selection_indicator);
-
-    let area = f.size();
-
-    let mut rect = rect;
-    rect.height = std::cmp::min(area.height / 2, self.completion_list.len() as u16 + 2);
-    rect.width = std::cmp::min(
-      area.width / 2,
-      self.completion_list.max_width().unwrap_or(40).try_into().unwrap_or(area.width / 2),
+    let title = format!("Log {}", logging::log_file().display());
+    let p = Paragraph::new(lines).block(
+      Block::default()
+        .borders(Borders::ALL)
+        .border_type(self.config.border_type)
+        .title(Span::styled(title, Style::default().add_modifier(Modifier::BOLD))),
     );
-    rect.y = rect.y.saturating_sub(rect.height);
-    if cursor_position as u16 + rect.width >= area.width {
-      rect.x = area.width - rect.width;
-    } else {
-      rect.x = cursor_position as u16;
-    }
-
-    // We can now render the item list
-    f.render_widget(Clear, rect);
-    f.render_stateful_widget(items, rect, &mut self.completion_list.state);
+    f.render_widget(p, area);
   }
 
-  fn draw_command(
-    &self,
-    f: &mut Frame<impl Backend>,
-    rect: Rect,
-    text: &str,
-    title: (Span, Option<Span>),
-    position: usize,
-    cursor: bool,
-    error: Option<String>,
-  ) {
-    // f.render_widget(Clear, rect);
-    if cursor {
-      f.set_cursor(std::cmp::min(rect.x + position as u16, rect.x + rect.width.saturating_sub(2)), rect.y + 1);
-    }
-    let rects = Layout::default()
-      .direction(Direction::Vertical)
-      .constraints([Constraint::Length(1), Constraint::Min(0)].as_ref())
-      .split(rect);
-
-    // render command title
-    let mut style = self.config.uda_style_command;
-    if error.is_some() {
-      style = style.fg(Color::Red);
-    };
-    let title_spans = if let Some(subtitle) = title.1 {
-      Line::from(vec![title.0, Span::from(" ["), subtitle, Span::from("]")])
-    } else {
-      Line::from(vec![title.0])
-    };
-    let title = Paragraph::new(Text::from(title_spans)).style(style);
-    f.render_widget(title, rects[0]);
-
-    // render command
-    let p = Paragraph::new(Text::from(text)).scroll((0, ((position + 2) as u16).saturating_sub(rects[1].width)));
-    f.render_widget(p, rects[1]);
+  /// Number of task rows visible in the report.
+  fn visible_task_rows(&self) -> usize {
+    let lines = self.task_report_rect.height.saturating_sub(1 + self.density.header_gap());
+    let rows = (lines / self.density.row_height()) as usize;
+    rows.min(self.tasks.len().saturating_sub(self.task_table_state.offset()))
   }
 
-  fn draw_task_details(&mut self, f: &mut Frame<impl Backend>, rect: Rect) {
-    if self.tasks.is_empty() {
-      let p = Paragraph::new(Text::from("Task not found")).block(Block::default().borders(Borders::TOP));
-      f.render_widget(p, rect);
-      return;
+  fn draw_hints(&mut self, f: &mut Frame<impl Backend>) {
+    let style = Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let rect = self.task_report_rect;
+    for (i, label) in hints::labels(self.visible_task_rows()).iter().enumerate() {
+      if !label.starts_with(&self.hint_input) {
+        continue;
+      }
+      let y = rect.y + 1 + self.density.header_gap() + i as u16 * self.density.row_height();
+      let area = Rect::new(rect.x, y, (label.len() as u16).min(rect.width), 1);
+      f.render_widget(Paragraph::new(Span::styled(label.clone(), style)), area);
     }
-    let selected = self.current_selection;
-    let task_id = self.tasks[selected].id().unwrap_or_default();
-    let task_uuid = *self.tasks[selected].uuid();
+  }
 
-    let data = match self.task_details.get(&task_uuid) {
-      Some(s) => s.clone(),
-      None => "Loading task details ...".to_string(),
+  fn draw_text_editor(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
+    let id = self.task_current().and_then(|t| t.id()).map(|id| id.to_string()).unwrap_or_default();
+    let title = match self.text_editor_target {
+      EditorTarget::Annotation => format!("Annotate Task {}", id),
+      EditorTarget::Description => format!("Description of Task {}", id),
+      EditorTarget::Secret => format!("Secret of Task {}", id),
     };
-    self.task_details_scroll = std::cmp::min(
-      (data.lines().count() as u16).saturating_sub(rect.height).saturating_add(2),
-      self.task_details_scroll,
-    );
-    let p = Paragraph::new(Text::from(&data[..]))
-      .block(Block::default().borders(Borders::TOP))
-      .scroll((self.task_details_scroll, 0));
-    f.render_widget(p, rect);
+    let block = Block::default()
+      .borders(Borders::ALL)
+      .border_type(self.config.border_type)
+      .title(Span::styled(title, Style::default().add_modifier(Modifier::BOLD)));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let (lines, (x, y)) = self.text_editor.wrap(inner.width as usize);
+    let scroll = y.saturating_sub(inner.height.saturating_sub(1) as usize);
+    let lines: Vec<Line> = lines.into_iter().skip(scroll).map(Line::from).collect();
+    f.render_widget(Paragraph::new(lines), inner);
+    self.cursor.place(f, inner.x + x as u16, inner.y + (y - scroll) as u16);
   }
 
-  fn task_details_scroll_up(&mut self) {
-    self.task_details_scroll = self.task_details_scroll.saturating_sub(1);
+  /// Switch between all tasks of the report and only the tasks that are ready, i.e. neither blocked
+  /// by a dependency nor waiting nor scheduled in the future.
+  pub async fn toggle_ready(&mut self) -> Result<()> {
+    self.ready_only = !self.ready_only;
+    self.update(true).await
   }
 
-  fn task_details_scroll_down(&mut self) {
-    self.task_details_scroll = self.task_details_scroll.saturating_add(1);
+  pub async fn toggle_mine(&mut self) -> Result<()> {
+    self.mine_only = !self.mine_only;
+    self.update(true).await
   }
 
-  fn task_by_index(&self, i: usize) -> Option<Task> {
-    let tasks = &self.tasks;
-    if i >= tasks.len() {
-      None
-    } else {
-      Some(tasks[i].clone())
+  /// Assign the selected tasks to `login`, or to nobody.
+  fn assign(&mut self, login: Option<&str>) -> Result<(), String> {
+    let team = match &self.config.uda_team {
+      Some(team) => team.clone(),
+      None => return Ok(()),
+    };
+    let task_uuids = self.selected_task_uuids();
+    if task_uuids.is_empty() {
+      return Ok(());
+    }
+    let previous: HashSet<String> = task_uuids
+      .iter()
+      .filter_map(|uuid| self.task_by_uuid(*uuid))
+      .map(|task| team.assignee(&task).unwrap_or_default().to_string())
+      .collect();
+    let mut command = overrides::task();
+    command.arg("rc.bulk=0").arg("rc.confirmation=off");
+    for uuid in &task_uuids {
+      command.arg(uuid.to_string());
+    }
+    command.arg("modify").arg(format!("{}:{}", team.uda, login.unwrap_or_default()));
+    let write = self
+      .run_write(&mut command)
+      .map_err(|e| format!("Cannot run `{}`: {}", Self::command_to_string(&command), e))?;
+    if let Written::Ran(output) = &write {
+      if !output.status.success() {
+        return Err(format!("Assigning failed. {}", String::from_utf8_lossy(&output.stderr)));
+      }
     }
+    // tasks assigned to different users before cannot be given back with one command
+    let compensation = (previous.len() == 1).then(|| {
+      let mut args: Vec<String> = task_uuids.iter().map(ToString::to_string).collect();
+      args.push("modify".to_string());
+      args.push(format!("{}:{}", team.uda, previous.iter().next().cloned().unwrap_or_default()));
+      args
+    });
+    self.journal_write(&write, JournalKind::Modify, &command, task_uuids, compensation);
+    Ok(())
   }
 
-  fn task_by_uuid(&self, uuid: Uuid) -> Option<Task> {
-    let tasks = &self.tasks;
-    let m = tasks.iter().find(|t| *t.uuid() == uuid);
-    m.cloned()
+  fn draw_assign(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let team = match &self.config.uda_team {
+      Some(team) => team,
+      None => return,
+    };
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
+    let mut items: Vec<ListItem> = team
+      .users
+      .iter()
+      .map(|user| {
+        let color = if self.capabilities.truecolor {
+          user.color()
+        } else {
+          capabilities::to_indexed(user.color())
+        };
+        let mut name = user.name.clone();
+        if user.login == team.me {
+          name.push_str(" (me)");
+        }
+        ListItem::new(Line::from(vec![
+          Span::styled(
+            format!(" {} ", user.initials()),
+            Style::default().fg(Color::Black).bg(color).add_modifier(Modifier::BOLD),
+          ),
+          Span::from(format!(" {}", name)),
+        ]))
+      })
+      .collect();
+    items.push(ListItem::new(Span::styled("    Nobody", Style::default().add_modifier(Modifier::DIM))));
+    let list = List::new(items)
+      .block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(self.config.border_type)
+          .title(Span::styled("Assign to", Style::default().add_modifier(Modifier::BOLD))),
+      )
+      .highlight_style(self.config.uda_style_report_selection.add_modifier(Modifier::BOLD))
+      .highlight_symbol(&self.config.uda_selection_indicator);
+    f.render_stateful_widget(list, area, &mut self.assign_state);
   }
 
-  fn task_by_id(&self, id: u64) -> Option<Task> {
-    let tasks = &self.tasks;
-    let m = tasks.iter().find(|t| t.id() == Some(id));
-    m.cloned()
+  /// Copy a link to the selected task to the clipboard.
+  fn copy_link(&mut self) {
+    let task = match self.task_current() {
+      Some(task) => task,
+      None => return,
+    };
+    let link = deeplink::format_link(&self.config.uda_deep_link_format, task.uuid());
+    self.status_message = Some(match utils::copy_to_clipboard(&link) {
+      Ok(_) => format!("Copied {}", link),
+      Err(e) => format!("Unable to copy link to clipboard: {}", e),
+    });
   }
 
-  fn task_index_by_id(&self, id: u64) -> Option<usize> {
-    let tasks = &self.tasks;
-    let m = tasks.iter().position(|t| t.id() == Some(id));
-    m
+  /// Narrow the filter of the report with filter arguments given on the command line.
+  pub async fn add_filter_args(&mut self, args: &[String]) -> Result<()> {
+    let filter = task_filter::combine(self.filter.as_str(), args);
+    self.filter.update(&filter, filter.len(), &mut self.changes);
+    self.filter_history.add(self.filter.as_str());
+    self.update(true).await
   }
 
-  fn task_index_by_uuid(&self, uuid: Uuid) -> Option<usize> {
-    let tasks = &self.tasks;
-    let m = tasks.iter().position(|t| *t.uuid() == uuid);
-    m
+  /// Select the task with `uuid`, narrowing the filter down to it when it is not part of the report.
+  pub async fn jump_to_uuid(&mut self, uuid: Uuid) -> Result<()> {
+    self.current_selection_uuid = Some(uuid);
+    self.update(true).await?;
+    if self.task_index_by_uuid(uuid).is_none() {
+      self.add_filter_args(&[format!("uuid:{}", uuid)]).await?;
+    }
+    if self.task_index_by_uuid(uuid).is_none() {
+      self.error = Some(format!("Task {} is not part of the {} report.", uuid, self.report));
+      self.mode = Mode::Tasks(Action::Error);
+    }
+    Ok(())
   }
 
-  fn style_for_task(&self, task: &Task) -> Style {
-    let virtual_tag_names_in_precedence = &self.config.rule_precedence_color;
-
-    let mut style = Style::default();
-
-    for tag_name in virtual_tag_names_in_precedence.iter().rev() {
-      if tag_name == "uda." || tag_name == "priority" {
-        if let Some(p) = task.priority() {
-          let s = self.config.color.get(&format!("color.uda.priority.{}", p)).copied().unwrap_or_default();
-          style = style.patch(s);
-        }
-      } else if tag_name == "tag." {
-        if let Some(tags) = task.tags() {
-          for t in tags {
-            let color_tag_name = format!("color.tag.{}", t);
-            let s = self.config.color.get(&color_tag_name).copied().unwrap_or_default();
-            style = style.patch(s);
-          }
-        }
-      } else if tag_name == "project." {
-        if let Some(p) = task.project() {
-          let s = self.config.color.get(&format!("color.project.{}", p)).copied().unwrap_or_default();
-          style = style.patch(s);
-        }
-      } else if task
-        .tags()
-        .unwrap_or(&vec![])
-        .contains(&tag_name.to_string().replace('.', "").to_uppercase())
-      {
-        let color_tag_name = format!("color.{}", tag_name);
-        let s = self.config.color.get(&color_tag_name).copied().unwrap_or_default();
-        style = style.patch(s);
-      }
+  /// Read the settings and key bindings again from `task show`, after the taskrc or the report
+  /// changed. Workspaces are kept when the new taskrc does not define any.
+  fn reload_config(&mut self) -> Result<()> {
+    let output = overrides::task()
+      .arg("rc.color=off")
+      .arg("rc._forcecolor=off")
+      .arg("rc.defaultwidth=0")
+      .arg("show")
+      .output()
+      .context("Unable to run `task show`.")?;
+    if !output.status.success() {
+      return Err(anyhow!("Unable to run `task show`.\n{}", String::from_utf8_lossy(&output.stderr)));
     }
-
-    style
+    let data = String::from_utf8_lossy(&output.stdout);
+    let mut config = Config::new(&data, &self.report)?;
+    config.apply_capabilities(&self.capabilities);
+    logging::set_level(config.uda_log_level.as_deref().unwrap_or("info"));
+    if config.uda_workspaces.is_empty() {
+      config.uda_workspaces = std::mem::take(&mut self.config.uda_workspaces);
+    }
+    self.keyconfig = KeyConfig::new(&data)?;
+    self.ticks = Ticks::new(&config.uda_ticks);
+    self.middleware = Pipeline::new(&self.keyconfig, Duration::from_millis(config.uda_chord_timeout));
+    self.task_report_table.date_time_vague_precise = config.uda_task_report_date_time_vague_more_precise;
+    self.task_report_table.auto_columns = config.uda_task_report_auto_columns.clone();
+    self.task_report_table.show_tracked = config.uda_focus_column;
+    self.task_report_table.show_aging = config.uda_aging_column;
+    self.task_report_table.team = config.uda_team.clone();
+    if config.uda_chips.filters != self.chips.filters {
+      self.chips = config.uda_chips.clone();
+    }
+    self.focus.source = config.uda_focus_source;
+    self.config = config;
+    Ok(())
   }
 
-  pub fn calculate_widths(&self, tasks: &[Vec<String>], headers: &[String], maximum_column_width: u16) -> Vec<usize> {
-    // naive implementation of calculate widths
-    let mut widths = headers.iter().map(String::len).collect::<Vec<usize>>();
-
-    for row in tasks.iter() {
-      for (i, cell) in row.iter().enumerate() {
-        widths[i] = std::cmp::max(cell.len(), widths[i]);
+  /// Switch to the taskrc, context, report, layout and filter of the workspace `name`, leaving
+  /// what the workspace does not set as it is.
+  pub async fn switch_workspace(&mut self, name: &str) -> Result<()> {
+    let workspace = match self.config.uda_workspaces.iter().find(|w| w.name == name) {
+      Some(workspace) => workspace.clone(),
+      None => return Ok(()),
+    };
+    if let Some(taskrc) = &workspace.taskrc {
+      overrides::set_taskrc(shellexpand::tilde(taskrc).to_string());
+    }
+    if let Some(report) = &workspace.report {
+      self.report = report.clone();
+      self.task_report_table.sort_overridden = false;
+    }
+    if workspace.taskrc.is_some() || workspace.report.is_some() {
+      self.reload_config()?;
+      let filter = self.config.filter.clone();
+      self.filter.update(&filter, filter.len(), &mut self.changes);
+    }
+    if let Some(context) = &workspace.context {
+      overrides::remove("context");
+      let output = overrides::task().arg("context").arg(context).output()?;
+      if !output.status.success() {
+        self.error = Some(format!("Unable to switch to context `{}`.", context));
       }
     }
+    if let Some(preset) = workspace.layout.as_ref().and_then(|l| self.layouts.get(l)).cloned() {
+      self.apply_layout(&preset);
+    }
+    if let Some(filter) = &workspace.filter {
+      self.filter.update(filter, filter.len(), &mut self.changes);
+    }
+    if let Err(e) = self.recent_workspaces.push(&workspace.name) {
+      warn!("Unable to save recent workspaces: {}", e);
+    }
+    self.workspace = Some(workspace.name);
+    self.update(true).await
+  }
 
-    for (i, header) in headers.iter().enumerate() {
-      if header == "Description" || header == "Definition" {
-        // always give description or definition the most room to breath
-        widths[i] = maximum_column_width as usize;
-        break;
-      }
+  /// Switch to `report`, with its own filter and sort order.
+  async fn switch_report(&mut self, report: &str) -> Result<()> {
+    if report != self.report {
+      self.report = report.to_string();
+      self.task_report_table.sort_overridden = false;
+      self.reload_config()?;
+      let filter = self.config.filter.clone();
+      self.filter.update(&filter, filter.len(), &mut self.changes);
     }
-    for (i, header) in headers.iter().enumerate() {
-      if i == 0 {
-        // always give ID a couple of extra for indicator
-        widths[i] += self.config.uda_selection_indicator.as_str().width();
-        // if let TableMode::MultipleSelection = self.task_table_state.mode() {
-        //     widths[i] += 2
-        // };
-      }
+    self.update(true).await
+  }
+
+  /// Run `task <args>` in the background and show its output as it is printed.
+  fn start_output_stream(&mut self, title: &str, args: &[String]) -> Result<(), String> {
+    if let Some(stream) = self.output_stream.as_ref().filter(|s| s.running()) {
+      return Err(format!("`{}` is still running, wait for it or cancel it first.", stream.title));
     }
+    info!("Streaming the output of `{}`", utils::command_line("task", args));
+    let args: Vec<String> = overrides::args().into_iter().chain(args.iter().cloned()).collect();
+    self.output_stream = Some(OutputStream::spawn(title, "task", &args, self.event_loop.tx.clone()));
+    self.stream_journal = None;
+    self.mode = Mode::Tasks(Action::Output);
+    Ok(())
+  }
 
-    // now start trimming
-    while (widths.iter().sum::<usize>() as u16) >= maximum_column_width - (headers.len()) as u16 {
-      let index = widths.iter().position(|i| i == widths.iter().max().unwrap_or(&0)).unwrap_or_default();
-      if widths[index] == 1 {
-        break;
+  /// Take the new output of the streamed command, and refresh the tasks once it exits. Returns
+  /// whether the output pane or the status bar changed.
+  async fn poll_output_stream(&mut self) -> Result<bool> {
+    let stream = match self.output_stream.as_mut() {
+      Some(stream) => stream,
+      None => return Ok(false),
+    };
+    let was_running = stream.running();
+    let changed = stream.poll();
+    if !was_running {
+      return Ok(changed);
+    }
+    if !stream.running() {
+      if stream.success() {
+        if let Some((kind, command, uuids)) = self.stream_journal.take() {
+          self.journal.record(kind, command, uuids, None);
+        }
       }
-      widths[index] -= 1;
+      self.update(true).await?;
     }
-
-    widths
+    // the elapsed time changes on every tick
+    Ok(true)
   }
 
-  fn draw_task_report(&mut self, f: &mut Frame<impl Backend>, rect: Rect) {
-    let (tasks, headers) = self.get_task_report();
+  fn draw_output_stream(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let stream = match &self.output_stream {
+      Some(stream) => stream,
+      None => return,
+    };
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
+    let height = area.height.saturating_sub(2) as usize;
+    // follow the end of the output
+    let lines: Vec<Line> = stream.lines[stream.lines.len().saturating_sub(height)..]
+      .iter()
+      .map(|(stderr, line)| {
+        if *stderr {
+          Line::from(Span::styled(line.clone(), Style::default().fg(Color::Red)))
+        } else {
+          Line::from(line.clone())
+        }
+      })
+      .collect();
+    let title = format!("{} - {}", stream.title, stream.status());
+    let p = Paragraph::new(lines).block(
+      Block::default()
+        .borders(Borders::ALL)
+        .border_type(self.config.border_type)
+        .title(Span::styled(title, Style::default().add_modifier(Modifier::BOLD))),
+    );
+    f.render_widget(p, area);
+  }
 
-    if tasks.is_empty() {
-      if !self.current_context.is_empty() {
-        let context_style = Style::default();
-        context_style.add_modifier(Modifier::ITALIC);
-      }
+  /// Open the urgency tuner with the coefficients of the taskrc and the tasks of the report.
+  fn open_urgency_tuner(&mut self) -> Result<(), String> {
+    let output = overrides::task()
+      .arg("rc.color=off")
+      .arg("rc._forcecolor=off")
+      .arg("show")
+      .arg("urgency")
+      .output()
+      .map_err(|e| format!("Unable to run `task show urgency`: {}", e))?;
+    let coefficients = urgency::parse(&String::from_utf8_lossy(&output.stdout));
+    if coefficients.is_empty() {
+      return Err("No urgency coefficients found in `task show urgency`.".to_string());
+    }
+    self.urgency_tuner = urgency::Tuner::new(coefficients, &self.tasks);
+    self.urgency_rankings.cancel();
+    self.mode = Mode::Tasks(Action::UrgencyTuner);
+    Ok(())
+  }
 
-      f.render_widget(Block::default(), rect);
+  /// Rank the tasks of the report again with the coefficients being tuned, as taskwarrior does.
+  fn refresh_urgency_preview(&mut self) {
+    let overrides = urgency::overrides(&self.urgency_tuner.coefficients);
+    if overrides.is_empty() || self.urgency_tuner.baseline.is_empty() {
+      self.urgency_rankings.cancel();
+      self.urgency_tuner.preview = self.urgency_tuner.baseline.clone();
       return;
     }
+    let uuids = self.urgency_tuner.baseline.iter().map(|(uuid, _)| *uuid).collect();
+    self.urgency_rankings.request(overrides, uuids, &self.event_loop.tx);
+  }
 
-    let maximum_column_width = rect.width;
-    let widths = self.calculate_widths(&tasks, &headers, maximum_column_width);
-
-    for (i, header) in headers.iter().enumerate() {
-      if header == "Description" || header == "Definition" {
-        self.task_report_table.description_width = widths[i] - 1;
-        break;
+  /// Write the changed coefficients to the taskrc.
+  fn save_urgency_coefficients(&mut self) -> Result<(), String> {
+    for coefficient in self.urgency_tuner.coefficients.iter().filter(|c| c.changed()) {
+      let output = overrides::task()
+        .arg("rc.confirmation=off")
+        .arg("config")
+        .arg(&coefficient.name)
+        .arg(coefficient.formatted())
+        .output()
+        .map_err(|e| format!("Unable to run `task config`: {}", e))?;
+      if !output.status.success() {
+        return Err(format!(
+          "Unable to set {}: {}",
+          coefficient.name,
+          String::from_utf8_lossy(&output.stderr).trim()
+        ));
       }
     }
-    let selected = self.current_selection;
-    let header = headers.iter();
-    let mut rows = vec![];
-    let mut highlight_style = Style::default();
-    let mut pos = 0;
-    for (i, task) in tasks.iter().enumerate() {
-      let style = self.style_for_task(&self.tasks[i]);
-      if i == selected {
-        pos = i;
-        highlight_style = style.patch(self.config.uda_style_report_selection);
-        if self.config.uda_selection_bold {
-          highlight_style = highlight_style.add_modifier(Modifier::BOLD);
-        }
-        if self.config.uda_selection_italic {
-          highlight_style = highlight_style.add_modifier(Modifier::ITALIC);
-        }
-        if self.config.uda_selection_dim {
-          highlight_style = highlight_style.add_modifier(Modifier::DIM);
-        }
-        if self.config.uda_selection_blink {
-          highlight_style = highlight_style.add_modifier(Modifier::SLOW_BLINK);
-        }
-        if self.config.uda_selection_reverse {
-          highlight_style = highlight_style.add_modifier(Modifier::REVERSED);
-        }
-      }
-      rows.push(Row::StyledData(task.iter(), style));
-    }
+    Ok(())
+  }
 
-    let constraints: Vec<Constraint> = widths
+  fn draw_urgency_tuner(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
+    let split = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+      .split(area);
+
+    let tuner = &mut self.urgency_tuner;
+    let highest = tuner.coefficients.iter().map(|c| c.value.abs()).fold(1.0, f64::max);
+    let items: Vec<ListItem> = tuner
+      .coefficients
       .iter()
-      .map(|i| Constraint::Length((*i).try_into().unwrap_or(maximum_column_width)))
+      .map(|c| {
+        let name = c.name.trim_start_matches("urgency.").trim_end_matches(".coefficient");
+        // a bar as long as the value relative to the largest one
+        let bar = "\u{2588}".repeat(((c.value.abs() / highest) * 10.0).round() as usize);
+        let style = if c.changed() {
+          Style::default().add_modifier(Modifier::BOLD)
+        } else {
+          Style::default()
+        };
+        ListItem::new(Line::from(vec![
+          Span::styled(format!("{:<24} {:>6} ", name, c.formatted()), style),
+          Span::styled(bar, Style::default().fg(if c.value < 0.0 { Color::Red } else { Color::Blue })),
+        ]))
+      })
       .collect();
-
-    let t = Table::new(header, rows.into_iter())
-      .header_style(
-        self
-          .config
-          .color
-          .get("color.label")
-          .copied()
-          .unwrap_or_default()
-          .add_modifier(Modifier::UNDERLINED),
+    let list = List::new(items)
+      .block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(self.config.border_type)
+          .title(Span::styled("Coefficients", Style::default().add_modifier(Modifier::BOLD))),
       )
-      .highlight_style(highlight_style)
-      .highlight_symbol(&self.config.uda_selection_indicator)
-      .mark_symbol(&self.config.uda_mark_indicator)
-      .unmark_symbol(&self.config.uda_unmark_indicator)
-      .widths(&constraints);
-
-    f.render_stateful_widget(t, rect, &mut self.task_table_state);
-    if tasks.iter().len() as u16 > rect.height.saturating_sub(4) {
-      let mut widget = Scrollbar::new(pos, tasks.iter().len());
-      widget.pos_style = self.config.uda_style_report_scrollbar;
-      widget.pos_symbol = self.config.uda_scrollbar_indicator.clone();
-      widget.area_style = self.config.uda_style_report_scrollbar_area;
-      widget.area_symbol = self.config.uda_scrollbar_area.clone();
-      f.render_widget(widget, rect);
-    }
-  }
+      .highlight_style(self.config.uda_style_report_selection.add_modifier(Modifier::BOLD))
+      .highlight_symbol(&self.config.uda_selection_indicator);
+    f.render_stateful_widget(list, split[0], &mut tuner.state);
 
-  fn get_all_contexts(&self) -> (Vec<Vec<String>>, Vec<String>) {
-    let contexts = self
-      .contexts
-      .rows
+    let lines: Vec<Line> = tuner
+      .preview
       .iter()
-      .filter(|c| &c.type_ == "read")
-      .map(|c| vec![c.name.clone(), c.definition.clone(), c.active.clone()])
+      .take(split[1].height.saturating_sub(2) as usize)
+      .map(|(uuid, value)| {
+        let moved = urgency::movement(&tuner.baseline, &tuner.preview, uuid);
+        let (arrow, style) = match moved {
+          m if m > 0 => (format!("\u{2191}{:<3}", m), Style::default().fg(Color::Green)),
+          m if m < 0 => (format!("\u{2193}{:<3}", -m), Style::default().fg(Color::Red)),
+          _ => ("    ".to_string(), Style::default()),
+        };
+        Line::from(vec![
+          Span::styled(arrow, style),
+          Span::raw(format!("{:>6.2} ", value)),
+          Span::raw(tuner.descriptions.get(uuid).cloned().unwrap_or_default()),
+        ])
+      })
       .collect();
-    let headers = vec!["Name".to_string(), "Definition".to_string(), "Active".to_string()];
-    (contexts, headers)
+    let p = Paragraph::new(lines).block(
+      Block::default()
+        .borders(Borders::ALL)
+        .border_type(self.config.border_type)
+        .title(Span::styled("Preview", Style::default().add_modifier(Modifier::BOLD))),
+    );
+    f.render_widget(p, split[1]);
   }
 
-  fn get_task_report(&mut self) -> (Vec<Vec<String>>, Vec<String>) {
-    self.task_report_table.generate_table(&self.tasks);
-    let (tasks, headers) = self.task_report_table.simplify_table();
-    (tasks, headers)
+  /// Show the start menu with the reports, the workspaces and a few actions to start with.
+  pub fn open_start_menu(&mut self) {
+    let reports = overrides::task()
+      .arg("rc.color=off")
+      .arg("rc._forcecolor=off")
+      .arg("show")
+      .arg("report")
+      .output()
+      .map(|o| startmenu::list_reports(&String::from_utf8_lossy(&o.stdout)))
+      .unwrap_or_default();
+    let workspaces: Vec<String> = self.config.uda_workspaces.iter().map(|w| w.name.clone()).collect();
+    self.start_menu = StartMenu::new(&self.report, &reports, &workspaces, &self.recent_workspaces.names);
+    self.mode = Mode::Tasks(Action::StartMenu);
   }
 
-  pub async fn update(&mut self, force: bool) -> Result<()> {
-    trace!("self.update({:?});", force);
-    if force || self.dirty || self.tasks_changed_since(self.last_export).unwrap_or(true) {
-      self.get_context()?;
-      let task_uuids = self.selected_task_uuids();
-      if self.current_selection_uuid.is_none() && self.current_selection_id.is_none() && task_uuids.len() == 1 {
-        if let Some(uuid) = task_uuids.get(0) {
-          self.current_selection_uuid = Some(*uuid);
-        }
+  async fn run_start_item(&mut self, item: StartItem) -> Result<()> {
+    self.mode = Mode::Tasks(Action::Report);
+    match item {
+      StartItem::Report(report) => self.switch_report(&report).await?,
+      StartItem::Workspace(name) => self.switch_workspace(&name).await?,
+      // typed as a key so that the prompt opens exactly as from the report
+      StartItem::AddTask => {
+        self.event_loop.tx.send(Event::Input(self.keyconfig.add)).ok();
       }
-
-      self.last_export = Some(std::time::SystemTime::now());
-      self.task_report_table.export_headers(None, &self.report)?;
-      self.export_tasks()?;
-      if self.config.uda_task_report_use_all_tasks_for_completion {
-        self.export_all_tasks()?;
+      StartItem::CommandPalette => {
+        let commands = self.palette_commands();
+        self.palette.open(commands);
+        self.mode = Mode::Tasks(Action::Palette);
       }
-      self.contexts.update_data()?;
-      self.projects.update_data()?;
-      self.update_tags();
-      self.task_details.clear();
-      self.dirty = false;
-      self.save_history()?;
-    }
-    self.cursor_fix();
-    self.update_task_table_state();
-    if self.task_report_show_info {
-      self.update_task_details().await?;
+      StartItem::Calendar => self.mode = Mode::Calendar,
+      StartItem::Help => self.mode = Mode::Tasks(Action::HelpPopup),
     }
-    self.selection_fix();
-
     Ok(())
   }
 
-  pub fn selection_fix(&mut self) {
-    if let (Some(t), Some(id)) = (self.task_current(), self.current_selection_id) {
-      if t.id() != Some(id) {
-        if let Some(i) = self.task_index_by_id(id) {
-          self.current_selection = i;
-          self.current_selection_id = None;
+  fn draw_start_menu(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
+    let items: Vec<ListItem> = self.start_menu.items.iter().map(|(label, _)| ListItem::new(label.clone())).collect();
+    let list = List::new(items)
+      .block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(self.config.border_type)
+          .title(Span::styled("Start", Style::default().add_modifier(Modifier::BOLD))),
+      )
+      .highlight_style(self.config.uda_style_report_selection.add_modifier(Modifier::BOLD))
+      .highlight_symbol(&self.config.uda_selection_indicator);
+    f.render_stateful_widget(list, area, &mut self.start_menu.state);
+  }
+
+  /// Edit the description of the current task, or write annotations for the selected tasks, in the
+  /// editor popup instead of `$EDITOR`.
+  pub fn open_text_editor(&mut self, target: EditorTarget) {
+    let task = match self.task_current() {
+      Some(task) => task,
+      None => return,
+    };
+    self.text_editor = match target {
+      EditorTarget::Annotation => TextEditor::default(),
+      EditorTarget::Description => TextEditor::new(task.description()),
+      EditorTarget::Secret => match &self.revealed_secret {
+        Some((uuid, text)) if uuid == task.uuid() => TextEditor::new(text.trim_end()),
+        _ => TextEditor::default(),
+      },
+    };
+    self.text_editor_target = target;
+    self.mode = Mode::Tasks(Action::TextEditor);
+  }
+
+  pub async fn text_editor_save(&mut self) -> Result<(), String> {
+    let text = self.text_editor.text();
+    let check = |write: io::Result<Written>, command: &std::process::Command| -> Result<Written, String> {
+      match write.map_err(|e| format!("Cannot run `{}`: {}", Self::command_to_string(command), e))? {
+        Written::Ran(output) if !output.status.success() => Err(format!("Saving failed. {}", String::from_utf8_lossy(&output.stderr))),
+        write => Ok(write),
+      }
+    };
+    match self.text_editor_target {
+      EditorTarget::Description => {
+        let task = match self.task_current() {
+          Some(task) => task,
+          None => return Ok(()),
+        };
+        let description = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if description.is_empty() || description == *task.description() {
+          return Ok(());
         }
+        let uuid = *task.uuid();
+        let mut command = overrides::task();
+        command
+          .arg("rc.confirmation=off")
+          .arg(uuid.to_string())
+          .arg("modify")
+          .arg(format!("description:{}", description));
+        let write = check(self.run_write(&mut command), &command)?;
+        let compensation = vec![uuid.to_string(), "modify".to_string(), format!("description:{}", task.description())];
+        self.journal_write(&write, JournalKind::Modify, &command, vec![uuid], Some(compensation));
+        self.current_selection_uuid = Some(uuid);
       }
-    }
-
-    if let (Some(t), Some(uuid)) = (self.task_current(), self.current_selection_uuid) {
-      if t.uuid() != &uuid {
-        if let Some(i) = self.task_index_by_uuid(uuid) {
-          self.current_selection = i;
-          self.current_selection_uuid = None;
+      EditorTarget::Annotation => {
+        let task_uuids = self.selected_task_uuids();
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+          let mut command = overrides::task();
+          command.arg("rc.bulk=0").arg("rc.confirmation=off");
+          for uuid in &task_uuids {
+            command.arg(uuid.to_string());
+          }
+          command.arg("annotate").arg("--").arg(line);
+          let write = check(self.run_write(&mut command), &command)?;
+          let compensation = Self::denotate_arguments(&task_uuids, line);
+          self.journal_write(&write, JournalKind::Annotate, &command, task_uuids.clone(), Some(compensation));
         }
       }
+      EditorTarget::Secret => {
+        let (secret, task) = match (self.config.uda_secret.clone(), self.task_current()) {
+          (Some(secret), Some(task)) => (secret, task),
+          _ => return Ok(()),
+        };
+        let value = if text.trim().is_empty() {
+          String::new()
+        } else {
+          // the command may ask for a passphrase on the terminal
+          self.pause_tui().await.map_err(|e| e.to_string())?;
+          let value = secret.encrypt(&text);
+          self.resume_tui().await.map_err(|e| e.to_string())?;
+          value.map_err(|e| e.to_string())?
+        };
+        let uuid = *task.uuid();
+        let mut command = overrides::task();
+        command
+          .arg("rc.confirmation=off")
+          .arg(uuid.to_string())
+          .arg("modify")
+          .arg(format!("{}:{}", secret.uda, value));
+        let write = check(self.run_write(&mut command), &command)?;
+        let compensation = vec![
+          uuid.to_string(),
+          "modify".to_string(),
+          format!("{}:{}", secret.uda, secret.value(&task).unwrap_or_default()),
+        ];
+        self.journal_write(&write, JournalKind::Modify, &command, vec![uuid], Some(compensation));
+        self.revealed_secret = None;
+        self.current_selection_uuid = Some(uuid);
+      }
     }
+    Ok(())
   }
 
-  pub fn save_history(&mut self) -> Result<()> {
-    self.filter_history.write()?;
-    self.command_history.write()?;
+  /// Decrypt the secret of the selected task to show it in its details.
+  async fn reveal_secret(&mut self) -> Result<(), String> {
+    let (secret, task) = match (self.config.uda_secret.clone(), self.task_current()) {
+      (Some(secret), Some(task)) => (secret, task),
+      _ => return Ok(()),
+    };
+    let text = match secret.value(&task) {
+      Some(value) => {
+        self.pause_tui().await.map_err(|e| e.to_string())?;
+        let text = secret.decrypt(value);
+        self.resume_tui().await.map_err(|e| e.to_string())?;
+        text.map_err(|e| e.to_string())?
+      }
+      None => String::new(),
+    };
+    self.revealed_secret = Some((*task.uuid(), text));
+    self.task_report_show_info = true;
     Ok(())
   }
 
-  pub fn cursor_fix(&mut self) {
-    while !self.tasks.is_empty() && self.current_selection >= self.tasks.len() {
-      self.task_report_previous();
-    }
+  fn draw_usage_stats(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.size());
+    self.panes.register(ScrollPane::UsageStats, area);
+    f.render_widget(Clear, area);
+    let heading = Style::default().add_modifier(Modifier::BOLD);
+    let mut lines = vec![];
+    for (category, title) in [("key", "Keys"), ("view", "Views"), ("command", "Commands")].iter() {
+      lines.push(Line::from(Span::styled(*title, heading)));
+      let ranked = self.usage.ranked(category);
+      if ranked.is_empty() {
+        lines.push(Line::from("  nothing yet"));
+      }
+      for (name, count) in ranked {
+        lines.push(Line::from(format!("  {:>6}  {}", count, name)));
+      }
+      lines.push(Line::from(""));
+    }
+    let mut unused: Vec<String> = self
+      .keyconfig
+      .bindings()
+      .into_iter()
+      .filter(|(name, _)| self.usage.get("key", name) == 0)
+      .map(|(name, key)| match key {
+        KeyCode::Null => name,
+        key => format!("{} ({})", name, self.keyconfig.key_label(key)),
+      })
+      .collect();
+    unused.sort();
+    lines.push(Line::from(Span::styled("Never used keys", heading)));
+    for name in unused {
+      lines.push(Line::from(format!("  {}", name)));
+    }
+    let p = Paragraph::new(lines).scroll((self.usage_scroll, 0)).block(
+      Block::default()
+        .borders(Borders::ALL)
+        .border_type(self.config.border_type)
+        .title(Span::styled("Usage Statistics", heading)),
+    );
+    f.render_widget(p, area);
   }
 
-  pub async fn update_task_details(&mut self) -> Result<()> {
-    if self.tasks.is_empty() {
-      return Ok(());
-    }
+  fn draw_perf_report(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
+    let text = self.perf.as_ref().map(|p| p.report()).unwrap_or_default();
+    let p = Paragraph::new(Text::from(text)).block(
+      Block::default()
+        .borders(Borders::ALL)
+        .border_type(self.config.border_type)
+        .title(Span::styled("Performance", Style::default().add_modifier(Modifier::BOLD))),
+    );
+    f.render_widget(p, area);
+  }
 
-    // remove task_details of tasks not in task report
-    let mut to_delete = vec![];
-    for k in self.task_details.keys() {
-      if !self.tasks.iter().map(Task::uuid).any(|x| x == k) {
-        to_delete.push(*k);
-      }
-    }
-    for k in to_delete {
-      self.task_details.remove(&k);
-    }
+  fn draw_changelog(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
+    let lines = match &self.updates.changelog {
+      None => vec!["Fetching the changelog\u{2026}".to_string()],
+      Some(Err(e)) => vec![e.clone()],
+      Some(Ok(releases)) => update::changelog(releases, update::CURRENT),
+    };
+    self.updates.scroll = std::cmp::min(self.updates.scroll, (lines.len() as u16).saturating_sub(1));
+    let p = Paragraph::new(Text::from(lines.join("\n")))
+      .wrap(Wrap { trim: false })
+      .block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(self.config.border_type)
+          .title(Span::styled(
+            format!("Changelog, v{} installed", update::CURRENT),
+            Style::default().add_modifier(Modifier::BOLD),
+          )),
+      )
+      .scroll((self.updates.scroll, 0));
+    f.render_widget(p, area);
+  }
 
-    let selected = self.current_selection;
-    if selected >= self.tasks.len() {
-      return Ok(());
-    }
-    let current_task_uuid = *self.tasks[selected].uuid();
+  fn draw_snapshot_diff(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.size());
+    self.panes.register(ScrollPane::SnapshotDiff, area);
+    f.render_widget(Clear, area);
 
-    let mut l = vec![selected];
+    let title = match self.snapshot.changes_since {
+      Some(since) => format!("Changes since {}", since.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S")),
+      None => "Changes".to_string(),
+    };
 
-    for s in 1..=self.config.uda_task_detail_prefetch {
-      l.insert(0, std::cmp::min(selected.saturating_sub(s), self.tasks.len() - 1));
-      l.push(std::cmp::min(selected + s, self.tasks.len() - 1));
+    let mut lines = vec![];
+    if self.snapshot.changes.is_empty() {
+      lines.push(Line::from("No changes found since the last refresh."));
     }
-
-    l.dedup();
-
-    let (tx, mut rx) = tokio::sync::mpsc::channel(100);
-    let tasks = self.tasks.clone();
-    let defaultwidth = self.terminal_width.saturating_sub(2);
-    for s in &l {
-      if tasks.is_empty() {
-        return Ok(());
-      }
-      if s >= &tasks.len() {
-        break;
-      }
-      let task_uuid = *tasks[*s].uuid();
-      if !self.task_details.contains_key(&task_uuid) || task_uuid == current_task_uuid {
-        debug!("Running task details for {}", task_uuid);
-        let _tx = tx.clone();
-        tokio::spawn(async move {
-          let output = tokio::process::Command::new("task")
-            .arg("rc.color=off")
-            .arg("rc._forcecolor=off")
-            .arg(format!("rc.defaultwidth={}", defaultwidth))
-            .arg(format!("{}", task_uuid))
-            .output()
-            .await;
-          if let Ok(output) = output {
-            let data = String::from_utf8_lossy(&output.stdout).to_string();
-            _tx.send(Some((task_uuid, data))).await.unwrap();
-          }
-        });
+    for change in &self.snapshot.changes {
+      let style = match change.kind {
+        ChangeKind::Added => Style::default().fg(Color::Green),
+        ChangeKind::Completed => Style::default().fg(Color::Blue),
+        ChangeKind::Deleted => Style::default().fg(Color::Red),
+        ChangeKind::Modified => Style::default().fg(Color::Yellow),
+      };
+      let id = match change.after.id() {
+        Some(id) if id > 0 => id.to_string(),
+        _ => change.after.uuid().to_string()[..8].to_string(),
+      };
+      let spans = vec![
+        Span::styled(format!("{} {:<10}", change.kind.symbol(), change.kind.label()), style),
+        Span::styled(format!("{:<9}", id), Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(change.after.description().clone()),
+      ];
+      lines.push(Line::from(spans));
+      if change.kind == ChangeKind::Modified {
+        lines.extend(diff::lines(&change.diff()));
       }
     }
-    drop(tx);
-    while let Some(Some((task_uuid, data))) = rx.recv().await {
-      self.task_details.insert(task_uuid, data);
-    }
-    Ok(())
+
+    self.snapshot.scroll = std::cmp::min(self.snapshot.scroll, (lines.len() as u16).saturating_sub(1));
+    let p = Paragraph::new(lines)
+      .block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(self.config.border_type)
+          .title(Span::styled(title, Style::default().add_modifier(Modifier::BOLD))),
+      )
+      .scroll((self.snapshot.scroll, 0));
+    f.render_widget(p, area);
   }
 
-  pub fn update_task_table_state(&mut self) {
-    trace!("self.update_task_table_state()");
-    self.task_table_state.select(Some(self.current_selection));
+  /// Draw the lines of a diff in a box at the bottom of `area`, taking at most half of it.
+  fn draw_diff(&self, f: &mut Frame<impl Backend>, area: Rect, title: &str, lines: Vec<Line>) {
+    let height = std::cmp::min(lines.len() as u16 + 2, area.height / 2);
+    if height < 3 {
+      return;
+    }
+    let rect = Rect::new(area.x, area.y + area.height - height, area.width, height);
+    f.render_widget(Clear, rect);
+    let p = Paragraph::new(lines).block(
+      Block::default()
+        .borders(Borders::ALL)
+        .border_type(self.config.border_type)
+        .title(Span::styled(title.to_string(), Style::default().add_modifier(Modifier::BOLD))),
+    );
+    f.render_widget(p, rect);
+  }
 
-    for uuid in self.marked.clone() {
-      if self.task_by_uuid(uuid).is_none() {
-        self.marked.remove(&uuid);
+  /// Diff of what the modification being typed does to each marked task, when several are marked.
+  fn modify_preview(&self) -> Vec<Line<'static>> {
+    if !matches!(self.task_table_state.mode(), TableMode::MultipleSelection) || self.marked.len() < 2 {
+      return vec![];
+    }
+    let args = match shlex::split(self.modify.as_str()) {
+      Some(args) if !args.is_empty() => args,
+      _ => return vec![],
+    };
+    let mut lines = vec![];
+    for task in self.tasks.iter().filter(|t| self.marked.contains(t.uuid())) {
+      let diffs = diff::preview_modify(task, &args);
+      if diffs.is_empty() {
+        continue;
       }
+      lines.push(Line::from(Span::styled(
+        format!("{} {}", task.id().unwrap_or_default(), task.description()),
+        Style::default().add_modifier(Modifier::BOLD),
+      )));
+      lines.extend(diff::lines(&diffs));
     }
+    lines
+  }
 
-    if self.marked.is_empty() {
-      self.task_table_state.single_selection();
+  /// Diff of the last transaction of the undo log, which `task undo` reverts.
+  fn read_undo_diff(&self) -> Vec<Line<'static>> {
+    let data_dir = shellexpand::tilde(&self.config.data_location).into_owned();
+    let data = match fs::read_to_string(Path::new(&data_dir).join("undo.data")) {
+      Ok(data) => data,
+      Err(_) => return vec![],
+    };
+    let mut lines = vec![];
+    for (old, new) in diff::last_undo_transaction(&data) {
+      let description = new.get("description").and_then(|d| d.as_str()).unwrap_or_default().to_string();
+      let header = if old.is_empty() {
+        format!("{} (removed)", description)
+      } else {
+        description
+      };
+      lines.push(Line::from(Span::styled(header, Style::default().add_modifier(Modifier::BOLD))));
+      // undoing turns the new version back into the old one
+      lines.extend(diff::lines(&diff::diff(&new, &old)));
     }
+    lines
+  }
 
-    self.task_table_state.clear();
+  fn draw_recent_actions(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
 
-    for uuid in &self.marked {
-      self.task_table_state.mark(self.task_index_by_uuid(*uuid));
+    let mut items: Vec<ListItem> = self
+      .recent_actions
+      .items
+      .iter()
+      .map(|a| {
+        ListItem::new(Line::from(vec![
+          Span::styled(format!("{} ", a.time.format("%H:%M:%S")), Style::default().add_modifier(Modifier::DIM)),
+          Span::styled(format!("{:<10}", a.kind.label()), Style::default().add_modifier(Modifier::BOLD)),
+          Span::raw(a.description.clone()),
+        ]))
+      })
+      .collect();
+    if items.is_empty() {
+      items.push(ListItem::new("No tasks were completed or deleted in this session."));
     }
+
+    let list = List::new(items)
+      .block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(self.config.border_type)
+          .title(Span::styled("Recent Actions", Style::default().add_modifier(Modifier::BOLD))),
+      )
+      .highlight_style(self.config.uda_style_report_selection.add_modifier(Modifier::BOLD))
+      .highlight_symbol(&self.config.uda_selection_indicator);
+    f.render_stateful_widget(list, area, &mut self.recent_actions.state);
   }
 
-  pub fn context_next(&mut self) {
-    let i = match self.contexts.table_state.current_selection() {
-      Some(i) => {
-        if i >= self.contexts.len() - 1 {
-          0
-        } else {
-          i + 1
-        }
-      }
-      None => 0,
-    };
-    self.contexts.table_state.select(Some(i));
+  /// Tasks a search and replace applies to: the marked tasks, or else all the tasks of the report.
+  fn replace_targets(&self) -> Vec<Task> {
+    self
+      .tasks
+      .iter()
+      .filter(|t| self.marked.is_empty() || self.marked.contains(t.uuid()))
+      .cloned()
+      .collect()
   }
 
-  pub fn context_previous(&mut self) {
-    let i = match self.contexts.table_state.current_selection() {
-      Some(i) => {
-        if i == 0 {
-          self.contexts.len() - 1
-        } else {
-          i - 1
+  /// Change the descriptions accepted in the review, each one undoable from the journal.
+  fn apply_replacements(&mut self) -> Result<(), String> {
+    let review = std::mem::take(&mut self.replace_review);
+    self.back_up_before_bulk(review.accepted().count())?;
+    let mut count = 0;
+    for change in review.accepted() {
+      let mut command = overrides::task();
+      command
+        .arg("rc.confirmation=off")
+        .arg(change.uuid.to_string())
+        .arg("modify")
+        .arg(format!("description:{}", change.after));
+      let write = self
+        .run_write(&mut command)
+        .map_err(|e| format!("Cannot run `{}`: {}", Self::command_to_string(&command), e))?;
+      if let Written::Ran(output) = &write {
+        if !output.status.success() {
+          return Err(format!(
+            "Replacing the description of task {} failed. {}",
+            change.uuid,
+            String::from_utf8_lossy(&output.stderr)
+          ));
         }
       }
-      None => 0,
+      let compensation = vec![change.uuid.to_string(), "modify".to_string(), format!("description:{}", change.before)];
+      self.journal_write(&write, JournalKind::Modify, &command, vec![change.uuid], Some(compensation));
+      count += 1;
+    }
+    self.status_message = Some(format!("Replaced {} of {} descriptions", count, review.changes.len()));
+    Ok(())
+  }
+
+  fn draw_replace_review(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let change = match self.replace_review.current() {
+      Some(change) => change,
+      None => return,
     };
-    self.contexts.table_state.select(Some(i));
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
+    let lines = vec![
+      Line::from(vec![
+        Span::styled("- ", Style::default().fg(Color::Red)),
+        Span::raw(change.before.clone()),
+      ]),
+      Line::from(vec![
+        Span::styled("+ ", Style::default().fg(Color::Green)),
+        Span::styled(change.after.clone(), Style::default().add_modifier(Modifier::BOLD)),
+      ]),
+    ];
+    let title = format!("Change {} of {}", self.replace_review.index + 1, self.replace_review.changes.len());
+    let p = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+      Block::default()
+        .borders(Borders::ALL)
+        .border_type(self.config.border_type)
+        .title(Span::styled(title, Style::default().add_modifier(Modifier::BOLD))),
+    );
+    f.render_widget(p, area);
   }
 
-  pub fn context_select(&mut self) -> Result<()> {
-    let i = self.contexts.table_state.current_selection().unwrap_or_default();
-    let mut command = std::process::Command::new("task");
-    command.arg("context").arg(&self.contexts.rows[i].name);
-    command.output()?;
+  /// Export the tasks changed lately and list their last changes.
+  fn open_activity(&mut self) -> Result<(), String> {
+    let output = overrides::task()
+      .arg("rc.json.array=on")
+      .arg("rc.confirmation=off")
+      .arg("rc.json.depends.array=on")
+      .arg("rc.color=off")
+      .arg("rc._forcecolor=off")
+      .arg(activity::filter(self.config.uda_activity_days))
+      .arg("export")
+      .output()
+      .map_err(|e| format!("Unable to run `task export`: {}", e))?;
+    let tasks = import(output.stdout.as_slice()).map_err(|e| format!("Unable to parse the changed tasks: {}", e))?;
+    self.activity = activity::feed(&tasks, self.config.uda_team.as_ref(), self.activity_seen.time());
+    self.activity_state.select((!self.activity.is_empty()).then_some(0));
+    self.mode = Mode::Tasks(Action::Activity);
     Ok(())
   }
 
-  pub fn task_report_top(&mut self) {
-    if self.tasks.is_empty() {
-      return;
+  /// Close the activity feed, its changes are no longer new.
+  fn close_activity(&mut self) {
+    if let Err(e) = self.activity_seen.mark() {
+      warn!("Unable to remember when the activity feed was seen: {}", e);
     }
-    self.current_selection = 0;
-    self.current_selection_id = None;
-    self.current_selection_uuid = None;
+    self.mode = Mode::Tasks(Action::Report);
   }
 
-  pub fn task_report_bottom(&mut self) {
-    if self.tasks.is_empty() {
-      return;
-    }
-    self.current_selection = self.tasks.len() - 1;
-    self.current_selection_id = None;
-    self.current_selection_uuid = None;
-  }
+  fn draw_activity(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
 
-  pub fn task_report_next(&mut self) {
-    if self.tasks.is_empty() {
-      return;
-    }
-    let i = {
-      if self.current_selection >= self.tasks.len() - 1 {
-        if self.config.uda_task_report_looping {
-          0
+    let now = Local::now().naive_utc();
+    let who_width = self.activity.iter().filter_map(|a| a.who.as_ref()).map(|w| w.width()).max().unwrap_or(0);
+    let mut items: Vec<ListItem> = self
+      .activity
+      .iter()
+      .map(|a| {
+        let style = if a.new {
+          Style::default().add_modifier(Modifier::BOLD)
         } else {
-          self.current_selection
+          Style::default().add_modifier(Modifier::DIM)
+        };
+        let mut spans = vec![Span::styled(format!("{:>4} ", vague_format_date_time(a.modified, now, false)), style)];
+        if who_width > 0 {
+          spans.push(Span::from(format!(
+            "{:<width$} ",
+            a.who.as_deref().unwrap_or_default(),
+            width = who_width
+          )));
         }
-      } else {
-        self.current_selection + 1
-      }
-    };
-    self.current_selection = i;
-    self.current_selection_id = None;
-    self.current_selection_uuid = None;
-  }
-
-  pub fn task_report_previous(&mut self) {
-    if self.tasks.is_empty() {
-      return;
+        spans.push(Span::styled(format!("{} {:<9} ", a.kind.symbol(), a.kind.label()), style));
+        spans.push(Span::from(a.description.clone()));
+        ListItem::new(Line::from(spans))
+      })
+      .collect();
+    if items.is_empty() {
+      items.push(ListItem::new(format!("No change in the last {} days.", self.config.uda_activity_days)));
     }
-    let i = {
-      if self.current_selection == 0 {
-        if self.config.uda_task_report_looping {
-          self.tasks.len() - 1
-        } else {
-          0
-        }
-      } else {
-        self.current_selection - 1
-      }
-    };
-    self.current_selection = i;
-    self.current_selection_id = None;
-    self.current_selection_uuid = None;
+    let new = self.activity.iter().filter(|a| a.new).count();
+    let list = List::new(items)
+      .block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(self.config.border_type)
+          .title(Span::styled(
+            format!("Activity, {} new since you last looked", new),
+            Style::default().add_modifier(Modifier::BOLD),
+          )),
+      )
+      .highlight_style(self.config.uda_style_report_selection.add_modifier(Modifier::BOLD))
+      .highlight_symbol(&self.config.uda_selection_indicator);
+    f.render_stateful_widget(list, area, &mut self.activity_state);
   }
 
-  pub fn task_report_next_page(&mut self) {
-    if self.tasks.is_empty() {
-      return;
-    }
-    let i = {
-      if self.current_selection == self.tasks.len() - 1 {
-        if self.config.uda_task_report_looping {
-          0
-        } else {
-          self.tasks.len() - 1
-        }
-      } else {
-        std::cmp::min(
-          self
-            .current_selection
-            .checked_add(self.task_report_height as usize)
-            .unwrap_or(self.tasks.len() - 1),
-          self.tasks.len() - 1,
-        )
-      }
-    };
-    self.current_selection = i;
-    self.current_selection_id = None;
-    self.current_selection_uuid = None;
+  fn open_backups(&mut self) -> Result<(), String> {
+    self
+      .backups
+      .load()
+      .map_err(|e| format!("Unable to list the backups in {:?}: {}", self.backup_dir(), e))?;
+    self.backups.state.select((!self.backups.backups.is_empty()).then_some(0));
+    self.mode = Mode::Tasks(Action::Backups);
+    Ok(())
   }
 
-  pub fn task_report_previous_page(&mut self) {
-    if self.tasks.is_empty() {
-      return;
-    }
-    let i = {
-      if self.current_selection == 0 {
-        if self.config.uda_task_report_looping {
-          self.tasks.len() - 1
-        } else {
-          0
-        }
-      } else {
-        self.current_selection.saturating_sub(self.task_report_height as usize)
-      }
+  /// Restore the selected backup, once the data it replaces was backed up too.
+  fn restore_backup(&mut self) -> Result<(), String> {
+    let backup = match self.backups.selected() {
+      Some(backup) => backup.clone(),
+      None => return Ok(()),
     };
-    self.current_selection = i;
-    self.current_selection_id = None;
-    self.current_selection_uuid = None;
+    let data_dir = shellexpand::tilde(&self.config.data_location).into_owned();
+    self
+      .backups
+      .restore(&backup, Path::new(&data_dir), Local::now().naive_local())
+      .map_err(|e| e.to_string())?;
+    self.status_message = Some(format!("Restored the backup of {}", backup.time.format("%Y-%m-%d %H:%M:%S")));
+    Ok(())
   }
 
-  pub fn task_report_jump(&mut self) -> Result<()> {
-    if self.tasks.is_empty() {
-      return Ok(());
-    }
-    let i = self.command.as_str().parse::<usize>()?;
-    if let Some(task) = self.task_by_id(i as u64) {
-      let j = self.task_index_by_uuid(*task.uuid()).unwrap_or_default();
-      self.current_selection = j;
-      self.current_selection_id = None;
-      self.current_selection_uuid = None;
-      Ok(())
-    } else {
-      Err(anyhow!("Cannot locate task id {} in report", i))
-    }
-  }
+  fn draw_backups(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
 
-  fn get_task_files_max_mtime(&self) -> Result<SystemTime> {
-    let data_dir = shellexpand::tilde(&self.config.data_location).into_owned();
-    ["backlog.data", "completed.data", "pending.data"]
+    let mut items: Vec<ListItem> = self
+      .backups
+      .backups
       .iter()
-      .map(|n| fs::metadata(Path::new(&data_dir).join(n)).map(|m| m.modified()))
-      .filter_map(Result::ok)
-      .filter_map(Result::ok)
-      .max()
-      .ok_or_else(|| anyhow!("Unable to get task files max time"))
-  }
-
-  pub fn tasks_changed_since(&mut self, prev: Option<SystemTime>) -> Result<bool> {
-    if let Some(prev) = prev {
-      let mtime = self.get_task_files_max_mtime()?;
-      if mtime > prev {
-        Ok(true)
-      } else {
-        // Unfortunately, we can not use std::time::Instant which is guaranteed to be monotonic,
-        // because we need to compare it to a file mtime as SystemTime, so as a safety for unexpected
-        // time shifts, cap maximum wait to 1 min
-        let now = SystemTime::now();
-        let max_delta = Duration::from_secs(60);
-        Ok(now.duration_since(prev)? > max_delta)
-      }
-    } else {
-      Ok(true)
+      .map(|b| {
+        ListItem::new(Line::from(vec![
+          Span::from(b.time.format("%Y-%m-%d %H:%M:%S").to_string()),
+          Span::styled(format!(" {:<9}", b.reason.label()), Style::default().add_modifier(Modifier::BOLD)),
+          Span::styled(
+            format!(" {} file{}, {} KiB", b.files, if b.files == 1 { "" } else { "s" }, b.bytes.div_ceil(1024)),
+            Style::default().add_modifier(Modifier::DIM),
+          ),
+        ]))
+      })
+      .collect();
+    if items.is_empty() {
+      items.push(ListItem::new("No backup yet, \"Back up tasks now\" in the command palette takes one."));
     }
+    let list = List::new(items)
+      .block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(self.config.border_type)
+          .title(Span::styled("Backups", Style::default().add_modifier(Modifier::BOLD))),
+      )
+      .highlight_style(self.config.uda_style_report_selection.add_modifier(Modifier::BOLD))
+      .highlight_symbol(&self.config.uda_selection_indicator);
+    f.render_stateful_widget(list, area, &mut self.backups.state);
   }
 
-  pub fn export_all_tasks(&mut self) -> Result<()> {
-    let mut task = std::process::Command::new("task");
-
-    task
+  /// Export the tasks still to do and list the projects without a next action among them.
+  fn open_stalled_projects(&mut self) -> Result<(), String> {
+    let output = overrides::task()
       .arg("rc.json.array=on")
       .arg("rc.confirmation=off")
       .arg("rc.json.depends.array=on")
       .arg("rc.color=off")
-      .arg("rc._forcecolor=off");
-    // .arg("rc.verbose:override=false");
-
-    task.arg("export");
+      .arg("rc._forcecolor=off")
+      .arg("( status:pending or status:waiting )")
+      .arg("export")
+      .output()
+      .map_err(|e| format!("Unable to run `task export`: {}", e))?;
+    let tasks = import(output.stdout.as_slice()).map_err(|e| format!("Unable to parse the pending tasks: {}", e))?;
+    self.stalled_projects = nextaction::stalled_projects(&tasks, &self.config.uda_next_action_tag, Local::now().naive_utc());
+    self.stalled_state.select((!self.stalled_projects.is_empty()).then_some(0));
+    self.mode = Mode::Tasks(Action::StalledProjects);
+    Ok(())
+  }
 
-    task.arg("all");
+  fn draw_stalled_projects(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
 
-    info!("Running `{:?}`", task);
-    let output = task.output()?;
-    let data = String::from_utf8_lossy(&output.stdout);
-    let error = String::from_utf8_lossy(&output.stderr);
+    let mut items: Vec<ListItem> = self
+      .stalled_projects
+      .iter()
+      .map(|p| {
+        ListItem::new(Line::from(vec![
+          Span::styled(p.name.clone(), self.config.project_style(&p.name)),
+          Span::styled(
+            format!(" {} task{} to do", p.pending, if p.pending == 1 { "" } else { "s" }),
+            Style::default().add_modifier(Modifier::DIM),
+          ),
+        ]))
+      })
+      .collect();
+    if items.is_empty() {
+      items.push(ListItem::new("Every project has a next action."));
+    }
+    let list = List::new(items)
+      .block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(self.config.border_type)
+          .title(Span::styled(
+            "Projects without a next action",
+            Style::default().add_modifier(Modifier::BOLD),
+          )),
+      )
+      .highlight_style(self.config.uda_style_report_selection.add_modifier(Modifier::BOLD))
+      .highlight_symbol(&self.config.uda_selection_indicator);
+    f.render_stateful_widget(list, area, &mut self.stalled_state);
+  }
 
-    if output.status.success() {
-      if let Ok(imported) = import(data.as_bytes()) {
-        self.all_tasks = imported;
-        info!("Imported {} tasks", self.tasks.len());
-        self.error = None;
-        if self.mode == Mode::Tasks(Action::Error) {
-          self.mode = self.previous_mode.clone().unwrap_or(Mode::Tasks(Action::Report));
-          self.previous_mode = None;
-        }
-      } else {
-        self.error = Some(format!("Unable to parse output of `{:?}`:\n`{:?}`", task, data));
-        self.mode = Mode::Tasks(Action::Error);
-        debug!("Unable to parse output: {:?}", data);
+  /// Open the checklist of the weekly review, starting one if none is in progress. The step whose
+  /// tasks were shown is checked off.
+  async fn open_review(&mut self) -> Result<(), String> {
+    let steps = self.config.uda_review_steps.clone();
+    self.review.start(self.filter.as_str()).map_err(|e| e.to_string())?;
+    match self.review.finish_current(&steps).map_err(|e| e.to_string())? {
+      Some(filter) => {
+        self.finish_review(&filter);
+        self.update(true).await.map_err(|e| e.to_string())?;
+      }
+      None => {
+        self.review_state.select(Some(self.review.next_step(&steps)));
+        self.mode = Mode::Tasks(Action::Review);
       }
-    } else {
-      self.error = Some(format!("Cannot run `{:?}` - ({}) error:\n{}", &task, output.status, error));
     }
-
     Ok(())
   }
 
-  pub fn export_tasks(&mut self) -> Result<()> {
-    let mut task = std::process::Command::new("task");
-
-    task
-      .arg("rc.json.array=on")
-      .arg("rc.confirmation=off")
-      .arg("rc.json.depends.array=on")
-      .arg("rc.color=off")
-      .arg("rc._forcecolor=off");
-    // .arg("rc.verbose:override=false");
+  /// Restore the filter from before the review, which is now complete.
+  fn finish_review(&mut self, filter: &str) {
+    self.filter.update(filter, filter.len(), &mut self.changes);
+    self.mode = Mode::Tasks(Action::Report);
+    self.status_message = Some("Weekly review completed".to_string());
+  }
 
-    if let Some(args) = shlex::split(format!(r#"rc.report.{}.filter='{}'"#, self.report, self.filter.trim()).trim()) {
-      for arg in args {
-        task.arg(arg);
-      }
-    }
+  fn draw_review(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
 
-    if !self.current_context_filter.trim().is_empty() && self.task_version >= *TASKWARRIOR_VERSION_SUPPORTED {
-      if let Some(args) = shlex::split(&self.current_context_filter) {
-        for arg in args {
-          task.arg(arg);
-        }
-      }
-    } else if !self.current_context_filter.trim().is_empty() {
-      task.arg(format!("'\\({}\\)'", self.current_context_filter));
-    }
+    let items: Vec<ListItem> = self
+      .config
+      .uda_review_steps
+      .iter()
+      .map(|step| {
+        let (mark, style) = if self.review.is_done(step) {
+          ("[x] ", Style::default().add_modifier(Modifier::DIM))
+        } else {
+          ("[ ] ", Style::default())
+        };
+        ListItem::new(vec![
+          Line::from(Span::styled(format!("{}{}", mark, step.label), style)),
+          Line::from(Span::styled(format!("    {}", step.filter), Style::default().add_modifier(Modifier::DIM))),
+        ])
+      })
+      .collect();
+    let completed = self.review.state.completed.as_deref().and_then(|c| DateTime::parse_from_rfc3339(c).ok());
+    let title = match completed {
+      Some(completed) => format!(
+        "Weekly Review (last completed {} ago)",
+        vague_format_date_time(completed.naive_utc(), Local::now().naive_utc(), false)
+      ),
+      None => "Weekly Review".to_string(),
+    };
+    let list = List::new(items)
+      .block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(self.config.border_type)
+          .title(Span::styled(title, Style::default().add_modifier(Modifier::BOLD))),
+      )
+      .highlight_style(self.config.uda_style_report_selection.add_modifier(Modifier::BOLD))
+      .highlight_symbol(&self.config.uda_selection_indicator);
+    f.render_stateful_widget(list, area, &mut self.review_state);
+  }
 
-    task.arg("export");
+  /// Export the tasks linked to a goal, whatever their status, and open the goals view.
+  fn open_goals(&mut self) -> Result<(), String> {
+    let output = overrides::task()
+      .arg("rc.json.array=on")
+      .arg("rc.confirmation=off")
+      .arg("rc.json.depends.array=on")
+      .arg("rc.color=off")
+      .arg("rc._forcecolor=off")
+      .arg(format!("{}.any:", goals::UDA))
+      .arg("export")
+      .output()
+      .map_err(|e| format!("Unable to run `task export`: {}", e))?;
+    let tasks = if output.status.success() {
+      import(output.stdout.as_slice()).map_err(|e| format!("Unable to parse the tasks linked to goals: {}", e))?
+    } else {
+      // the goal UDA is not defined yet, so that no task can be linked to a goal
+      vec![]
+    };
+    self.goal_progress = goals::progress(&self.config.uda_goals, &tasks);
+    self.goals_state.select((!self.goal_progress.is_empty()).then_some(0));
+    self.mode = Mode::Tasks(Action::Goals);
+    Ok(())
+  }
 
-    if self.task_version >= *TASKWARRIOR_VERSION_SUPPORTED {
-      task.arg(&self.report);
+  fn draw_goals(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
+
+    let now = Local::now().naive_utc();
+    let width = 20;
+    let mut items: Vec<ListItem> = self
+      .goal_progress
+      .iter()
+      .map(|p| {
+        let mut title = vec![Span::styled(p.goal.name.clone(), Style::default().add_modifier(Modifier::BOLD))];
+        if let Some(description) = &p.goal.description {
+          title.push(Span::raw(format!(" {}", description)));
+        }
+        let filled = (p.ratio() * width as f64).round() as usize;
+        let mut progress = vec![
+          Span::raw("  "),
+          Span::styled("\u{2588}".repeat(filled), Style::default().fg(Color::Green)),
+          Span::styled("\u{2591}".repeat(width - filled), Style::default().add_modifier(Modifier::DIM)),
+          Span::raw(format!(" {:>3.0}% {}/{} tasks", p.ratio() * 100.0, p.completed, p.total)),
+        ];
+        if let Some(horizon) = p.horizon() {
+          let overdue = horizon < now && p.completed < p.total;
+          progress.push(Span::styled(
+            format!(", due {} ({})", horizon.format("%Y-%m-%d"), vague_format_date_time(now, horizon, false)),
+            if overdue { Style::default().fg(Color::Red) } else { Style::default() },
+          ));
+        }
+        let mut lines = vec![Line::from(title), Line::from(progress)];
+        for description in p.remaining.iter().take(3) {
+          lines.push(Line::from(Span::styled(
+            format!("  {}", description),
+            Style::default().add_modifier(Modifier::DIM),
+          )));
+        }
+        if p.remaining.len() > 3 {
+          lines.push(Line::from(Span::styled(
+            format!("  and {} more", p.remaining.len() - 3),
+            Style::default().add_modifier(Modifier::DIM),
+          )));
+        }
+        ListItem::new(lines)
+      })
+      .collect();
+    if items.is_empty() {
+      items.push(ListItem::new(format!("No goals yet. Link tasks to a goal with `{}:<name>`.", goals::UDA)));
     }
 
-    info!("Running `{:?}`", task);
-    let output = task.output()?;
-    let data = String::from_utf8_lossy(&output.stdout);
-    let error = String::from_utf8_lossy(&output.stderr);
+    let list = List::new(items)
+      .block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(self.config.border_type)
+          .title(Span::styled("Goals", Style::default().add_modifier(Modifier::BOLD))),
+      )
+      .highlight_style(self.config.uda_style_report_selection.add_modifier(Modifier::BOLD))
+      .highlight_symbol(&self.config.uda_selection_indicator);
+    f.render_stateful_widget(list, area, &mut self.goals_state);
+  }
 
-    if output.status.success() {
-      if let Ok(imported) = import(data.as_bytes()) {
-        self.tasks = imported;
-        info!("Imported {} tasks", self.tasks.len());
-        self.error = None;
-        if self.mode == Mode::Tasks(Action::Error) {
-          self.mode = self.previous_mode.clone().unwrap_or(Mode::Tasks(Action::Report));
-          self.previous_mode = None;
+  fn draw_rules(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
+
+    let mut items: Vec<ListItem> = self
+      .rule_matches
+      .iter()
+      .map(|m| {
+        let mut lines = vec![Line::from(vec![
+          Span::styled(format!("{} ", m.rule.name), Style::default().add_modifier(Modifier::BOLD)),
+          Span::raw(format!("{} tasks: {}", m.tasks.len(), m.rule.action.label())),
+        ])];
+        for (_, description) in m.tasks.iter().take(5) {
+          lines.push(Line::from(Span::styled(
+            format!("  {}", description),
+            Style::default().add_modifier(Modifier::DIM),
+          )));
         }
-      } else {
-        self.error = Some(format!("Unable to parse output of `{:?}`:\n`{:?}`", task, data));
-        self.mode = Mode::Tasks(Action::Error);
-        debug!("Unable to parse output: {:?}", data);
-      }
-    } else {
-      self.error = Some(format!("Cannot run `{:?}` - ({}) error:\n{}", &task, output.status, error));
+        if m.tasks.len() > 5 {
+          lines.push(Line::from(Span::styled(
+            format!("  and {} more", m.tasks.len() - 5),
+            Style::default().add_modifier(Modifier::DIM),
+          )));
+        }
+        ListItem::new(lines)
+      })
+      .collect();
+    if items.is_empty() {
+      items.push(ListItem::new("No rule applies to any task."));
     }
 
-    Ok(())
+    let list = List::new(items)
+      .block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(self.config.border_type)
+          .title(Span::styled("Archive Rules", Style::default().add_modifier(Modifier::BOLD))),
+      )
+      .highlight_style(self.config.uda_style_report_selection.add_modifier(Modifier::BOLD))
+      .highlight_symbol(&self.config.uda_selection_indicator);
+    f.render_stateful_widget(list, area, &mut self.rules_state);
   }
 
-  pub fn selected_task_uuids(&self) -> Vec<Uuid> {
-    let selected = match self.task_table_state.mode() {
-      TableMode::SingleSelection => vec![self.current_selection],
-      TableMode::MultipleSelection => self.task_table_state.marked().copied().collect::<Vec<usize>>(),
+  fn draw_spell_suggestions(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let suggestions = match &mut self.spell_suggestions {
+      Some(s) => s,
+      None => return,
     };
-
-    let mut task_uuids = vec![];
-
-    for s in selected {
-      if self.tasks.is_empty() {
-        break;
-      }
-      let task_id = self.tasks[s].id().unwrap_or_default();
-      let task_uuid = *self.tasks[s].uuid();
-      task_uuids.push(task_uuid);
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
+    let mut items: Vec<ListItem> = suggestions.items.iter().map(|s| ListItem::new(s.as_str())).collect();
+    if items.is_empty() {
+      items.push(ListItem::new("No suggestions."));
     }
+    let list = List::new(items)
+      .block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(self.config.border_type)
+          .title(Span::styled("Suggestions", Style::default().add_modifier(Modifier::BOLD))),
+      )
+      .highlight_style(self.config.uda_style_report_selection.add_modifier(Modifier::BOLD))
+      .highlight_symbol(&self.config.uda_selection_indicator);
+    f.render_stateful_widget(list, area, &mut suggestions.state);
+  }
 
-    task_uuids
+  /// Open the spelling suggestions for the word at the cursor of the prompt of `action`.
+  pub fn open_spell_suggestions(&mut self, action: Action) {
+    let checker = match &self.spellchecker {
+      Some(c) => c,
+      None => return,
+    };
+    let buffer = if action == Action::Modify { &self.modify } else { &self.command };
+    let text = buffer.as_str();
+    let pos = buffer.pos();
+    let word = match spellcheck::words(text).into_iter().find(|w| w.start <= pos && pos <= w.end) {
+      Some(w) => w,
+      None => return,
+    };
+    let items = checker.suggestions(&text[word.clone()]);
+    self.spell_suggestions = Some(SpellSuggestions::new(word, items));
+    self.previous_mode = Some(self.mode.clone());
+    self.mode = Mode::Tasks(Action::SpellSuggestions);
   }
 
-  pub fn task_subprocess(&mut self) -> Result<(), String> {
-    let task_uuids = if self.tasks.is_empty() { vec![] } else { self.selected_task_uuids() };
+  fn draw_calculator(&mut self, f: &mut Frame<impl Backend>) {
+    let size = f.size();
+    let width = size.width.saturating_sub(4).min(60);
+    let area = Rect::new(
+      size.x + (size.width - width) / 2,
+      size.y + size.height.saturating_sub(5) / 2,
+      width,
+      5.min(size.height),
+    );
+    f.render_widget(Clear, area);
+    let inserts = matches!(
+      self.previous_mode,
+      Some(Mode::Tasks(
+        Action::Add | Action::Log | Action::Annotate | Action::Modify | Action::Filter
+      ))
+    );
+    let result = match &self.calculator.result {
+      Some(Ok(value)) => Span::styled(format!("= {}", value), Style::default().add_modifier(Modifier::BOLD)),
+      Some(Err(e)) => Span::styled(e.clone(), Style::default().add_modifier(Modifier::DIM)),
+      None => Span::styled("e.g. eom - 3d, now + 2h, 2wk / 5", Style::default().add_modifier(Modifier::DIM)),
+    };
+    let hint = if inserts { "Enter inserts the result, Esc closes" } else { "Esc closes" };
+    let block = Block::default()
+      .borders(Borders::ALL)
+      .border_type(self.config.border_type)
+      .title(Span::styled("Calculator", Style::default().add_modifier(Modifier::BOLD)));
+    let inner = block.inner(area);
+    let text = vec![
+      Line::from(self.calculator.input.as_str()),
+      Line::from(result),
+      Line::from(Span::styled(hint, Style::default().add_modifier(Modifier::DIM))),
+    ];
+    f.render_widget(Paragraph::new(text).block(block), area);
+    let position = Self::get_position(&self.calculator.input) as u16;
+    self.cursor.place(f, inner.x + position.min(inner.width.saturating_sub(1)), inner.y);
+  }
 
-    let shell = self.command.as_str();
+  /// Open the calculator over the report or the prompt of `action`.
+  fn open_calculator(&mut self, action: Action) {
+    self.previous_mode = Some(Mode::Tasks(action));
+    self.mode = Mode::Tasks(Action::Calculator);
+  }
 
-    let r = match shlex::split(shell) {
-      Some(cmd) => {
-        if cmd.is_empty() {
-          Err(format!("Shell command empty: {}", shell))
-        } else {
-          // first argument must be a binary
-          let mut command = std::process::Command::new(&cmd[0]);
-          // remaining arguments are args
-          for (i, s) in cmd.iter().enumerate() {
-            if i == 0 {
-              continue;
-            }
-            command.arg(s);
-          }
-          let output = command.output();
-          match output {
-            Ok(o) => {
-              let output = String::from_utf8_lossy(&o.stdout);
-              if !output.is_empty() {
-                Err(format!("Shell command `{}` ran successfully but printed the following output:\n\n{}\n\nSuppress output of shell commands to prevent the error prompt from showing up.", shell, output))
-              } else {
-                Ok(())
-              }
-            }
-            Err(_) => Err(format!("Shell command `{}` exited with non-zero output", shell)),
-          }
-        }
-      }
-      None => Err(format!("Cannot run subprocess. Unable to shlex split `{}`", shell)),
+  /// Type the result of the calculator at the cursor of the prompt of `action`.
+  fn insert_calculator_result(&mut self, action: Action) {
+    let value = match &self.calculator.result {
+      Some(Ok(value)) => value.clone(),
+      _ => return,
+    };
+    let buffer = match action {
+      Action::Modify => &mut self.modify,
+      Action::Filter => &mut self.filter,
+      Action::Add | Action::Log | Action::Annotate => &mut self.command,
+      _ => return,
     };
+    let pos = buffer.pos();
+    let text = format!("{}{}{}", &buffer.as_str()[..pos], value, &buffer.as_str()[pos..]);
+    buffer.update(&text, pos + value.len(), &mut self.changes);
+    if action == Action::Filter {
+      self.dirty = true;
+    }
+  }
 
-    if task_uuids.len() == 1 {
-      if let Some(uuid) = task_uuids.get(0) {
-        self.current_selection_uuid = Some(*uuid);
+  fn apply_spell_suggestion(&mut self, action: Action) {
+    if let Some(suggestions) = self.spell_suggestions.take() {
+      if let Some(replacement) = suggestions.selected() {
+        let buffer = if action == Action::Modify { &mut self.modify } else { &mut self.command };
+        let text = format!(
+          "{}{}{}",
+          &buffer.as_str()[..suggestions.word.start],
+          replacement,
+          &buffer.as_str()[suggestions.word.end..]
+        );
+        buffer.update(&text, suggestions.word.start + replacement.len(), &mut self.changes);
       }
     }
-
-    r
   }
 
-  pub fn task_log(&mut self) -> Result<(), String> {
-    let mut command = std::process::Command::new("task");
-
-    command.arg("log");
-
-    let shell = self.command.as_str();
+  fn draw_action_history(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
 
-    match shlex::split(shell) {
-      Some(cmd) => {
-        for s in cmd {
-          command.arg(&s);
-        }
-        let output = command.output();
-        match output {
-          Ok(_) => Ok(()),
-          Err(_) => Err(format!("Cannot run `task log {}`. Check documentation for more information", shell)),
-        }
-      }
-      None => Err(format!("Unable to run `{:?}`: shlex::split(`{}`) failed.", command, shell)),
+    let mut items: Vec<ListItem> = self
+      .journal
+      .entries
+      .iter()
+      .map(|e| {
+        let mut lines = vec![Line::from(vec![
+          Span::styled(format!("{} ", e.local_time()), Style::default().add_modifier(Modifier::DIM)),
+          Span::styled(format!("{:<11}", e.kind.label()), Style::default().add_modifier(Modifier::BOLD)),
+          Span::raw(e.command.clone()),
+        ])];
+        let compensation = if e.compensated {
+          "already reverted".to_string()
+        } else {
+          e.compensation_command().unwrap_or_else(|| "no compensating action".to_string())
+        };
+        lines.push(Line::from(Span::styled(
+          format!("{:>20}\u{21b3} {}", "", compensation),
+          Style::default().add_modifier(Modifier::DIM),
+        )));
+        ListItem::new(lines)
+      })
+      .collect();
+    if items.is_empty() {
+      items.push(ListItem::new("No actions recorded yet."));
     }
+
+    let list = List::new(items)
+      .block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(self.config.border_type)
+          .title(Span::styled("Action History", Style::default().add_modifier(Modifier::BOLD))),
+      )
+      .highlight_style(self.config.uda_style_report_selection.add_modifier(Modifier::BOLD))
+      .highlight_symbol(&self.config.uda_selection_indicator);
+    f.render_stateful_widget(list, area, &mut self.journal.state);
   }
 
-  pub fn task_background(&mut self) {
-    let shell = self.config.uda_background_process.clone();
-    if shell.is_empty() {
+  fn draw_completion_pop_up(&mut self, f: &mut Frame<impl Backend>, rect: Rect, cursor_position: usize) {
+    if self.completion_list.candidates().is_empty() {
+      self.show_completion_pane = false;
       return;
     }
-    let shell = shellexpand::tilde(&shell).into_owned();
-    let period = self.config.uda_background_process_period;
-    std::thread::spawn(move || loop {
-      std::thread::sleep(Duration::from_secs(period as u64));
-      match shlex::split(&shell) {
-        Some(cmd) => {
-          let mut command = std::process::Command::new(&cmd[0]);
-          for s in cmd.iter().skip(1) {
-            command.arg(s);
-          }
-          if let Ok(output) = command.output() {
-            if !output.status.success() {
-              break;
-            }
-          } else {
-            break;
-          }
-        }
-        None => break,
-      };
-    });
-  }
-
-  pub async fn task_shortcut(&mut self, s: usize) -> Result<(), String> {
-    self.pause_tui().await.unwrap();
-
-    let task_uuids = if self.tasks.is_empty() { vec![] } else { self.selected_task_uuids() };
+    // Iterate through all elements in the `items` app and append some debug text to it.
+    let items: Vec<ListItem> = self
+      .completion_list
+      .candidates()
+      .iter()
+      .map(|p| {
+        let lines = vec![Line::from(vec![
+          Span::styled(p.3.clone(), Style::default().add_modifier(Modifier::BOLD)),
+          Span::from(p.4.clone()),
+        ])];
+        ListItem::new(lines)
+      })
+      .collect();
 
-    let shell = &self.config.uda_shortcuts[s];
+    // Create a List from all list items and highlight the currently selected one
+    let items = List::new(items)
+      .block(Block::default().borders(Borders::NONE).title(""))
+      .style(self.config.uda_style_report_completion_pane)
+      .highlight_style(self.config.uda_style_report_completion_pane_highlight)
+      .highlight_symbol(&self.config.uda_selection_indicator);
 
-    if shell.is_empty() {
-      self.resume_tui().await.unwrap();
-      return Err("Trying to run empty shortcut.".to_string());
-    }
+    let area = f.size();
 
-    let shell = format!(
-      "{} {}",
-      shell,
-      task_uuids.iter().map(ToString::to_string).collect::<Vec<String>>().join(" ")
+    let mut rect = rect;
+    rect.height = std::cmp::min(area.height / 2, self.completion_list.len() as u16 + 2);
+    rect.width = std::cmp::min(
+      area.width / 2,
+      self.completion_list.max_width().unwrap_or(40).try_into().unwrap_or(area.width / 2),
     );
+    rect.y = rect.y.saturating_sub(rect.height);
+    if cursor_position as u16 + rect.width >= area.width {
+      rect.x = area.width - rect.width;
+    } else {
+      rect.x = cursor_position as u16;
+    }
 
-    let shell = shellexpand::tilde(&shell).into_owned();
-    let r = match shlex::split(&shell) {
-      Some(cmd) => {
-        let mut command = std::process::Command::new(&cmd[0]);
-        for i in cmd.iter().skip(1) {
-          command.arg(i);
-        }
-        match command.spawn() {
-          Ok(child) => {
-            let output = child.wait_with_output();
-            match output {
-              Ok(o) => {
-                if o.status.success() {
-                  Ok(())
-                } else {
-                  Err(format!(
-                    "Unable to run shortcut {}. Status Code: {} - stdout: {} stderr: {}",
-                    s,
-                    o.status.code().unwrap_or_default(),
-                    String::from_utf8_lossy(&o.stdout),
-                    String::from_utf8_lossy(&o.stderr),
-                  ))
-                }
-              }
-              Err(s) => Err(format!("`{}` failed to wait with output: {}", shell, s)),
+    // We can now render the item list
+    f.render_widget(Clear, rect);
+    f.render_stateful_widget(items, rect, &mut self.completion_list.state);
+  }
+
+  fn draw_command(
+    &self,
+    f: &mut Frame<impl Backend>,
+    rect: Rect,
+    text: &str,
+    title: (Span, Option<Span>),
+    position: usize,
+    cursor: bool,
+    error: Option<String>,
+  ) {
+    // f.render_widget(Clear, rect);
+    if cursor {
+      self.cursor.place(
+        f,
+        std::cmp::min(rect.x + position as u16, rect.x + rect.width.saturating_sub(2)),
+        rect.y + 1,
+      );
+    }
+    let rects = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Length(1), Constraint::Min(0)].as_ref())
+      .split(rect);
+
+    // render command title
+    let mut style = self.config.uda_style_command;
+    if error.is_some() {
+      style = style.fg(Color::Red);
+    };
+    let mut title_spans = if let Some(subtitle) = title.1 {
+      Line::from(vec![title.0, Span::from(" ["), subtitle, Span::from("]")])
+    } else {
+      Line::from(vec![title.0])
+    };
+    if let Some((token, resolved)) = self.date_preview.as_ref().filter(|_| !self.degraded()) {
+      if cursor && text.contains(token.as_str()) {
+        title_spans.spans.push(Span::from(format!("  {} \u{2192} ", token)));
+        title_spans
+          .spans
+          .push(Span::styled(resolved.clone(), Style::default().add_modifier(Modifier::BOLD)));
+        let load = self.due_load.as_ref().filter(|_| token.starts_with("due"));
+        if let (Some(load), Some(day)) = (load, Self::preview_day(resolved)) {
+          title_spans.spans.push(Span::from("  due:"));
+          for (d, count) in load.days_from(day) {
+            let mut style = if count < self.config.uda_due_capacity.max(1) {
+              Style::default().fg(Color::Green)
+            } else {
+              Style::default()
+            };
+            if d == day {
+              style = style.add_modifier(Modifier::BOLD);
             }
+            title_spans.spans.push(Span::styled(format!(" {} {}", d.format("%a"), count), style));
           }
-          Err(err) => Err(format!("`{}` failed: Unable to spawn shortcut number {} - Error: {}", shell, s, err)),
         }
       }
-      None => Err(format!("Unable to run shortcut number {}: shlex::split(`{}`) failed.", s, shell)),
-    };
-
-    if task_uuids.len() == 1 {
-      if let Some(uuid) = task_uuids.get(0) {
-        self.current_selection_uuid = Some(*uuid);
+    }
+    if cursor && self.config.uda_quick_add && matches!(self.mode, Mode::Tasks(Action::Add | Action::Log)) {
+      if let Some(quick) = QuickAdd::parse(text).filter(|q| !q.attributes.is_empty()) {
+        title_spans.spans.push(Span::from("  \u{2192} "));
+        title_spans
+          .spans
+          .push(Span::styled(quick.attributes.join(" "), Style::default().add_modifier(Modifier::BOLD)));
       }
     }
+    let title = Paragraph::new(Text::from(title_spans)).style(style);
+    f.render_widget(title, rects[0]);
 
-    self.resume_tui().await.unwrap();
-
-    r
+    // render command
+    let line = match &self.spellchecker {
+      _ if self.degraded() => Line::from(text),
+      _ if cursor && self.mode == Mode::Tasks(Action::Filter) => self.highlight_filter(text),
+      Some(checker) if cursor && matches!(self.mode, Mode::Tasks(Action::Add | Action::Modify | Action::Log | Action::Annotate)) => {
+        let mut spans = vec![];
+        let mut last = 0;
+        for word in checker.misspelled(text) {
+          spans.push(Span::raw(&text[last..word.start]));
+          spans.push(Span::styled(
+            &text[word.clone()],
+            Style::default().fg(Color::Red).add_modifier(Modifier::UNDERLINED),
+          ));
+          last = word.end;
+        }
+        spans.push(Span::raw(&text[last..]));
+        Line::from(spans)
+      }
+      _ => Line::from(text),
+    };
+    let p = Paragraph::new(Text::from(line)).scroll((0, ((position + 2) as u16).saturating_sub(rects[1].width)));
+    f.render_widget(p, rects[1]);
   }
 
-  pub fn task_modify(&mut self) -> Result<(), String> {
-    if self.tasks.is_empty() {
-      return Ok(());
+  fn highlight_filter<'a>(&self, text: &'a str) -> Line<'a> {
+    let mut spans = vec![];
+    let mut last = 0;
+    for token in task_filter::tokenize(text) {
+      let style = match token.kind {
+        TokenKind::Attribute => Style::default().fg(Color::Blue),
+        TokenKind::Tag => Style::default().fg(Color::Green),
+        TokenKind::Operator => Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+        TokenKind::Parenthesis => Style::default().fg(Color::Yellow),
+        TokenKind::Value | TokenKind::Text => Style::default(),
+      };
+      spans.push(Span::raw(&text[last..token.range.start]));
+      spans.push(Span::styled(&text[token.range.clone()], style));
+      last = token.range.end;
+    }
+    spans.push(Span::raw(&text[last..]));
+    if let Err(e) = task_filter::validate(text) {
+      // mark where the filter goes wrong
+      let mut offset = 0;
+      let mut marked = vec![];
+      for span in spans {
+        let range = offset..offset + span.content.len();
+        offset = range.end;
+        if range.contains(&e.position) {
+          let (before, rest) = span.content.split_at(e.position - range.start);
+          let c = rest.chars().next().map_or(0, char::len_utf8);
+          let (at, after) = rest.split_at(c);
+          marked.push(Span::styled(before.to_string(), span.style));
+          marked.push(Span::styled(at.to_string(), Style::default().fg(Color::White).bg(Color::Red)));
+          marked.push(Span::styled(after.to_string(), span.style));
+        } else {
+          marked.push(span);
+        }
+      }
+      spans = marked;
     }
+    Line::from(spans)
+  }
 
-    let task_uuids = self.selected_task_uuids();
-
-    let mut command = std::process::Command::new("task");
-    command.arg("rc.bulk=0");
-    command.arg("rc.confirmation=off");
-    command.arg("rc.dependency.confirmation=off");
-    command.arg("rc.recurrence.confirmation=off");
-    for task_uuid in &task_uuids {
-      command.arg(task_uuid.to_string());
+  fn draw_task_details(&mut self, f: &mut Frame<impl Backend>, rect: Rect) {
+    if self.tasks.is_empty() {
+      let p = Paragraph::new(Text::from("Task not found")).block(Block::default().borders(Borders::TOP));
+      f.render_widget(p, rect);
+      return;
     }
-    command.arg("modify");
-
-    let shell = self.modify.as_str();
+    let selected = self.current_selection;
+    let task_id = self.tasks[selected].id().unwrap_or_default();
+    let task_uuid = *self.tasks[selected].uuid();
 
-    let r = match shlex::split(shell) {
-      Some(cmd) => {
-        for s in cmd {
-          command.arg(&s);
-        }
-        let output = command.output();
-        match output {
-          Ok(o) => {
-            if o.status.success() {
-              Ok(())
-            } else {
-              Err(format!("Modify failed. {}", String::from_utf8_lossy(&o.stdout)))
-            }
-          }
-          Err(_) => Err(format!(
-            "Cannot run `task {:?} modify {}`. Check documentation for more information",
-            task_uuids, shell,
-          )),
+    let mut data = match self.task_details.get(&task_uuid) {
+      Some(s) if details::enormous(&self.tasks[selected]) => self.details_pages.text(s, &self.tasks[selected], rect.width).to_string(),
+      Some(s) => s.clone(),
+      None => "Loading task details ...".to_string(),
+    };
+    let tracked = self.focus.total(&self.tasks[selected], chrono::Utc::now());
+    if let Some(secret) = &self.config.uda_secret {
+      match &self.revealed_secret {
+        Some((uuid, text)) if *uuid == task_uuid => data = format!("Secret\n{}\n\n{}", text.trim_end(), data),
+        // hide the secret again once another task is selected
+        Some(_) => self.revealed_secret = None,
+        None if secret.value(&self.tasks[selected]).is_some() => {
+          data = format!(
+            "{:<10} {}\n\n{}",
+            "Secret", "encrypted, \"Reveal secret\" in the command palette shows it", data
+          );
         }
+        None => {}
       }
-      None => Err(format!("Cannot shlex split `{}`", shell)),
-    };
-
-    if task_uuids.len() == 1 {
-      if let Some(uuid) = task_uuids.get(0) {
-        self.current_selection_uuid = Some(*uuid);
+    }
+    if tracked.num_minutes() > 0 {
+      data = format!("{:<10} {}\n\n{}", "Tracked", focus::format_tracked(tracked), data);
+    }
+    if let Some(zone) = &self.config.uda_timezone_alternate {
+      let dates = zone.describe(&self.tasks[selected]);
+      if !dates.is_empty() {
+        data = format!("{}\n\n{}", dates, data);
+      }
+    }
+    let image = self.tasks[selected]
+      .annotations()
+      .and_then(|annotations| annotations.iter().find_map(|a| preview::image_path(a.description())));
+    let mut rect = rect;
+    if let Some(path) = image {
+      if self.image_preview.can_show(&path) && self.mode == Mode::Tasks(Action::Report) && rect.height > 6 {
+        let split = Layout::default()
+          .direction(Direction::Vertical)
+          .constraints([Constraint::Min(3), Constraint::Percentage(50)])
+          .split(rect);
+        rect = split[0];
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let block = Block::default().borders(Borders::TOP).title(name);
+        self.image_preview.request(path, block.inner(split[1]));
+        f.render_widget(block, split[1]);
+      } else {
+        data = format!("{}\n\n{}", preview::describe(&path), data);
       }
     }
+    self.task_details_scroll_max = (data.lines().count() as u16).saturating_sub(rect.height).saturating_add(2);
+    self.task_details_scroll = std::cmp::min(self.task_details_scroll_max, self.task_details_scroll);
+    let p = Paragraph::new(Text::from(&data[..]))
+      .block(Block::default().borders(Borders::TOP))
+      .scroll((self.task_details_scroll, 0));
+    f.render_widget(p, rect);
+    self.panes.register(ScrollPane::Details, rect);
+  }
 
-    r
+  fn task_details_scroll_up(&mut self) {
+    self.task_details_scroll = self.task_details_scroll.saturating_sub(1);
   }
 
-  pub fn task_annotate(&mut self) -> Result<(), String> {
-    if self.tasks.is_empty() {
-      return Ok(());
+  fn task_details_scroll_down(&mut self) {
+    if self.task_details_scroll >= self.task_details_scroll_max && self.details_pages.more {
+      self.details_pages.load_more();
     }
+    self.task_details_scroll = self.task_details_scroll.saturating_add(1);
+  }
 
-    let task_uuids = self.selected_task_uuids();
-
-    let mut command = std::process::Command::new("task");
-    command.arg("rc.bulk=0");
-    command.arg("rc.confirmation=off");
-    command.arg("rc.dependency.confirmation=off");
-    command.arg("rc.recurrence.confirmation=off");
-    for task_uuid in &task_uuids {
-      command.arg(task_uuid.to_string());
+  fn task_by_index(&self, i: usize) -> Option<Task> {
+    let tasks = &self.tasks;
+    if i >= tasks.len() {
+      None
+    } else {
+      Some(tasks[i].clone())
     }
-    command.arg("annotate");
+  }
 
-    let shell = self.command.as_str();
+  fn task_by_uuid(&self, uuid: Uuid) -> Option<Task> {
+    let tasks = &self.tasks;
+    let m = tasks.iter().find(|t| *t.uuid() == uuid);
+    m.cloned()
+  }
 
-    let r = match shlex::split(shell) {
-      Some(cmd) => {
-        for s in cmd {
-          command.arg(&s);
-        }
-        let output = command.output();
-        match output {
-          Ok(o) => {
-            if o.status.success() {
-              Ok(())
-            } else {
-              Err(format!("Annotate failed. {}", String::from_utf8_lossy(&o.stdout)))
-            }
-          }
-          Err(_) => Err(format!(
-            "Cannot run `task {} annotate {}`. Check documentation for more information",
-            task_uuids.iter().map(ToString::to_string).collect::<Vec<String>>().join(" "),
-            shell
-          )),
-        }
-      }
-      None => Err(format!("Cannot shlex split `{}`", shell)),
-    };
+  fn task_by_id(&self, id: u64) -> Option<Task> {
+    let tasks = &self.tasks;
+    let m = tasks.iter().find(|t| t.id() == Some(id));
+    m.cloned()
+  }
 
-    if task_uuids.len() == 1 {
-      if let Some(uuid) = task_uuids.get(0) {
-        self.current_selection_uuid = Some(*uuid);
-      }
-    }
-    r
+  fn task_index_by_id(&self, id: u64) -> Option<usize> {
+    let tasks = &self.tasks;
+    let m = tasks.iter().position(|t| t.id() == Some(id));
+    m
   }
 
-  pub fn task_add(&mut self) -> Result<(), String> {
-    let mut command = std::process::Command::new("task");
-    command.arg("add");
+  fn task_index_by_uuid(&self, uuid: Uuid) -> Option<usize> {
+    let tasks = &self.tasks;
+    let m = tasks.iter().position(|t| *t.uuid() == uuid);
+    m
+  }
 
-    let shell = self.command.as_str();
+  fn style_for_task(&self, task: &Task) -> Style {
+    let virtual_tag_names_in_precedence = &self.config.rule_precedence_color;
 
-    match shlex::split(shell) {
-      Some(cmd) => {
-        for s in cmd {
-          command.arg(&s);
+    let mut style = Style::default();
+
+    for tag_name in virtual_tag_names_in_precedence.iter().rev() {
+      if tag_name == "uda." || tag_name == "priority" {
+        if let Some(p) = task.priority() {
+          let s = self.config.color.get(&format!("color.uda.priority.{}", p)).copied().unwrap_or_default();
+          style = style.patch(s);
         }
-        let output = command.output();
-        match output {
-          Ok(output) => {
-            if output.status.code() == Some(0) {
-              let data = String::from_utf8_lossy(&output.stdout);
-              let re = Regex::new(r"^Created task (?P<task_id>\d+).\n$").unwrap();
-              if self.config.uda_task_report_jump_to_task_on_add {
-                if let Some(caps) = re.captures(&data) {
-                  self.current_selection_id = Some(caps["task_id"].parse::<u64>().unwrap_or_default());
-                }
-              }
-              Ok(())
-            } else {
-              Err(format!("Error: {}", String::from_utf8_lossy(&output.stderr)))
-            }
+      } else if tag_name == "tag." {
+        if let Some(tags) = task.tags() {
+          for t in tags {
+            let color_tag_name = format!("color.tag.{}", t);
+            let s = self.config.color.get(&color_tag_name).copied().unwrap_or_default();
+            style = style.patch(s);
           }
-          Err(e) => Err(format!("Cannot run `{:?}`: {}", command, e)),
         }
+      } else if tag_name == "project." {
+        if let Some(p) = task.project() {
+          let s = self.config.color.get(&format!("color.project.{}", p)).copied().unwrap_or_default();
+          style = style.patch(s);
+        }
+      } else if task
+        .tags()
+        .unwrap_or(&vec![])
+        .contains(&tag_name.to_string().replace('.', "").to_uppercase())
+      {
+        let color_tag_name = format!("color.{}", tag_name);
+        let s = self.config.color.get(&color_tag_name).copied().unwrap_or_default();
+        style = style.patch(s);
       }
-      None => Err(format!("Unable to run `{:?}`: shlex::split(`{}`) failed.", command, shell)),
     }
+
+    style
   }
 
-  pub fn task_virtual_tags(task_uuid: Uuid) -> Result<String, String> {
-    let output = std::process::Command::new("task").arg(format!("{}", task_uuid)).output();
+  /// Widths of the columns, `indicators` being drawn after the headers they match by position.
+  pub fn calculate_widths(&self, tasks: &[Vec<String>], headers: &[String], indicators: &[&str], maximum_column_width: u16) -> Vec<usize> {
+    // naive implementation of calculate widths
+    let mut widths = headers
+      .iter()
+      .enumerate()
+      .map(|(i, header)| header.len() + indicators.get(i).map_or(0, |s| s.len()))
+      .collect::<Vec<usize>>();
 
-    match output {
-      Ok(output) => {
-        let data = String::from_utf8_lossy(&output.stdout);
-        for line in data.split('\n') {
-          for prefix in &["Virtual tags", "Virtual"] {
-            if line.starts_with(prefix) {
-              let line = line.to_string();
-              let line = line.replace(prefix, "");
-              return Ok(line);
-            }
-          }
-        }
-        Err(format!(
-          "Cannot find any tags for `task {}`. Check documentation for more information",
-          task_uuid
-        ))
+    for row in tasks.iter() {
+      for (i, cell) in row.iter().enumerate() {
+        widths[i] = std::cmp::max(cell.len(), widths[i]);
       }
-      Err(_) => Err(format!("Cannot run `task {}`. Check documentation for more information", task_uuid)),
     }
+
+    for (i, header) in headers.iter().enumerate() {
+      if header == "Description" || header == "Definition" {
+        // always give description or definition the most room to breath
+        widths[i] = maximum_column_width as usize;
+        break;
+      }
+    }
+    for (i, header) in headers.iter().enumerate() {
+      if i == 0 {
+        // always give ID a couple of extra for indicator
+        widths[i] += self.config.uda_selection_indicator.as_str().width();
+        // if let TableMode::MultipleSelection = self.task_table_state.mode() {
+        //     widths[i] += 2
+        // };
+      }
+    }
+
+    // now start trimming
+    while (widths.iter().sum::<usize>() as u16) >= maximum_column_width - (headers.len()) as u16 {
+      let index = widths.iter().position(|i| i == widths.iter().max().unwrap_or(&0)).unwrap_or_default();
+      if widths[index] == 1 {
+        break;
+      }
+      widths[index] -= 1;
+    }
+
+    widths
   }
 
-  pub fn task_start_stop(&mut self) -> Result<(), String> {
-    if self.tasks.is_empty() {
-      return Ok(());
+  fn draw_task_report(&mut self, f: &mut Frame<impl Backend>, rect: Rect) {
+    self.task_report_rect = rect;
+    self.panes.register(ScrollPane::Report, rect);
+    let (tasks, headers) = self.get_task_report();
+    let degraded = self.degraded();
+    let indicators: Vec<&str> = self
+      .task_report_table
+      .visible_columns
+      .iter()
+      .map(|column| match self.task_report_table.sort_direction(column) {
+        _ if degraded => "",
+        Some(true) => " \u{25b2}",
+        Some(false) => " \u{25bc}",
+        None => "",
+      })
+      .collect();
+
+    if tasks.is_empty() {
+      if !self.current_context.is_empty() {
+        let context_style = Style::default();
+        context_style.add_modifier(Modifier::ITALIC);
+      }
+
+      f.render_widget(Block::default(), rect);
+      return;
     }
 
-    let task_uuids = self.selected_task_uuids();
+    let maximum_column_width = rect.width;
+    let widths = self.calculate_widths(&tasks, &headers, &indicators, maximum_column_width);
 
-    for task_uuid in &task_uuids {
-      let mut command = "start";
-      for tag in TaskwarriorTui::task_virtual_tags(*task_uuid).unwrap_or_default().split(' ') {
-        if tag == "ACTIVE" {
-          command = "stop";
+    let mut description_column = None;
+    for (i, header) in headers.iter().enumerate() {
+      if header == "Description" || header == "Definition" {
+        self.task_report_table.description_width = widths[i] - 1;
+        description_column = Some(i);
+        break;
+      }
+    }
+    let selected = self.current_selection;
+    let labels: Vec<String> = headers
+      .iter()
+      .enumerate()
+      .map(|(i, header)| format!("{}{}", header, indicators.get(i).unwrap_or(&"")))
+      .collect();
+    let header = labels.iter();
+    let mut rows = vec![];
+    let mut highlight_style = Style::default();
+    let mut pos = 0;
+    for (i, task) in tasks.iter().enumerate() {
+      let style = if degraded {
+        Style::default()
+      } else {
+        self.style_for_task(&self.tasks[i])
+      };
+      if i == selected {
+        pos = i;
+        highlight_style = style.patch(self.config.uda_style_report_selection);
+        if self.config.uda_selection_bold {
+          highlight_style = highlight_style.add_modifier(Modifier::BOLD);
+        }
+        if self.config.uda_selection_italic {
+          highlight_style = highlight_style.add_modifier(Modifier::ITALIC);
+        }
+        if self.config.uda_selection_dim {
+          highlight_style = highlight_style.add_modifier(Modifier::DIM);
+        }
+        if self.config.uda_selection_blink {
+          highlight_style = highlight_style.add_modifier(Modifier::SLOW_BLINK);
+        }
+        if self.config.uda_selection_reverse {
+          highlight_style = highlight_style.add_modifier(Modifier::REVERSED);
         }
       }
+      rows.push(Row::StyledData(task.iter(), style));
+    }
+
+    let constraints: Vec<Constraint> = widths
+      .iter()
+      .map(|i| Constraint::Length((*i).try_into().unwrap_or(maximum_column_width)))
+      .collect();
 
-      let output = std::process::Command::new("task").arg(task_uuid.to_string()).arg(command).output();
-      if output.is_err() {
-        return Err(format!("Error running `task {}` for task `{}`.", command, task_uuid));
+    let mut tag_styles = HashMap::new();
+    let tags_column = self.task_report_table.visible_columns.iter().position(|c| c == "tags");
+    if self.config.uda_task_report_tag_colors && !degraded {
+      for tag in self.tasks.iter().filter_map(|t| t.tags()).flatten() {
+        if !tag_styles.contains_key(tag) {
+          tag_styles.insert(tag.clone(), self.config.tag_style(tag));
+        }
       }
     }
 
-    if task_uuids.len() == 1 {
-      if let Some(uuid) = task_uuids.get(0) {
-        self.current_selection_uuid = Some(*uuid);
+    let mut t = Table::new(header, rows.into_iter())
+      .header_style(
+        self
+          .config
+          .color
+          .get("color.label")
+          .copied()
+          .unwrap_or_default()
+          .add_modifier(Modifier::UNDERLINED),
+      )
+      .focused_header(if self.mode == Mode::Tasks(Action::HeaderFocus) {
+        Some(self.task_report_header_focus)
+      } else {
+        None
+      })
+      .focused_header_style(Style::default().add_modifier(Modifier::REVERSED))
+      .highlight_style(highlight_style)
+      .highlight_symbol(&self.config.uda_selection_indicator)
+      .mark_symbol(&self.config.uda_mark_indicator)
+      .unmark_symbol(&self.config.uda_unmark_indicator)
+      .widths(&constraints)
+      .column_spacing(self.density.column_spacing())
+      .header_gap(self.density.header_gap())
+      .row_height(self.density.row_height());
+    if let Some(column) = tags_column.filter(|_| !tag_styles.is_empty()) {
+      t = t.word_styles(column, &tag_styles);
+    }
+    let aging_column = self.task_report_table.visible_columns.iter().position(|c| c == "aging");
+    let aging_styles: Vec<Style> = match aging_column {
+      Some(_) if !degraded => {
+        let now = Local::now().naive_utc();
+        self
+          .tasks
+          .iter()
+          .map(|task| {
+            let color = self.config.uda_aging.color(**task.entry(), now);
+            Style::default().fg(if self.capabilities.truecolor {
+              color
+            } else {
+              capabilities::to_indexed(color)
+            })
+          })
+          .collect()
       }
+      _ => vec![],
+    };
+    if let Some(column) = aging_column.filter(|_| !aging_styles.is_empty()) {
+      t = t.cell_styles(column, &aging_styles);
+    }
+    let team = self.config.uda_team.as_ref().filter(|_| !degraded);
+    let assignee_column = team.and_then(|team| self.task_report_table.visible_columns.iter().position(|c| *c == team.uda));
+    let assignee_styles: Vec<Style> = match (team, assignee_column) {
+      (Some(team), Some(_)) => self
+        .tasks
+        .iter()
+        .map(|task| match team.assignee(task) {
+          Some(login) => {
+            let color = team.user(login).color();
+            Style::default()
+              .fg(Color::Black)
+              .bg(if self.capabilities.truecolor {
+                color
+              } else {
+                capabilities::to_indexed(color)
+              })
+              .add_modifier(Modifier::BOLD)
+          }
+          None => Style::default(),
+        })
+        .collect(),
+      _ => vec![],
+    };
+    if let Some(column) = assignee_column.filter(|_| !assignee_styles.is_empty()) {
+      t = t.cell_styles(column, &assignee_styles);
+    }
+    let details: Vec<String> = if self.density.details() {
+      self.tasks.iter().map(|task| tooltip::details(task).join("  ")).collect()
+    } else {
+      vec![]
+    };
+    if let Some(column) = description_column.filter(|_| self.density.details()) {
+      t = t.details(column, &details);
     }
 
-    Ok(())
+    f.render_stateful_widget(t, rect, &mut self.task_table_state);
+    if tasks.iter().len() as u16 * self.density.row_height() > rect.height.saturating_sub(4) {
+      let mut widget = Scrollbar::new(pos, tasks.iter().len());
+      widget.pos_style = self.config.uda_style_report_scrollbar;
+      widget.pos_symbol = self.config.uda_scrollbar_indicator.clone();
+      widget.area_style = self.config.uda_style_report_scrollbar_area;
+      widget.area_symbol = self.config.uda_scrollbar_area.clone();
+      f.render_widget(widget, rect);
+    }
   }
 
-  pub fn task_quick_tag(&mut self) -> Result<(), String> {
-    let tag_name = &self.config.uda_quick_tag_name;
-    let ptag_name = format!("+{}", tag_name);
-    let ntag_name = format!("-{}", tag_name);
-    if self.tasks.is_empty() {
-      return Ok(());
-    }
+  fn get_all_contexts(&self) -> (Vec<Vec<String>>, Vec<String>) {
+    let contexts = self
+      .contexts
+      .rows
+      .iter()
+      .filter(|c| &c.type_ == "read")
+      .map(|c| vec![c.name.clone(), c.definition.clone(), c.active.clone()])
+      .collect();
+    let headers = vec!["Name".to_string(), "Definition".to_string(), "Active".to_string()];
+    (contexts, headers)
+  }
 
-    let task_uuids = self.selected_task_uuids();
+  fn get_task_report(&mut self) -> (Vec<Vec<String>>, Vec<String>) {
+    if self.task_report_table.show_tracked {
+      let now = chrono::Utc::now();
+      self.task_report_table.tracked = self.tasks.iter().map(|t| (*t.uuid(), self.focus.total(t, now))).collect();
+    }
+    self.task_report_table.generate_table(&self.tasks);
+    let (tasks, headers) = self.task_report_table.simplify_table();
+    (tasks, headers)
+  }
 
-    for task_uuid in &task_uuids {
-      if let Some(task) = self.task_by_uuid(*task_uuid) {
-        let mut tag_to_set = &ptag_name;
-        for tag in task.tags().unwrap() {
-          if tag == tag_name {
-            tag_to_set = &ntag_name;
+  pub async fn update(&mut self, force: bool) -> Result<()> {
+    trace!("self.update({:?});", force);
+    if force || self.dirty || self.tasks_changed_since(self.last_export).unwrap_or(true) {
+      self.get_context()?;
+      let task_uuids = self.selected_task_uuids();
+      if self.current_selection_keep_index {
+        self.current_selection_keep_index = false;
+      } else {
+        if self.current_selection_uuid.is_none() && self.current_selection_id.is_none() && task_uuids.len() == 1 {
+          if let Some(uuid) = task_uuids.first() {
+            self.current_selection_uuid = Some(*uuid);
           }
         }
-
-        let output = std::process::Command::new("task")
-          .arg(task_uuid.to_string())
-          .arg("modify")
-          .arg(tag_to_set)
-          .output();
-
-        if output.is_err() {
-          return Err(format!("Error running `task modify {}` for task `{}`.", tag_to_set, task_uuid,));
+        if self.config.uda_task_report_sticky_selection {
+          self.current_selection_neighbors = Self::neighbor_uuids(&self.tasks, self.current_selection);
         }
       }
-    }
 
-    if task_uuids.len() == 1 {
-      if let Some(uuid) = task_uuids.get(0) {
-        self.current_selection_uuid = Some(*uuid);
+      self.last_export = Some(std::time::SystemTime::now());
+      self.task_report_table.export_headers(None, &self.report)?;
+      let started = Instant::now();
+      self.export_tasks()?;
+      self.trace(Metric::Export, started);
+      self.update_snapshot(force);
+      if self.config.uda_task_report_use_all_tasks_for_completion {
+        self.export_all_tasks()?;
+      }
+      self.contexts.update_data()?;
+      self.projects.update_data()?;
+      self.update_tags();
+      self.task_details.clear();
+      self.prefetch.invalidate();
+      // relative dates such as `now+1h` move, do not keep their resolved values forever
+      self.date_previews.clear();
+      self.due_load = None;
+      self.dirty = false;
+      self.save_history()?;
+      self.refresh_subscriptions();
+      self.wip_counts.refresh(&self.config.uda_wip_limits, &self.event_loop.tx);
+      self.focus.refresh_timewarrior(chrono::Utc::now());
+      self.rule_evaluations.refresh(&self.config.uda_rules, &self.event_loop.tx);
+      let now = Local::now().naive_local();
+      if !self.scheduled_backup.running() && self.backups.due(self.config.uda_backup_interval, now) {
+        let data_dir = shellexpand::tilde(&self.config.data_location).into_owned();
+        self.scheduled_backup.start(&self.backups, data_dir.into(), now, &self.event_loop.tx);
+      }
+      self.badges.refresh(&self.config.uda_named_filters, &self.event_loop.tx);
+      if self.config.uda_update_check && !self.config.uda_offline && self.updates.due(chrono::Utc::now()) {
+        self.updates.check(self.config.uda_update_source, &self.event_loop.tx);
       }
     }
+    self.cursor_fix();
+    self.update_task_table_state();
+    if self.task_report_show_info {
+      self.update_task_details().await?;
+    } else {
+      self.schedule_prefetch();
+    }
+    self.selection_fix();
 
     Ok(())
   }
 
-  pub fn task_delete(&mut self) -> Result<(), String> {
-    if self.tasks.is_empty() {
-      return Ok(());
+  pub fn update_snapshot(&mut self, force: bool) {
+    let data_changed = match (self.snapshot.taken_at, self.get_task_files_max_mtime()) {
+      (Some(taken_at), Ok(mtime)) => DateTime::<chrono::Utc>::from(mtime) > taken_at,
+      _ => true,
+    };
+    if data_changed {
+      // only pop up for changes that did not originate from this session
+      let show = !force && self.config.uda_snapshot_diff_show_on_change;
+      self.snapshot.refresh(show, &self.event_loop.tx);
     }
+    self.snapshot.remember(&self.tasks);
+  }
 
-    let task_uuids = self.selected_task_uuids();
-
-    let mut cmd = std::process::Command::new("task");
-    cmd
-      .arg("rc.bulk=0")
-      .arg("rc.confirmation=off")
-      .arg("rc.dependency.confirmation=off")
-      .arg("rc.recurrence.confirmation=off");
-    for task_uuid in &task_uuids {
-      cmd.arg(task_uuid.to_string());
+  /// Take in the changes exported in the background. Returns whether any were found.
+  fn poll_snapshot(&mut self) -> bool {
+    match self.snapshot.poll() {
+      Some(show) => {
+        if show && self.mode == Mode::Tasks(Action::Report) {
+          self.mode = Mode::Tasks(Action::SnapshotDiff);
+        }
+        true
+      }
+      None => false,
     }
-    cmd.arg("delete");
-    let output = cmd.output();
-    let r = match output {
-      Ok(_) => Ok(()),
-      Err(_) => Err(format!(
-        "Cannot run `task delete` for tasks `{}`. Check documentation for more information",
-        task_uuids.iter().map(ToString::to_string).collect::<Vec<String>>().join(" ")
-      )),
-    };
-    self.current_selection_uuid = None;
-    self.current_selection_id = None;
-    r
   }
 
-  pub fn task_done(&mut self) -> Result<(), String> {
-    if self.tasks.is_empty() {
-      return Ok(());
+  pub fn selection_fix(&mut self) {
+    if let (Some(t), Some(id)) = (self.task_current(), self.current_selection_id) {
+      if t.id() != Some(id) {
+        if let Some(i) = self.task_index_by_id(id) {
+          self.current_selection = i;
+          self.current_selection_id = None;
+        }
+      }
     }
-    let task_uuids = self.selected_task_uuids();
-    let mut cmd = std::process::Command::new("task");
-    cmd
-      .arg("rc.bulk=0")
-      .arg("rc.confirmation=off")
-      .arg("rc.dependency.confirmation=off")
-      .arg("rc.recurrence.confirmation=off");
-    for task_uuid in &task_uuids {
-      cmd.arg(task_uuid.to_string());
+
+    if let (Some(t), Some(uuid)) = (self.task_current(), self.current_selection_uuid) {
+      if t.uuid() != &uuid {
+        if let Some(i) = self.task_index_by_uuid(uuid) {
+          self.current_selection = i;
+          self.current_selection_uuid = None;
+        } else if let Some(i) = self
+          .current_selection_neighbors
+          .iter()
+          .find_map(|neighbor| self.task_index_by_uuid(*neighbor))
+        {
+          // the selected task is no longer visible, fall back to the closest task that still is
+          self.current_selection = i;
+          self.current_selection_uuid = None;
+        }
+      }
     }
-    cmd.arg("done");
-    let output = cmd.output();
-    let r = match output {
-      Ok(_) => Ok(()),
-      Err(_) => Err(format!(
-        "Cannot run `task done` for task `{}`. Check documentation for more information",
-        task_uuids.iter().map(ToString::to_string).collect::<Vec<String>>().join(" ")
-      )),
-    };
-    self.current_selection_uuid = None;
+    self.current_selection_neighbors.clear();
+  }
+
+  /// Choose the task to select once `task_uuids` are completed or deleted.
+  fn select_after_removal(&mut self, task_uuids: &[Uuid]) {
     self.current_selection_id = None;
-    r
+    self.current_selection_uuid = None;
+    match Self::task_after_removal(&self.tasks, task_uuids, self.config.uda_task_report_cursor_after_removal) {
+      Some(uuid) => self.current_selection_uuid = Some(uuid),
+      None => self.current_selection_keep_index = true,
+    }
   }
 
-  pub fn task_undo(&mut self) -> Result<(), String> {
-    let output = std::process::Command::new("task").arg("rc.confirmation=off").arg("undo").output();
+  /// Task of `tasks` to select once `task_uuids` are removed, or `None` to stay on the same row.
+  fn task_after_removal(tasks: &[Task], task_uuids: &[Uuid], mode: CursorAfterRemoval) -> Option<Uuid> {
+    let indices: Vec<usize> = task_uuids.iter().filter_map(|uuid| tasks.iter().position(|t| t.uuid() == uuid)).collect();
+    let candidate = match mode {
+      CursorAfterRemoval::Stay => None,
+      CursorAfterRemoval::Next => indices.iter().max().and_then(|i| tasks.get(i + 1)),
+      CursorAfterRemoval::Previous => indices.iter().min().and_then(|i| i.checked_sub(1)).and_then(|i| tasks.get(i)),
+    };
+    candidate.map(|t| *t.uuid())
+  }
 
-    match output {
-      Ok(output) => {
-        let data = String::from_utf8_lossy(&output.stdout);
-        let re = Regex::new(r"(?P<task_uuid>[a-fA-F0-9]{8}-[a-fA-F0-9]{4}-[a-fA-F0-9]{4}-[a-fA-F0-9]{4}-[a-fA-F0-9]{12})").unwrap();
-        if let Some(caps) = re.captures(&data) {
-          if let Ok(uuid) = Uuid::parse_str(&caps["task_uuid"]) {
-            self.current_selection_uuid = Some(uuid);
-          }
-        }
-        Ok(())
+  /// Tasks around `index`, the closest first and the one below before the one above.
+  fn neighbor_uuids(tasks: &[Task], index: usize) -> Vec<Uuid> {
+    let mut neighbors = vec![];
+    for distance in 1..=STICKY_NEIGHBORS {
+      if let Some(t) = tasks.get(index + distance) {
+        neighbors.push(*t.uuid());
+      }
+      if let Some(t) = index.checked_sub(distance).and_then(|i| tasks.get(i)) {
+        neighbors.push(*t.uuid());
       }
-      Err(_) => Err("Cannot run `task undo`. Check documentation for more information".to_string()),
     }
+    neighbors
   }
 
-  pub async fn task_edit(&mut self) -> Result<(), String> {
+  pub fn save_history(&mut self) -> Result<()> {
+    self.filter_history.write()?;
+    self.command_history.write()?;
+    Ok(())
+  }
+
+  pub fn cursor_fix(&mut self) {
+    while !self.tasks.is_empty() && self.current_selection >= self.tasks.len() {
+      self.task_report_previous();
+    }
+  }
+
+  pub async fn update_task_details(&mut self) -> Result<()> {
     if self.tasks.is_empty() {
       return Ok(());
     }
 
-    self.pause_tui().await.unwrap();
-
-    let selected = self.current_selection;
-    let task_id = self.tasks[selected].id().unwrap_or_default();
-    let task_uuid = *self.tasks[selected].uuid();
-
-    let r = std::process::Command::new("task").arg(format!("{}", task_uuid)).arg("edit").spawn();
-
-    let r = match r {
-      Ok(child) => {
-        let output = child.wait_with_output();
-        match output {
-          Ok(output) => {
-            if output.status.success() {
-              Ok(())
-            } else {
-              Err(format!(
-                "`task edit` for task `{}` failed. {}{}",
-                task_uuid,
-                String::from_utf8_lossy(&output.stdout),
-                String::from_utf8_lossy(&output.stderr),
-              ))
-            }
-          }
-          Err(err) => Err(format!("Cannot run `task edit` for task `{}`. {}", task_uuid, err)),
-        }
+    // remove task_details of tasks not in task report
+    let mut to_delete = vec![];
+    for k in self.task_details.keys() {
+      if !self.tasks.iter().map(Task::uuid).any(|x| x == k) {
+        to_delete.push(*k);
       }
-      _ => Err(format!(
-        "Cannot start `task edit` for task `{}`. Check documentation for more information",
-        task_uuid
-      )),
-    };
-
-    self.current_selection_uuid = Some(task_uuid);
+    }
+    for k in to_delete {
+      self.task_details.remove(&k);
+    }
 
-    self.resume_tui().await.unwrap();
+    let selected = self.current_selection;
+    if selected >= self.tasks.len() {
+      return Ok(());
+    }
+    let current_task_uuid = *self.tasks[selected].uuid();
 
-    r
+    // the selected task is refreshed right away, the tasks around it in the background
+    let output = overrides::task_async()
+      .arg("rc.color=off")
+      .arg("rc._forcecolor=off")
+      .arg(format!("rc.defaultwidth={}", self.terminal_width.saturating_sub(2)))
+      .arg(current_task_uuid.to_string())
+      .output()
+      .await;
+    if let Ok(output) = output {
+      self
+        .task_details
+        .insert(current_task_uuid, String::from_utf8_lossy(&output.stdout).to_string());
+    }
+    self.schedule_prefetch();
+    Ok(())
   }
 
-  pub fn task_current(&self) -> Option<Task> {
+  /// Fetch the details of the selected task and the tasks around it in the background, the
+  /// closest first.
+  fn schedule_prefetch(&mut self) {
     if self.tasks.is_empty() {
-      return None;
+      return;
     }
-    let selected = self.current_selection;
-    Some(self.tasks[selected].clone())
-  }
-
-  pub fn update_tags(&mut self) {
-    let tasks = &mut self.tasks;
-
-    // dependency scan
-    for l_i in 0..tasks.len() {
-      let default_deps = vec![];
-      let deps = tasks[l_i].depends().unwrap_or(&default_deps).clone();
-      add_tag(&mut tasks[l_i], "UNBLOCKED".to_string());
-      for dep in deps {
-        for r_i in 0..tasks.len() {
-          if tasks[r_i].uuid() == &dep {
-            let l_status = tasks[l_i].status();
-            let r_status = tasks[r_i].status();
-            if l_status != &TaskStatus::Completed
-              && l_status != &TaskStatus::Deleted
-              && r_status != &TaskStatus::Completed
-              && r_status != &TaskStatus::Deleted
-            {
-              remove_tag(&mut tasks[l_i], "UNBLOCKED");
-              add_tag(&mut tasks[l_i], "BLOCKED".to_string());
-              add_tag(&mut tasks[r_i], "BLOCKING".to_string());
-            }
-            break;
-          }
-        }
+    let selected = self.current_selection.min(self.tasks.len() - 1);
+    let mut uuids = vec![*self.tasks[selected].uuid()];
+    for s in 1..=self.config.uda_task_detail_prefetch {
+      if let Some(task) = self.tasks.get(selected + s) {
+        uuids.push(*task.uuid());
+      }
+      if let Some(task) = selected.checked_sub(s).and_then(|i| self.tasks.get(i)) {
+        uuids.push(*task.uuid());
       }
     }
+    self.prefetch.schedule(uuids, &self.task_details);
+    self.prefetch.start(self.terminal_width.saturating_sub(2), &self.event_loop.tx);
+  }
 
-    // other virtual tags
-    // TODO: support all virtual tags that taskwarrior supports
-    for task in tasks.iter_mut() {
-      match task.status() {
-        TaskStatus::Waiting => add_tag(task, "WAITING".to_string()),
-        TaskStatus::Completed => add_tag(task, "COMPLETED".to_string()),
-        TaskStatus::Pending => add_tag(task, "PENDING".to_string()),
-        TaskStatus::Deleted => add_tag(task, "DELETED".to_string()),
-        TaskStatus::Recurring => (),
-      }
-      if task.start().is_some() {
-        add_tag(task, "ACTIVE".to_string());
-      }
-      if task.scheduled().is_some() {
-        add_tag(task, "SCHEDULED".to_string());
-      }
-      if task.parent().is_some() {
-        add_tag(task, "INSTANCE".to_string());
-      }
-      if task.until().is_some() {
-        add_tag(task, "UNTIL".to_string());
-      }
-      if task.annotations().is_some() {
-        add_tag(task, "ANNOTATED".to_string());
-      }
-      let virtual_tags = self.task_report_table.virtual_tags.clone();
-      if task.tags().is_some() && task.tags().unwrap().iter().any(|s| !virtual_tags.contains(s)) {
-        add_tag(task, "TAGGED".to_string());
-      }
-      if !task.uda().is_empty() {
-        add_tag(task, "UDA".to_string());
-      }
-      if task.mask().is_some() {
-        add_tag(task, "TEMPLATE".to_string());
-      }
-      if task.project().is_some() {
-        add_tag(task, "PROJECT".to_string());
-      }
-      if task.priority().is_some() {
-        add_tag(task, "PRIORITY".to_string());
-      }
-      if task.recur().is_some() {
-        add_tag(task, "RECURRING".to_string());
-        let r = task.recur().unwrap();
-      }
-      if let Some(d) = task.due() {
-        let status = task.status();
-        // due today
-        if status != &TaskStatus::Completed && status != &TaskStatus::Deleted {
-          let now = Local::now();
-          let reference = TimeZone::from_utc_datetime(now.offset(), d);
-          let now = TimeZone::from_utc_datetime(now.offset(), &now.naive_utc());
-          let d = d.clone();
-          if (reference - chrono::Duration::nanoseconds(1)).month() == now.month() {
-            add_tag(task, "MONTH".to_string());
-          }
-          if (reference - chrono::Duration::nanoseconds(1)).month() % 4 == now.month() % 4 {
-            add_tag(task, "QUARTER".to_string());
-          }
-          if reference.year() == now.year() {
-            add_tag(task, "YEAR".to_string());
-          }
-          match get_date_state(&d, self.config.due) {
-            DateState::EarlierToday | DateState::LaterToday => {
-              add_tag(task, "DUE".to_string());
-              add_tag(task, "TODAY".to_string());
-              add_tag(task, "DUETODAY".to_string());
-            }
-            DateState::AfterToday => {
-              add_tag(task, "DUE".to_string());
-              if reference.date_naive() == (now + chrono::Duration::days(1)).date_naive() {
-                add_tag(task, "TOMORROW".to_string());
-              }
-            }
-            _ => (),
-          }
-        }
-      }
-      if let Some(d) = task.due() {
-        let status = task.status();
-        // overdue
-        if status != &TaskStatus::Completed && status != &TaskStatus::Deleted && status != &TaskStatus::Recurring {
-          let now = Local::now().naive_utc();
-          let d = NaiveDateTime::new(d.date(), d.time());
-          if d < now {
-            add_tag(task, "OVERDUE".to_string());
-          }
-        }
+  /// Keep the details fetched in the background and fetch the next ones. Returns whether any
+  /// arrived.
+  fn poll_prefetch(&mut self) -> bool {
+    let fetched = self.prefetch.poll();
+    if fetched.is_empty() {
+      return false;
+    }
+    for (uuid, data) in fetched {
+      if self.task_index_by_uuid(uuid).is_some() {
+        self.task_details.insert(uuid, data);
       }
     }
+    self.prefetch.start(self.terminal_width.saturating_sub(2), &self.event_loop.tx);
+    true
   }
 
-  pub fn toggle_mark(&mut self) {
-    if !self.tasks.is_empty() {
-      let selected = self.current_selection;
-      let task_id = self.tasks[selected].id().unwrap_or_default();
-      let task_uuid = *self.tasks[selected].uuid();
+  pub fn update_task_table_state(&mut self) {
+    trace!("self.update_task_table_state()");
+    self.task_table_state.select(Some(self.current_selection));
 
-      if !self.marked.insert(task_uuid) {
-        self.marked.remove(&task_uuid);
+    for uuid in self.marked.clone() {
+      if self.task_by_uuid(uuid).is_none() {
+        self.marked.remove(&uuid);
       }
     }
-  }
 
-  pub fn toggle_mark_all(&mut self) {
-    for task in &self.tasks {
-      if !self.marked.insert(*task.uuid()) {
-        self.marked.remove(task.uuid());
-      }
+    if self.marked.is_empty() {
+      self.task_table_state.single_selection();
+    }
+
+    self.task_table_state.clear();
+
+    for uuid in &self.marked {
+      self.task_table_state.mark(self.task_index_by_uuid(*uuid));
     }
   }
 
-  pub fn escape(s: &str) -> String {
-    let mut es = String::with_capacity(s.len() + 2);
+  pub fn context_next(&mut self) {
+    let i = match self.contexts.table_state.current_selection() {
+      Some(i) => {
+        if i >= self.contexts.len() - 1 {
+          0
+        } else {
+          i + 1
+        }
+      }
+      None => 0,
+    };
+    self.contexts.table_state.select(Some(i));
+  }
+
+  pub fn context_previous(&mut self) {
+    let i = match self.contexts.table_state.current_selection() {
+      Some(i) => {
+        if i == 0 {
+          self.contexts.len() - 1
+        } else {
+          i - 1
+        }
+      }
+      None => 0,
+    };
+    self.contexts.table_state.select(Some(i));
+  }
+
+  pub fn context_select(&mut self) -> Result<()> {
+    let i = self.contexts.table_state.current_selection().unwrap_or_default();
+    // a context given with `--context` would otherwise keep hiding the one chosen here
+    overrides::remove("context");
+    let mut command = overrides::task();
+    command.arg("context").arg(&self.contexts.rows[i].name);
+    command.output()?;
+    Ok(())
+  }
+
+  pub fn task_report_top(&mut self) {
+    if self.tasks.is_empty() {
+      return;
+    }
+    self.current_selection = 0;
+    self.current_selection_id = None;
+    self.current_selection_uuid = None;
+  }
+
+  pub fn task_report_bottom(&mut self) {
+    if self.tasks.is_empty() {
+      return;
+    }
+    self.current_selection = self.tasks.len() - 1;
+    self.current_selection_id = None;
+    self.current_selection_uuid = None;
+  }
+
+  pub fn task_report_next(&mut self) {
+    if self.tasks.is_empty() {
+      return;
+    }
+    let i = {
+      if self.current_selection >= self.tasks.len() - 1 {
+        if self.config.uda_task_report_looping {
+          0
+        } else {
+          self.current_selection
+        }
+      } else {
+        self.current_selection + 1
+      }
+    };
+    self.current_selection = i;
+    self.current_selection_id = None;
+    self.current_selection_uuid = None;
+  }
+
+  pub fn task_report_previous(&mut self) {
+    if self.tasks.is_empty() {
+      return;
+    }
+    let i = {
+      if self.current_selection == 0 {
+        if self.config.uda_task_report_looping {
+          self.tasks.len() - 1
+        } else {
+          0
+        }
+      } else {
+        self.current_selection - 1
+      }
+    };
+    self.current_selection = i;
+    self.current_selection_id = None;
+    self.current_selection_uuid = None;
+  }
+
+  pub fn task_report_next_page(&mut self) {
+    if self.tasks.is_empty() {
+      return;
+    }
+    let i = {
+      if self.current_selection == self.tasks.len() - 1 {
+        if self.config.uda_task_report_looping {
+          0
+        } else {
+          self.tasks.len() - 1
+        }
+      } else {
+        std::cmp::min(
+          self
+            .current_selection
+            .checked_add(self.task_report_height as usize)
+            .unwrap_or(self.tasks.len() - 1),
+          self.tasks.len() - 1,
+        )
+      }
+    };
+    self.current_selection = i;
+    self.current_selection_id = None;
+    self.current_selection_uuid = None;
+  }
+
+  pub fn task_report_previous_page(&mut self) {
+    if self.tasks.is_empty() {
+      return;
+    }
+    let i = {
+      if self.current_selection == 0 {
+        if self.config.uda_task_report_looping {
+          self.tasks.len() - 1
+        } else {
+          0
+        }
+      } else {
+        self.current_selection.saturating_sub(self.task_report_height as usize)
+      }
+    };
+    self.current_selection = i;
+    self.current_selection_id = None;
+    self.current_selection_uuid = None;
+  }
+
+  pub fn task_report_jump(&mut self) -> Result<()> {
+    if self.tasks.is_empty() {
+      return Ok(());
+    }
+    let i = self.command.as_str().parse::<usize>()?;
+    if let Some(task) = self.task_by_id(i as u64) {
+      let j = self.task_index_by_uuid(*task.uuid()).unwrap_or_default();
+      self.current_selection = j;
+      self.current_selection_id = None;
+      self.current_selection_uuid = None;
+      Ok(())
+    } else {
+      Err(anyhow!("Cannot locate task id {} in report", i))
+    }
+  }
+
+  fn get_task_files_max_mtime(&self) -> Result<SystemTime> {
+    let data_dir = shellexpand::tilde(&self.config.data_location).into_owned();
+    ["backlog.data", "completed.data", "pending.data"]
+      .iter()
+      .map(|n| fs::metadata(Path::new(&data_dir).join(n)).map(|m| m.modified()))
+      .filter_map(Result::ok)
+      .filter_map(Result::ok)
+      .max()
+      .ok_or_else(|| anyhow!("Unable to get task files max time"))
+  }
+
+  pub fn tasks_changed_since(&mut self, prev: Option<SystemTime>) -> Result<bool> {
+    if let Some(prev) = prev {
+      let mtime = self.get_task_files_max_mtime()?;
+      if mtime > prev {
+        Ok(true)
+      } else {
+        // Unfortunately, we can not use std::time::Instant which is guaranteed to be monotonic,
+        // because we need to compare it to a file mtime as SystemTime, so as a safety for unexpected
+        // time shifts, cap maximum wait to 1 min
+        let now = SystemTime::now();
+        let max_delta = Duration::from_secs(60);
+        Ok(now.duration_since(prev)? > max_delta)
+      }
+    } else {
+      Ok(true)
+    }
+  }
+
+  pub fn export_all_tasks(&mut self) -> Result<()> {
+    let mut task = overrides::task();
+
+    task
+      .arg("rc.json.array=on")
+      .arg("rc.confirmation=off")
+      .arg("rc.json.depends.array=on")
+      .arg("rc.color=off")
+      .arg("rc._forcecolor=off");
+    // .arg("rc.verbose:override=false");
+
+    task.arg("export");
+
+    task.arg("all");
+
+    info!("Running `{:?}`", task);
+    let output = task.output()?;
+    let data = String::from_utf8_lossy(&output.stdout);
+    let error = String::from_utf8_lossy(&output.stderr);
+
+    if output.status.success() {
+      if let Ok(imported) = import(data.as_bytes()) {
+        self.all_tasks = imported;
+        info!("Imported {} tasks", self.tasks.len());
+        self.error = None;
+        if self.mode == Mode::Tasks(Action::Error) {
+          self.mode = self.previous_mode.clone().unwrap_or(Mode::Tasks(Action::Report));
+          self.previous_mode = None;
+        }
+      } else {
+        self.error = Some(format!("Unable to parse output of `{:?}`:\n`{:?}`", task, data));
+        self.mode = Mode::Tasks(Action::Error);
+        debug!("Unable to parse output: {:?}", data);
+      }
+    } else {
+      self.error = Some(format!("Cannot run `{:?}` - ({}) error:\n{}", &task, output.status, error));
+    }
+
+    Ok(())
+  }
+
+  pub fn export_tasks(&mut self) -> Result<()> {
+    if let Err(e) = task_filter::validate(self.filter.as_str()) {
+      // keep showing the previous tasks rather than the empty report a malformed filter would give
+      self.error = Some(format!(
+        "Invalid filter `{}`: {} at column {}.",
+        self.filter.as_str(),
+        e,
+        e.column(self.filter.as_str())
+      ));
+      // while the filter is typed its prompt shows the mistake already
+      if self.mode != Mode::Tasks(Action::Filter) {
+        self.mode = Mode::Tasks(Action::Error);
+      }
+      return Ok(());
+    }
+
+    let mut task = overrides::task();
+
+    task
+      .arg("rc.json.array=on")
+      .arg("rc.confirmation=off")
+      .arg("rc.json.depends.array=on")
+      .arg("rc.color=off")
+      .arg("rc._forcecolor=off");
+    // .arg("rc.verbose:override=false");
+
+    // passed as is, taskwarrior reads the quotes in the filter itself
+    task.arg(format!("rc.report.{}.filter={}", self.report, self.filter.trim()));
+
+    if !self.current_context_filter.trim().is_empty() && self.task_version >= *TASKWARRIOR_VERSION_SUPPORTED {
+      if let Some(args) = shlex::split(&self.current_context_filter) {
+        for arg in args {
+          task.arg(arg);
+        }
+      }
+    } else if !self.current_context_filter.trim().is_empty() {
+      task.arg(format!("'\\({}\\)'", self.current_context_filter));
+    }
+
+    if self.ready_only {
+      task.arg("+READY");
+    }
+
+    if let Some(team) = self.config.uda_team.as_ref().filter(|_| self.mine_only) {
+      task.arg(format!("{}:{}", team.uda, team.me));
+    }
+
+    if let Some(args) = shlex::split(&self.chips.filter()) {
+      for arg in args {
+        task.arg(arg);
+      }
+    }
+
+    if self.task_report_table.sort_overridden {
+      task.arg(format!("rc.report.{}.sort={}", self.report, self.task_report_table.sort_string()));
+    }
+
+    task.arg("export");
+
+    if self.task_version >= *TASKWARRIOR_VERSION_SUPPORTED {
+      task.arg(&self.report);
+    }
+
+    info!("Running `{:?}`", task);
+    let output = task.output()?;
+    let data = String::from_utf8_lossy(&output.stdout);
+    let error = String::from_utf8_lossy(&output.stderr);
+
+    if output.status.success() {
+      if let Ok(imported) = import(data.as_bytes()) {
+        self.tasks = imported;
+        if !self.task_report_table.sort_overridden {
+          if let Some(keys) = self.config.uda_sorts.get(&self.report) {
+            sort::sort(&mut self.tasks, keys);
+          }
+        }
+        info!("Imported {} tasks", self.tasks.len());
+        self.error = None;
+        if self.mode == Mode::Tasks(Action::Error) {
+          self.mode = self.previous_mode.clone().unwrap_or(Mode::Tasks(Action::Report));
+          self.previous_mode = None;
+        }
+      } else {
+        self.error = Some(format!("Unable to parse output of `{:?}`:\n`{:?}`", task, data));
+        self.mode = Mode::Tasks(Action::Error);
+        debug!("Unable to parse output: {:?}", data);
+      }
+    } else {
+      self.error = Some(format!("Cannot run `{:?}` - ({}) error:\n{}", &task, output.status, error));
+    }
+
+    Ok(())
+  }
+
+  pub fn selected_task_uuids(&self) -> Vec<Uuid> {
+    let selected = match self.task_table_state.mode() {
+      TableMode::SingleSelection => vec![self.current_selection],
+      TableMode::MultipleSelection => self.task_table_state.marked().copied().collect::<Vec<usize>>(),
+    };
+
+    let mut task_uuids = vec![];
+
+    for s in selected {
+      if self.tasks.is_empty() {
+        break;
+      }
+      let task_id = self.tasks[s].id().unwrap_or_default();
+      let task_uuid = *self.tasks[s].uuid();
+      task_uuids.push(task_uuid);
+    }
+
+    task_uuids
+  }
+
+  pub fn task_subprocess(&mut self) -> Result<(), String> {
+    let task_uuids = if self.tasks.is_empty() { vec![] } else { self.selected_task_uuids() };
+
+    let shell = self.command.as_str();
+
+    let r = match shlex::split(shell) {
+      Some(cmd) => {
+        if cmd.is_empty() {
+          Err(format!("Shell command empty: {}", shell))
+        } else {
+          // first argument must be a binary
+          let mut command = std::process::Command::new(&cmd[0]);
+          // remaining arguments are args
+          for (i, s) in cmd.iter().enumerate() {
+            if i == 0 {
+              continue;
+            }
+            command.arg(s);
+          }
+          let output = command.output();
+          match output {
+            Ok(o) => {
+              let output = String::from_utf8_lossy(&o.stdout);
+              if !output.is_empty() {
+                Err(format!("Shell command `{}` ran successfully but printed the following output:\n\n{}\n\nSuppress output of shell commands to prevent the error prompt from showing up.", shell, output))
+              } else {
+                Ok(())
+              }
+            }
+            Err(_) => Err(format!("Shell command `{}` exited with non-zero output", shell)),
+          }
+        }
+      }
+      None => Err(format!("Cannot run subprocess. Unable to shlex split `{}`", shell)),
+    };
+
+    if task_uuids.len() == 1 {
+      if let Some(uuid) = task_uuids.first() {
+        self.current_selection_uuid = Some(*uuid);
+      }
+    }
+
+    r
+  }
+
+  pub fn task_log(&mut self) -> Result<(), String> {
+    let mut command = overrides::task();
+
+    command.arg("log");
+
+    let shell = self.command.as_str().to_string();
+
+    match Self::entry_args(&shell, self.config.uda_quick_add) {
+      Some(cmd) => {
+        for s in cmd {
+          command.arg(&s);
+        }
+        let output = self.run_write(&mut command);
+        match output {
+          Ok(write) => {
+            if !matches!(&write, Written::Ran(o) if !o.status.success()) {
+              self.journal_write(&write, JournalKind::Log, &command, vec![], None);
+            }
+            Ok(())
+          }
+          Err(_) => Err(format!("Cannot run `task log {}`. Check documentation for more information", shell)),
+        }
+      }
+      None => Err(format!("Unable to run `{:?}`: shlex::split(`{}`) failed.", command, shell)),
+    }
+  }
+
+  pub fn task_background(&mut self) {
+    let shell = self.config.uda_background_process.clone();
+    if shell.is_empty() {
+      return;
+    }
+    let shell = shellexpand::tilde(&shell).into_owned();
+    let period = self.config.uda_background_process_period;
+    let job = self.background_job.clone();
+    std::thread::spawn(move || loop {
+      std::thread::sleep(Duration::from_secs(period as u64));
+      match shlex::split(&shell) {
+        Some(cmd) => {
+          let mut command = std::process::Command::new(&cmd[0]);
+          for s in cmd.iter().skip(1) {
+            command.arg(s);
+          }
+          command.stdout(std::process::Stdio::null()).stderr(std::process::Stdio::null());
+          match command.spawn() {
+            Ok(child) => *job.lock().unwrap() = Some(child),
+            Err(_) => break,
+          }
+          // keep the child where quitting can find it, and poll until it exits
+          let status = loop {
+            std::thread::sleep(Duration::from_millis(100));
+            let mut job = job.lock().unwrap();
+            match job.as_mut().map(|c| c.try_wait()) {
+              Some(Ok(Some(status))) => {
+                job.take();
+                break Some(status);
+              }
+              Some(Ok(None)) => {}
+              Some(Err(_)) | None => {
+                job.take();
+                break None;
+              }
+            }
+          };
+          if !status.map(|s| s.success()).unwrap_or(false) {
+            break;
+          }
+        }
+        None => break,
+      };
+    });
+  }
+
+  pub fn background_job_running(&self) -> bool {
+    self.background_job.lock().map(|j| j.is_some()).unwrap_or(false)
+  }
+
+  /// Quit, unless a background job is still running or confirmation is configured, in which case ask first.
+  pub fn request_quit(&mut self) {
+    if self.background_job_running() || self.config.uda_quit_confirm {
+      self.previous_mode = Some(self.mode.clone());
+      self.mode = Mode::Tasks(Action::QuitPrompt);
+    } else {
+      self.should_quit = true;
+    }
+  }
+
+  fn force_quit(&mut self) {
+    if let Ok(mut job) = self.background_job.lock() {
+      if let Some(mut child) = job.take() {
+        child.kill().unwrap_or_else(|e| warn!("Unable to stop background job: {}", e));
+        child.wait().ok();
+      }
+    }
+    self.should_quit = true;
+  }
+
+  pub async fn task_shortcut(&mut self, s: usize) -> Result<(), String> {
+    self.pause_tui().await.map_err(|e| e.to_string())?;
+
+    let task_uuids = if self.tasks.is_empty() { vec![] } else { self.selected_task_uuids() };
+
+    let shell = &self.config.uda_shortcuts[s];
+
+    if shell.is_empty() {
+      self.resume_tui().await.map_err(|e| e.to_string())?;
+      return Err("Trying to run empty shortcut.".to_string());
+    }
+
+    let shell = format!(
+      "{} {}",
+      shell,
+      task_uuids.iter().map(ToString::to_string).collect::<Vec<String>>().join(" ")
+    );
+
+    let shell = shellexpand::tilde(&shell).into_owned();
+    let r = match shlex::split(&shell) {
+      Some(cmd) => {
+        let mut command = std::process::Command::new(&cmd[0]);
+        for i in cmd.iter().skip(1) {
+          command.arg(i);
+        }
+        match command.spawn() {
+          Ok(child) => {
+            let output = child.wait_with_output();
+            match output {
+              Ok(o) => {
+                if o.status.success() {
+                  Ok(())
+                } else {
+                  Err(format!(
+                    "Unable to run shortcut {}. Status Code: {} - stdout: {} stderr: {}",
+                    s,
+                    o.status.code().unwrap_or_default(),
+                    String::from_utf8_lossy(&o.stdout),
+                    String::from_utf8_lossy(&o.stderr),
+                  ))
+                }
+              }
+              Err(s) => Err(format!("`{}` failed to wait with output: {}", shell, s)),
+            }
+          }
+          Err(err) => Err(format!("`{}` failed: Unable to spawn shortcut number {} - Error: {}", shell, s, err)),
+        }
+      }
+      None => Err(format!("Unable to run shortcut number {}: shlex::split(`{}`) failed.", s, shell)),
+    };
+
+    if task_uuids.len() == 1 {
+      if let Some(uuid) = task_uuids.first() {
+        self.current_selection_uuid = Some(*uuid);
+      }
+    }
+
+    self.resume_tui().await.map_err(|e| e.to_string())?;
+
+    r
+  }
+
+  pub fn task_modify(&mut self) -> Result<(), String> {
+    if self.tasks.is_empty() {
+      return Ok(());
+    }
+
+    let task_uuids = self.selected_task_uuids();
+    self.back_up_before_bulk(task_uuids.len())?;
+
+    let mut command = overrides::task();
+    command.arg("rc.bulk=0");
+    command.arg("rc.confirmation=off");
+    command.arg("rc.dependency.confirmation=off");
+    command.arg("rc.recurrence.confirmation=off");
+    for task_uuid in &task_uuids {
+      command.arg(task_uuid.to_string());
+    }
+    command.arg("modify");
+
+    let shell = self.modify.as_str().to_string();
+
+    let r = match shlex::split(&shell) {
+      Some(cmd) => {
+        for s in cmd {
+          command.arg(&s);
+        }
+        let threshold = self.config.uda_stream_bulk_threshold;
+        if threshold > 0 && task_uuids.len() >= threshold && self.write_queue.is_empty() {
+          let args: Vec<String> = command.get_args().map(|a| a.to_string_lossy().to_string()).collect();
+          let title = format!("Modify {} tasks", task_uuids.len());
+          self.start_output_stream(&title, &args)?;
+          self.stream_journal = Some((JournalKind::Modify, Self::command_to_string(&command), task_uuids.clone()));
+          return Ok(());
+        }
+        let output = self.run_write(&mut command);
+        match output {
+          Ok(Written::Ran(o)) if !o.status.success() => Err(format!("Modify failed. {}", String::from_utf8_lossy(&o.stdout))),
+          Ok(write) => {
+            self.journal_write(&write, JournalKind::Modify, &command, task_uuids.clone(), None);
+            Ok(())
+          }
+          Err(_) => Err(format!(
+            "Cannot run `task {:?} modify {}`. Check documentation for more information",
+            task_uuids, shell,
+          )),
+        }
+      }
+      None => Err(format!("Cannot shlex split `{}`", shell)),
+    };
+
+    if task_uuids.len() == 1 {
+      if let Some(uuid) = task_uuids.first() {
+        self.current_selection_uuid = Some(*uuid);
+      }
+    }
+
+    r
+  }
+
+  pub fn task_annotate(&mut self) -> Result<(), String> {
+    if self.tasks.is_empty() {
+      return Ok(());
+    }
+
+    let task_uuids = self.selected_task_uuids();
+
+    let mut command = overrides::task();
+    command.arg("rc.bulk=0");
+    command.arg("rc.confirmation=off");
+    command.arg("rc.dependency.confirmation=off");
+    command.arg("rc.recurrence.confirmation=off");
+    for task_uuid in &task_uuids {
+      command.arg(task_uuid.to_string());
+    }
+    command.arg("annotate");
+
+    let shell = self.command.as_str().to_string();
+
+    let r = match shlex::split(&shell) {
+      Some(cmd) => {
+        // a single argument after `--` is annotated as is, so the compensation can name it exactly
+        let annotation = cmd.join(" ");
+        command.arg("--").arg(&annotation);
+        let output = self.run_write(&mut command);
+        match output {
+          Ok(Written::Ran(o)) if !o.status.success() => Err(format!("Annotate failed. {}", String::from_utf8_lossy(&o.stdout))),
+          Ok(write) => {
+            let compensation = Self::denotate_arguments(&task_uuids, &annotation);
+            self.journal_write(&write, JournalKind::Annotate, &command, task_uuids.clone(), Some(compensation));
+            Ok(())
+          }
+          Err(_) => Err(format!(
+            "Cannot run `task {} annotate {}`. Check documentation for more information",
+            task_uuids.iter().map(ToString::to_string).collect::<Vec<String>>().join(" "),
+            shell
+          )),
+        }
+      }
+      None => Err(format!("Cannot shlex split `{}`", shell)),
+    };
+
+    if task_uuids.len() == 1 {
+      if let Some(uuid) = task_uuids.first() {
+        self.current_selection_uuid = Some(*uuid);
+      }
+    }
+    r
+  }
+
+  pub fn task_add(&mut self) -> Result<(), String> {
+    let mut command = overrides::task();
+    command.arg("add");
+
+    let shell = self.command.as_str();
+
+    match Self::entry_args(shell, self.config.uda_quick_add) {
+      Some(cmd) => {
+        for s in cmd {
+          command.arg(&s);
+        }
+        let output = self.run_write(&mut command);
+        match output {
+          Ok(Written::Queued) => {
+            // the id of the task is only known once it is added
+            self.journal_write(&Written::Queued, JournalKind::Add, &command, vec![], None);
+            Ok(())
+          }
+          Ok(Written::Ran(output)) => {
+            if output.status.code() == Some(0) {
+              let data = String::from_utf8_lossy(&output.stdout);
+              let re = Regex::new(r"^Created task (?P<task_id>\d+).\n$").unwrap();
+              let task_id = re.captures(&data).map(|caps| caps["task_id"].parse::<u64>().unwrap_or_default());
+              if self.config.uda_task_report_jump_to_task_on_add {
+                if let Some(task_id) = task_id {
+                  self.current_selection_id = Some(task_id);
+                }
+              }
+              let task_uuid = task_id.and_then(Self::task_uuid_by_id);
+              self.journal.record(
+                JournalKind::Add,
+                Self::command_to_string(&command),
+                task_uuid.into_iter().collect(),
+                task_uuid.map(|uuid| vec![uuid.to_string(), "delete".to_string()]),
+              );
+              Ok(())
+            } else {
+              Err(format!("Error: {}", String::from_utf8_lossy(&output.stderr)))
+            }
+          }
+          Err(e) => Err(format!("Cannot run `{:?}`: {}", command, e)),
+        }
+      }
+      None => Err(format!("Unable to run `{:?}`: shlex::split(`{}`) failed.", command, shell)),
+    }
+  }
+
+  /// Arguments of `task add` or `task log` for what was typed, with the dates, tags and priority
+  /// of quick add turned into attributes when `quick_add` is on.
+  fn entry_args(shell: &str, quick_add: bool) -> Option<Vec<String>> {
+    if quick_add {
+      QuickAdd::parse(shell).map(|quick| quick.args())
+    } else {
+      shlex::split(shell)
+    }
+  }
+
+  pub fn task_virtual_tags(task_uuid: Uuid) -> Result<String, String> {
+    let output = overrides::task().arg(format!("{}", task_uuid)).output();
+
+    match output {
+      Ok(output) => {
+        let data = String::from_utf8_lossy(&output.stdout);
+        for line in data.split('\n') {
+          for prefix in &["Virtual tags", "Virtual"] {
+            if line.starts_with(prefix) {
+              let line = line.to_string();
+              let line = line.replace(prefix, "");
+              return Ok(line);
+            }
+          }
+        }
+        Err(format!(
+          "Cannot find any tags for `task {}`. Check documentation for more information",
+          task_uuid
+        ))
+      }
+      Err(_) => Err(format!("Cannot run `task {}`. Check documentation for more information", task_uuid)),
+    }
+  }
+
+  pub fn task_start_stop(&mut self) -> Result<(), String> {
+    if self.tasks.is_empty() {
+      return Ok(());
+    }
+
+    let task_uuids = self.selected_task_uuids();
+
+    for task_uuid in &task_uuids {
+      let mut command = "start";
+      for tag in TaskwarriorTui::task_virtual_tags(*task_uuid).unwrap_or_default().split(' ') {
+        if tag == "ACTIVE" {
+          command = "stop";
+        }
+      }
+
+      let mut cmd = overrides::task();
+      cmd.arg(task_uuid.to_string()).arg(command);
+      let write = match self.run_write(&mut cmd) {
+        Ok(Written::Ran(o)) if !o.status.success() => {
+          return Err(format!(
+            "Error running `task {}` for task `{}`. {}",
+            command,
+            task_uuid,
+            String::from_utf8_lossy(&o.stderr).trim()
+          ))
+        }
+        Ok(write) => write,
+        Err(_) => return Err(format!("Error running `task {}` for task `{}`.", command, task_uuid)),
+      };
+      if command == "stop" {
+        self.record_focus(*task_uuid);
+      }
+      let (kind, inverse) = if command == "start" {
+        (JournalKind::Start, "stop")
+      } else {
+        (JournalKind::Stop, "start")
+      };
+      self.journal_write(
+        &write,
+        kind,
+        &cmd,
+        vec![*task_uuid],
+        Some(vec![task_uuid.to_string(), inverse.to_string()]),
+      );
+    }
+
+    if task_uuids.len() == 1 {
+      if let Some(uuid) = task_uuids.first() {
+        self.current_selection_uuid = Some(*uuid);
+      }
+    }
+
+    Ok(())
+  }
+
+  pub fn task_quick_tag(&mut self) -> Result<(), String> {
+    let tag_name = self.config.uda_quick_tag_name.clone();
+    let ptag_name = format!("+{}", tag_name);
+    let ntag_name = format!("-{}", tag_name);
+    if self.tasks.is_empty() {
+      return Ok(());
+    }
+
+    let task_uuids = self.selected_task_uuids();
+
+    for task_uuid in &task_uuids {
+      if let Some(task) = self.task_by_uuid(*task_uuid) {
+        let mut tag_to_set = &ptag_name;
+        for tag in task.tags().unwrap() {
+          if *tag == tag_name {
+            tag_to_set = &ntag_name;
+          }
+        }
+
+        let mut cmd = overrides::task();
+        cmd.arg(task_uuid.to_string()).arg("modify").arg(tag_to_set);
+        let write = match self.run_write(&mut cmd) {
+          Ok(Written::Ran(o)) if !o.status.success() => {
+            return Err(format!(
+              "Error running `task modify {}` for task `{}`. {}",
+              tag_to_set,
+              task_uuid,
+              String::from_utf8_lossy(&o.stderr).trim()
+            ))
+          }
+          Ok(write) => write,
+          Err(_) => return Err(format!("Error running `task modify {}` for task `{}`.", tag_to_set, task_uuid,)),
+        };
+
+        let inverse = if tag_to_set == &ptag_name { &ntag_name } else { &ptag_name };
+        self.journal_write(
+          &write,
+          JournalKind::Tag,
+          &cmd,
+          vec![*task_uuid],
+          Some(vec![task_uuid.to_string(), "modify".to_string(), inverse.clone()]),
+        );
+      }
+    }
+
+    if task_uuids.len() == 1 {
+      if let Some(uuid) = task_uuids.first() {
+        self.current_selection_uuid = Some(*uuid);
+      }
+    }
+
+    Ok(())
+  }
+
+  pub fn task_delete(&mut self) -> Result<(), String> {
+    if self.tasks.is_empty() {
+      return Ok(());
+    }
+
+    let task_uuids = self.selected_task_uuids();
+    self.back_up_before_bulk(task_uuids.len())?;
+
+    let mut cmd = overrides::task();
+    cmd
+      .arg("rc.bulk=0")
+      .arg("rc.confirmation=off")
+      .arg("rc.dependency.confirmation=off")
+      .arg("rc.recurrence.confirmation=off");
+    for task_uuid in &task_uuids {
+      cmd.arg(task_uuid.to_string());
+    }
+    cmd.arg("delete");
+    let output = self.run_write(&mut cmd);
+    let r = match output {
+      Ok(write) => {
+        if !matches!(&write, Written::Ran(o) if !o.status.success()) {
+          self.remember_recent_actions(RecentActionKind::Delete, &task_uuids);
+          self.journal_write(
+            &write,
+            JournalKind::Delete,
+            &cmd,
+            task_uuids.clone(),
+            Some(Self::restore_arguments(&task_uuids)),
+          );
+        }
+        Ok(())
+      }
+      Err(_) => Err(format!(
+        "Cannot run `task delete` for tasks `{}`. Check documentation for more information",
+        task_uuids.iter().map(ToString::to_string).collect::<Vec<String>>().join(" ")
+      )),
+    };
+    self.select_after_removal(&task_uuids);
+    r
+  }
+
+  pub fn task_done(&mut self) -> Result<(), String> {
+    if self.tasks.is_empty() {
+      return Ok(());
+    }
+    let task_uuids = self.selected_task_uuids();
+    self.back_up_before_bulk(task_uuids.len())?;
+    let mut cmd = overrides::task();
+    cmd
+      .arg("rc.bulk=0")
+      .arg("rc.confirmation=off")
+      .arg("rc.dependency.confirmation=off")
+      .arg("rc.recurrence.confirmation=off");
+    for task_uuid in &task_uuids {
+      cmd.arg(task_uuid.to_string());
+    }
+    cmd.arg("done");
+    let output = self.run_write(&mut cmd);
+    let r = match output {
+      Ok(write) => {
+        if !matches!(&write, Written::Ran(o) if !o.status.success()) {
+          for uuid in &task_uuids {
+            self.record_focus(*uuid);
+          }
+          self.remember_recent_actions(RecentActionKind::Done, &task_uuids);
+          self.journal_write(
+            &write,
+            JournalKind::Done,
+            &cmd,
+            task_uuids.clone(),
+            Some(Self::restore_arguments(&task_uuids)),
+          );
+        }
+        Ok(())
+      }
+      Err(_) => Err(format!(
+        "Cannot run `task done` for task `{}`. Check documentation for more information",
+        task_uuids.iter().map(ToString::to_string).collect::<Vec<String>>().join(" ")
+      )),
+    };
+    self.select_after_removal(&task_uuids);
+    r
+  }
+
+  /// Add the time `uuid` was active for to its tracked time, when it is being stopped.
+  fn record_focus(&mut self, uuid: Uuid) {
+    if let Some(task) = self.task_by_uuid(uuid) {
+      if let Err(e) = self.focus.stop(&task, chrono::Utc::now()) {
+        warn!("Unable to save tracked time: {}", e);
+      }
+    }
+  }
+
+  fn remember_recent_actions(&mut self, kind: RecentActionKind, task_uuids: &[Uuid]) {
+    for uuid in task_uuids {
+      let description = self.task_by_uuid(*uuid).map(|t| t.description().clone()).unwrap_or_default();
+      self.recent_actions.push(kind, *uuid, description);
+    }
+  }
+
+  pub fn task_restore(&mut self) -> Result<(), String> {
+    let action = match self.recent_actions.selected() {
+      Some(a) => a.clone(),
+      None => return Ok(()),
+    };
+    let mut cmd = overrides::task();
+    cmd.arg("rc.confirmation=off").args(Self::restore_arguments(&[action.uuid]));
+    let output = self.run_write(&mut cmd);
+    match output {
+      Ok(Written::Ran(o)) if !o.status.success() => Err(format!(
+        "Unable to restore task `{}`. {}",
+        action.uuid,
+        String::from_utf8_lossy(&o.stderr)
+      )),
+      Ok(write) => {
+        let inverse = match action.kind {
+          RecentActionKind::Done => "done",
+          RecentActionKind::Delete => "delete",
+        };
+        self.journal_write(
+          &write,
+          JournalKind::Restore,
+          &cmd,
+          vec![action.uuid],
+          Some(vec![action.uuid.to_string(), inverse.to_string()]),
+        );
+        self.recent_actions.remove_selected();
+        self.current_selection_uuid = Some(action.uuid);
+        Ok(())
+      }
+      Err(_) => Err(format!(
+        "Cannot run `task {} modify status:pending end:`. Check documentation for more information",
+        action.uuid
+      )),
+    }
+  }
+
+  fn denotate_arguments(task_uuids: &[Uuid], annotation: &str) -> Vec<String> {
+    let mut args: Vec<String> = task_uuids.iter().map(ToString::to_string).collect();
+    args.push("denotate".to_string());
+    args.push("--".to_string());
+    args.push(annotation.to_string());
+    args
+  }
+
+  fn restore_arguments(task_uuids: &[Uuid]) -> Vec<String> {
+    let mut args: Vec<String> = task_uuids.iter().map(ToString::to_string).collect();
+    args.push("modify".to_string());
+    args.push("status:pending".to_string());
+    args.push("end:".to_string());
+    args
+  }
+
+  fn command_to_string(command: &std::process::Command) -> String {
+    let args: Vec<String> = command.get_args().map(|a| a.to_string_lossy().to_string()).collect();
+    utils::command_line(&command.get_program().to_string_lossy(), &args)
+  }
+
+  fn task_uuid_by_id(id: u64) -> Option<Uuid> {
+    let output = overrides::task().arg("_get").arg(format!("{}.uuid", id)).output().ok()?;
+    Uuid::parse_str(String::from_utf8_lossy(&output.stdout).trim()).ok()
+  }
+
+  pub fn task_compensate(&mut self) -> Result<(), String> {
+    let entry = match self.journal.selected() {
+      Some(e) => e.clone(),
+      None => return Ok(()),
+    };
+    if entry.compensated {
+      return Err(format!("`{}` was already reverted.", entry.command));
+    }
+    let args = match entry.compensation {
+      Some(args) => args,
+      None => return Err(format!("There is no compensating action for `{}`.", entry.command)),
+    };
+    let mut cmd = overrides::task();
+    cmd
+      .arg("rc.bulk=0")
+      .arg("rc.confirmation=off")
+      .arg("rc.dependency.confirmation=off")
+      .arg("rc.recurrence.confirmation=off")
+      .args(&args);
+    match self.run_write(&mut cmd) {
+      Ok(Written::Ran(o)) if !o.status.success() => Err(format!(
+        "Compensating action `{}` failed. {}",
+        Self::command_to_string(&cmd),
+        String::from_utf8_lossy(&o.stderr)
+      )),
+      Ok(write) => {
+        self.journal.mark_selected_compensated();
+        self.journal_write(&write, JournalKind::Compensate, &cmd, entry.uuids.clone(), None);
+        self.journal.state.select(Some(0));
+        if let Some(uuid) = entry.uuids.first() {
+          self.current_selection_uuid = Some(*uuid);
+        }
+        Ok(())
+      }
+      Err(e) => Err(format!("Cannot run `{}`: {}", Self::command_to_string(&cmd), e)),
+    }
+  }
+
+  pub fn task_undo(&mut self) -> Result<(), String> {
+    let mut cmd = overrides::task();
+    cmd.arg("rc.confirmation=off").arg("undo");
+    let output = self.run_write(&mut cmd);
+
+    match output {
+      Ok(Written::Ran(output)) if !output.status.success() => Err(format!("Undo failed. {}", String::from_utf8_lossy(&output.stderr).trim())),
+      Ok(Written::Queued) => {
+        self.journal_write(&Written::Queued, JournalKind::Undo, &cmd, vec![], None);
+        Ok(())
+      }
+      Ok(Written::Ran(output)) => {
+        self.journal.record(JournalKind::Undo, Self::command_to_string(&cmd), vec![], None);
+        let data = String::from_utf8_lossy(&output.stdout);
+        let re = Regex::new(r"(?P<task_uuid>[a-fA-F0-9]{8}-[a-fA-F0-9]{4}-[a-fA-F0-9]{4}-[a-fA-F0-9]{4}-[a-fA-F0-9]{12})").unwrap();
+        if let Some(caps) = re.captures(&data) {
+          if let Ok(uuid) = Uuid::parse_str(&caps["task_uuid"]) {
+            self.current_selection_uuid = Some(uuid);
+          }
+        }
+        Ok(())
+      }
+      Err(_) => Err("Cannot run `task undo`. Check documentation for more information".to_string()),
+    }
+  }
+
+  pub async fn task_edit(&mut self) -> Result<(), String> {
+    if self.tasks.is_empty() {
+      return Ok(());
+    }
+
+    self.pause_tui().await.map_err(|e| e.to_string())?;
+
+    let selected = self.current_selection;
+    let task_id = self.tasks[selected].id().unwrap_or_default();
+    let task_uuid = *self.tasks[selected].uuid();
+
+    let mut cmd = overrides::task();
+    cmd.arg(format!("{}", task_uuid)).arg("edit");
+    let r = cmd.spawn();
+
+    let r = match r {
+      Ok(child) => {
+        let output = child.wait_with_output();
+        match output {
+          Ok(output) => {
+            if output.status.success() {
+              self
+                .journal
+                .record(JournalKind::Edit, Self::command_to_string(&cmd), vec![task_uuid], None);
+              Ok(())
+            } else {
+              Err(format!(
+                "`task edit` for task `{}` failed. {}{}",
+                task_uuid,
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr),
+              ))
+            }
+          }
+          Err(err) => Err(format!("Cannot run `task edit` for task `{}`. {}", task_uuid, err)),
+        }
+      }
+      _ => Err(format!(
+        "Cannot start `task edit` for task `{}`. Check documentation for more information",
+        task_uuid
+      )),
+    };
+
+    self.current_selection_uuid = Some(task_uuid);
+
+    self.resume_tui().await.map_err(|e| e.to_string())?;
+
+    r
+  }
+
+  pub fn task_current(&self) -> Option<Task> {
+    if self.tasks.is_empty() {
+      return None;
+    }
+    let selected = self.current_selection;
+    Some(self.tasks[selected].clone())
+  }
+
+  pub fn update_tags(&mut self) {
+    let tasks = &mut self.tasks;
+
+    // dependency scan
+    for l_i in 0..tasks.len() {
+      let default_deps = vec![];
+      let deps = tasks[l_i].depends().unwrap_or(&default_deps).clone();
+      add_tag(&mut tasks[l_i], "UNBLOCKED".to_string());
+      for dep in deps {
+        for r_i in 0..tasks.len() {
+          if tasks[r_i].uuid() == &dep {
+            let l_status = tasks[l_i].status();
+            let r_status = tasks[r_i].status();
+            if l_status != &TaskStatus::Completed
+              && l_status != &TaskStatus::Deleted
+              && r_status != &TaskStatus::Completed
+              && r_status != &TaskStatus::Deleted
+            {
+              remove_tag(&mut tasks[l_i], "UNBLOCKED");
+              add_tag(&mut tasks[l_i], "BLOCKED".to_string());
+              add_tag(&mut tasks[r_i], "BLOCKING".to_string());
+            }
+            break;
+          }
+        }
+      }
+    }
+
+    // other virtual tags
+    // TODO: support all virtual tags that taskwarrior supports
+    for task in tasks.iter_mut() {
+      match task.status() {
+        TaskStatus::Waiting => add_tag(task, "WAITING".to_string()),
+        TaskStatus::Completed => add_tag(task, "COMPLETED".to_string()),
+        TaskStatus::Pending => add_tag(task, "PENDING".to_string()),
+        TaskStatus::Deleted => add_tag(task, "DELETED".to_string()),
+        TaskStatus::Recurring => (),
+      }
+      if task.start().is_some() {
+        add_tag(task, "ACTIVE".to_string());
+      }
+      if task.scheduled().is_some() {
+        add_tag(task, "SCHEDULED".to_string());
+      }
+      if task.parent().is_some() {
+        add_tag(task, "INSTANCE".to_string());
+      }
+      if task.until().is_some() {
+        add_tag(task, "UNTIL".to_string());
+      }
+      if task.annotations().is_some() {
+        add_tag(task, "ANNOTATED".to_string());
+      }
+      let virtual_tags = self.task_report_table.virtual_tags.clone();
+      if task.tags().is_some() && task.tags().unwrap().iter().any(|s| !virtual_tags.contains(s)) {
+        add_tag(task, "TAGGED".to_string());
+      }
+      if !task.uda().is_empty() {
+        add_tag(task, "UDA".to_string());
+      }
+      if task.mask().is_some() {
+        add_tag(task, "TEMPLATE".to_string());
+      }
+      if task.project().is_some() {
+        add_tag(task, "PROJECT".to_string());
+      }
+      if task.priority().is_some() {
+        add_tag(task, "PRIORITY".to_string());
+      }
+      if task.recur().is_some() {
+        add_tag(task, "RECURRING".to_string());
+        let r = task.recur().unwrap();
+      }
+      if let Some(d) = task.due() {
+        let status = task.status();
+        // due today
+        if status != &TaskStatus::Completed && status != &TaskStatus::Deleted {
+          let now = Local::now();
+          let reference = TimeZone::from_utc_datetime(now.offset(), d);
+          let now = TimeZone::from_utc_datetime(now.offset(), &now.naive_utc());
+          let d = d.clone();
+          if (reference - chrono::Duration::nanoseconds(1)).month() == now.month() {
+            add_tag(task, "MONTH".to_string());
+          }
+          if (reference - chrono::Duration::nanoseconds(1)).month() % 4 == now.month() % 4 {
+            add_tag(task, "QUARTER".to_string());
+          }
+          if reference.year() == now.year() {
+            add_tag(task, "YEAR".to_string());
+          }
+          match get_date_state(&d, self.config.due) {
+            DateState::EarlierToday | DateState::LaterToday => {
+              add_tag(task, "DUE".to_string());
+              add_tag(task, "TODAY".to_string());
+              add_tag(task, "DUETODAY".to_string());
+            }
+            DateState::AfterToday => {
+              add_tag(task, "DUE".to_string());
+              if reference.date_naive() == (now + chrono::Duration::days(1)).date_naive() {
+                add_tag(task, "TOMORROW".to_string());
+              }
+            }
+            _ => (),
+          }
+        }
+      }
+      if let Some(d) = task.due() {
+        let status = task.status();
+        // overdue
+        if status != &TaskStatus::Completed && status != &TaskStatus::Deleted && status != &TaskStatus::Recurring {
+          let now = Local::now().naive_utc();
+          let d = NaiveDateTime::new(d.date(), d.time());
+          if d < now {
+            add_tag(task, "OVERDUE".to_string());
+          }
+        }
+      }
+    }
+  }
+
+  pub fn toggle_mark(&mut self) {
+    if !self.tasks.is_empty() {
+      let selected = self.current_selection;
+      let task_id = self.tasks[selected].id().unwrap_or_default();
+      let task_uuid = *self.tasks[selected].uuid();
+
+      if !self.marked.insert(task_uuid) {
+        self.marked.remove(&task_uuid);
+      }
+    }
+  }
+
+  pub fn toggle_mark_all(&mut self) {
+    for task in &self.tasks {
+      if !self.marked.insert(*task.uuid()) {
+        self.marked.remove(task.uuid());
+      }
+    }
+  }
+
+  pub fn apply_layout(&mut self, preset: &LayoutPreset) {
+    self.task_report_show_info = preset.details;
+    if let Some(filter) = &preset.filter {
+      self.filter.update(filter, filter.len(), &mut self.changes);
+      self.dirty = true;
+    }
+    self.mode = match preset.view {
+      View::Tasks => Mode::Tasks(Action::Report),
+      View::Projects => Mode::Projects,
+      View::Calendar => Mode::Calendar,
+      View::Review => {
+        // the review remembers the filter to go back to, so the preset filter is set first
+        if let Err(e) = self.review.start(self.filter.as_str()) {
+          self.error = Some(format!("Unable to start the weekly review: {}", e));
+          self.mode = Mode::Tasks(Action::Error);
+          return;
+        }
+        self.review_state.select(Some(self.review.next_step(&self.config.uda_review_steps)));
+        Mode::Tasks(Action::Review)
+      }
+    };
+  }
+
+  pub fn current_layout(&self, name: &str) -> LayoutPreset {
+    LayoutPreset {
+      name: name.to_string(),
+      view: match self.mode {
+        Mode::Projects => View::Projects,
+        Mode::Calendar => View::Calendar,
+        Mode::Tasks(Action::Review) => View::Review,
+        Mode::Tasks(_) => View::Tasks,
+      },
+      details: self.task_report_show_info,
+      filter: Some(self.filter.as_str().to_string()),
+      key: None,
+    }
+  }
+
+  fn palette_commands(&self) -> Vec<(String, PaletteCommand)> {
+    let mut commands: Vec<(String, PaletteCommand)> = self
+      .layouts
+      .presets
+      .iter()
+      .map(|p| {
+        let key = p.key.as_ref().map(|k| format!(" ({})", k)).unwrap_or_default();
+        (format!("Layout: {}{}", p.name, key), PaletteCommand::Layout(p.name.clone()))
+      })
+      .collect();
+    commands.extend([
+      ("Save current layout as ...".to_string(), PaletteCommand::SaveLayout),
+      ("Toggle task details".to_string(), PaletteCommand::ToggleDetails),
+      ("Refresh".to_string(), PaletteCommand::Refresh),
+      ("Help".to_string(), PaletteCommand::Help),
+      ("Switch context".to_string(), PaletteCommand::ContextMenu),
+      ("What changed".to_string(), PaletteCommand::WhatChanged),
+      ("Recent actions".to_string(), PaletteCommand::RecentActions),
+      ("Action history".to_string(), PaletteCommand::ActionHistory),
+      ("Sort columns".to_string(), PaletteCommand::SortColumns),
+    ]);
+    if self.perf.is_some() {
+      commands.push(("Performance report".to_string(), PaletteCommand::PerfReport));
+    }
+    commands.push(("Show log".to_string(), PaletteCommand::ShowLog));
+    if self.config.uda_usage_stats {
+      commands.push(("Usage statistics".to_string(), PaletteCommand::UsageStats));
+    }
+    commands.push(("Toggle ready tasks only".to_string(), PaletteCommand::ToggleReady));
+    if self.config.uda_team.is_some() {
+      commands.push(("Toggle my tasks only".to_string(), PaletteCommand::ToggleMine));
+      commands.push(("Assign to ...".to_string(), PaletteCommand::Assign));
+    }
+    commands.push(("Take screenshot".to_string(), PaletteCommand::Screenshot));
+    commands.push(("Copy link to task".to_string(), PaletteCommand::CopyLink));
+    if !self.config.uda_offline {
+      commands.push(("Sync tasks (task sync)".to_string(), PaletteCommand::Sync));
+    }
+    commands.push(("Tune urgency coefficients".to_string(), PaletteCommand::UrgencyTuner));
+    commands.push(("Show goals".to_string(), PaletteCommand::Goals));
+    commands.push(("Replace in descriptions".to_string(), PaletteCommand::Replace));
+    commands.push(("Projects without a next action".to_string(), PaletteCommand::StalledProjects));
+    commands.push(("Activity feed".to_string(), PaletteCommand::Activity));
+    if !self.config.uda_offline {
+      commands.push(("Changelog".to_string(), PaletteCommand::Changelog));
+    }
+    commands.push(("Take the tour".to_string(), PaletteCommand::Tour));
+    commands.push(("Back up tasks now".to_string(), PaletteCommand::BackUp));
+    if self.config.uda_secret.is_some() {
+      if self.revealed_secret.is_some() {
+        commands.push(("Hide secret".to_string(), PaletteCommand::HideSecret));
+      } else {
+        commands.push(("Reveal secret".to_string(), PaletteCommand::RevealSecret));
+      }
+      commands.push(("Edit secret".to_string(), PaletteCommand::EditSecret));
+    }
+    commands.push(("Restore from a backup".to_string(), PaletteCommand::Backups));
+    if !self.config.uda_review_steps.is_empty() {
+      commands.push(("Weekly review".to_string(), PaletteCommand::Review));
+    }
+    if self.review.in_progress() {
+      commands.push(("Abandon weekly review".to_string(), PaletteCommand::AbandonReview));
+    }
+    if self.output_stream.is_some() {
+      commands.push(("Show command output".to_string(), PaletteCommand::ShowOutput));
+    }
+    if !self.config.uda_rules.is_empty() {
+      commands.push(("Review archive rules".to_string(), PaletteCommand::Rules));
+    }
+    for workspace in &self.config.uda_workspaces {
+      commands.push((
+        format!("Workspace: {}", workspace.name),
+        PaletteCommand::Workspace(workspace.name.clone()),
+      ));
+    }
+    for named in &self.config.uda_named_filters {
+      commands.push((format!("Filter: {}", named.name), PaletteCommand::Filter(named.filter.clone())));
+    }
+    for density in Density::ALL.iter().filter(|d| **d != self.density) {
+      commands.push((format!("Density: {}", density.name()), PaletteCommand::Density(*density)));
+    }
+    commands.push(("Edit description in editor".to_string(), PaletteCommand::EditDescription));
+    commands.push(("Annotate in editor".to_string(), PaletteCommand::AnnotateInEditor));
+    commands
+  }
+
+  async fn run_palette_command(&mut self, command: PaletteCommand) -> Result<()> {
+    self.mode = Mode::Tasks(Action::Report);
+    match command {
+      PaletteCommand::Layout(name) => {
+        if let Some(preset) = self.layouts.get(&name).cloned() {
+          self.apply_layout(&preset);
+        }
+      }
+      PaletteCommand::SaveLayout => {
+        self.reset_command();
+        self.mode = Mode::Tasks(Action::SaveLayout);
+      }
+      PaletteCommand::ToggleDetails => self.task_report_show_info = !self.task_report_show_info,
+      PaletteCommand::Refresh => self.update(true).await?,
+      PaletteCommand::Help => self.mode = Mode::Tasks(Action::HelpPopup),
+      PaletteCommand::ContextMenu => self.mode = Mode::Tasks(Action::ContextMenu),
+      PaletteCommand::WhatChanged => {
+        self.snapshot.scroll = 0;
+        self.mode = Mode::Tasks(Action::SnapshotDiff);
+      }
+      PaletteCommand::RecentActions => {
+        self.recent_actions.select_first_if_needed();
+        self.mode = Mode::Tasks(Action::RecentActions);
+      }
+      PaletteCommand::ActionHistory => {
+        self.journal.select_first_if_needed();
+        self.mode = Mode::Tasks(Action::Journal);
+      }
+      PaletteCommand::SortColumns => {
+        let columns = self.task_report_table.visible_columns.len();
+        self.task_report_header_focus = self.task_report_header_focus.min(columns.saturating_sub(1));
+        self.mode = Mode::Tasks(Action::HeaderFocus);
+      }
+      PaletteCommand::PerfReport => self.mode = Mode::Tasks(Action::PerfReport),
+      PaletteCommand::UsageStats => {
+        self.usage_scroll = 0;
+        self.mode = Mode::Tasks(Action::UsageStats);
+      }
+      PaletteCommand::ShowLog => {
+        self.load_log();
+        self.mode = Mode::Tasks(Action::LogViewer);
+      }
+      PaletteCommand::ToggleReady => self.toggle_ready().await?,
+      PaletteCommand::ToggleMine => self.toggle_mine().await?,
+      PaletteCommand::Activity => {
+        if let Err(e) = self.open_activity() {
+          self.error = Some(e);
+          self.mode = Mode::Tasks(Action::Error);
+        }
+      }
+      PaletteCommand::Tour => self.open_tour(),
+      PaletteCommand::Changelog => {
+        self.updates.fetch_changelog(&self.event_loop.tx);
+        self.mode = Mode::Tasks(Action::Changelog);
+      }
+      PaletteCommand::Assign => {
+        if !self.tasks.is_empty() {
+          self.assign_state.select(Some(0));
+          self.mode = Mode::Tasks(Action::Assign);
+        }
+      }
+      PaletteCommand::Rules => {
+        self.rule_confirmation = false;
+        self.mode = Mode::Tasks(Action::Rules);
+      }
+      // the palette closes first so that it is not part of the screenshot
+      PaletteCommand::Screenshot => self.screenshot_requested = true,
+      PaletteCommand::CopyLink => self.copy_link(),
+      PaletteCommand::Sync => {
+        if let Err(e) = self.start_output_stream("Sync", &["sync".to_string()]) {
+          self.error = Some(e);
+          self.mode = Mode::Tasks(Action::Error);
+        }
+      }
+      PaletteCommand::ShowOutput => self.mode = Mode::Tasks(Action::Output),
+      PaletteCommand::UrgencyTuner => {
+        if let Err(e) = self.open_urgency_tuner() {
+          self.error = Some(e);
+          self.mode = Mode::Tasks(Action::Error);
+        }
+      }
+      PaletteCommand::Replace => {
+        self.reset_command();
+        self.mode = Mode::Tasks(Action::ReplacePrompt);
+      }
+      PaletteCommand::BackUp => match self.back_up(backup::Reason::Manual) {
+        Ok(()) => self.status_message = Some("Backed up the task data".to_string()),
+        Err(e) => {
+          self.error = Some(e);
+          self.mode = Mode::Tasks(Action::Error);
+        }
+      },
+      PaletteCommand::RevealSecret => {
+        if let Err(e) = self.reveal_secret().await {
+          self.error = Some(e);
+          self.mode = Mode::Tasks(Action::Error);
+        }
+      }
+      PaletteCommand::HideSecret => self.revealed_secret = None,
+      PaletteCommand::EditSecret => {
+        let revealed = self.revealed_secret.as_ref().map(|(uuid, _)| *uuid);
+        let result = if revealed.is_some() && revealed == self.task_current().map(|t| *t.uuid()) {
+          Ok(())
+        } else {
+          self.reveal_secret().await
+        };
+        match result {
+          Ok(()) => self.open_text_editor(EditorTarget::Secret),
+          Err(e) => {
+            self.error = Some(e);
+            self.mode = Mode::Tasks(Action::Error);
+          }
+        }
+      }
+      PaletteCommand::Backups => {
+        if let Err(e) = self.open_backups() {
+          self.error = Some(e);
+          self.mode = Mode::Tasks(Action::Error);
+        }
+      }
+      PaletteCommand::StalledProjects => {
+        if let Err(e) = self.open_stalled_projects() {
+          self.error = Some(e);
+          self.mode = Mode::Tasks(Action::Error);
+        }
+      }
+      PaletteCommand::Review => {
+        if let Err(e) = self.open_review().await {
+          self.error = Some(e);
+          self.mode = Mode::Tasks(Action::Error);
+        }
+      }
+      PaletteCommand::AbandonReview => match self.review.abandon() {
+        Ok(filter) => {
+          self.filter.update(&filter, filter.len(), &mut self.changes);
+          self.update(true).await?;
+        }
+        Err(e) => {
+          self.error = Some(e.to_string());
+          self.mode = Mode::Tasks(Action::Error);
+        }
+      },
+      PaletteCommand::Goals => {
+        if let Err(e) = self.open_goals() {
+          self.error = Some(e);
+          self.mode = Mode::Tasks(Action::Error);
+        }
+      }
+      PaletteCommand::Workspace(name) => self.switch_workspace(&name).await?,
+      PaletteCommand::Filter(filter) => {
+        self.filter.update(&filter, filter.len(), &mut self.changes);
+        self.update(true).await?;
+      }
+      PaletteCommand::Density(density) => self.density = density,
+      PaletteCommand::EditDescription => self.open_text_editor(EditorTarget::Description),
+      PaletteCommand::AnnotateInEditor => self.open_text_editor(EditorTarget::Annotation),
+    }
+    Ok(())
+  }
+
+  fn draw_palette(&mut self, f: &mut Frame<impl Backend>, percent_x: u16, percent_y: u16) {
+    let area = centered_rect(percent_x, percent_y, f.size());
+    f.render_widget(Clear, area);
+    let labels: Vec<String> = self.palette.matches().iter().map(|(label, _)| label.clone()).collect();
+    let mut items: Vec<ListItem> = labels.into_iter().map(ListItem::new).collect();
+    if items.is_empty() {
+      items.push(ListItem::new("No matching command."));
+    }
+    let list = List::new(items)
+      .block(
+        Block::default()
+          .borders(Borders::ALL)
+          .border_type(self.config.border_type)
+          .title(Span::styled("Commands", Style::default().add_modifier(Modifier::BOLD))),
+      )
+      .highlight_style(self.config.uda_style_report_selection.add_modifier(Modifier::BOLD))
+      .highlight_symbol(&self.config.uda_selection_indicator);
+    f.render_stateful_widget(list, area, &mut self.palette.state);
+  }
+
+  pub fn escape(s: &str) -> String {
+    let mut es = String::with_capacity(s.len() + 2);
     es.push('"');
     for ch in s.chars() {
       match ch {
@@ -2429,18 +6658,72 @@ impl TaskwarriorTui {
     es
   }
 
+  /// Collect the keys of sequences bound in the key config, e.g. `gg`, before handling them. Keys
+  /// that do not complete a sequence within the chord timeout are handled one by one.
+  fn middleware_context(&self) -> middleware::Context {
+    middleware::Context {
+      bindings_apply: matches!(self.mode, Mode::Tasks(Action::Report) | Mode::Projects | Mode::Calendar),
+    }
+  }
+
+  /// Run `input` through the middleware, and handle the keys it lets through.
+  pub async fn handle_key(&mut self, input: KeyCode) -> Result<()> {
+    let context = self.middleware_context();
+    let waiting = self.middleware.deadline().is_some();
+    let keys = self.middleware.handle(input, &context);
+    if let Some(deadline) = self.middleware.deadline().filter(|_| !waiting) {
+      // wake up when the held back keys time out, whatever the tick rate is
+      let tx = self.event_loop.tx.clone();
+      tokio::spawn(async move {
+        tokio::time::sleep_until(deadline.into()).await;
+        tx.send(Event::Tick).ok();
+      });
+    }
+    for key in keys {
+      self.handle_input(key).await?;
+    }
+    Ok(())
+  }
+
+  /// Handle the keys the middleware held back for too long, e.g. of an incomplete sequence.
+  async fn expire_middleware(&mut self) -> Result<()> {
+    let context = self.middleware_context();
+    for key in self.middleware.expire(Instant::now(), &context) {
+      self.handle_input(key).await?;
+    }
+    Ok(())
+  }
+
   pub async fn handle_input(&mut self, input: KeyCode) -> Result<()> {
+    if input == self.keyconfig.screenshot {
+      self.screenshot_requested = true;
+      return Ok(());
+    }
+    if matches!(self.mode, Mode::Tasks(Action::Report) | Mode::Projects | Mode::Calendar) {
+      if let Some(preset) = self.layouts.by_key(input).cloned() {
+        self.apply_layout(&preset);
+        return Ok(());
+      }
+    }
     match self.mode {
       Mode::Tasks(_) => {
         self.handle_input_by_task_mode(input).await?;
       }
       Mode::Projects => {
+        if input == self.keyconfig.edit {
+          if let Err(e) = self.edit_project_notes().await {
+            self.error = Some(e);
+            self.previous_mode = Some(Mode::Projects);
+            self.mode = Mode::Tasks(Action::Error);
+          }
+          return Ok(());
+        }
         ProjectsState::handle_input(self, input)?;
         self.update(false).await?;
       }
       Mode::Calendar => {
         if input == self.keyconfig.quit || input == KeyCode::Ctrl('c') {
-          self.should_quit = true;
+          self.request_quit();
         } else if input == self.keyconfig.next_tab {
           if self.config.uda_change_focus_rotate {
             self.mode = Mode::Tasks(Action::Report);
@@ -2487,13 +6770,24 @@ impl TaskwarriorTui {
   }
 
   async fn handle_input_by_task_mode(&mut self, input: KeyCode) -> Result<()> {
+    if let Mode::Tasks(action @ (Action::Report | Action::Add | Action::Log | Action::Annotate | Action::Modify | Action::Filter)) = self.mode {
+      if input == self.keyconfig.calculator {
+        self.open_calculator(action);
+        return Ok(());
+      }
+    }
     if let Mode::Tasks(task_mode) = &self.mode {
       match task_mode {
         Action::Report => {
           if input == KeyCode::Esc {
             self.marked.clear();
           } else if input == self.keyconfig.quit || input == KeyCode::Ctrl('c') {
-            self.should_quit = true;
+            self.request_quit();
+          } else if let Some(i) = match input {
+            KeyCode::Alt(c @ '1'..='9') => Some(c as usize - '1' as usize).filter(|i| *i < self.chips.filters.len()),
+            _ => None,
+          } {
+            self.toggle_chip(i).await?;
           } else if input == self.keyconfig.select {
             self.task_table_state.multiple_selection();
             self.toggle_mark();
@@ -2548,6 +6842,8 @@ impl TaskwarriorTui {
                 }
               }
             }
+          } else if input == self.keyconfig.start_stop && self.config.uda_wip_limit_confirm && !self.wip_limits_exceeded_by_start().is_empty() {
+            self.mode = Mode::Tasks(Action::StartPrompt);
           } else if input == self.keyconfig.start_stop {
             match self.task_start_stop() {
               Ok(_) => self.update(true).await?,
@@ -2564,6 +6860,10 @@ impl TaskwarriorTui {
                 self.mode = Mode::Tasks(Action::Error);
               }
             }
+          } else if input == self.keyconfig.edit && self.config.uda_editor_inline {
+            self.open_text_editor(EditorTarget::Description);
+          } else if input == self.keyconfig.annotate && self.config.uda_editor_inline {
+            self.open_text_editor(EditorTarget::Annotation);
           } else if input == self.keyconfig.edit {
             match self.task_edit().await {
               Ok(_) => self.update(true).await?,
@@ -2574,6 +6874,7 @@ impl TaskwarriorTui {
             }
           } else if input == self.keyconfig.undo {
             if self.config.uda_task_report_prompt_on_undo {
+              self.undo_diff = self.read_undo_diff();
               self.mode = Mode::Tasks(Action::UndoPrompt);
               if self.task_current().is_none() {
                 self.mode = Mode::Tasks(Action::Report);
@@ -2630,6 +6931,7 @@ impl TaskwarriorTui {
               },
               TableMode::MultipleSelection => self.modify.update("", 0, &mut self.changes),
             }
+            self.restore_draft(Action::Modify);
           } else if input == self.keyconfig.shell {
             self.mode = Mode::Tasks(Action::Subprocess);
           } else if input == self.keyconfig.log {
@@ -2645,6 +6947,7 @@ impl TaskwarriorTui {
               self.command_history.history_len()
             ));
             self.update_completion_list();
+            self.restore_draft(Action::Log);
           } else if input == self.keyconfig.add {
             self.mode = Mode::Tasks(Action::Add);
             self.command_history.reset();
@@ -2658,6 +6961,7 @@ impl TaskwarriorTui {
               self.command_history.history_len()
             ));
             self.update_completion_list();
+            self.restore_draft(Action::Add);
           } else if input == self.keyconfig.annotate {
             self.mode = Mode::Tasks(Action::Annotate);
             self.command_history.reset();
@@ -2671,6 +6975,7 @@ impl TaskwarriorTui {
               self.command_history.history_len()
             ));
             self.update_completion_list();
+            self.restore_draft(Action::Annotate);
           } else if input == self.keyconfig.help {
             self.mode = Mode::Tasks(Action::HelpPopup);
           } else if input == self.keyconfig.filter {
@@ -2697,8 +7002,26 @@ impl TaskwarriorTui {
                 self.mode = Mode::Tasks(Action::Error);
               }
             }
-          } else if input == self.keyconfig.shortcut2 {
-            match self.task_shortcut(2).await {
+          } else if input == self.keyconfig.shortcut2 {
+            match self.task_shortcut(2).await {
+              Ok(_) => self.update(true).await?,
+              Err(e) => {
+                self.update(true).await?;
+                self.error = Some(e);
+                self.mode = Mode::Tasks(Action::Error);
+              }
+            }
+          } else if input == self.keyconfig.shortcut3 {
+            match self.task_shortcut(3).await {
+              Ok(_) => self.update(true).await?,
+              Err(e) => {
+                self.update(true).await?;
+                self.error = Some(e);
+                self.mode = Mode::Tasks(Action::Error);
+              }
+            }
+          } else if input == self.keyconfig.shortcut4 {
+            match self.task_shortcut(4).await {
               Ok(_) => self.update(true).await?,
               Err(e) => {
                 self.update(true).await?;
@@ -2706,8 +7029,26 @@ impl TaskwarriorTui {
                 self.mode = Mode::Tasks(Action::Error);
               }
             }
-          } else if input == self.keyconfig.shortcut3 {
-            match self.task_shortcut(3).await {
+          } else if input == self.keyconfig.shortcut5 {
+            match self.task_shortcut(5).await {
+              Ok(_) => self.update(true).await?,
+              Err(e) => {
+                self.update(true).await?;
+                self.error = Some(e);
+                self.mode = Mode::Tasks(Action::Error);
+              }
+            }
+          } else if input == self.keyconfig.shortcut6 {
+            match self.task_shortcut(6).await {
+              Ok(_) => self.update(true).await?,
+              Err(e) => {
+                self.update(true).await?;
+                self.error = Some(e);
+                self.mode = Mode::Tasks(Action::Error);
+              }
+            }
+          } else if input == self.keyconfig.shortcut7 {
+            match self.task_shortcut(7).await {
               Ok(_) => self.update(true).await?,
               Err(e) => {
                 self.update(true).await?;
@@ -2715,118 +7056,638 @@ impl TaskwarriorTui {
                 self.mode = Mode::Tasks(Action::Error);
               }
             }
-          } else if input == self.keyconfig.shortcut4 {
-            match self.task_shortcut(4).await {
+          } else if input == self.keyconfig.shortcut8 {
+            match self.task_shortcut(8).await {
+              Ok(_) => self.update(true).await?,
+              Err(e) => {
+                self.update(true).await?;
+                self.error = Some(e);
+                self.mode = Mode::Tasks(Action::Error);
+              }
+            }
+          } else if input == self.keyconfig.shortcut9 {
+            match self.task_shortcut(9).await {
+              Ok(_) => self.update(true).await?,
+              Err(e) => {
+                self.update(true).await?;
+                self.error = Some(e);
+                self.mode = Mode::Tasks(Action::Error);
+              }
+            }
+          } else if input == self.keyconfig.zoom {
+            self.task_report_show_info = !self.task_report_show_info;
+          } else if input == self.keyconfig.context_menu {
+            self.mode = Mode::Tasks(Action::ContextMenu);
+          } else if input == self.keyconfig.what_changed {
+            self.snapshot.scroll = 0;
+            self.mode = Mode::Tasks(Action::SnapshotDiff);
+          } else if input == self.keyconfig.recent_actions {
+            self.recent_actions.select_first_if_needed();
+            self.mode = Mode::Tasks(Action::RecentActions);
+          } else if input == self.keyconfig.action_history {
+            self.journal.select_first_if_needed();
+            self.mode = Mode::Tasks(Action::Journal);
+          } else if input == self.keyconfig.hint_select {
+            if !self.tasks.is_empty() {
+              self.hint_input.clear();
+              self.mode = Mode::Tasks(Action::HintSelect);
+            }
+          } else if input == self.keyconfig.toggle_ready {
+            self.toggle_ready().await?;
+          } else if input == self.keyconfig.copy_link {
+            self.copy_link();
+          } else if input == self.keyconfig.command_palette {
+            let commands = self.palette_commands();
+            self.palette.open(commands);
+            self.mode = Mode::Tasks(Action::Palette);
+          } else if input == self.keyconfig.header_focus {
+            let columns = self.task_report_table.visible_columns.len();
+            self.task_report_header_focus = self.task_report_header_focus.min(columns.saturating_sub(1));
+            self.mode = Mode::Tasks(Action::HeaderFocus);
+          } else if input == self.keyconfig.previous_tab {
+            if self.config.uda_change_focus_rotate {
+              self.mode = Mode::Calendar;
+            }
+          } else if input == self.keyconfig.next_tab {
+            self.mode = Mode::Projects;
+          }
+        }
+        Action::ContextMenu => {
+          if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.mode = Mode::Tasks(Action::Report);
+          } else if input == KeyCode::Down || input == self.keyconfig.down {
+            self.context_next();
+            if self.config.uda_context_menu_select_on_move {
+              if self.error.is_some() {
+                self.previous_mode = Some(self.mode.clone());
+                self.mode = Mode::Tasks(Action::Error);
+              } else {
+                match self.context_select() {
+                  Ok(_) => self.update(true).await?,
+                  Err(e) => {
+                    self.error = Some(e.to_string());
+                  }
+                }
+              }
+            }
+          } else if input == KeyCode::Up || input == self.keyconfig.up {
+            self.context_previous();
+            if self.config.uda_context_menu_select_on_move {
+              if self.error.is_some() {
+                self.previous_mode = Some(self.mode.clone());
+                self.mode = Mode::Tasks(Action::Error);
+              } else {
+                match self.context_select() {
+                  Ok(_) => self.update(true).await?,
+                  Err(e) => {
+                    self.error = Some(e.to_string());
+                  }
+                }
+              }
+            }
+          } else if input == KeyCode::Char('\n') {
+            if self.error.is_some() {
+              self.previous_mode = Some(self.mode.clone());
+              self.mode = Mode::Tasks(Action::Error);
+            } else if self.config.uda_context_menu_select_on_move {
+              self.mode = Mode::Tasks(Action::Report);
+            } else {
+              match self.context_select() {
+                Ok(_) => self.update(true).await?,
+                Err(e) => {
+                  self.error = Some(e.to_string());
+                  self.mode = Mode::Tasks(Action::Error);
+                }
+              }
+            }
+          }
+        }
+        Action::SnapshotDiff => {
+          if input == self.keyconfig.quit || input == KeyCode::Esc || input == self.keyconfig.what_changed {
+            self.mode = Mode::Tasks(Action::Report);
+          } else if input == KeyCode::Down || input == self.keyconfig.down {
+            self.snapshot.scroll = self.snapshot.scroll.saturating_add(1);
+          } else if input == KeyCode::Up || input == self.keyconfig.up {
+            self.snapshot.scroll = self.snapshot.scroll.saturating_sub(1);
+          }
+        }
+        Action::PerfReport => self.mode = Mode::Tasks(Action::Report),
+        Action::QuitPrompt => {
+          let back = self.previous_mode.take().unwrap_or(Mode::Tasks(Action::Report));
+          if !self.background_job_running() {
+            // nothing to wait for, this only confirms quitting
+            if input == KeyCode::Char('y') || input == KeyCode::Char('\n') || input == self.keyconfig.quit {
+              self.should_quit = true;
+            } else {
+              self.mode = back;
+            }
+          } else if input == KeyCode::Char('w') || input == KeyCode::Char('\n') {
+            self.quit_when_idle = true;
+            self.history_status = Some("quitting when the background job finishes".to_string());
+            self.mode = back;
+          } else if input == KeyCode::Char('f') || input == KeyCode::Ctrl('c') {
+            self.force_quit();
+          } else {
+            self.mode = back;
+          }
+        }
+        Action::HintSelect => match input {
+          KeyCode::Char(c) if c != '\n' => {
+            self.hint_input.push(c);
+            let labels = hints::labels(self.visible_task_rows());
+            match hints::find(&labels, &self.hint_input) {
+              Hint::Row(i) => {
+                self.current_selection = self.task_table_state.offset() + i;
+                self.current_selection_id = None;
+                self.current_selection_uuid = None;
+                self.mode = Mode::Tasks(Action::Report);
+              }
+              Hint::Partial => {}
+              Hint::None => self.mode = Mode::Tasks(Action::Report),
+            }
+          }
+          _ => self.mode = Mode::Tasks(Action::Report),
+        },
+        Action::TextEditor => {
+          if input == KeyCode::Esc {
+            self.text_editor = TextEditor::default();
+            self.mode = Mode::Tasks(Action::Report);
+          } else if input == KeyCode::Ctrl('s') || input == KeyCode::CtrlEnter || input == KeyCode::AltEnter {
+            let result = self.text_editor_save().await;
+            // do not keep a decrypted secret around
+            self.text_editor = TextEditor::default();
+            match result {
+              Ok(_) => {
+                self.mode = Mode::Tasks(Action::Report);
+                self.update(true).await?;
+              }
+              Err(e) => {
+                self.error = Some(e);
+                self.mode = Mode::Tasks(Action::Error);
+              }
+            }
+          } else {
+            self.text_editor.handle_input(input);
+          }
+        }
+        Action::UsageStats => {
+          if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.mode = Mode::Tasks(Action::Report);
+          } else if input == KeyCode::Down || input == self.keyconfig.down {
+            self.usage_scroll = self.usage_scroll.saturating_add(1);
+          } else if input == KeyCode::Up || input == self.keyconfig.up {
+            self.usage_scroll = self.usage_scroll.saturating_sub(1);
+          }
+        }
+        Action::LogViewer => {
+          let last = self.log_lines.len().saturating_sub(1);
+          let page = self.terminal_height as usize / 2;
+          if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.mode = Mode::Tasks(Action::Report);
+          } else if input == KeyCode::Char('r') {
+            self.load_log();
+          } else if input == KeyCode::Down || input == self.keyconfig.down {
+            self.log_scroll = (self.log_scroll + 1).min(last);
+          } else if input == KeyCode::Up || input == self.keyconfig.up {
+            self.log_scroll = self.log_scroll.saturating_sub(1);
+          } else if input == KeyCode::PageDown || input == self.keyconfig.page_down {
+            self.log_scroll = (self.log_scroll + page).min(last);
+          } else if input == KeyCode::PageUp || input == self.keyconfig.page_up {
+            self.log_scroll = self.log_scroll.saturating_sub(page);
+          } else if input == KeyCode::End || input == self.keyconfig.go_to_bottom {
+            self.log_scroll = last;
+          } else if input == KeyCode::Home || input == self.keyconfig.go_to_top {
+            self.log_scroll = 0;
+          }
+        }
+        Action::UrgencyTuner => {
+          let step = match input {
+            KeyCode::Left => Some(-0.5),
+            KeyCode::Right => Some(0.5),
+            KeyCode::Char('-') => Some(-0.1),
+            KeyCode::Char('+') | KeyCode::Char('=') => Some(0.1),
+            _ if input == self.keyconfig.left => Some(-0.5),
+            _ if input == self.keyconfig.right => Some(0.5),
+            _ => None,
+          };
+          if let Some(step) = step {
+            if let Some(coefficient) = self.urgency_tuner.selected_mut() {
+              coefficient.adjust(step);
+            }
+            self.refresh_urgency_preview();
+          } else if input == KeyCode::Char('0') {
+            if let Some(coefficient) = self.urgency_tuner.selected_mut() {
+              coefficient.value = coefficient.original;
+            }
+            self.refresh_urgency_preview();
+          } else if input == KeyCode::Down || input == self.keyconfig.down {
+            self.urgency_tuner.next();
+          } else if input == KeyCode::Up || input == self.keyconfig.up {
+            self.urgency_tuner.previous();
+          } else if input == KeyCode::Char('\n') {
+            let result = self.save_urgency_coefficients();
+            self.mode = Mode::Tasks(Action::Report);
+            self.update(true).await?;
+            if let Err(e) = result {
+              self.error = Some(e);
+              self.mode = Mode::Tasks(Action::Error);
+            }
+          } else if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.mode = Mode::Tasks(Action::Report);
+          }
+        }
+        Action::Output => {
+          let running = self.output_stream.as_ref().is_some_and(|s| s.running());
+          if input == KeyCode::Ctrl('c') && running {
+            if let Some(stream) = self.output_stream.as_mut() {
+              stream.cancel();
+            }
+          } else if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.mode = Mode::Tasks(Action::Report);
+          }
+        }
+        Action::StartMenu => {
+          if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.mode = Mode::Tasks(Action::Report);
+          } else if input == KeyCode::Down || input == self.keyconfig.down {
+            self.start_menu.next();
+          } else if input == KeyCode::Up || input == self.keyconfig.up {
+            self.start_menu.previous();
+          } else if input == KeyCode::Char('\n') {
+            if let Some(item) = self.start_menu.selected() {
+              self.run_start_item(item).await?;
+            }
+          }
+        }
+        Action::Tour => {
+          if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.end_tour();
+          } else if input == KeyCode::Char('\n') || input == KeyCode::Right {
+            if self.tour.step + 1 < tour::steps(&self.keyconfig).len() {
+              self.tour.step += 1;
+            } else {
+              self.end_tour();
+            }
+          } else if input == KeyCode::Left {
+            self.tour.step = self.tour.step.saturating_sub(1);
+          }
+        }
+        Action::Changelog => {
+          if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.mode = Mode::Tasks(Action::Report);
+          } else if input == KeyCode::Down || input == self.keyconfig.down {
+            self.updates.scroll = self.updates.scroll.saturating_add(1);
+          } else if input == KeyCode::Up || input == self.keyconfig.up {
+            self.updates.scroll = self.updates.scroll.saturating_sub(1);
+          } else if input == KeyCode::PageDown {
+            self.updates.scroll = self.updates.scroll.saturating_add(10);
+          } else if input == KeyCode::PageUp {
+            self.updates.scroll = self.updates.scroll.saturating_sub(10);
+          }
+        }
+        Action::Activity => {
+          let len = self.activity.len();
+          let selected = self.activity_state.selected().unwrap_or_default();
+          if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.close_activity();
+          } else if len > 0 && (input == KeyCode::Down || input == self.keyconfig.down) {
+            self.activity_state.select(Some((selected + 1) % len));
+          } else if len > 0 && (input == KeyCode::Up || input == self.keyconfig.up) {
+            self.activity_state.select(Some((selected + len - 1) % len));
+          } else if input == KeyCode::Char('\n') {
+            if let Some(uuid) = self.activity.get(selected).map(|a| a.uuid) {
+              self.close_activity();
+              self.jump_to_uuid(uuid).await?;
+            }
+          }
+        }
+        Action::Assign => {
+          let users: Vec<String> = self
+            .config
+            .uda_team
+            .as_ref()
+            .map(|t| t.users.iter().map(|u| u.login.clone()).collect())
+            .unwrap_or_default();
+          // the last item is nobody
+          let len = users.len() + 1;
+          let selected = self.assign_state.selected().unwrap_or_default();
+          if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.mode = Mode::Tasks(Action::Report);
+          } else if input == KeyCode::Down || input == self.keyconfig.down {
+            self.assign_state.select(Some((selected + 1) % len));
+          } else if input == KeyCode::Up || input == self.keyconfig.up {
+            self.assign_state.select(Some((selected + len - 1) % len));
+          } else if input == KeyCode::Char('\n') {
+            let result = self.assign(users.get(selected).map(String::as_str));
+            self.mode = Mode::Tasks(Action::Report);
+            self.update(true).await?;
+            if let Err(e) = result {
+              self.error = Some(e);
+              self.mode = Mode::Tasks(Action::Error);
+            }
+          }
+        }
+        Action::Backups => {
+          let len = self.backups.backups.len();
+          let selected = self.backups.state.selected().unwrap_or_default();
+          if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.mode = Mode::Tasks(Action::Report);
+          } else if len > 0 && (input == KeyCode::Down || input == self.keyconfig.down) {
+            self.backups.state.select(Some((selected + 1) % len));
+          } else if len > 0 && (input == KeyCode::Up || input == self.keyconfig.up) {
+            self.backups.state.select(Some((selected + len - 1) % len));
+          } else if input == KeyCode::Char('\n') {
+            let result = self.restore_backup();
+            self.mode = Mode::Tasks(Action::Report);
+            self.update(true).await?;
+            if let Err(e) = result {
+              self.error = Some(e);
+              self.mode = Mode::Tasks(Action::Error);
+            }
+          }
+        }
+        Action::StalledProjects => {
+          let len = self.stalled_projects.len();
+          let selected = self.stalled_state.selected().unwrap_or_default();
+          if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.mode = Mode::Tasks(Action::Report);
+          } else if len > 0 && (input == KeyCode::Down || input == self.keyconfig.down) {
+            self.stalled_state.select(Some((selected + 1) % len));
+          } else if len > 0 && (input == KeyCode::Up || input == self.keyconfig.up) {
+            self.stalled_state.select(Some((selected + len - 1) % len));
+          } else if input == KeyCode::Char('\n') {
+            if let Some(project) = self.stalled_projects.get(selected) {
+              let mut command = format!("project:{} ", project.name);
+              if !self.config.uda_next_action_tag.is_empty() {
+                command.push_str(&format!("+{} ", self.config.uda_next_action_tag));
+              }
+              self.mode = Mode::Tasks(Action::Add);
+              self.command_history.reset();
+              self.command.update(&command, command.len(), &mut self.changes);
+              self.update_completion_list();
+            }
+          }
+        }
+        Action::Review => {
+          let steps = self.config.uda_review_steps.clone();
+          let len = steps.len();
+          let selected = self.review_state.selected().unwrap_or_default();
+          if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.mode = Mode::Tasks(Action::Report);
+          } else if len > 0 && (input == KeyCode::Down || input == self.keyconfig.down) {
+            self.review_state.select(Some((selected + 1) % len));
+          } else if len > 0 && (input == KeyCode::Up || input == self.keyconfig.up) {
+            self.review_state.select(Some((selected + len - 1) % len));
+          } else if let Some(step) = steps.get(selected) {
+            let result = if input == KeyCode::Char('\n') {
+              self.filter.update(&step.filter, step.filter.len(), &mut self.changes);
+              self.mode = Mode::Tasks(Action::Report);
+              self.review.load_step(step).map(|_| None)
+            } else if input == KeyCode::Char(' ') {
+              let done = !self.review.is_done(step);
+              self.review.set_done(step, done, &steps)
+            } else {
+              Ok(None)
+            };
+            match result {
+              Ok(Some(filter)) => self.finish_review(&filter),
+              Ok(None) => {}
+              Err(e) => {
+                self.error = Some(e.to_string());
+                self.mode = Mode::Tasks(Action::Error);
+              }
+            }
+            self.update(true).await?;
+          }
+        }
+        Action::Goals => {
+          let len = self.goal_progress.len();
+          if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.mode = Mode::Tasks(Action::Report);
+          } else if len > 0 && (input == KeyCode::Down || input == self.keyconfig.down) {
+            self.goals_state.select(Some((self.goals_state.selected().unwrap_or_default() + 1) % len));
+          } else if len > 0 && (input == KeyCode::Up || input == self.keyconfig.up) {
+            self
+              .goals_state
+              .select(Some((self.goals_state.selected().unwrap_or_default() + len - 1) % len));
+          } else if input == KeyCode::Char('\n') {
+            if let Some(progress) = self.goals_state.selected().and_then(|i| self.goal_progress.get(i)) {
+              let filter = format!("{}:{}", goals::UDA, progress.goal.name);
+              self.filter.update(&filter, filter.len(), &mut self.changes);
+              self.mode = Mode::Tasks(Action::Report);
+              self.update(true).await?;
+            }
+          }
+        }
+        Action::Rules => {
+          let len = self.rule_matches.len();
+          let destructive = self
+            .rules_state
+            .selected()
+            .and_then(|i| self.rule_matches.get(i))
+            .is_some_and(|m| matches!(m.rule.action, RuleAction::Purge | RuleAction::Delete));
+          let mut apply = false;
+          if self.rule_confirmation {
+            // any other key than `y` cancels
+            self.rule_confirmation = false;
+            apply = input == KeyCode::Char('y');
+          } else if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.mode = Mode::Tasks(Action::Report);
+          } else if len > 0 && (input == KeyCode::Down || input == self.keyconfig.down) {
+            self.rules_state.select(Some((self.rules_state.selected().unwrap_or_default() + 1) % len));
+          } else if len > 0 && (input == KeyCode::Up || input == self.keyconfig.up) {
+            self
+              .rules_state
+              .select(Some((self.rules_state.selected().unwrap_or_default() + len - 1) % len));
+          } else if input == KeyCode::Char('\n') {
+            // purging and deleting wait for a confirmation
+            self.rule_confirmation = destructive;
+            apply = !destructive;
+          }
+          if apply {
+            let result = self.apply_selected_rule();
+            self.update(true).await?;
+            if let Err(e) = result {
+              self.error = Some(e);
+              self.previous_mode = Some(self.mode.clone());
+              self.mode = Mode::Tasks(Action::Error);
+            }
+          }
+        }
+        Action::RecentActions => {
+          if input == self.keyconfig.quit || input == KeyCode::Esc || input == self.keyconfig.recent_actions {
+            self.mode = Mode::Tasks(Action::Report);
+          } else if input == KeyCode::Down || input == self.keyconfig.down {
+            self.recent_actions.next();
+          } else if input == KeyCode::Up || input == self.keyconfig.up {
+            self.recent_actions.previous();
+          } else if input == KeyCode::Char('\n') || input == self.keyconfig.undo {
+            match self.task_restore() {
               Ok(_) => self.update(true).await?,
               Err(e) => {
-                self.update(true).await?;
                 self.error = Some(e);
+                self.previous_mode = Some(self.mode.clone());
                 self.mode = Mode::Tasks(Action::Error);
               }
             }
-          } else if input == self.keyconfig.shortcut5 {
-            match self.task_shortcut(5).await {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.update(true).await?;
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
-              }
+          }
+        }
+        Action::Palette => match input {
+          KeyCode::Esc => self.mode = Mode::Tasks(Action::Report),
+          KeyCode::Char('\n') => {
+            if let Some(command) = self.palette.selected() {
+              self.run_palette_command(command).await?;
             }
-          } else if input == self.keyconfig.shortcut6 {
-            match self.task_shortcut(6).await {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.update(true).await?;
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
+          }
+          KeyCode::Down | KeyCode::Ctrl('n') | KeyCode::Tab => self.palette.next(),
+          KeyCode::Up | KeyCode::Ctrl('p') | KeyCode::BackTab => self.palette.previous(),
+          KeyCode::Backspace | KeyCode::Ctrl('h') => self.palette.pop(),
+          KeyCode::Char(c) => self.palette.push(c),
+          _ => {}
+        },
+        Action::ReplacePrompt => match input {
+          KeyCode::Esc => {
+            self.reset_command();
+            self.error = None;
+            self.mode = Mode::Tasks(Action::Report);
+          }
+          KeyCode::Char('\n') => match Replacement::parse(self.command.as_str()) {
+            Ok(replacement) => {
+              self.error = None;
+              self.reset_command();
+              let changes = replace::changes(&replacement, &self.replace_targets());
+              if changes.is_empty() {
+                self.status_message = Some("No description to change".to_string());
+                self.mode = Mode::Tasks(Action::Report);
+              } else {
+                self.replace_review = ReplaceReview::new(changes);
+                self.mode = Mode::Tasks(Action::ReplaceReview);
               }
             }
-          } else if input == self.keyconfig.shortcut7 {
-            match self.task_shortcut(7).await {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.update(true).await?;
+            Err(e) => self.error = Some(e),
+          },
+          _ => {
+            self.error = None;
+            handle_movement(&mut self.command, input, &mut self.changes);
+          }
+        },
+        Action::ReplaceReview => {
+          if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.replace_review = ReplaceReview::default();
+            self.mode = Mode::Tasks(Action::Report);
+          } else {
+            match input {
+              KeyCode::Char('y') | KeyCode::Char('\n') => self.replace_review.decide(true),
+              KeyCode::Char('n') | KeyCode::Char(' ') => self.replace_review.decide(false),
+              KeyCode::Char('a') => self.replace_review.accept_rest(),
+              _ => {}
+            }
+            if self.replace_review.done() {
+              let result = self.apply_replacements();
+              self.mode = Mode::Tasks(Action::Report);
+              self.update(true).await?;
+              if let Err(e) = result {
                 self.error = Some(e);
                 self.mode = Mode::Tasks(Action::Error);
               }
             }
-          } else if input == self.keyconfig.shortcut8 {
-            match self.task_shortcut(8).await {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.update(true).await?;
-                self.error = Some(e);
+          }
+        }
+        Action::SaveLayout => match input {
+          KeyCode::Esc => {
+            self.reset_command();
+            self.mode = Mode::Tasks(Action::Report);
+          }
+          KeyCode::Char('\n') => {
+            let name = self.command.as_str().trim().to_string();
+            self.reset_command();
+            self.mode = Mode::Tasks(Action::Report);
+            if !name.is_empty() {
+              let preset = self.current_layout(&name);
+              if let Err(e) = self.layouts.save(preset) {
+                self.error = Some(format!("Unable to save layout `{}`: {}", name, e));
                 self.mode = Mode::Tasks(Action::Error);
               }
             }
-          } else if input == self.keyconfig.shortcut9 {
-            match self.task_shortcut(9).await {
-              Ok(_) => self.update(true).await?,
-              Err(e) => {
-                self.update(true).await?;
-                self.error = Some(e);
-                self.mode = Mode::Tasks(Action::Error);
+          }
+          _ => handle_movement(&mut self.command, input, &mut self.changes),
+        },
+        Action::SpellSuggestions => {
+          let action = match self.previous_mode.take() {
+            Some(Mode::Tasks(action)) => action,
+            _ => Action::Report,
+          };
+          if input == KeyCode::Esc {
+            self.spell_suggestions = None;
+            self.mode = Mode::Tasks(action);
+          } else if input == KeyCode::Char('\n') {
+            self.apply_spell_suggestion(action);
+            self.mode = Mode::Tasks(action);
+          } else {
+            if let Some(suggestions) = &mut self.spell_suggestions {
+              if input == KeyCode::Down || input == self.keyconfig.down || input == KeyCode::Ctrl('n') {
+                suggestions.next();
+              } else if input == KeyCode::Up || input == self.keyconfig.up || input == KeyCode::Ctrl('p') {
+                suggestions.previous();
               }
             }
-          } else if input == self.keyconfig.zoom {
-            self.task_report_show_info = !self.task_report_show_info;
-          } else if input == self.keyconfig.context_menu {
-            self.mode = Mode::Tasks(Action::ContextMenu);
-          } else if input == self.keyconfig.previous_tab {
-            if self.config.uda_change_focus_rotate {
-              self.mode = Mode::Calendar;
+            self.previous_mode = Some(Mode::Tasks(action));
+          }
+        }
+        Action::Calculator => {
+          let action = match &self.previous_mode {
+            Some(Mode::Tasks(action)) => *action,
+            _ => Action::Report,
+          };
+          if input == KeyCode::Esc || input == self.keyconfig.calculator {
+            self.previous_mode = None;
+            self.mode = Mode::Tasks(action);
+          } else if input == KeyCode::Char('\n') {
+            self.insert_calculator_result(action);
+            self.previous_mode = None;
+            self.mode = Mode::Tasks(action);
+          } else {
+            handle_movement(&mut self.calculator.input, input, &mut self.changes);
+            self.calculator.evaluate();
+          }
+        }
+        Action::HeaderFocus => {
+          let columns = self.task_report_table.visible_columns.len();
+          if input == self.keyconfig.quit || input == KeyCode::Esc || input == self.keyconfig.header_focus {
+            self.mode = Mode::Tasks(Action::Report);
+          } else if input == KeyCode::Left || input == self.keyconfig.left {
+            self.task_report_header_focus = self.task_report_header_focus.saturating_sub(1);
+          } else if input == KeyCode::Right || input == self.keyconfig.right {
+            self.task_report_header_focus = (self.task_report_header_focus + 1).min(columns.saturating_sub(1));
+          } else if input == KeyCode::Char('\n') {
+            if let Some(column) = self.task_report_table.visible_columns.get(self.task_report_header_focus).cloned() {
+              self.task_report_table.cycle_sort(&column);
+              self.update(true).await?;
             }
-          } else if input == self.keyconfig.next_tab {
-            self.mode = Mode::Projects;
           }
         }
-        Action::ContextMenu => {
-          if input == self.keyconfig.quit || input == KeyCode::Esc {
+        Action::Journal => {
+          if input == self.keyconfig.quit || input == KeyCode::Esc || input == self.keyconfig.action_history {
             self.mode = Mode::Tasks(Action::Report);
           } else if input == KeyCode::Down || input == self.keyconfig.down {
-            self.context_next();
-            if self.config.uda_context_menu_select_on_move {
-              if self.error.is_some() {
-                self.previous_mode = Some(self.mode.clone());
-                self.mode = Mode::Tasks(Action::Error);
-              } else {
-                match self.context_select() {
-                  Ok(_) => self.update(true).await?,
-                  Err(e) => {
-                    self.error = Some(e.to_string());
-                  }
-                }
-              }
-            }
+            self.journal.next();
           } else if input == KeyCode::Up || input == self.keyconfig.up {
-            self.context_previous();
-            if self.config.uda_context_menu_select_on_move {
-              if self.error.is_some() {
+            self.journal.previous();
+          } else if input == KeyCode::Char('y') {
+            if let Some(entry) = self.journal.selected() {
+              if let Err(e) = utils::copy_to_clipboard(&entry.command) {
+                self.error = Some(format!("Unable to copy command to clipboard: {}", e));
                 self.previous_mode = Some(self.mode.clone());
                 self.mode = Mode::Tasks(Action::Error);
-              } else {
-                match self.context_select() {
-                  Ok(_) => self.update(true).await?,
-                  Err(e) => {
-                    self.error = Some(e.to_string());
-                  }
-                }
               }
             }
           } else if input == KeyCode::Char('\n') {
-            if self.error.is_some() {
-              self.previous_mode = Some(self.mode.clone());
-              self.mode = Mode::Tasks(Action::Error);
-            } else if self.config.uda_context_menu_select_on_move {
-              self.mode = Mode::Tasks(Action::Report);
-            } else {
-              match self.context_select() {
-                Ok(_) => self.update(true).await?,
-                Err(e) => {
-                  self.error = Some(e.to_string());
-                  self.mode = Mode::Tasks(Action::Error);
-                }
+            match self.task_compensate() {
+              Ok(_) => self.update(true).await?,
+              Err(e) => {
+                self.error = Some(e);
+                self.previous_mode = Some(self.mode.clone());
+                self.mode = Mode::Tasks(Action::Error);
               }
             }
           }
@@ -2850,10 +7711,14 @@ impl TaskwarriorTui {
               self.show_completion_pane = false;
               self.completion_list.unselect();
             } else {
+              self.save_draft(Action::Modify);
               self.modify.update("", 0, &mut self.changes);
               self.mode = Mode::Tasks(Action::Report);
             }
           }
+          KeyCode::Ctrl('x') => self.discard_draft(Action::Modify),
+          KeyCode::Alt('s') => self.open_spell_suggestions(Action::Modify),
+          KeyCode::Alt('n') => self.suggest_free_due_day(),
           KeyCode::Char('\n') => {
             if self.show_completion_pane {
               self.show_completion_pane = false;
@@ -2869,7 +7734,10 @@ impl TaskwarriorTui {
             } else {
               match self.task_modify() {
                 Ok(_) => {
-                  self.mode = Mode::Tasks(Action::Report);
+                  // a bulk modification streaming its output shows it instead
+                  if self.mode == Mode::Tasks(Action::Modify) {
+                    self.mode = Mode::Tasks(Action::Report);
+                  }
                   self.command_history.add(self.modify.as_str());
                   self.modify.update("", 0, &mut self.changes);
                   self.update(true).await?;
@@ -2940,8 +7808,13 @@ impl TaskwarriorTui {
           }
           _ => {
             self.command_history.reset();
+            if input == KeyCode::Char(' ') {
+              expand_snippet(&mut self.modify, &self.config.uda_snippets, &mut self.changes);
+            }
             handle_movement(&mut self.modify, input, &mut self.changes);
             self.update_input_for_completion();
+            self.open_value_picker(input);
+            self.update_date_preview();
           }
         },
         Action::Subprocess => match input {
@@ -2975,11 +7848,14 @@ impl TaskwarriorTui {
               self.show_completion_pane = false;
               self.completion_list.unselect();
             } else {
+              self.save_draft(Action::Log);
               self.reset_command();
               self.history_status = None;
               self.mode = Mode::Tasks(Action::Report);
             }
           }
+          KeyCode::Ctrl('x') => self.discard_draft(Action::Log),
+          KeyCode::Alt('s') => self.open_spell_suggestions(Action::Log),
           KeyCode::Char('\n') => {
             if self.show_completion_pane {
               self.show_completion_pane = false;
@@ -3067,8 +7943,13 @@ impl TaskwarriorTui {
           }
           _ => {
             self.command_history.reset();
+            if input == KeyCode::Char(' ') {
+              expand_snippet(&mut self.command, &self.config.uda_snippets, &mut self.changes);
+            }
             handle_movement(&mut self.command, input, &mut self.changes);
             self.update_input_for_completion();
+            self.open_value_picker(input);
+            self.update_date_preview();
           }
         },
         Action::Annotate => match input {
@@ -3077,11 +7958,14 @@ impl TaskwarriorTui {
               self.show_completion_pane = false;
               self.completion_list.unselect();
             } else {
+              self.save_draft(Action::Annotate);
               self.reset_command();
               self.mode = Mode::Tasks(Action::Report);
               self.history_status = None;
             }
           }
+          KeyCode::Ctrl('x') => self.discard_draft(Action::Annotate),
+          KeyCode::Alt('s') => self.open_spell_suggestions(Action::Annotate),
           KeyCode::Char('\n') => {
             if self.show_completion_pane {
               self.show_completion_pane = false;
@@ -3169,8 +8053,13 @@ impl TaskwarriorTui {
 
           _ => {
             self.command_history.reset();
+            if input == KeyCode::Char(' ') {
+              expand_snippet(&mut self.command, &self.config.uda_snippets, &mut self.changes);
+            }
             handle_movement(&mut self.command, input, &mut self.changes);
             self.update_input_for_completion();
+            self.open_value_picker(input);
+            self.update_date_preview();
           }
         },
         Action::Jump => match input {
@@ -3205,11 +8094,15 @@ impl TaskwarriorTui {
               self.show_completion_pane = false;
               self.completion_list.unselect();
             } else {
+              self.save_draft(Action::Add);
               self.reset_command();
               self.history_status = None;
               self.mode = Mode::Tasks(Action::Report);
             }
           }
+          KeyCode::Ctrl('x') => self.discard_draft(Action::Add),
+          KeyCode::Alt('s') => self.open_spell_suggestions(Action::Add),
+          KeyCode::Alt('n') => self.suggest_free_due_day(),
           KeyCode::Char('\n') => {
             if self.show_completion_pane {
               self.show_completion_pane = false;
@@ -3297,8 +8190,13 @@ impl TaskwarriorTui {
           }
           _ => {
             self.command_history.reset();
+            if input == KeyCode::Char(' ') {
+              expand_snippet(&mut self.command, &self.config.uda_snippets, &mut self.changes);
+            }
             handle_movement(&mut self.command, input, &mut self.changes);
             self.update_input_for_completion();
+            self.open_value_picker(input);
+            self.update_date_preview();
           }
         },
         Action::Filter => match input {
@@ -3409,8 +8307,13 @@ impl TaskwarriorTui {
             self.dirty = true;
           }
           _ => {
+            if input == KeyCode::Char(' ') {
+              expand_snippet(&mut self.filter, &self.config.uda_snippets, &mut self.changes);
+            }
             handle_movement(&mut self.filter, input, &mut self.changes);
             self.update_input_for_completion();
+            self.open_value_picker(input);
+            self.update_date_preview();
             self.dirty = true;
           }
         },
@@ -3437,6 +8340,20 @@ impl TaskwarriorTui {
             handle_movement(&mut self.command, input, &mut self.changes);
           }
         }
+        Action::StartPrompt => {
+          if input == self.keyconfig.start_stop || input == KeyCode::Char('\n') {
+            self.mode = Mode::Tasks(Action::Report);
+            match self.task_start_stop() {
+              Ok(_) => self.update(true).await?,
+              Err(e) => {
+                self.error = Some(e);
+                self.mode = Mode::Tasks(Action::Error);
+              }
+            }
+          } else if input == self.keyconfig.quit || input == KeyCode::Esc {
+            self.mode = Mode::Tasks(Action::Report);
+          }
+        }
         Action::DeletePrompt => {
           if input == self.keyconfig.delete || input == KeyCode::Char('\n') {
             if self.error.is_some() {
@@ -3516,9 +8433,13 @@ impl TaskwarriorTui {
         "scheduled:".to_string(),
         "wait:".to_string(),
         "depends:".to_string(),
+        "recur:".to_string(),
       ] {
         self.completion_list.insert(("attribute".to_string(), s));
       }
+      for uda in &self.config.uda {
+        self.completion_list.insert(("attribute".to_string(), format!("{}:", uda.name)));
+      }
     }
 
     if let Mode::Tasks(Action::Modify | Action::Filter | Action::Annotate | Action::Add | Action::Log) = self.mode {
@@ -3584,6 +8505,12 @@ impl TaskwarriorTui {
           self.completion_list.insert(("project".to_string(), p));
         }
       }
+      // times of day, which a date alone would set to midnight
+      for attribute in ["due", "scheduled", "wait"] {
+        for time in DATE_TIMES {
+          self.completion_list.insert((attribute.to_string(), time.to_string()));
+        }
+      }
       for task in tasks {
         if let Some(date) = task.due() {
           self.completion_list.insert(("due".to_string(), get_formatted_datetime(date)));
@@ -3604,6 +8531,27 @@ impl TaskwarriorTui {
           self.completion_list.insert(("end".to_string(), get_formatted_datetime(date)));
         }
       }
+      for recur in RECURRENCE_PERIODS {
+        self.completion_list.insert(("recur".to_string(), recur.to_string()));
+      }
+      for task in tasks {
+        if let Some(recur) = task.recur() {
+          self.completion_list.insert(("recur".to_string(), recur.to_string()));
+        }
+      }
+      for uda in &self.config.uda {
+        for value in uda.values.iter().flatten().filter(|v| !v.is_empty()) {
+          self.completion_list.insert((uda.name.clone(), value.clone()));
+        }
+        if uda.kind == "string" && uda.values.is_none() {
+          for task in tasks {
+            if let Some(UDAValue::Str(value)) = task.uda().get(&uda.name) {
+              let value = if value.contains(' ') { format!(r#""{}""#, value) } else { value.clone() };
+              self.completion_list.insert((uda.name.clone(), value));
+            }
+          }
+        }
+      }
     }
 
     if self.mode == Mode::Tasks(Action::Filter) {
@@ -3614,6 +8562,118 @@ impl TaskwarriorTui {
     }
   }
 
+  /// Resolve the date expression under the cursor of the current prompt, e.g. `due:eom+3d`.
+  pub fn update_date_preview(&mut self) {
+    let buffer = match self.mode {
+      Mode::Tasks(Action::Modify) => &self.modify,
+      Mode::Tasks(Action::Filter) => &self.filter,
+      Mode::Tasks(Action::Add | Action::Log | Action::Annotate) => &self.command,
+      _ => return,
+    };
+    if self.degraded() {
+      return;
+    }
+    let text = buffer.as_str();
+    let start = get_start_word_under_cursor(text, buffer.pos());
+    let end = text[start..].find(' ').map_or(text.len(), |i| start + i);
+    let token = &text[start..end];
+    let expression = match DATE_ATTRIBUTE.captures(token) {
+      Some(caps) => caps["expression"].trim_matches(|c| c == '"' || c == '\'').to_string(),
+      None => {
+        self.date_preview = None;
+        return;
+      }
+    };
+    if token.starts_with("due") && self.due_load.is_none() {
+      self.due_load = Some(Self::export_due_load());
+    }
+    self.date_preview = match self.date_previews.get(&expression) {
+      Some(resolved) => Some((token.to_string(), resolved.clone())),
+      None => {
+        // shown once resolved in the background
+        self.date_previews.request(&expression, &self.event_loop.tx);
+        None
+      }
+    };
+  }
+
+  /// Count the tasks still to do on each day they are due.
+  fn export_due_load() -> DueLoad {
+    let output = overrides::task()
+      .arg("rc.json.array=on")
+      .arg("rc.confirmation=off")
+      .arg("rc.json.depends.array=on")
+      .arg("rc.color=off")
+      .arg("rc._forcecolor=off")
+      .arg("( status:pending or status:waiting )")
+      .arg("due.any:")
+      .arg("export")
+      .output();
+    match output.map(|o| import(o.stdout.as_slice())) {
+      Ok(Ok(tasks)) => DueLoad::new(&tasks),
+      _ => {
+        warn!("Unable to export the due tasks");
+        DueLoad::default()
+      }
+    }
+  }
+
+  /// Day of a date resolved for the preview, e.g. `Thu 2026-10-15 00:00`.
+  fn preview_day(resolved: &str) -> Option<NaiveDate> {
+    resolved.split(' ').nth(1).and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+  }
+
+  /// Set the due date of the current prompt to the first day from the one typed, or from today,
+  /// with fewer tasks due than `uda.taskwarrior-tui.due.capacity`.
+  fn suggest_free_due_day(&mut self) {
+    if self.due_load.is_none() {
+      self.due_load = Some(Self::export_due_load());
+    }
+    let from = self
+      .date_preview
+      .as_ref()
+      .filter(|(token, _)| token.starts_with("due"))
+      .and_then(|(_, resolved)| Self::preview_day(resolved))
+      .unwrap_or_else(|| Local::now().date_naive());
+    let day = self
+      .due_load
+      .as_ref()
+      .map_or(from, |load| load.next_free(from, self.config.uda_due_capacity));
+    let replacement = format!("due:{}", day.format("%Y-%m-%d"));
+    let buffer = match self.mode {
+      Mode::Tasks(Action::Modify) => &mut self.modify,
+      Mode::Tasks(Action::Add) => &mut self.command,
+      _ => return,
+    };
+    let text = buffer.as_str().to_string();
+    let start = get_start_word_under_cursor(&text, buffer.pos());
+    let end = text[start..].find(' ').map_or(text.len(), |i| start + i);
+    let on_due = DATE_ATTRIBUTE.captures(&text[start..end]).is_some_and(|caps| &caps[1] == "due");
+    let (text, pos) = if on_due {
+      (format!("{}{}{}", &text[..start], replacement, &text[end..]), start + replacement.len())
+    } else {
+      let text = format!("{} {}", text.trim_end(), replacement);
+      let len = text.len();
+      (text, len)
+    };
+    buffer.update(&text, pos, &mut self.changes);
+    self.update_date_preview();
+  }
+
+  /// Show the values of an attribute as soon as its `:` is typed, for attributes with a known set of values.
+  pub fn open_value_picker(&mut self, input: KeyCode) {
+    if input != KeyCode::Char(':') || self.show_completion_pane {
+      return;
+    }
+    let context = self.completion_list.helper.context.as_str();
+    let has_values =
+      ["project", "priority", "recur"].contains(&context) || self.config.uda.iter().any(|uda| uda.name == context && uda.kind == "string");
+    if has_values && !self.completion_list.is_empty() {
+      self.show_completion_pane = true;
+      self.completion_list.next();
+    }
+  }
+
   pub fn update_input_for_completion(&mut self) {
     match self.mode {
       Mode::Tasks(Action::Add | Action::Annotate | Action::Log) => {
@@ -3636,6 +8696,25 @@ impl TaskwarriorTui {
   }
 }
 
+/// Replace the abbreviation right before the cursor with its snippet, returns `true` if it was expanded.
+pub fn expand_snippet(linebuffer: &mut LineBuffer, snippets: &HashMap<String, String>, changes: &mut utils::Changeset) -> bool {
+  let pos = linebuffer.pos();
+  let (before, after) = linebuffer.as_str().split_at(pos);
+  let start = before
+    .char_indices()
+    .rev()
+    .find(|(_, c)| c.is_whitespace() || *c == '"' || *c == '\'')
+    .map_or(0, |(i, c)| i + c.len_utf8());
+  match snippets.get(&before[start..]) {
+    Some(snippet) => {
+      let text = format!("{}{}{}", &before[..start], snippet, after);
+      linebuffer.update(&text, start + snippet.len(), changes);
+      true
+    }
+    None => false,
+  }
+}
+
 pub fn handle_movement(linebuffer: &mut LineBuffer, input: KeyCode, changes: &mut utils::Changeset) {
   match input {
     KeyCode::Ctrl('f') | KeyCode::Right => {
@@ -3678,7 +8757,16 @@ pub fn handle_movement(linebuffer: &mut LineBuffer, input: KeyCode, changes: &mu
       linebuffer.transpose_words(1, changes);
     }
     KeyCode::Char(c) => {
-      linebuffer.insert(c, 1, changes);
+      let previous = linebuffer.as_str()[..linebuffer.pos()].chars().next_back();
+      match previous.and_then(|p| compose::compose(p, c)) {
+        Some(composed) => {
+          linebuffer.backspace(1, changes);
+          linebuffer.insert(composed, 1, changes);
+        }
+        None => {
+          linebuffer.insert(c, 1, changes);
+        }
+      }
     }
     _ => {}
   }
@@ -3736,6 +8824,97 @@ mod tests {
     assert_eq!(centered_rect(50, 50, Rect::new(0, 0, 100, 100)), Rect::new(25, 25, 50, 50));
   }
 
+  #[test]
+  fn test_denotate_arguments() {
+    let args = TaskwarriorTui::denotate_arguments(&[Uuid::from_u128(1)], "-- see  project:home");
+    assert_eq!(args, ["00000000-0000-0000-0000-000000000001", "denotate", "--", "-- see  project:home"]);
+    assert_eq!(
+      utils::command_line("task", &args),
+      r#"task 00000000-0000-0000-0000-000000000001 denotate -- "-- see  project:home""#
+    );
+  }
+
+  #[test]
+  fn test_entry_args() {
+    let text = "pay rent tomorrow #home @bills";
+    assert_eq!(
+      TaskwarriorTui::entry_args(text, true).unwrap(),
+      ["pay rent", "due:tomorrow", "project:home", "+bills"]
+    );
+    assert_eq!(
+      TaskwarriorTui::entry_args("pay rent tomorrow +bills", false).unwrap(),
+      ["pay", "rent", "tomorrow", "+bills"]
+    );
+    assert_eq!(TaskwarriorTui::entry_args("\"unbalanced", true), None);
+  }
+
+  /// Pending tasks whose uuids are their positions.
+  fn numbered_tasks(count: u128) -> Vec<Task> {
+    let json: Vec<String> = (0..count)
+      .map(|n| {
+        format!(
+          r#"{{"uuid":"{}","description":"Task {}","status":"pending","entry":"20261001T090000Z"}}"#,
+          Uuid::from_u128(n),
+          n
+        )
+      })
+      .collect();
+    import(format!("[{}]", json.join(",")).as_bytes()).unwrap()
+  }
+
+  #[test]
+  fn test_neighbor_uuids() {
+    let tasks = numbered_tasks(20);
+    let neighbors = |index| -> Vec<u128> { TaskwarriorTui::neighbor_uuids(&tasks, index).iter().map(Uuid::as_u128).collect() };
+    assert_eq!(neighbors(1), [2, 0, 3, 4, 5, 6]);
+    assert_eq!(neighbors(10), [11, 9, 12, 8, 13, 7, 14, 6, 15, 5]);
+    assert_eq!(neighbors(19), [18, 17, 16, 15, 14]);
+  }
+
+  #[test]
+  fn test_task_after_removal() {
+    let tasks = numbered_tasks(6);
+    let removed = [Uuid::from_u128(2), Uuid::from_u128(3)];
+    let after = |removed: &[Uuid], mode| TaskwarriorTui::task_after_removal(&tasks, removed, mode).map(|u| u.as_u128());
+    assert_eq!(after(&removed, CursorAfterRemoval::Stay), None);
+    assert_eq!(after(&removed, CursorAfterRemoval::Next), Some(4));
+    assert_eq!(after(&removed, CursorAfterRemoval::Previous), Some(1));
+    // at the ends of the report there is nothing to move to, the cursor stays on its row
+    assert_eq!(after(&[Uuid::from_u128(5)], CursorAfterRemoval::Next), None);
+    assert_eq!(after(&[Uuid::from_u128(0)], CursorAfterRemoval::Previous), None);
+    assert_eq!(after(&[Uuid::from_u128(9)], CursorAfterRemoval::Next), None);
+  }
+
+  #[test]
+  fn test_degraded_after_frame() {
+    let start = Instant::now();
+    let frame = |since, elapsed_ms, budget, seconds| {
+      TaskwarriorTui::degraded_after_frame(since, Duration::from_millis(elapsed_ms), budget, start + Duration::from_secs(seconds))
+    };
+    assert_eq!(frame(None, 5000, 0, 0), None);
+    assert_eq!(frame(None, 150, 200, 0), None);
+    assert_eq!(frame(None, 250, 200, 0), Some(start));
+    // fast frames do not restore the decorations before the retry
+    assert_eq!(frame(Some(start), 10, 200, 29), Some(start));
+    assert_eq!(frame(Some(start), 10, 200, 30), None);
+    assert_eq!(frame(Some(start), 10, 0, 10), None);
+  }
+
+  #[test]
+  fn test_restore_arguments() {
+    let uuids = [Uuid::from_u128(1), Uuid::from_u128(2)];
+    assert_eq!(
+      TaskwarriorTui::restore_arguments(&uuids),
+      [
+        "00000000-0000-0000-0000-000000000001",
+        "00000000-0000-0000-0000-000000000002",
+        "modify",
+        "status:pending",
+        "end:"
+      ]
+    );
+  }
+
   fn setup() {
     use std::process::Stdio;
     let mut f = File::open(Path::new(env!("TASKDATA")).parent().unwrap().join("export.json")).unwrap();
@@ -3830,6 +9009,7 @@ mod tests {
   }
 
   #[test]
+  #[allow(clippy::let_unit_value)]
   fn test_taskwarrior_tui() {
     let r = tokio::runtime::Builder::new_multi_thread()
       .enable_all()
@@ -3882,6 +9062,7 @@ mod tests {
     teardown();
   }
 
+  #[allow(clippy::useless_vec)]
   async fn test_task_tags() {
     // testing tags
     let app = TaskwarriorTui::new("next", false).await.unwrap();
@@ -4009,7 +9190,7 @@ mod tests {
     let now = Local::now();
     let now = TimeZone::from_utc_datetime(now.offset(), &now.naive_utc());
 
-    let mut command = std::process::Command::new("task");
+    let mut command = overrides::task();
     command.arg("add");
     let tomorrow = now + chrono::Duration::days(1);
     let message = format!(
@@ -4044,11 +9225,7 @@ mod tests {
       }
     }
 
-    let output = std::process::Command::new("task")
-      .arg("rc.confirmation=off")
-      .arg("undo")
-      .output()
-      .unwrap();
+    let output = overrides::task().arg("rc.confirmation=off").arg("undo").output().unwrap();
 
     let mut app = TaskwarriorTui::new("next", false).await.unwrap();
     assert!(app.update(true).await.is_ok());
@@ -4067,7 +9244,7 @@ mod tests {
     let now = Local::now();
     let now = TimeZone::from_utc_datetime(now.offset(), &now.naive_utc());
 
-    let mut command = std::process::Command::new("task");
+    let mut command = overrides::task();
     command.arg("add");
     let message = "'new task for testing earlier today' due:now";
 
@@ -4103,11 +9280,7 @@ mod tests {
       assert!(task.tags().unwrap().contains(&s.to_string()));
     }
 
-    let output = std::process::Command::new("task")
-      .arg("rc.confirmation=off")
-      .arg("undo")
-      .output()
-      .unwrap();
+    let output = overrides::task().arg("rc.confirmation=off").arg("undo").output().unwrap();
 
     let mut app = TaskwarriorTui::new("next", false).await.unwrap();
     assert!(app.update(true).await.is_ok());
@@ -4126,7 +9299,7 @@ mod tests {
     let now = Local::now();
     let now = TimeZone::from_utc_datetime(now.offset(), &now.naive_utc());
 
-    let mut command = std::process::Command::new("task");
+    let mut command = overrides::task();
     command.arg("add");
     let message = format!(
       "'new task for testing later today' due:'{:04}-{:02}-{:02}T{:02}:{:02}:{:02}'",
@@ -4159,11 +9332,7 @@ mod tests {
       assert!(task.tags().unwrap().contains(&s.to_string()));
     }
 
-    let output = std::process::Command::new("task")
-      .arg("rc.confirmation=off")
-      .arg("undo")
-      .output()
-      .unwrap();
+    let output = overrides::task().arg("rc.confirmation=off").arg("undo").output().unwrap();
 
     let mut app = TaskwarriorTui::new("next", false).await.unwrap();
     assert!(app.update(true).await.is_ok());
@@ -4461,7 +9630,7 @@ mod tests {
     let now = Local::now();
     let now = TimeZone::from_utc_datetime(now.offset(), &now.naive_utc());
 
-    let mut command = std::process::Command::new("task");
+    let mut command = overrides::task();
     command.arg("add");
     let message = "'new task 1 for testing draw' priority:U";
 
@@ -4477,7 +9646,7 @@ mod tests {
     let task_id = caps["task_id"].parse::<u64>().unwrap();
     assert_eq!(task_id, total_tasks + 1);
 
-    let mut command = std::process::Command::new("task");
+    let mut command = overrides::task();
     command.arg("add");
     let message = "'new task 2 for testing draw' priority:U +none";
 
@@ -4518,16 +9687,8 @@ mod tests {
       })
       .unwrap();
 
-    let output = std::process::Command::new("task")
-      .arg("rc.confirmation=off")
-      .arg("undo")
-      .output()
-      .unwrap();
-    let output = std::process::Command::new("task")
-      .arg("rc.confirmation=off")
-      .arg("undo")
-      .output()
-      .unwrap();
+    let output = overrides::task().arg("rc.confirmation=off").arg("undo").output().unwrap();
+    let output = overrides::task().arg("rc.confirmation=off").arg("undo").output().unwrap();
 
     assert_eq!(terminal.backend().size().unwrap(), expected.area);
     terminal.backend().assert_buffer(&expected);
@@ -0,0 +1,340 @@
+use std::{
+  fs,
+  path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Result};
+use chrono::{Duration, NaiveDateTime};
+use log::warn;
+use ratatui::widgets::ListState;
+use tokio::sync::mpsc;
+
+use crate::event::{Event, KeyCode};
+
+const TIME_FORMAT: &str = "%Y%m%dT%H%M%S";
+/// Folder of the data directory the files of a backup are copied to before they replace the data.
+const STAGING: &str = ".taskwarrior-tui-restore";
+
+/// Why a backup was taken, the end of the name of its folder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+  Scheduled,
+  Manual,
+  /// Before changing many tasks at once
+  Bulk,
+  /// Of the data a restore replaced
+  Restore,
+}
+
+impl Reason {
+  pub fn label(&self) -> &'static str {
+    match self {
+      Reason::Scheduled => "scheduled",
+      Reason::Manual => "manual",
+      Reason::Bulk => "bulk",
+      Reason::Restore => "restore",
+    }
+  }
+
+  fn parse(s: &str) -> Option<Self> {
+    [Reason::Scheduled, Reason::Manual, Reason::Bulk, Reason::Restore]
+      .iter()
+      .copied()
+      .find(|r| r.label() == s)
+  }
+}
+
+/// A copy of the files of the task data folder, in a folder named after the local time it was
+/// taken at and why, e.g. `20261015T093000-scheduled`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+  pub path: PathBuf,
+  pub time: NaiveDateTime,
+  pub reason: Reason,
+  pub files: usize,
+  pub bytes: u64,
+}
+
+fn parse_name(name: &str) -> Option<(NaiveDateTime, Reason)> {
+  let (time, reason) = name.split_once('-')?;
+  Some((NaiveDateTime::parse_from_str(time, TIME_FORMAT).ok()?, Reason::parse(reason)?))
+}
+
+/// Copy the files of `from`, not its folders such as `hooks`, to `to`. Returns how many files and
+/// bytes were copied.
+fn copy_files(from: &Path, to: &Path) -> Result<(usize, u64)> {
+  let mut copied = (0, 0);
+  for entry in fs::read_dir(from)? {
+    let entry = entry?;
+    if entry.file_type()?.is_file() {
+      copied.0 += 1;
+      copied.1 += fs::copy(entry.path(), to.join(entry.file_name()))?;
+    }
+  }
+  Ok(copied)
+}
+
+/// Backups of the task data, newest first.
+#[derive(Debug, Clone, Default)]
+pub struct Backups {
+  pub backups: Vec<Backup>,
+  pub state: ListState,
+  dir: PathBuf,
+  /// Backups kept when taking a new one, all are when `0`
+  keep: usize,
+}
+
+impl Backups {
+  pub fn new(dir: PathBuf, keep: usize) -> Self {
+    Self {
+      dir,
+      keep,
+      ..Self::default()
+    }
+  }
+
+  pub fn load(&mut self) -> Result<()> {
+    self.backups.clear();
+    if !self.dir.exists() {
+      return Ok(());
+    }
+    for entry in fs::read_dir(&self.dir)?.flatten() {
+      let name = entry.file_name().to_string_lossy().to_string();
+      let (time, reason) = match parse_name(&name) {
+        Some(parsed) => parsed,
+        None => continue,
+      };
+      let files: Vec<u64> = fs::read_dir(entry.path())?
+        .flatten()
+        .filter_map(|f| f.metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .collect();
+      self.backups.push(Backup {
+        path: entry.path(),
+        time,
+        reason,
+        files: files.len(),
+        bytes: files.iter().sum(),
+      });
+    }
+    self.backups.sort_by_key(|b| std::cmp::Reverse(b.time));
+    Ok(())
+  }
+
+  pub fn latest(&self) -> Option<&Backup> {
+    self.backups.first()
+  }
+
+  /// Whether the latest backup is at least `hours` old, never when `hours` is `0`.
+  pub fn due(&self, hours: u64, now: NaiveDateTime) -> bool {
+    hours > 0 && self.latest().is_none_or(|b| now - b.time >= Duration::hours(hours as i64))
+  }
+
+  /// Copy the files of `data_dir` to a new backup, then remove the oldest ones beyond those kept.
+  pub fn create(&mut self, data_dir: &Path, reason: Reason, now: NaiveDateTime) -> Result<()> {
+    self.take(data_dir, reason, now)?;
+    self.prune(None);
+    Ok(())
+  }
+
+  fn take(&mut self, data_dir: &Path, reason: Reason, now: NaiveDateTime) -> Result<()> {
+    self.load()?;
+    // one backup per second, so that they sort in the order they were taken, e.g. when a bulk
+    // change follows a restore
+    let mut time = now;
+    while self.backups.iter().any(|b| b.time == time) {
+      time += Duration::seconds(1);
+    }
+    let path = self.dir.join(format!("{}-{}", time.format(TIME_FORMAT), reason.label()));
+    fs::create_dir_all(&path)?;
+    if let Err(e) = copy_files(data_dir, &path) {
+      fs::remove_dir_all(&path).ok();
+      return Err(anyhow!("Unable to back up {:?}: {}", data_dir, e));
+    }
+    self.load()
+  }
+
+  /// Remove the oldest backups beyond those kept, except `spare`.
+  fn prune(&mut self, spare: Option<&Path>) {
+    if self.keep == 0 {
+      return;
+    }
+    let old = self.backups.split_off(self.keep.min(self.backups.len()));
+    for old in old {
+      if spare == Some(old.path.as_path()) {
+        self.backups.push(old);
+      } else if let Err(e) = fs::remove_dir_all(&old.path) {
+        warn!("Unable to remove the old backup {:?}: {}", old.path, e);
+      }
+    }
+  }
+
+  /// Replace the files of `data_dir` with those of `backup`, after backing up the data they replace.
+  /// Files that `backup` does not have are removed, so that the data is exactly as it was.
+  pub fn restore(&mut self, backup: &Backup, data_dir: &Path, now: NaiveDateTime) -> Result<()> {
+    self.take(data_dir, Reason::Restore, now)?;
+    // copied next to the data first, so that a failed copy leaves the data as it is
+    let staging = data_dir.join(STAGING);
+    fs::create_dir_all(&staging)?;
+    if let Err(e) = copy_files(&backup.path, &staging) {
+      fs::remove_dir_all(&staging).ok();
+      return Err(anyhow!("Unable to restore {:?}: {}", backup.path, e));
+    }
+    for entry in fs::read_dir(data_dir)? {
+      let entry = entry?;
+      if entry.file_type()?.is_file() && !staging.join(entry.file_name()).exists() {
+        fs::remove_file(entry.path())?;
+      }
+    }
+    for entry in fs::read_dir(&staging)? {
+      let entry = entry?;
+      fs::rename(entry.path(), data_dir.join(entry.file_name()))?;
+    }
+    fs::remove_dir_all(&staging)?;
+    self.prune(Some(&backup.path));
+    Ok(())
+  }
+
+  pub fn selected(&self) -> Option<&Backup> {
+    self.state.selected().and_then(|i| self.backups.get(i))
+  }
+}
+
+/// Scheduled backups, copied on a blocking thread so that refreshing the report never waits for
+/// the copy.
+pub struct Scheduled {
+  running: bool,
+  tx: mpsc::UnboundedSender<Result<Backups, String>>,
+  rx: mpsc::UnboundedReceiver<Result<Backups, String>>,
+}
+
+impl Scheduled {
+  pub fn new() -> Self {
+    let (tx, rx) = mpsc::unbounded_channel();
+    Self { running: false, tx, rx }
+  }
+
+  pub fn running(&self) -> bool {
+    self.running
+  }
+
+  /// Back up `data_dir` next to `backups` in the background, sending `Event::Tick` on `wake` once
+  /// done.
+  pub fn start(&mut self, backups: &Backups, data_dir: PathBuf, now: NaiveDateTime, wake: &mpsc::UnboundedSender<Event<KeyCode>>) {
+    if self.running {
+      return;
+    }
+    self.running = true;
+    let mut backups = backups.clone();
+    let tx = self.tx.clone();
+    let wake = wake.clone();
+    tokio::task::spawn_blocking(move || {
+      let result = backups.create(&data_dir, Reason::Scheduled, now).map_err(|e| e.to_string());
+      tx.send(result.map(|_| backups)).ok();
+      wake.send(Event::Tick).ok();
+    });
+  }
+
+  /// The backups once the backup is taken, or why it could not be.
+  pub fn poll(&mut self) -> Option<Result<Backups, String>> {
+    let result = self.rx.try_recv().ok()?;
+    self.running = false;
+    Some(result)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::NaiveDate;
+
+  use super::*;
+
+  #[test]
+  fn test_backups() {
+    let root = std::env::temp_dir().join(format!("taskwarrior-tui-backups-{}", std::process::id()));
+    let data = root.join("data");
+    fs::create_dir_all(data.join("hooks")).unwrap();
+    fs::write(data.join("pending.data"), "v1").unwrap();
+
+    let now = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap().and_hms_opt(9, 30, 0).unwrap();
+    let mut backups = Backups::new(root.join("backups"), 2);
+    assert!(backups.due(24, now));
+    assert!(!backups.due(0, now));
+    backups.create(&data, Reason::Scheduled, now).unwrap();
+    assert!(!backups.due(24, now + Duration::hours(23)));
+    assert!(backups.due(24, now + Duration::hours(24)));
+    assert_eq!(backups.latest().unwrap().files, 1);
+
+    fs::write(data.join("pending.data"), "v2").unwrap();
+    let first = backups.latest().unwrap().clone();
+    backups.restore(&first, &data, now + Duration::hours(1)).unwrap();
+    assert_eq!(fs::read_to_string(data.join("pending.data")).unwrap(), "v1");
+    assert_eq!(backups.latest().unwrap().reason, Reason::Restore);
+
+    backups.create(&data, Reason::Manual, now + Duration::hours(1)).unwrap();
+    assert_eq!(
+      backups.backups.iter().map(|b| b.reason).collect::<Vec<_>>(),
+      [Reason::Manual, Reason::Restore]
+    );
+    assert_eq!(parse_name("20261015T093000-bulk"), Some((now, Reason::Bulk)));
+    assert_eq!(parse_name("notes"), None);
+    fs::remove_dir_all(&root).unwrap();
+  }
+
+  #[test]
+  fn test_restore_oldest() {
+    let root = std::env::temp_dir().join(format!("taskwarrior-tui-restore-{}", std::process::id()));
+    let data = root.join("data");
+    fs::create_dir_all(data.join("hooks")).unwrap();
+    fs::write(data.join("pending.data"), "v1").unwrap();
+
+    let now = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap().and_hms_opt(9, 30, 0).unwrap();
+    let mut backups = Backups::new(root.join("backups"), 2);
+    backups.create(&data, Reason::Scheduled, now).unwrap();
+    fs::write(data.join("pending.data"), "v2").unwrap();
+    fs::write(data.join("backlog.data"), "added since").unwrap();
+    backups.create(&data, Reason::Manual, now + Duration::hours(1)).unwrap();
+
+    // with two backups kept, restoring the oldest one must not remove it before it is copied
+    let oldest = backups.backups[1].clone();
+    backups.restore(&oldest, &data, now + Duration::hours(2)).unwrap();
+    assert_eq!(fs::read_to_string(data.join("pending.data")).unwrap(), "v1");
+    assert!(!data.join("backlog.data").exists());
+    assert!(data.join("hooks").exists());
+    assert!(!data.join(STAGING).exists());
+    assert!(oldest.path.exists());
+    assert_eq!(backups.latest().unwrap().reason, Reason::Restore);
+    assert_eq!(backups.latest().unwrap().files, 2);
+
+    // it goes with the next backup
+    backups.create(&data, Reason::Manual, now + Duration::hours(3)).unwrap();
+    assert_eq!(backups.backups.len(), 2);
+    assert!(!oldest.path.exists());
+    fs::remove_dir_all(&root).unwrap();
+  }
+
+  #[tokio::test]
+  async fn test_scheduled() {
+    let root = std::env::temp_dir().join(format!("taskwarrior-tui-scheduled-{}", std::process::id()));
+    let data = root.join("data");
+    fs::create_dir_all(&data).unwrap();
+    fs::write(data.join("pending.data"), "v1").unwrap();
+
+    let now = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap().and_hms_opt(9, 30, 0).unwrap();
+    let backups = Backups::new(root.join("backups"), 2);
+    let (wake, mut woken) = mpsc::unbounded_channel();
+    let mut scheduled = Scheduled::new();
+    scheduled.start(&backups, data.clone(), now, &wake);
+    assert!(scheduled.running());
+    woken.recv().await.unwrap();
+    let backups = scheduled.poll().unwrap().unwrap();
+    assert!(!scheduled.running());
+    assert_eq!(backups.latest().unwrap().reason, Reason::Scheduled);
+
+    scheduled.start(&backups, root.join("missing"), now + Duration::hours(1), &wake);
+    woken.recv().await.unwrap();
+    assert!(scheduled.poll().unwrap().is_err());
+    fs::remove_dir_all(&root).unwrap();
+  }
+}
@@ -0,0 +1,99 @@
+use tokio::sync::mpsc;
+
+use crate::{
+  event::{Event, KeyCode},
+  notify::NamedFilter,
+  overrides,
+};
+
+/// Number of tasks matching each named filter with `badge`, counted in the background so that
+/// drawing never waits for `task`.
+pub struct Badges {
+  /// Names and counts, in the order of the filters
+  pub counts: Vec<(String, u64)>,
+  /// Bumped on each refresh, the counts of an earlier one are then dropped
+  generation: u64,
+  tx: mpsc::UnboundedSender<(u64, Vec<(String, u64)>)>,
+  rx: mpsc::UnboundedReceiver<(u64, Vec<(String, u64)>)>,
+}
+
+impl Badges {
+  pub fn new() -> Self {
+    let (tx, rx) = mpsc::unbounded_channel();
+    Self {
+      counts: vec![],
+      generation: 0,
+      tx,
+      rx,
+    }
+  }
+
+  /// Count the tasks of the filters with `badge` one after the other with `task count`, sending
+  /// `Event::Tick` on `wake` once done.
+  pub fn refresh(&mut self, filters: &[NamedFilter], wake: &mpsc::UnboundedSender<Event<KeyCode>>) {
+    let filters: Vec<NamedFilter> = filters.iter().filter(|f| f.badge).cloned().collect();
+    if filters.is_empty() {
+      return;
+    }
+    self.generation += 1;
+    let generation = self.generation;
+    let tx = self.tx.clone();
+    let wake = wake.clone();
+    tokio::spawn(async move {
+      let mut counts = vec![];
+      for filter in filters {
+        let output = overrides::task_async()
+          .arg("rc.color=off")
+          .arg("rc.verbose=nothing")
+          .args(shlex::split(&filter.filter).unwrap_or_default())
+          .arg("count")
+          .output()
+          .await;
+        match output.map(|o| String::from_utf8_lossy(&o.stdout).trim().parse::<u64>()) {
+          Ok(Ok(count)) => counts.push((filter.name, count)),
+          _ => log::warn!("Unable to count the tasks of filter {}", filter.name),
+        }
+      }
+      tx.send((generation, counts)).ok();
+      wake.send(Event::Tick).ok();
+    });
+  }
+
+  /// Take in the counts of the last refresh, returning whether they arrived.
+  pub fn poll(&mut self) -> bool {
+    let mut arrived = false;
+    while let Ok((generation, counts)) = self.rx.try_recv() {
+      if generation == self.generation {
+        self.counts = counts;
+        arrived = true;
+      }
+    }
+    arrived
+  }
+}
+
+/// `name: count` of each badge, e.g. `inbox: 4 overdue: 2`.
+pub fn label(counts: &[(String, u64)]) -> String {
+  counts
+    .iter()
+    .map(|(name, count)| format!("{}: {}", name, count))
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_badges() {
+    let mut badges = Badges::new();
+    badges.generation = 2;
+    badges.tx.send((1, vec![("stale".to_string(), 9)])).unwrap();
+    assert!(!badges.poll());
+    let counts = vec![("inbox".to_string(), 4), ("overdue".to_string(), 2)];
+    badges.tx.send((2, counts)).unwrap();
+    assert!(badges.poll());
+    assert_eq!(label(&badges.counts), "inbox: 4 overdue: 2");
+  }
+}
@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::layout::LayoutPreset;
+
+const SETTING_PREFIX: &str = "uda.taskwarrior-tui.";
+const VERSION: u32 = 1;
+
+/// Settings whose value is a command that taskwarrior-tui runs, and prefixes of such settings.
+const COMMANDS: [&str; 4] = [
+  "uda.taskwarrior-tui.background_process",
+  "uda.taskwarrior-tui.secret.encrypt",
+  "uda.taskwarrior-tui.secret.decrypt",
+  "uda.taskwarrior-tui.shortcuts.",
+];
+
+/// The settings of `taskwarrior-tui` in a single file, to share a setup or carry it to another
+/// machine: the `uda.taskwarrior-tui.*` settings of the taskrc, which hold the styles, key bindings
+/// and named filters, and the layout presets of the data directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bundle {
+  pub version: u32,
+  pub settings: BTreeMap<String, String>,
+  #[serde(default)]
+  pub layouts: Vec<LayoutPreset>,
+}
+
+/// A setting or layout of a bundle that is set differently here.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict<T> {
+  pub name: String,
+  pub current: T,
+  pub incoming: T,
+}
+
+/// What importing a bundle changes: what is new is added once confirmed, conflicts are asked about.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Plan {
+  pub settings: Vec<(String, String)>,
+  pub setting_conflicts: Vec<Conflict<String>>,
+  /// Settings running a command that are new or set differently, each asked about on its own. The
+  /// current value of a new one is empty.
+  pub commands: Vec<Conflict<String>>,
+  pub layouts: Vec<LayoutPreset>,
+  pub layout_conflicts: Vec<Conflict<LayoutPreset>>,
+}
+
+/// Whether the value of setting `name` is a command that taskwarrior-tui runs.
+pub fn runs_command(name: &str) -> bool {
+  COMMANDS.iter().any(|c| if c.ends_with('.') { name.starts_with(c) } else { name == *c })
+}
+
+/// All `uda.taskwarrior-tui.*` settings from the output of `task show`.
+pub fn settings(data: &str) -> BTreeMap<String, String> {
+  let mut settings = BTreeMap::new();
+  let mut last: Option<String> = None;
+  for line in data.lines() {
+    if line.starts_with(SETTING_PREFIX) {
+      let (name, value) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+      settings.insert(name.to_string(), value.trim().to_string());
+      last = Some(name.to_string());
+    } else if line.starts_with("   ") && !line.trim().is_empty() {
+      // long values are wrapped over the following lines
+      if let Some(value) = last.as_ref().and_then(|name| settings.get_mut(name)) {
+        value.push(' ');
+        value.push_str(line.trim());
+      }
+    } else {
+      last = None;
+    }
+  }
+  settings
+}
+
+impl Bundle {
+  pub fn new(settings: BTreeMap<String, String>, layouts: Vec<LayoutPreset>) -> Self {
+    Self {
+      version: VERSION,
+      settings,
+      layouts,
+    }
+  }
+
+  pub fn parse(data: &str) -> anyhow::Result<Self> {
+    let bundle: Self = serde_json::from_str(data)?;
+    if bundle.version > VERSION {
+      anyhow::bail!("The bundle was written by a newer version of taskwarrior-tui");
+    }
+    if let Some(name) = bundle.settings.keys().find(|name| !name.starts_with(SETTING_PREFIX)) {
+      anyhow::bail!("The bundle sets `{}`, which is not a taskwarrior-tui setting", name);
+    }
+    Ok(bundle)
+  }
+
+  pub fn to_json(&self) -> anyhow::Result<String> {
+    Ok(serde_json::to_string_pretty(self)?)
+  }
+
+  /// Changes importing this bundle makes to the `settings` and `layouts` in use.
+  pub fn plan(&self, settings: &BTreeMap<String, String>, layouts: &[LayoutPreset]) -> Plan {
+    let mut plan = Plan::default();
+    for (name, incoming) in &self.settings {
+      if runs_command(name) {
+        if settings.get(name) != Some(incoming) {
+          plan.commands.push(Conflict {
+            name: name.clone(),
+            current: settings.get(name).cloned().unwrap_or_default(),
+            incoming: incoming.clone(),
+          });
+        }
+        continue;
+      }
+      match settings.get(name) {
+        None => plan.settings.push((name.clone(), incoming.clone())),
+        Some(current) if current != incoming => plan.setting_conflicts.push(Conflict {
+          name: name.clone(),
+          current: current.clone(),
+          incoming: incoming.clone(),
+        }),
+        Some(_) => {}
+      }
+    }
+    for incoming in &self.layouts {
+      match layouts.iter().find(|l| l.name == incoming.name) {
+        None => plan.layouts.push(incoming.clone()),
+        Some(current) if current != incoming => plan.layout_conflicts.push(Conflict {
+          name: incoming.name.clone(),
+          current: current.clone(),
+          incoming: incoming.clone(),
+        }),
+        Some(_) => {}
+      }
+    }
+    plan
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::layout::View;
+
+  #[test]
+  fn test_plan() {
+    let data =
+      "uda.taskwarrior-tui.keyconfig.quit  x\nuda.taskwarrior-tui.task-report.next.filter  status:pending\n    -WAITING\nweekstart  monday\n";
+    let current = settings(data);
+    assert_eq!(current.len(), 2);
+    assert_eq!(current["uda.taskwarrior-tui.task-report.next.filter"], "status:pending -WAITING");
+
+    let layout = |name: &str, details: bool| LayoutPreset {
+      name: name.to_string(),
+      view: View::Tasks,
+      details,
+      filter: None,
+      key: None,
+    };
+    let mut incoming = BTreeMap::new();
+    incoming.insert("uda.taskwarrior-tui.keyconfig.quit".to_string(), "q".to_string());
+    incoming.insert("uda.taskwarrior-tui.density".to_string(), "compact".to_string());
+    incoming.insert("uda.taskwarrior-tui.shortcuts.1".to_string(), "~/bin/sync.sh".to_string());
+    incoming.insert(
+      "uda.taskwarrior-tui.task-report.next.filter".to_string(),
+      "status:pending -WAITING".to_string(),
+    );
+    let bundle = Bundle::new(incoming, vec![layout("triage", false), layout("review", true)]);
+    let bundle = Bundle::parse(&bundle.to_json().unwrap()).unwrap();
+
+    let plan = bundle.plan(&current, &[layout("triage", true)]);
+    assert_eq!(plan.settings, vec![("uda.taskwarrior-tui.density".to_string(), "compact".to_string())]);
+    assert_eq!(plan.setting_conflicts.len(), 1);
+    assert_eq!(plan.setting_conflicts[0].current, "x");
+    assert_eq!(plan.commands.len(), 1);
+    assert_eq!(plan.commands[0].current, "");
+    assert!(runs_command("uda.taskwarrior-tui.secret.decrypt"));
+    assert!(!runs_command("uda.taskwarrior-tui.background_process_period"));
+    assert_eq!(plan.layouts, vec![layout("review", true)]);
+    assert_eq!(plan.layout_conflicts[0].name, "triage");
+
+    assert!(Bundle::parse(r#"{"version":1,"settings":{"color":"on"}}"#).is_err());
+  }
+}
@@ -0,0 +1,63 @@
+use chrono::NaiveDateTime;
+use rustyline::line_buffer::LineBuffer;
+
+use crate::{app::MAX_LINE, overrides};
+
+/// A date or duration expression evaluated by `task calc` as it is typed, e.g. `eom - 3d` or
+/// `2wk / 5`.
+pub struct Calculator {
+  pub input: LineBuffer,
+  /// Value of the expression ready to be typed into a prompt, or why there is none
+  pub result: Option<Result<String, String>>,
+}
+
+impl Default for Calculator {
+  fn default() -> Self {
+    Self {
+      input: LineBuffer::with_capacity(MAX_LINE),
+      result: None,
+    }
+  }
+}
+
+impl Calculator {
+  pub fn evaluate(&mut self) {
+    let expression = self.input.as_str().trim();
+    if expression.is_empty() {
+      self.result = None;
+      return;
+    }
+    let output = overrides::task()
+      .arg("rc.date.iso=yes")
+      .arg("rc.verbose=nothing")
+      .arg("calc")
+      .arg(expression)
+      .output();
+    self.result = Some(match output {
+      Ok(o) if o.status.success() => Ok(format_value(String::from_utf8_lossy(&o.stdout).trim())),
+      Ok(o) => Err(String::from_utf8_lossy(&o.stderr).trim().to_string()),
+      Err(e) => Err(e.to_string()),
+    });
+  }
+}
+
+/// The output of `task calc` as taskwarrior reads it back, dates at midnight without their time.
+pub fn format_value(output: &str) -> String {
+  match NaiveDateTime::parse_from_str(output, "%Y-%m-%dT%H:%M:%S") {
+    Ok(date) if date.time() == chrono::NaiveTime::MIN => date.format("%Y-%m-%d").to_string(),
+    _ => output.to_string(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_format_value() {
+    assert_eq!(format_value("2024-01-31T00:00:00"), "2024-01-31");
+    assert_eq!(format_value("2024-01-31T17:30:00"), "2024-01-31T17:30:00");
+    assert_eq!(format_value("P2W"), "P2W");
+    assert_eq!(format_value("true"), "true");
+  }
+}
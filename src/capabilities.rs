@@ -0,0 +1,157 @@
+use std::{
+  env,
+  io::{self, IsTerminal},
+  sync::atomic::{AtomicBool, Ordering},
+};
+
+use crossterm::{
+  event::{KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags},
+  execute,
+};
+
+use log::info;
+use ratatui::style::{Color, Style};
+
+/// What the terminal is able to show, detected at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+  /// 24-bit colors, otherwise RGB colors are replaced by the closest of the 256 indexed colors
+  pub truecolor: bool,
+  /// Symbols outside of ASCII, otherwise indicators fall back to ASCII and borders are not rounded
+  pub unicode: bool,
+  /// The kitty keyboard protocol, to tell apart keys that are otherwise sent identically
+  pub kitty_keyboard: bool,
+}
+
+impl Default for Capabilities {
+  fn default() -> Self {
+    Self {
+      truecolor: true,
+      unicode: true,
+      kitty_keyboard: false,
+    }
+  }
+}
+
+fn env_contains(vars: &[&str], needles: &[&str]) -> bool {
+  vars.iter().filter_map(|v| env::var(v).ok()).any(|value| {
+    let value = value.to_lowercase();
+    needles.iter().any(|n| value.contains(n))
+  })
+}
+
+fn detect_truecolor() -> bool {
+  env_contains(&["COLORTERM"], &["truecolor", "24bit"]) || env_contains(&["TERM"], &["direct", "kitty", "alacritty", "wezterm"])
+}
+
+/// The first of `LC_ALL`, `LC_CTYPE` and `LANG` that is set decides the character set.
+fn detect_unicode() -> bool {
+  if env::var("TERM").map(|t| t == "linux" || t == "dumb").unwrap_or(false) {
+    return false;
+  }
+  ["LC_ALL", "LC_CTYPE", "LANG"]
+    .iter()
+    .filter_map(|v| env::var(v).ok())
+    .find(|v| !v.is_empty())
+    .map(|v| {
+      let v = v.to_lowercase();
+      v.contains("utf-8") || v.contains("utf8")
+    })
+    .unwrap_or(cfg!(windows))
+}
+
+fn detect_kitty_keyboard() -> bool {
+  std::io::stdout().is_terminal() && crossterm::terminal::supports_keyboard_enhancement().unwrap_or(false)
+}
+
+impl Capabilities {
+  /// Detect the capabilities that are not given in `overrides` (truecolor, unicode, kitty keyboard).
+  pub fn detect(overrides: (Option<bool>, Option<bool>, Option<bool>)) -> Self {
+    let (truecolor, unicode, kitty_keyboard) = overrides;
+    let capabilities = Self {
+      truecolor: truecolor.unwrap_or_else(detect_truecolor),
+      unicode: unicode.unwrap_or_else(detect_unicode),
+      kitty_keyboard: kitty_keyboard.unwrap_or_else(detect_kitty_keyboard),
+    };
+    info!("Terminal capabilities: {:?}", capabilities);
+    capabilities
+  }
+}
+
+/// Whether the keyboard enhancement flags were pushed and have to be popped when leaving.
+static KITTY_KEYBOARD_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Ask the terminal to report keys unambiguously, so that e.g. `ctrl-shift-a`, `ctrl-enter` and the
+/// keypad keys can be bound.
+pub fn enable_kitty_keyboard() -> io::Result<()> {
+  execute!(
+    io::stdout(),
+    PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+  )?;
+  KITTY_KEYBOARD_ENABLED.store(true, Ordering::SeqCst);
+  Ok(())
+}
+
+pub fn disable_kitty_keyboard() -> io::Result<()> {
+  if KITTY_KEYBOARD_ENABLED.swap(false, Ordering::SeqCst) {
+    execute!(io::stdout(), PopKeyboardEnhancementFlags)?;
+  }
+  Ok(())
+}
+
+/// Closest color of the 256 indexed colors, from the 6x6x6 cube or the gray ramp.
+pub fn to_indexed(color: Color) -> Color {
+  let (r, g, b) = match color {
+    Color::Rgb(r, g, b) => (r, g, b),
+    c => return c,
+  };
+  let levels = [0u8, 95, 135, 175, 215, 255];
+  let level = |v: u8| (0..levels.len()).min_by_key(|&i| (levels[i] as i32 - v as i32).abs()).unwrap_or(0);
+  let (ri, gi, bi) = (level(r), level(g), level(b));
+  let cube = (levels[ri], levels[gi], levels[bi]);
+  let average = (r as u32 + g as u32 + b as u32) / 3;
+  let gray_index = ((average.saturating_sub(8)) / 10).min(23) as u8;
+  let gray = 8 + gray_index * 10;
+  let distance = |(cr, cg, cb): (u8, u8, u8)| {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+    d(cr, r) + d(cg, g) + d(cb, b)
+  };
+  if distance((gray, gray, gray)) < distance(cube) {
+    Color::Indexed(232 + gray_index)
+  } else {
+    Color::Indexed(16 + 36 * ri as u8 + 6 * gi as u8 + bi as u8)
+  }
+}
+
+pub fn style_to_indexed(style: Style) -> Style {
+  Style {
+    fg: style.fg.map(to_indexed),
+    bg: style.bg.map(to_indexed),
+    ..style
+  }
+}
+
+/// ASCII replacement for an indicator, keeping its width.
+pub fn ascii_indicator(indicator: &str, fallback: &str) -> String {
+  if indicator.is_ascii() {
+    indicator.to_string()
+  } else {
+    let padding = indicator.chars().count().saturating_sub(fallback.chars().count());
+    format!("{}{}", fallback, " ".repeat(padding))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_to_indexed() {
+    assert_eq!(to_indexed(Color::Rgb(0, 0, 0)), Color::Indexed(16));
+    assert_eq!(to_indexed(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+    assert_eq!(to_indexed(Color::Rgb(223, 223, 223)), Color::Indexed(253));
+    assert_eq!(to_indexed(Color::Indexed(4)), Color::Indexed(4));
+    assert_eq!(ascii_indicator("\u{2022} ", "*"), "* ");
+    assert_eq!(ascii_indicator("> ", "*"), "> ");
+  }
+}
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, Local, NaiveDate, TimeZone};
+use task_hookrs::task::Task;
+
+/// Days shown from the date being set, to pick a less loaded one.
+pub const DAYS: i64 = 7;
+
+/// Number of tasks due on each day, in the local timezone.
+#[derive(Debug, Clone, Default)]
+pub struct DueLoad {
+  counts: HashMap<NaiveDate, usize>,
+}
+
+impl DueLoad {
+  pub fn new(tasks: &[Task]) -> Self {
+    Self::from_days(tasks.iter().filter_map(|t| t.due()).map(|d| Local.from_utc_datetime(d).date_naive()))
+  }
+
+  pub fn from_days(days: impl Iterator<Item = NaiveDate>) -> Self {
+    let mut counts = HashMap::new();
+    for day in days {
+      *counts.entry(day).or_default() += 1;
+    }
+    Self { counts }
+  }
+
+  pub fn count(&self, day: NaiveDate) -> usize {
+    self.counts.get(&day).copied().unwrap_or_default()
+  }
+
+  /// `day` and the days after it, with the number of tasks due on each.
+  pub fn days_from(&self, day: NaiveDate) -> Vec<(NaiveDate, usize)> {
+    (0..DAYS).map(|i| day + Duration::days(i)).map(|d| (d, self.count(d))).collect()
+  }
+
+  /// First day from `day` on with fewer than `capacity` tasks due.
+  pub fn next_free(&self, day: NaiveDate, capacity: usize) -> NaiveDate {
+    let mut day = day;
+    while self.count(day) >= capacity.max(1) {
+      day += Duration::days(1);
+    }
+    day
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_due_load() {
+    let day = |d| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+    let load = DueLoad::from_days(vec![day(1), day(1), day(2), day(3), day(3)].into_iter());
+    assert_eq!(load.count(day(1)), 2);
+    assert_eq!(load.days_from(day(2))[..3], [(day(2), 1), (day(3), 2), (day(4), 0)]);
+    assert_eq!(load.next_free(day(1), 1), day(4));
+    assert_eq!(load.next_free(day(1), 2), day(2));
+    assert_eq!(load.next_free(day(5), 0), day(5));
+  }
+}
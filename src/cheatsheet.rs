@@ -0,0 +1,135 @@
+use std::{fmt::Write, path::Path};
+
+use crate::keyconfig::KeyConfig;
+
+const SETTING_PREFIX: &str = "uda.taskwarrior-tui.";
+
+/// Layout of the cheatsheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+  Markdown,
+  Text,
+}
+
+impl Format {
+  /// Format given with `--format`, or guessed from the extension of the output file.
+  pub fn new(format: Option<&str>, path: Option<&Path>) -> Self {
+    match format {
+      Some("markdown") | Some("md") => Format::Markdown,
+      Some(_) => Format::Text,
+      None => match path.and_then(|p| p.extension()).and_then(|e| e.to_str()) {
+        Some("txt") => Format::Text,
+        _ => Format::Markdown,
+      },
+    }
+  }
+}
+
+/// The `uda.taskwarrior-tui.*` settings set in the taskrc, from the output of `task show`, without
+/// the key bindings which are listed separately.
+pub fn settings(data: &str) -> Vec<(String, String)> {
+  let mut settings: Vec<(String, String)> = vec![];
+  let mut continued = false;
+  for line in data.lines() {
+    if line.starts_with(SETTING_PREFIX) {
+      let (name, value) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+      continued = !name.contains(".keyconfig.") || name.ends_with(".chord-timeout") || name.contains(".keyconfig.remap.");
+      if continued {
+        settings.push((name.to_string(), value.trim().to_string()));
+      }
+    } else if continued && line.starts_with("   ") && !line.trim().is_empty() {
+      // long values are wrapped over the following lines
+      if let Some((_, value)) = settings.last_mut() {
+        value.push(' ');
+        value.push_str(line.trim());
+      }
+    } else {
+      continued = false;
+    }
+  }
+  settings
+}
+
+/// The effective key bindings, sorted by name, with the keys as they are written in the key config.
+pub fn bindings(keyconfig: &KeyConfig) -> Vec<(String, String)> {
+  let mut bindings: Vec<(String, String)> = keyconfig
+    .bindings()
+    .into_iter()
+    .map(|(name, key)| (name, keyconfig.key_label(key)))
+    .filter(|(_, key)| !key.is_empty() && key != "null")
+    .collect();
+  bindings.sort();
+  bindings
+}
+
+fn table(out: &mut String, format: Format, headers: (&str, &str), rows: &[(String, String)]) {
+  match format {
+    Format::Markdown => {
+      writeln!(out, "| {} | {} |", headers.0, headers.1).ok();
+      writeln!(out, "| --- | --- |").ok();
+      for (name, value) in rows {
+        let value = if value.is_empty() {
+          String::new()
+        } else {
+          format!("`{}`", value.replace('|', "\\|"))
+        };
+        writeln!(out, "| {} | {} |", name, value).ok();
+      }
+    }
+    Format::Text => {
+      let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0).max(headers.0.len());
+      writeln!(out, "{:width$}  {}", headers.0, headers.1, width = width).ok();
+      for (name, value) in rows {
+        writeln!(out, "{:width$}  {}", name, value, width = width).ok();
+      }
+    }
+  }
+}
+
+/// The cheatsheet with the key bindings and the settings.
+pub fn render(format: Format, bindings: &[(String, String)], settings: &[(String, String)]) -> String {
+  let mut out = String::new();
+  let heading = |out: &mut String, title: &str, level: usize| match format {
+    Format::Markdown => {
+      writeln!(out, "{} {}\n", "#".repeat(level), title).ok();
+    }
+    Format::Text => {
+      let underline = if level == 1 { "=" } else { "-" };
+      writeln!(out, "{}\n{}\n", title, underline.repeat(title.chars().count())).ok();
+    }
+  };
+  heading(&mut out, "taskwarrior-tui cheatsheet", 1);
+  heading(&mut out, "Keys", 2);
+  table(&mut out, format, ("Binding", "Key"), bindings);
+  out.push('\n');
+  heading(&mut out, "Settings", 2);
+  if settings.is_empty() {
+    writeln!(out, "All settings have their default values.").ok();
+  } else {
+    table(&mut out, format, ("Setting", "Value"), settings);
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_cheatsheet() {
+    let data = "uda.taskwarrior-tui.keyconfig.done  D\nuda.taskwarrior-tui.style.context.active  black on\n    rgb444\nweekstart  monday\n";
+    let settings = settings(data);
+    assert_eq!(
+      settings,
+      vec![("uda.taskwarrior-tui.style.context.active".to_string(), "black on rgb444".to_string())]
+    );
+    let bindings = vec![("done".to_string(), "D".to_string())];
+    let markdown = render(Format::Markdown, &bindings, &settings);
+    assert!(markdown.contains("| done | `D` |"));
+    let text = render(Format::Text, &bindings, &[]);
+    assert!(text.contains("done     D"));
+    assert!(text.contains("All settings have their default values."));
+    assert_eq!(Format::new(None, Some(Path::new("keys.txt"))), Format::Text);
+    assert_eq!(Format::new(None, None), Format::Markdown);
+  }
+}
@@ -0,0 +1,72 @@
+/// Filters shown as chips above the report, each toggled on and off on its own, and added to the
+/// filter of the report while on.
+///
+/// ```plaintext
+/// uda.taskwarrior-tui.chips=+OVERDUE,due:today,+next,project:Work
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Chips {
+  pub filters: Vec<String>,
+  pub active: Vec<bool>,
+}
+
+impl Chips {
+  /// Chips from a comma separated list of filters.
+  pub fn parse(s: &str) -> Self {
+    let filters: Vec<String> = s.split(',').map(str::trim).filter(|f| !f.is_empty()).map(ToString::to_string).collect();
+    Self {
+      active: vec![false; filters.len()],
+      filters,
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.filters.is_empty()
+  }
+
+  pub fn toggle(&mut self, i: usize) {
+    if let Some(active) = self.active.get_mut(i) {
+      *active = !*active;
+    }
+  }
+
+  /// Filter of the chips that are on, each in parentheses, empty when none is.
+  pub fn filter(&self) -> String {
+    self
+      .filters
+      .iter()
+      .zip(&self.active)
+      .filter(|(_, active)| **active)
+      .map(|(f, _)| format!("( {} )", f))
+      .collect::<Vec<_>>()
+      .join(" ")
+  }
+
+  /// Label of the chip `i`, with the number of the `Alt` key toggling it.
+  pub fn label(&self, i: usize) -> String {
+    if i < 9 {
+      format!(" {} {} ", i + 1, self.filters[i])
+    } else {
+      format!(" {} ", self.filters[i])
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_chips() {
+    let mut chips = Chips::parse("+OVERDUE, due:today,,project:Work");
+    assert_eq!(chips.filters.len(), 3);
+    assert_eq!(chips.filter(), "");
+    chips.toggle(0);
+    chips.toggle(2);
+    assert_eq!(chips.filter(), "( +OVERDUE ) ( project:Work )");
+    chips.toggle(0);
+    chips.toggle(7);
+    assert_eq!(chips.filter(), "( project:Work )");
+    assert_eq!(chips.label(1), " 2 due:today ");
+  }
+}
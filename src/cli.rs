@@ -8,6 +8,7 @@ pub fn generate_cli_app() -> clap::Command {
     .version(APP_VERSION)
     .author("Dheepak Krishnamurthy <@kdheepak>")
     .about("A taskwarrior terminal user interface")
+    .disable_help_subcommand(true)
     .arg(
       Arg::new("data")
         .short('d')
@@ -45,6 +46,160 @@ pub fn generate_cli_app() -> clap::Command {
         .value_name("STRING")
         .help("Sets default report")
         .action(clap::ArgAction::Set),
+    )
+    .arg(
+      Arg::new("uuid")
+        .long("uuid")
+        .value_name("LINK")
+        .help("Selects a task on start, given its uuid or a link to it such as `task://<uuid>`")
+        .action(clap::ArgAction::Set),
+    )
+    .arg(
+      Arg::new("context")
+        .long("context")
+        .value_name("CONTEXT")
+        .help("Uses a taskwarrior context while running, `none` for no context, without changing the taskrc")
+        .action(clap::ArgAction::Set),
+    )
+    .arg(
+      Arg::new("trace-perf")
+        .long("trace-perf")
+        .help("Records draw, export and event timings, viewable from the command palette and written to the data folder on exit")
+        .action(clap::ArgAction::SetTrue),
+    )
+    .arg(
+      Arg::new("demo")
+        .long("demo")
+        .help("Runs against a temporary copy of bundled example tasks, leaving your own tasks and settings untouched")
+        .conflicts_with_all(["taskdata", "taskrc", "data"])
+        .action(clap::ArgAction::SetTrue),
+    )
+    .arg(
+      Arg::new("record")
+        .long("record")
+        .value_name("FILE")
+        .help("Records keys, resizes and taskwarrior commands of the session to a file")
+        .action(clap::ArgAction::Set),
+    )
+    .arg(
+      Arg::new("replay")
+        .long("replay")
+        .value_name("FILE")
+        .help("Replays a session recorded with --record, use with --taskdata to replay against a copy of the tasks")
+        .conflicts_with("record")
+        .action(clap::ArgAction::Set),
+    )
+    .arg(
+      Arg::new("filter")
+        .value_name("FILTER")
+        .help("Taskwarrior filter added to the filter of the report, e.g. `project:work +urgent`, and `rc.NAME=VALUE` overrides passed to every `task` command")
+        .num_args(1..)
+        .trailing_var_arg(true)
+        .allow_hyphen_values(true)
+        .action(clap::ArgAction::Append),
+    )
+    .subcommand(
+      clap::Command::new("completions")
+        .about("Prints shell completions, including the reports and contexts of the taskrc")
+        .arg(
+          Arg::new("shell")
+            .value_name("SHELL")
+            .required(true)
+            .help("Shell to print completions for")
+            .value_parser(clap::value_parser!(clap_complete::Shell)),
+        ),
+    )
+    .subcommand(clap::Command::new("man").about("Prints the man page, including the reports and contexts of the taskrc"))
+    .subcommand(
+      clap::Command::new("cheatsheet")
+        .about("Writes the effective key bindings and settings to a cheatsheet, to share a setup")
+        .arg(
+          Arg::new("output")
+            .value_name("FILE")
+            .help("File to write the cheatsheet to, printed when not given"),
+        )
+        .arg(
+          Arg::new("format")
+            .long("format")
+            .value_name("FORMAT")
+            .value_parser(["markdown", "text"])
+            .help("Layout of the cheatsheet, guessed from the extension of the file by default"),
+        ),
+    )
+    .subcommand(
+      clap::Command::new("settings")
+        .about("Exports or imports the settings, key bindings, styles, named filters and layouts as one bundle file")
+        .subcommand_required(true)
+        .subcommand(
+          clap::Command::new("export").about("Writes the bundle of the current settings").arg(
+            Arg::new("output")
+              .value_name("FILE")
+              .help("File to write the bundle to, printed when not given"),
+          ),
+        )
+        .subcommand(
+          clap::Command::new("import")
+            .about("Adds the settings and layouts of a bundle, asking before replacing ones set differently")
+            .arg(
+              Arg::new("input")
+                .value_name("FILE")
+                .required(true)
+                .help("Bundle written by `settings export`"),
+            )
+            .arg(
+              Arg::new("yes")
+                .long("yes")
+                .short('y')
+                .help("Adds new settings and layouts and replaces conflicting ones without asking, except settings running a command")
+                .action(clap::ArgAction::SetTrue),
+            ),
+        ),
+    )
+    .subcommand(
+      clap::Command::new("sample-data")
+        .about("Launches against generated tasks in a temporary data folder, to evaluate performance settings")
+        .hide(true)
+        .arg(
+          Arg::new("tasks")
+            .long("tasks")
+            .short('n')
+            .value_name("N")
+            .default_value("1000")
+            .value_parser(clap::value_parser!(usize))
+            .help("Number of tasks to generate"),
+        )
+        .arg(
+          Arg::new("projects")
+            .long("projects")
+            .value_name("N")
+            .default_value("10")
+            .value_parser(clap::value_parser!(usize))
+            .help("Number of projects the tasks are spread over"),
+        )
+        .arg(
+          Arg::new("tags")
+            .long("tags")
+            .value_name("N")
+            .default_value("20")
+            .value_parser(clap::value_parser!(usize))
+            .help("Number of tags the tasks are tagged with"),
+        )
+        .arg(
+          Arg::new("days")
+            .long("days")
+            .value_name("N")
+            .default_value("90")
+            .value_parser(clap::value_parser!(i64))
+            .help("Entry and due dates are spread over this many days around today"),
+        )
+        .arg(
+          Arg::new("seed")
+            .long("seed")
+            .value_name("N")
+            .default_value("0")
+            .value_parser(clap::value_parser!(u64))
+            .help("Seed of the generator, the same seed gives the same tasks"),
+        ),
     );
 
   app.set_bin_name(APP_NAME);
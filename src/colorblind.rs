@@ -0,0 +1,48 @@
+use ratatui::style::{Color, Modifier, Style};
+
+const ORANGE: Color = Color::Indexed(208);
+const YELLOW: Color = Color::Indexed(220);
+const SKY_BLUE: Color = Color::Indexed(75);
+const BLUE: Color = Color::Indexed(33);
+const GRAY: Color = Color::Indexed(245);
+
+/// Colors of the task states, told apart by people with deuteranopia or protanopia: blue and
+/// orange instead of green and red. Every state also has its own text modifiers, so that no state
+/// is shown by its color alone.
+pub fn palette() -> Vec<(&'static str, Style)> {
+  let style = |fg: Color, modifiers: Modifier| Style::default().fg(fg).add_modifier(modifiers);
+  vec![
+    ("color.overdue", style(ORANGE, Modifier::BOLD | Modifier::REVERSED)),
+    ("color.due.today", style(YELLOW, Modifier::BOLD | Modifier::UNDERLINED)),
+    ("color.due", style(SKY_BLUE, Modifier::UNDERLINED)),
+    ("color.active", style(BLUE, Modifier::BOLD | Modifier::ITALIC)),
+    ("color.scheduled", style(SKY_BLUE, Modifier::ITALIC)),
+    ("color.recurring", style(SKY_BLUE, Modifier::ITALIC | Modifier::UNDERLINED)),
+    ("color.blocking", style(Color::Reset, Modifier::BOLD)),
+    ("color.blocked", style(GRAY, Modifier::DIM)),
+    ("color.completed", style(GRAY, Modifier::DIM | Modifier::CROSSED_OUT)),
+    ("color.deleted", style(GRAY, Modifier::CROSSED_OUT)),
+    ("color.uda.priority.H", style(ORANGE, Modifier::BOLD)),
+    ("color.uda.priority.M", style(YELLOW, Modifier::empty())),
+    ("color.uda.priority.L", style(SKY_BLUE, Modifier::DIM)),
+  ]
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashSet;
+
+  use super::*;
+
+  #[test]
+  fn test_states_have_modifiers() {
+    let states: Vec<Style> = palette()
+      .into_iter()
+      .filter(|(key, _)| !key.starts_with("color.uda.priority."))
+      .map(|(_, style)| style)
+      .collect();
+    assert!(states.iter().all(|s| !s.add_modifier.is_empty()));
+    let modifiers: HashSet<Modifier> = states.iter().map(|s| s.add_modifier).collect();
+    assert_eq!(modifiers.len(), states.len());
+  }
+}
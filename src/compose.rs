@@ -0,0 +1,37 @@
+/// Letters with a precomposed form for each combining mark, in the same order as their composed
+/// forms.
+const COMPOSED: &[(char, &str, &str)] = &[
+  ('\u{300}', "aeiouynAEIOUYN", "àèìòùỳǹÀÈÌÒÙỲǸ"),
+  ('\u{301}', "aeiouycnszAEIOUYCNSZ", "áéíóúýćńśźÁÉÍÓÚÝĆŃŚŹ"),
+  ('\u{302}', "aeiouycszAEIOUYCSZ", "âêîôûŷĉŝẑÂÊÎÔÛŶĈŜẐ"),
+  ('\u{303}', "aeiouynAEIOUYN", "ãẽĩõũỹñÃẼĨÕŨỸÑ"),
+  ('\u{308}', "aeiouyAEIOUY", "äëïöüÿÄËÏÖÜŸ"),
+  ('\u{30a}', "auyAU", "åůẙÅŮ"),
+  ('\u{327}', "ecnsECNS", "ȩçņşȨÇŅŞ"),
+  ('\u{30c}', "aeioucnszAEIOUCNSZ", "ǎěǐǒǔčňšžǍĚǏǑǓČŇŠŽ"),
+];
+
+/// The single character for `base` followed by the combining `mark`, as sent by input methods and
+/// dead keys that produce decomposed text, so that what is typed matches what taskwarrior filters.
+pub fn compose(base: char, mark: char) -> Option<char> {
+  let (_, bases, composed) = COMPOSED.iter().find(|(m, _, _)| *m == mark)?;
+  let i = bases.chars().position(|c| c == base)?;
+  composed.chars().nth(i)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_compose() {
+    assert_eq!(compose('e', '\u{301}'), Some('é'));
+    assert_eq!(compose('N', '\u{303}'), Some('Ñ'));
+    assert_eq!(compose('z', '\u{30c}'), Some('ž'));
+    assert_eq!(compose('x', '\u{301}'), None);
+    assert_eq!(compose('e', 'e'), None);
+    for (_, bases, composed) in COMPOSED {
+      assert_eq!(bases.chars().count(), composed.chars().count());
+    }
+  }
+}
@@ -1,11 +1,51 @@
-use std::{collections::HashMap, error::Error, str};
+use std::{collections::HashMap, error::Error, str, time::Duration};
 
 use anyhow::{Context, Result};
 use ratatui::{
   style::{Color, Modifier, Style},
   symbols::{bar::FULL, line::DOUBLE_VERTICAL},
+  widgets::BorderType,
 };
 
+use crate::capabilities::{ascii_indicator, style_to_indexed, Capabilities};
+use crate::colorblind;
+use crate::cursor::CursorShape;
+use crate::density::Density;
+use crate::focus::Source as FocusSource;
+use crate::notify::{self, NamedFilter};
+use crate::task_report::AutoColumns;
+use crate::ticks::Component;
+use crate::timezone::AlternateZone;
+use crate::wip::{self, WipLimit};
+use crate::workspace::{self, Workspace};
+use crate::{
+  aging::{self, Aging},
+  chips::Chips,
+  goals::{self, Goal},
+  review::{self, Step},
+  rules::{self, Rule},
+  secret::Secret,
+  sort::{self, SortKey},
+  team::Team,
+  update::Source,
+};
+
+/// Colors of tags and projects without a configured color, readable on dark and light backgrounds.
+const ACCENT_COLORS: [Color; 12] = [
+  Color::Indexed(33),
+  Color::Indexed(35),
+  Color::Indexed(37),
+  Color::Indexed(69),
+  Color::Indexed(99),
+  Color::Indexed(130),
+  Color::Indexed(133),
+  Color::Indexed(166),
+  Color::Indexed(170),
+  Color::Indexed(172),
+  Color::Indexed(64),
+  Color::Indexed(160),
+];
+
 trait TaskWarriorBool {
   fn get_bool(&self) -> Option<bool>;
 }
@@ -34,13 +74,25 @@ impl TaskWarriorBool for str {
   }
 }
 
+/// Where the cursor goes after the selected task was completed or deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorAfterRemoval {
+  /// Stay on the same row, whichever task ends up there.
+  Stay,
+  /// Select the task that came after the removed one.
+  Next,
+  /// Select the task that came before the removed one.
+  Previous,
+}
+
 #[derive(Debug)]
 pub struct Uda {
-  label: String,
-  kind: String,
-  values: Option<Vec<String>>,
-  default: Option<String>,
-  urgency: Option<f64>,
+  pub name: String,
+  pub label: String,
+  pub kind: String,
+  pub values: Option<Vec<String>>,
+  pub default: Option<String>,
+  pub urgency: Option<f64>,
 }
 
 #[derive(Debug)]
@@ -56,16 +108,21 @@ pub struct Config {
   pub rule_precedence_color: Vec<String>,
   pub uda_priority_values: Vec<String>,
   pub uda_tick_rate: u64,
+  pub uda_frame_budget: u64,
+  pub uda_tooltip_delay: u64,
   pub uda_auto_insert_double_quotes_on_add: bool,
   pub uda_auto_insert_double_quotes_on_annotate: bool,
   pub uda_auto_insert_double_quotes_on_log: bool,
   pub uda_prefill_task_metadata: bool,
   pub uda_reset_filter_on_esc: bool,
   pub uda_task_detail_prefetch: usize,
+  pub uda_task_detail_prefetch_concurrency: usize,
   pub uda_task_report_use_all_tasks_for_completion: bool,
   pub uda_task_report_show_info: bool,
   pub uda_task_report_looping: bool,
   pub uda_task_report_jump_to_task_on_add: bool,
+  pub uda_task_report_sticky_selection: bool,
+  pub uda_task_report_cursor_after_removal: CursorAfterRemoval,
   pub uda_selection_indicator: String,
   pub uda_mark_indicator: String,
   pub uda_unmark_indicator: String,
@@ -92,11 +149,72 @@ pub struct Config {
   pub uda_background_process: String,
   pub uda_background_process_period: usize,
   pub uda_quick_tag_name: String,
+  pub uda_spellcheck_dictionary: String,
+  pub uda_usage_stats: bool,
+  pub uda_quit_confirm: bool,
+  pub uda_single_instance: bool,
+  pub uda_editor_inline: bool,
+  pub uda_image_preview: String,
+  pub uda_task_report_tag_colors: bool,
+  pub uda_task_report_breadcrumb: bool,
+  pub uda_chord_timeout: u64,
+  pub uda_task_report_auto_columns: AutoColumns,
+  pub uda_workspaces: Vec<Workspace>,
+  pub uda_named_filters: Vec<NamedFilter>,
+  pub uda_rules: Vec<Rule>,
+  pub uda_goals: Vec<Goal>,
+  /// Sorts of reports by report, applied by `taskwarrior-tui` instead of taskwarrior
+  pub uda_sorts: HashMap<String, Vec<SortKey>>,
+  pub uda_review_steps: Vec<Step>,
+  pub uda_ticks: Vec<(Component, Option<Duration>)>,
+  pub uda_status_clock: bool,
+  pub uda_status_next_due: bool,
+  pub uda_cursor_shape: CursorShape,
+  pub uda_density: Density,
+  pub uda_timezone_alternate: Option<AlternateZone>,
+  pub uda_quick_add: bool,
+  pub uda_wip_limits: Vec<WipLimit>,
+  pub uda_wip_limit_confirm: bool,
+  pub uda_focus_source: FocusSource,
+  pub uda_focus_column: bool,
+  pub uda_write_retry_limit: u32,
+  pub uda_color_blind: bool,
+  pub uda_screenshot_dir: String,
+  pub uda_deep_link_format: String,
+  pub uda_start_menu: bool,
+  pub uda_stream_bulk_threshold: usize,
+  pub uda_next_action_tag: String,
+  pub uda_due_capacity: usize,
+  pub uda_aging_column: bool,
+  pub uda_aging: Aging,
+  pub uda_chips: Chips,
+  pub uda_backup_interval: u64,
+  pub uda_backup_keep: usize,
+  pub uda_backup_bulk_threshold: usize,
+  pub uda_backup_dir: String,
+  pub uda_secret: Option<Secret>,
+  pub uda_team: Option<Team>,
+  pub uda_activity_days: i64,
+  pub uda_update_check: bool,
+  pub uda_update_source: Source,
+  /// No network access, even for commands that would need it
+  pub uda_offline: bool,
+  pub uda_notifications: bool,
+  /// Start the tour by itself, unset to start it only on a fresh install
+  pub uda_tour: Option<bool>,
+  /// Per-module levels of the log, e.g. `warn,app=debug`
+  pub uda_log_level: Option<String>,
+  pub uda_terminal_truecolor: Option<bool>,
+  pub uda_terminal_unicode: Option<bool>,
+  pub uda_terminal_kitty_keyboard: Option<bool>,
+  pub border_type: BorderType,
+  pub uda_snippets: HashMap<String, String>,
   pub uda_task_report_prompt_on_undo: bool,
   pub uda_task_report_prompt_on_delete: bool,
   pub uda_task_report_prompt_on_done: bool,
   pub uda_task_report_date_time_vague_more_precise: bool,
   pub uda_context_menu_select_on_move: bool,
+  pub uda_snapshot_diff_show_on_change: bool,
   pub uda: Vec<Uda>,
 }
 
@@ -108,7 +226,11 @@ impl Config {
     let obfuscate = bool_collection.get("obfuscate").copied().unwrap_or(false);
     let print_empty_columns = bool_collection.get("print_empty_columns").copied().unwrap_or(false);
 
-    let color = Self::get_color_collection(data);
+    let uda_color_blind = Self::get_uda_color_blind(data);
+    let mut color = Self::get_color_collection(data);
+    if uda_color_blind {
+      color.extend(colorblind::palette().into_iter().map(|(key, style)| (key.to_string(), style)));
+    }
     let filter = Self::get_filter(data, report)?;
     let filter = if filter.trim_start().trim_end().is_empty() {
       filter
@@ -121,6 +243,8 @@ impl Config {
     let rule_precedence_color = Self::get_rule_precedence_color(data);
     let uda_priority_values = Self::get_uda_priority_values(data);
     let uda_tick_rate = Self::get_uda_tick_rate(data);
+    let uda_frame_budget = Self::get_uda_frame_budget(data);
+    let uda_tooltip_delay = Self::get_uda_tooltip_delay(data);
     let uda_change_focus_rotate = Self::get_uda_change_focus_rotate(data);
     let uda_auto_insert_double_quotes_on_add = Self::get_uda_auto_insert_double_quotes_on_add(data);
     let uda_auto_insert_double_quotes_on_annotate = Self::get_uda_auto_insert_double_quotes_on_annotate(data);
@@ -128,10 +252,13 @@ impl Config {
     let uda_prefill_task_metadata = Self::get_uda_prefill_task_metadata(data);
     let uda_reset_filter_on_esc = Self::get_uda_reset_filter_on_esc(data);
     let uda_task_detail_prefetch = Self::get_uda_task_detail_prefetch(data);
+    let uda_task_detail_prefetch_concurrency = Self::get_uda_task_detail_prefetch_concurrency(data);
     let uda_task_report_use_all_tasks_for_completion = Self::get_uda_task_report_use_all_tasks_for_completion(data);
     let uda_task_report_show_info = Self::get_uda_task_report_show_info(data);
     let uda_task_report_looping = Self::get_uda_task_report_looping(data);
     let uda_task_report_jump_to_task_on_add = Self::get_uda_task_report_jump_to_task_on_add(data);
+    let uda_task_report_sticky_selection = Self::get_uda_task_report_sticky_selection(data);
+    let uda_task_report_cursor_after_removal = Self::get_uda_task_report_cursor_after_removal(data);
     let uda_selection_indicator = Self::get_uda_selection_indicator(data);
     let uda_mark_indicator = Self::get_uda_mark_indicator(data);
     let uda_unmark_indicator = Self::get_uda_unmark_indicator(data);
@@ -168,11 +295,71 @@ impl Config {
       uda_style_report_completion_pane.unwrap_or_else(|| Style::default().fg(Color::Black).bg(Color::Rgb(223, 223, 223)));
     let uda_style_report_completion_pane_highlight = uda_style_report_completion_pane_highlight.unwrap_or(uda_style_report_completion_pane);
     let uda_quick_tag_name = Self::get_uda_quick_tag_name(data);
+    let uda_spellcheck_dictionary = Self::get_uda_spellcheck_dictionary(data);
+    let uda_usage_stats = Self::get_uda_usage_stats(data);
+    let uda_quit_confirm = Self::get_uda_quit_confirm(data);
+    let uda_single_instance = Self::get_uda_single_instance(data);
+    let uda_editor_inline = Self::get_uda_editor_inline(data);
+    let uda_image_preview = Self::get_uda_image_preview(data);
+    let uda_task_report_tag_colors = Self::get_uda_task_report_tag_colors(data);
+    let uda_task_report_breadcrumb = Self::get_uda_task_report_breadcrumb(data);
+    let uda_chord_timeout = Self::get_uda_chord_timeout(data);
+    let uda_task_report_auto_columns = Self::get_uda_task_report_auto_columns(data);
+    let uda_workspaces = workspace::parse(data);
+    let uda_named_filters = notify::parse(data);
+    let uda_rules = rules::parse(data);
+    let uda_goals = goals::parse(data);
+    let uda_sorts = sort::parse(data);
+    let uda_review_steps = review::parse(data);
+    let uda_status_clock = Self::get_uda_status_clock(data);
+    let uda_status_next_due = Self::get_uda_status_next_due(data);
+    let uda_ticks = Self::get_uda_ticks(
+      data,
+      uda_tick_rate,
+      uda_status_clock || uda_status_next_due,
+      uda_named_filters.iter().any(|f| f.notify),
+    );
+    let uda_cursor_shape = Self::get_uda_cursor_shape(data);
+    let uda_density = Self::get_uda_density(data);
+    let uda_timezone_alternate = Self::get_uda_timezone_alternate(data);
+    let uda_quick_add = Self::get_uda_quick_add(data);
+    let uda_wip_limits = wip::parse(data);
+    let uda_wip_limit_confirm = Self::get_uda_wip_limit_confirm(data);
+    let uda_focus_source = Self::get_uda_focus_source(data);
+    let uda_focus_column = Self::get_uda_focus_column(data);
+    let uda_write_retry_limit = Self::get_uda_write_retry_limit(data);
+    let uda_screenshot_dir = Self::get_uda_screenshot_dir(data);
+    let uda_deep_link_format = Self::get_uda_deep_link_format(data);
+    let uda_start_menu = Self::get_uda_start_menu(data);
+    let uda_stream_bulk_threshold = Self::get_uda_stream_bulk_threshold(data);
+    let uda_next_action_tag = Self::get_uda_next_action_tag(data);
+    let uda_due_capacity = Self::get_uda_due_capacity(data);
+    let uda_aging_column = Self::get_uda_aging_column(data);
+    let uda_aging = Self::get_uda_aging(data);
+    let uda_chips = Chips::parse(&Self::get_config("uda.taskwarrior-tui.chips", data).unwrap_or_default());
+    let uda_backup_interval = Self::get_uda_backup_interval(data);
+    let uda_backup_keep = Self::get_uda_backup_keep(data);
+    let uda_backup_bulk_threshold = Self::get_uda_backup_bulk_threshold(data);
+    let uda_backup_dir = Self::get_uda_backup_dir(data);
+    let uda_secret = Self::get_uda_secret(data);
+    let uda_team = Self::get_uda_team(data);
+    let uda_activity_days = Self::get_uda_activity_days(data);
+    let uda_update_check = Self::get_uda_update_check(data);
+    let uda_update_source = Self::get_uda_update_source(data);
+    let uda_offline = Self::get_uda_offline(data);
+    let uda_notifications = Self::get_uda_notifications(data);
+    let uda_tour = Self::get_uda_tour(data);
+    let uda_log_level = Self::get_config("uda.taskwarrior-tui.log.level", data).filter(|s| !s.is_empty());
+    let uda_terminal_truecolor = Self::get_uda_terminal_capability("truecolor", data);
+    let uda_terminal_unicode = Self::get_uda_terminal_capability("unicode", data);
+    let uda_terminal_kitty_keyboard = Self::get_uda_terminal_capability("kitty-keyboard", data);
+    let uda_snippets = Self::get_uda_snippets(data);
     let uda_task_report_prompt_on_undo = Self::get_uda_task_report_prompt_on_undo(data);
     let uda_task_report_prompt_on_delete = Self::get_uda_task_report_prompt_on_delete(data);
     let uda_task_report_prompt_on_done = Self::get_uda_task_report_prompt_on_done(data);
     let uda_context_menu_select_on_move = Self::get_uda_context_menu_select_on_move(data);
     let uda_task_report_date_time_vague_more_precise = Self::get_uda_task_report_date_time_vague_more_precise(data);
+    let uda_snapshot_diff_show_on_change = Self::get_uda_snapshot_diff_show_on_change(data);
 
     Ok(Self {
       enabled,
@@ -186,6 +373,8 @@ impl Config {
       rule_precedence_color,
       uda_priority_values,
       uda_tick_rate,
+      uda_frame_budget,
+      uda_tooltip_delay,
       uda_change_focus_rotate,
       uda_auto_insert_double_quotes_on_add,
       uda_auto_insert_double_quotes_on_annotate,
@@ -193,10 +382,13 @@ impl Config {
       uda_prefill_task_metadata,
       uda_reset_filter_on_esc,
       uda_task_detail_prefetch,
+      uda_task_detail_prefetch_concurrency,
       uda_task_report_use_all_tasks_for_completion,
       uda_task_report_show_info,
       uda_task_report_looping,
       uda_task_report_jump_to_task_on_add,
+      uda_task_report_sticky_selection,
+      uda_task_report_cursor_after_removal,
       uda_selection_indicator,
       uda_mark_indicator,
       uda_unmark_indicator,
@@ -222,15 +414,139 @@ impl Config {
       uda_background_process,
       uda_background_process_period,
       uda_quick_tag_name,
+      uda_spellcheck_dictionary,
+      uda_usage_stats,
+      uda_quit_confirm,
+      uda_single_instance,
+      uda_editor_inline,
+      uda_image_preview,
+      uda_task_report_tag_colors,
+      uda_task_report_breadcrumb,
+      uda_chord_timeout,
+      uda_task_report_auto_columns,
+      uda_workspaces,
+      uda_named_filters,
+      uda_rules,
+      uda_goals,
+      uda_sorts,
+      uda_review_steps,
+      uda_ticks,
+      uda_status_clock,
+      uda_status_next_due,
+      uda_cursor_shape,
+      uda_density,
+      uda_timezone_alternate,
+      uda_quick_add,
+      uda_wip_limits,
+      uda_wip_limit_confirm,
+      uda_focus_source,
+      uda_focus_column,
+      uda_write_retry_limit,
+      uda_color_blind,
+      uda_screenshot_dir,
+      uda_deep_link_format,
+      uda_start_menu,
+      uda_stream_bulk_threshold,
+      uda_next_action_tag,
+      uda_due_capacity,
+      uda_aging_column,
+      uda_aging,
+      uda_chips,
+      uda_backup_interval,
+      uda_backup_keep,
+      uda_backup_bulk_threshold,
+      uda_backup_dir,
+      uda_secret,
+      uda_team,
+      uda_activity_days,
+      uda_update_check,
+      uda_update_source,
+      uda_offline,
+      uda_notifications,
+      uda_tour,
+      uda_log_level,
+      uda_terminal_truecolor,
+      uda_terminal_unicode,
+      uda_terminal_kitty_keyboard,
+      border_type: BorderType::Rounded,
+      uda_snippets,
       uda_task_report_prompt_on_undo,
       uda_task_report_prompt_on_delete,
       uda_task_report_prompt_on_done,
       uda_task_report_date_time_vague_more_precise,
       uda_context_menu_select_on_move,
-      uda: vec![],
+      uda_snapshot_diff_show_on_change,
+      uda: Self::get_udas(data),
     })
   }
 
+  /// Style of a tag in the tags column: `color.tag.<tag>` when it is configured, otherwise a color
+  /// picked from the name of the tag so that a tag always has the same color.
+  pub fn tag_style(&self, tag: &str) -> Style {
+    if let Some(style) = self.color.get(&format!("color.tag.{}", tag)) {
+      return *style;
+    }
+    Style::default().fg(Self::accent_color(tag))
+  }
+
+  /// Accent color of a project: `color.project.<project>` of the project or of the closest parent
+  /// project when configured, otherwise a color picked from the name of the top level project.
+  pub fn project_style(&self, project: &str) -> Style {
+    let mut name = project;
+    loop {
+      if let Some(style) = self.color.get(&format!("color.project.{}", name)) {
+        return *style;
+      }
+      match name.rsplit_once('.') {
+        Some((parent, _)) => name = parent,
+        None => break,
+      }
+    }
+    Style::default().fg(Self::accent_color(name))
+  }
+
+  fn accent_color(tag: &str) -> Color {
+    // FNV-1a, stable across runs and versions unlike the hasher of the standard library
+    let hash = tag.bytes().fold(0x811c_9dc5_u32, |h, b| (h ^ b as u32).wrapping_mul(0x0100_0193));
+    ACCENT_COLORS[hash as usize % ACCENT_COLORS.len()]
+  }
+
+  /// Overrides of the detected terminal capabilities, `auto` or unset leaves them to the detection.
+  pub fn terminal_overrides(&self) -> (Option<bool>, Option<bool>, Option<bool>) {
+    (self.uda_terminal_truecolor, self.uda_terminal_unicode, self.uda_terminal_kitty_keyboard)
+  }
+
+  /// Replace colors and symbols the terminal is unable to show.
+  pub fn apply_capabilities(&mut self, capabilities: &Capabilities) {
+    if !capabilities.truecolor {
+      for style in self.color.values_mut() {
+        *style = style_to_indexed(*style);
+      }
+      for style in [
+        &mut self.uda_style_report_scrollbar,
+        &mut self.uda_style_report_scrollbar_area,
+        &mut self.uda_style_context_active,
+        &mut self.uda_style_report_selection,
+        &mut self.uda_style_calendar_title,
+        &mut self.uda_style_calendar_today,
+        &mut self.uda_style_navbar,
+        &mut self.uda_style_command,
+        &mut self.uda_style_report_completion_pane,
+        &mut self.uda_style_report_completion_pane_highlight,
+      ] {
+        *style = style_to_indexed(*style);
+      }
+    }
+    if !capabilities.unicode {
+      self.uda_selection_indicator = ascii_indicator(&self.uda_selection_indicator, ">");
+      self.uda_mark_indicator = ascii_indicator(&self.uda_mark_indicator, "*");
+      self.uda_unmark_indicator = ascii_indicator(&self.uda_unmark_indicator, " ");
+      self.uda_scrollbar_indicator = ascii_indicator(&self.uda_scrollbar_indicator, "#");
+      self.uda_scrollbar_area = ascii_indicator(&self.uda_scrollbar_area, "|");
+      self.border_type = BorderType::Plain;
+    }
+  }
+
   fn get_bool_collection() -> HashMap<String, bool> {
     HashMap::new()
   }
@@ -256,6 +572,18 @@ impl Config {
     v
   }
 
+  fn get_uda_snippets(data: &str) -> HashMap<String, String> {
+    let mut snippets = HashMap::new();
+    for line in data.split('\n') {
+      if let Some(line) = line.strip_prefix("uda.taskwarrior-tui.snippet.") {
+        if let Some((abbreviation, snippet)) = line.split_once(' ') {
+          snippets.insert(abbreviation.to_string(), snippet.trim().to_string());
+        }
+      }
+    }
+    snippets
+  }
+
   fn get_uda_style(config: &str, data: &str) -> Option<Style> {
     let c = format!("uda.taskwarrior-tui.style.{}", config);
     let s = Self::get_config(&c, data)?;
@@ -470,6 +798,31 @@ impl Config {
     data.split(',').map(ToString::to_string).collect::<Vec<_>>()
   }
 
+  fn get_udas(data: &str) -> Vec<Uda> {
+    let mut udas = vec![];
+    for line in data.split('\n') {
+      let (key, kind) = match line.split_once(' ') {
+        Some((key, kind)) => (key, kind.trim()),
+        None => continue,
+      };
+      let name = match key.strip_prefix("uda.").and_then(|k| k.strip_suffix(".type")) {
+        Some(name) if !name.contains('.') && name != "priority" => name,
+        _ => continue,
+      };
+      udas.push(Uda {
+        name: name.to_string(),
+        label: Self::get_config(&format!("uda.{}.label", name), data).unwrap_or_else(|| name.to_string()),
+        kind: kind.to_string(),
+        values: Self::get_config(&format!("uda.{}.values", name), data)
+          .filter(|v| !v.is_empty())
+          .map(|v| v.split(',').map(ToString::to_string).collect()),
+        default: Self::get_config(&format!("uda.{}.default", name), data),
+        urgency: Self::get_config(&format!("urgency.uda.{}.coefficient", name), data).and_then(|u| u.parse().ok()),
+      });
+    }
+    udas
+  }
+
   fn get_uda_priority_values(data: &str) -> Vec<String> {
     let data = Self::get_config("uda.priority.values", data)
       .context("Unable to parse `task show uda.priority.values`.")
@@ -542,6 +895,255 @@ impl Config {
       .unwrap_or(250)
   }
 
+  /// How often each component is updated, in milliseconds, `0` for only on demand. The clock only
+  /// ticks when one of its status segments is shown.
+  fn get_uda_ticks(data: &str, tick_rate: u64, clock: bool, notify: bool) -> Vec<(Component, Option<Duration>)> {
+    Component::ALL
+      .iter()
+      .filter(|component| match component {
+        Component::Clock => clock,
+        Component::Notify => notify,
+        _ => true,
+      })
+      .map(|component| {
+        let interval = Self::get_config(&format!("uda.taskwarrior-tui.tick.{}", component.name()), data)
+          .and_then(|s| s.parse::<u64>().ok())
+          .unwrap_or_else(|| component.default_interval(tick_rate));
+        (*component, Some(Duration::from_millis(interval)).filter(|d| !d.is_zero()))
+      })
+      .collect()
+  }
+
+  fn get_uda_status_clock(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.status.clock", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
+  /// Show a countdown to the next due task of the report in the status bar.
+  fn get_uda_status_next_due(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.status.next-due", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
+  /// Shape of the terminal cursor in prompts, the terminal's own shape with `default`.
+  fn get_uda_cursor_shape(data: &str) -> CursorShape {
+    Self::get_config("uda.taskwarrior-tui.cursor.shape", data)
+      .and_then(|s| CursorShape::parse(&s))
+      .unwrap_or(CursorShape::Bar)
+  }
+
+  /// How long the mouse rests on a task before its tooltip shows, in milliseconds, `0` to never show it.
+  fn get_uda_tooltip_delay(data: &str) -> u64 {
+    Self::get_config("uda.taskwarrior-tui.tooltip.delay", data)
+      .unwrap_or_default()
+      .parse::<u64>()
+      .unwrap_or(750)
+  }
+
+  fn get_uda_density(data: &str) -> Density {
+    Self::get_config("uda.taskwarrior-tui.density", data)
+      .and_then(|s| Density::parse(&s))
+      .unwrap_or_default()
+  }
+
+  fn get_uda_timezone_alternate(data: &str) -> Option<AlternateZone> {
+    Self::get_config("uda.taskwarrior-tui.timezone.alternate", data).and_then(|s| AlternateZone::parse(&s))
+  }
+
+  /// Parse `#project`, `@tag`, `p:H` and due dates such as `tomorrow 5pm` in the add prompt.
+  fn get_uda_quick_add(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.quick-add", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
+  /// Ask before starting tasks would go over a work in progress limit.
+  fn get_uda_wip_limit_confirm(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.wip-limit.confirm", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
+  /// Where the time spent on tasks is read from, `tui` or `timewarrior`.
+  fn get_uda_focus_source(data: &str) -> FocusSource {
+    Self::get_config("uda.taskwarrior-tui.focus.source", data)
+      .and_then(|s| FocusSource::parse(&s))
+      .unwrap_or_default()
+  }
+
+  /// Add a column with the time spent on each task to the report.
+  fn get_uda_focus_column(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.focus.column", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
+  /// Replace the colors of the task states with a palette safe for red-green color blindness.
+  fn get_uda_color_blind(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.color-blind", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
+  /// Folder of screenshots, the data folder when empty.
+  fn get_uda_screenshot_dir(data: &str) -> String {
+    Self::get_config("uda.taskwarrior-tui.screenshot.dir", data).unwrap_or_default()
+  }
+
+  /// Modifications of at least this many tasks show their output as it is printed, `0` never does.
+  fn get_uda_stream_bulk_threshold(data: &str) -> usize {
+    Self::get_config("uda.taskwarrior-tui.stream.bulk-threshold", data)
+      .and_then(|s| s.parse().ok())
+      .unwrap_or(50)
+  }
+
+  /// Tag of the tasks that are next actions, any ready task is one when empty.
+  fn get_uda_next_action_tag(data: &str) -> String {
+    Self::get_config("uda.taskwarrior-tui.next-action.tag", data).unwrap_or_default()
+  }
+
+  /// A day with fewer tasks due than this is free when suggesting a due date.
+  fn get_uda_due_capacity(data: &str) -> usize {
+    Self::get_config("uda.taskwarrior-tui.due.capacity", data)
+      .and_then(|s| s.parse().ok())
+      .unwrap_or(1)
+  }
+
+  /// Add a column with the age of each task, colored from new to old, to the report.
+  fn get_uda_aging_column(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.aging.column", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
+  fn get_uda_aging(data: &str) -> Aging {
+    match Self::get_config("uda.taskwarrior-tui.aging.breakpoints", data) {
+      Some(s) => Aging::new(aging::parse_breakpoints(&s)),
+      None => Aging::default(),
+    }
+  }
+
+  /// Hours between backups of the task data, `0` only backs up on demand.
+  fn get_uda_backup_interval(data: &str) -> u64 {
+    Self::get_config("uda.taskwarrior-tui.backup.interval", data)
+      .and_then(|s| s.parse().ok())
+      .unwrap_or(0)
+  }
+
+  /// Backups kept, the oldest are removed beyond this many, `0` keeps them all.
+  fn get_uda_backup_keep(data: &str) -> usize {
+    Self::get_config("uda.taskwarrior-tui.backup.keep", data)
+      .and_then(|s| s.parse().ok())
+      .unwrap_or(10)
+  }
+
+  /// Back up the task data before changing at least this many tasks at once, `0` never does.
+  fn get_uda_backup_bulk_threshold(data: &str) -> usize {
+    Self::get_config("uda.taskwarrior-tui.backup.bulk-threshold", data)
+      .and_then(|s| s.parse().ok())
+      .unwrap_or(0)
+  }
+
+  /// Folder of the backups, `backups` in the data folder of `taskwarrior-tui` when empty.
+  fn get_uda_backup_dir(data: &str) -> String {
+    Self::get_config("uda.taskwarrior-tui.backup.dir", data).unwrap_or_default()
+  }
+
+  /// UDA holding encrypted text, with the commands encrypting and decrypting it.
+  fn get_uda_secret(data: &str) -> Option<Secret> {
+    let get = |name: &str| Self::get_config(&format!("uda.taskwarrior-tui.secret.{}", name), data).unwrap_or_default();
+    Secret::parse(&get("uda"), &get("encrypt"), &get("decrypt"))
+  }
+
+  /// UDA holding who each task is assigned to, with the users to pick from and the current one.
+  fn get_uda_team(data: &str) -> Option<Team> {
+    let get = |name: &str| Self::get_config(&format!("uda.taskwarrior-tui.assignee.{}", name), data);
+    let me = get("me").unwrap_or_else(|| std::env::var("USER").unwrap_or_default());
+    Team::parse(&get("uda").unwrap_or_default(), &get("users").unwrap_or_default(), &me)
+  }
+
+  /// Days of changes listed in the activity feed.
+  fn get_uda_activity_days(data: &str) -> i64 {
+    Self::get_config("uda.taskwarrior-tui.activity.days", data)
+      .and_then(|s| s.parse().ok())
+      .unwrap_or(7)
+  }
+
+  /// Look up once a day whether a newer version was released.
+  fn get_uda_update_check(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.update.check", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
+  /// Where the latest version is looked up, `github` or `crates.io`.
+  fn get_uda_update_source(data: &str) -> Source {
+    Self::get_config("uda.taskwarrior-tui.update.source", data)
+      .and_then(|s| Source::parse(&s))
+      .unwrap_or(Source::GitHub)
+  }
+
+  /// Builds without the `network` feature are always offline.
+  fn get_uda_offline(data: &str) -> bool {
+    cfg!(not(feature = "network"))
+      || Self::get_config("uda.taskwarrior-tui.offline", data)
+        .unwrap_or_default()
+        .get_bool()
+        .unwrap_or(false)
+  }
+
+  /// Alert when tasks start matching the named filters with `notify`.
+  fn get_uda_notifications(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.notifications", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(true)
+  }
+
+  /// Start the tour the first time `taskwarrior-tui` runs, by default only on a fresh install.
+  fn get_uda_tour(data: &str) -> Option<bool> {
+    Self::get_config("uda.taskwarrior-tui.tour", data).and_then(|v| v.get_bool())
+  }
+
+  /// Show the start menu when `taskwarrior-tui` starts without a report.
+  fn get_uda_start_menu(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.start-menu", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(true)
+  }
+
+  /// Format of the links to tasks copied to the clipboard, `{uuid}` is replaced by the uuid of the task.
+  fn get_uda_deep_link_format(data: &str) -> String {
+    Self::get_config("uda.taskwarrior-tui.deep-link.format", data)
+      .filter(|f| f.contains("{uuid}") || f.contains("{short}"))
+      .unwrap_or_else(|| "task://{uuid}".to_string())
+  }
+
+  /// Attempts at a change to the tasks while the task database is locked, before giving up on it.
+  fn get_uda_write_retry_limit(data: &str) -> u32 {
+    Self::get_config("uda.taskwarrior-tui.write.retry-limit", data)
+      .and_then(|s| s.parse().ok())
+      .unwrap_or(20)
+  }
+
+  fn get_uda_frame_budget(data: &str) -> u64 {
+    Self::get_config("uda.taskwarrior-tui.frame-budget", data)
+      .unwrap_or_default()
+      .parse::<u64>()
+      .unwrap_or(0)
+  }
+
   fn get_uda_task_detail_prefetch(data: &str) -> usize {
     Self::get_config("uda.taskwarrior-tui.task-report.task-detail-prefetch", data)
       .unwrap_or_default()
@@ -549,6 +1151,13 @@ impl Config {
       .unwrap_or(10)
   }
 
+  /// Processes fetching the details of tasks in the background at once.
+  fn get_uda_task_detail_prefetch_concurrency(data: &str) -> usize {
+    Self::get_config("uda.taskwarrior-tui.task-report.task-detail-prefetch-concurrency", data)
+      .and_then(|s| s.parse().ok())
+      .unwrap_or(2)
+  }
+
   fn get_uda_task_report_use_all_tasks_for_completion(data: &str) -> bool {
     Self::get_config("uda.taskwarrior-tui.task-report.use-all-tasks-for-completion", data)
       .unwrap_or_default()
@@ -570,6 +1179,24 @@ impl Config {
       .unwrap_or(true)
   }
 
+  fn get_uda_task_report_sticky_selection(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.task-report.sticky-selection", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(true)
+  }
+
+  fn get_uda_task_report_cursor_after_removal(data: &str) -> CursorAfterRemoval {
+    match Self::get_config("uda.taskwarrior-tui.task-report.cursor-after-removal", data)
+      .unwrap_or_default()
+      .as_str()
+    {
+      "next" => CursorAfterRemoval::Next,
+      "previous" => CursorAfterRemoval::Previous,
+      _ => CursorAfterRemoval::Stay,
+    }
+  }
+
   fn get_uda_task_report_looping(data: &str) -> bool {
     Self::get_config("uda.taskwarrior-tui.task-report.looping", data)
       .unwrap_or_default()
@@ -612,6 +1239,13 @@ impl Config {
       .unwrap_or(false)
   }
 
+  fn get_uda_snapshot_diff_show_on_change(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.snapshot-diff.show-on-change", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
   fn get_uda_selection_indicator(data: &str) -> String {
     let indicator = Self::get_config("uda.taskwarrior-tui.selection.indicator", data);
     match indicator {
@@ -713,6 +1347,73 @@ impl Config {
       .unwrap_or(4)
   }
 
+  fn get_uda_spellcheck_dictionary(data: &str) -> String {
+    Self::get_config("uda.taskwarrior-tui.spellcheck.dictionary", data).unwrap_or_default()
+  }
+
+  fn get_uda_usage_stats(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.usage-stats", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
+  fn get_uda_quit_confirm(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.quit.confirm", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
+  fn get_uda_terminal_capability(capability: &str, data: &str) -> Option<bool> {
+    Self::get_config(&format!("uda.taskwarrior-tui.terminal.{}", capability), data).and_then(|v| v.get_bool())
+  }
+
+  fn get_uda_chord_timeout(data: &str) -> u64 {
+    Self::get_config("uda.taskwarrior-tui.keyconfig.chord-timeout", data)
+      .unwrap_or_default()
+      .parse::<u64>()
+      .unwrap_or(500)
+  }
+
+  fn get_uda_task_report_breadcrumb(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.task-report.breadcrumb", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(true)
+  }
+
+  fn get_uda_task_report_auto_columns(data: &str) -> AutoColumns {
+    Self::get_config("uda.taskwarrior-tui.task-report.auto-columns", data)
+      .map(|s| AutoColumns::parse(&s))
+      .unwrap_or_default()
+  }
+
+  fn get_uda_task_report_tag_colors(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.task-report.tag-colors", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
+  fn get_uda_image_preview(data: &str) -> String {
+    Self::get_config("uda.taskwarrior-tui.image-preview", data).unwrap_or_else(|| "auto".to_string())
+  }
+
+  fn get_uda_editor_inline(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.editor.inline", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
+  fn get_uda_single_instance(data: &str) -> bool {
+    Self::get_config("uda.taskwarrior-tui.single-instance", data)
+      .unwrap_or_default()
+      .get_bool()
+      .unwrap_or(false)
+  }
+
   fn get_uda_quick_tag_name(data: &str) -> String {
     let tag_name = Self::get_config("uda.taskwarrior-tui.quick-tag.name", data);
     match tag_name {
@@ -726,6 +1427,17 @@ impl Config {
 mod tests {
   use super::*;
 
+  #[test]
+  fn test_accent_color() {
+    assert_eq!(Config::accent_color("work"), Config::accent_color("work"));
+    assert!(ACCENT_COLORS.contains(&Config::accent_color("home")));
+    let colors: std::collections::HashSet<Color> = ["work", "home", "errand", "call", "email"]
+      .iter()
+      .map(|t| Config::accent_color(t))
+      .collect();
+    assert!(colors.len() > 1);
+  }
+
   #[test]
   fn test_colors() {
     let c = Config::get_tcolor("red on blue");
@@ -880,4 +1592,26 @@ mod tests {
     );
     assert_eq!(config.unwrap(), "filter and test");
   }
+
+  #[test]
+  fn test_get_uda_snippets() {
+    let snippets =
+      Config::get_uda_snippets("uda.taskwarrior-tui.snippet.;mtg meeting with\nuda.taskwarrior-tui.snippet.;re   review of\nreport.test.filter x");
+    assert_eq!(snippets.len(), 2);
+    assert_eq!(snippets[";mtg"], "meeting with");
+    assert_eq!(snippets[";re"], "review of");
+  }
+
+  #[test]
+  fn test_get_udas() {
+    let udas =
+      Config::get_udas("uda.priority.type string\nuda.size.label Size\nuda.size.type string\nuda.size.values S,M,L\nuda.estimate.type duration");
+    assert_eq!(udas.len(), 2);
+    assert_eq!(udas[0].name, "size");
+    assert_eq!(udas[0].label, "Size");
+    assert_eq!(udas[0].values, Some(vec!["S".to_string(), "M".to_string(), "L".to_string()]));
+    assert_eq!(udas[1].name, "estimate");
+    assert_eq!(udas[1].kind, "duration");
+    assert_eq!(udas[1].values, None);
+  }
 }
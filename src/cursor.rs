@@ -0,0 +1,100 @@
+use std::{cell::Cell, io};
+
+use crossterm::{cursor::SetCursorStyle, execute};
+use ratatui::{backend::Backend, Frame};
+
+/// Shape of the terminal cursor while a prompt has focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorShape {
+  /// Whatever the terminal is configured with
+  Default,
+  Block,
+  BlinkingBlock,
+  Underline,
+  BlinkingUnderline,
+  Bar,
+  BlinkingBar,
+}
+
+impl CursorShape {
+  /// Parse the `uda.taskwarrior-tui.cursor.shape` setting, e.g. `bar` or `blinking-block`.
+  pub fn parse(s: &str) -> Option<Self> {
+    let shape = match s.trim() {
+      "default" => CursorShape::Default,
+      "block" => CursorShape::Block,
+      "blinking-block" => CursorShape::BlinkingBlock,
+      "underline" => CursorShape::Underline,
+      "blinking-underline" => CursorShape::BlinkingUnderline,
+      "bar" => CursorShape::Bar,
+      "blinking-bar" => CursorShape::BlinkingBar,
+      _ => return None,
+    };
+    Some(shape)
+  }
+
+  fn style(self) -> SetCursorStyle {
+    match self {
+      CursorShape::Default => SetCursorStyle::DefaultUserShape,
+      CursorShape::Block => SetCursorStyle::SteadyBlock,
+      CursorShape::BlinkingBlock => SetCursorStyle::BlinkingBlock,
+      CursorShape::Underline => SetCursorStyle::SteadyUnderScore,
+      CursorShape::BlinkingUnderline => SetCursorStyle::BlinkingUnderScore,
+      CursorShape::Bar => SetCursorStyle::SteadyBar,
+      CursorShape::BlinkingBar => SetCursorStyle::BlinkingBar,
+    }
+  }
+}
+
+/// The terminal cursor, shown at the edit point of the prompt that has focus and hidden otherwise,
+/// so that input methods and screen readers follow the text being typed.
+#[derive(Debug, Default)]
+pub struct PromptCursor {
+  /// Set while drawing a frame by the prompt with focus
+  position: Cell<Option<(u16, u16)>>,
+  /// The prompt shape is applied to the terminal cursor
+  shaped: bool,
+}
+
+impl PromptCursor {
+  /// Show the cursor at `(x, y)` after the frame.
+  pub fn place(&self, f: &mut Frame<impl Backend>, x: u16, y: u16) {
+    f.set_cursor(x, y);
+    self.position.set(Some((x, y)));
+  }
+
+  /// Where the cursor was placed in the frame that was drawn last.
+  pub fn position(&self) -> Option<(u16, u16)> {
+    self.position.get()
+  }
+
+  /// Change the shape of the cursor after a frame when a prompt gained or lost focus.
+  pub fn finish_frame(&mut self, shape: CursorShape) -> io::Result<()> {
+    let focused = self.position.take().is_some();
+    if focused != self.shaped && shape != CursorShape::Default {
+      let style = if focused { shape.style() } else { SetCursorStyle::DefaultUserShape };
+      execute!(io::stdout(), style)?;
+    }
+    self.shaped = focused;
+    Ok(())
+  }
+
+  /// Give the terminal its own cursor shape back, e.g. before running an external command.
+  pub fn reset(&mut self) -> io::Result<()> {
+    if std::mem::take(&mut self.shaped) {
+      execute!(io::stdout(), SetCursorStyle::DefaultUserShape)?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    assert_eq!(CursorShape::parse("bar"), Some(CursorShape::Bar));
+    assert_eq!(CursorShape::parse("blinking-underline"), Some(CursorShape::BlinkingUnderline));
+    assert_eq!(CursorShape::parse("beam"), None);
+  }
+}
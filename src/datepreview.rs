@@ -0,0 +1,156 @@
+use std::{
+  collections::HashMap,
+  sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+  },
+  time::Duration,
+};
+
+use chrono::NaiveDateTime;
+use tokio::sync::mpsc;
+
+use crate::{
+  event::{Event, KeyCode},
+  overrides,
+};
+
+/// How long typing has to pause before an expression is resolved.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Date expressions typed in the prompts, e.g. `eom+3d`, resolved by `task calc` in the background
+/// once typing pauses.
+pub struct DatePreviews {
+  resolved: HashMap<String, String>,
+  /// Expression requested last, the only one worth resolving
+  latest: Option<String>,
+  /// Bumped on each request, so that a request still waiting out the pause can tell it was replaced
+  requests: Arc<AtomicU64>,
+  /// Bumped when the resolved values are forgotten, those still being resolved are then dropped
+  generation: u64,
+  tx: mpsc::UnboundedSender<(u64, String, String)>,
+  rx: mpsc::UnboundedReceiver<(u64, String, String)>,
+}
+
+impl DatePreviews {
+  pub fn new() -> Self {
+    let (tx, rx) = mpsc::unbounded_channel();
+    Self {
+      resolved: HashMap::new(),
+      latest: None,
+      requests: Arc::new(AtomicU64::new(0)),
+      generation: 0,
+      tx,
+      rx,
+    }
+  }
+
+  /// Value of `expression` if it was resolved already.
+  pub fn get(&self, expression: &str) -> Option<&String> {
+    self.resolved.get(expression)
+  }
+
+  /// Resolve `expression` once no other is requested for a while, sending `Event::Tick` on `wake`
+  /// once done.
+  pub fn request(&mut self, expression: &str, wake: &mpsc::UnboundedSender<Event<KeyCode>>) {
+    if self.resolved.contains_key(expression) || self.latest.as_deref() == Some(expression) {
+      return;
+    }
+    self.latest = Some(expression.to_string());
+    let request = self.requests.fetch_add(1, Ordering::SeqCst) + 1;
+    let requests = self.requests.clone();
+    let expression = expression.to_string();
+    let generation = self.generation;
+    let tx = self.tx.clone();
+    let wake = wake.clone();
+    tokio::spawn(async move {
+      tokio::time::sleep(DEBOUNCE).await;
+      if requests.load(Ordering::SeqCst) != request {
+        return;
+      }
+      let output = overrides::task_async()
+        .arg("rc.date.iso=yes")
+        .arg("rc.verbose=nothing")
+        .arg("calc")
+        .arg(&expression)
+        .output()
+        .await;
+      let resolved = match output {
+        Ok(o) if o.status.success() => format_calc(&String::from_utf8_lossy(&o.stdout)),
+        _ => "invalid date".to_string(),
+      };
+      tx.send((generation, expression, resolved)).ok();
+      wake.send(Event::Tick).ok();
+    });
+  }
+
+  /// Forget the resolved values, relative dates such as `now+1h` move.
+  pub fn clear(&mut self) {
+    self.resolved.clear();
+    self.latest = None;
+    self.generation += 1;
+  }
+
+  /// Keep the values resolved since the last call. Returns whether any was.
+  pub fn poll(&mut self) -> bool {
+    let mut any = false;
+    while let Ok((generation, expression, resolved)) = self.rx.try_recv() {
+      if generation != self.generation {
+        continue;
+      }
+      if self.latest.as_deref() == Some(expression.as_str()) {
+        self.latest = None;
+      }
+      self.resolved.insert(expression, resolved);
+      any = true;
+    }
+    any
+  }
+}
+
+impl Default for DatePreviews {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Output of `task calc` as shown in the preview, e.g. `Thu 2026-10-15 00:00`.
+fn format_calc(output: &str) -> String {
+  match NaiveDateTime::parse_from_str(output.trim(), "%Y-%m-%dT%H:%M:%S") {
+    Ok(date) => date.format("%a %Y-%m-%d %H:%M").to_string(),
+    Err(_) => "invalid date".to_string(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_format_calc() {
+    assert_eq!(format_calc("2026-10-15T00:00:00\n"), "Thu 2026-10-15 00:00");
+    assert_eq!(format_calc("P3D"), "invalid date");
+  }
+
+  #[tokio::test]
+  async fn test_date_previews() {
+    let (wake, _) = mpsc::unbounded_channel();
+    let mut previews = DatePreviews::new();
+    previews.request("eom", &wake);
+    previews.request("eom+1d", &wake);
+    // only the last of the expressions typed in a row is resolved
+    assert_eq!(previews.requests.load(Ordering::SeqCst), 2);
+    previews.request("eom+1d", &wake);
+    assert_eq!(previews.requests.load(Ordering::SeqCst), 2);
+
+    previews.tx.send((0, "eom+1d".to_string(), "Sun 2026-11-01 00:00".to_string())).unwrap();
+    assert!(previews.poll());
+    assert_eq!(previews.get("eom+1d").map(String::as_str), Some("Sun 2026-11-01 00:00"));
+    assert!(previews.latest.is_none());
+
+    previews.clear();
+    assert!(previews.get("eom+1d").is_none());
+    previews.tx.send((0, "eom".to_string(), "stale".to_string())).unwrap();
+    assert!(!previews.poll());
+  }
+}
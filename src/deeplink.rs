@@ -0,0 +1,31 @@
+use regex::Regex;
+use uuid::Uuid;
+
+/// Link to a task, `format` with `{uuid}` replaced by the uuid of the task and `{short}` by its
+/// first eight characters, e.g. `task://{uuid}` or `obsidian://open?vault=notes&file=tasks%2F{uuid}`.
+pub fn format_link(format: &str, uuid: &Uuid) -> String {
+  let uuid = uuid.to_string();
+  format.replace("{uuid}", &uuid).replace("{short}", &uuid[..8])
+}
+
+/// Uuid of the task a link points to, whatever its format, or the uuid itself.
+pub fn parse_link(link: &str) -> Option<Uuid> {
+  let re = Regex::new(r"[a-fA-F0-9]{8}-[a-fA-F0-9]{4}-[a-fA-F0-9]{4}-[a-fA-F0-9]{4}-[a-fA-F0-9]{12}").unwrap();
+  re.find(link).and_then(|m| Uuid::parse_str(m.as_str()).ok())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_links() {
+    let uuid = Uuid::parse_str("6ef1e5b0-a0c0-4f4c-9c4e-3c1a4c2b1f10").unwrap();
+    let link = format_link("task://{uuid}", &uuid);
+    assert_eq!(link, "task://6ef1e5b0-a0c0-4f4c-9c4e-3c1a4c2b1f10");
+    assert_eq!(format_link("[{short}](task://{uuid})", &uuid), format!("[6ef1e5b0]({})", link));
+    assert_eq!(parse_link(&link), Some(uuid));
+    assert_eq!(parse_link("6ef1e5b0-a0c0-4f4c-9c4e-3c1a4c2b1f10"), Some(uuid));
+    assert_eq!(parse_link("task://42"), None);
+  }
+}
@@ -0,0 +1,244 @@
+use std::{
+  env, fs,
+  path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::overrides;
+
+const TASKRC: &str = "# taskrc of the taskwarrior-tui demo
+confirmation=off
+news.version=2.6.0
+uda.priority.values=H,M,,L
+context.work.read=+work or project:work
+context.home.read=project:home
+";
+
+/// (description, project, tags, priority, due in days, entered days ago, completed days ago)
+type DemoTask = (
+  &'static str,
+  &'static str,
+  &'static [&'static str],
+  &'static str,
+  Option<i64>,
+  i64,
+  Option<i64>,
+);
+
+const TASKS: [DemoTask; 16] = [
+  (
+    "Prepare slides for the quarterly review",
+    "work.reports",
+    &["meeting"],
+    "H",
+    Some(1),
+    6,
+    None,
+  ),
+  ("Send the budget to finance", "work.reports", &["email"], "M", Some(0), 3, None),
+  ("Review pull request for the login page", "work.dev", &["review"], "H", Some(-1), 2, None),
+  ("Fix flaky integration test", "work.dev", &["bug"], "M", None, 9, None),
+  ("Write release notes", "work.dev", &[], "L", Some(5), 4, None),
+  ("Book a meeting room for the offsite", "work", &["meeting"], "", Some(12), 1, None),
+  ("Renew passport", "home.admin", &["errand"], "H", Some(20), 30, None),
+  ("Pay electricity bill", "home.admin", &["bill"], "M", Some(3), 2, None),
+  ("Buy groceries", "home", &["errand"], "", Some(0), 1, None),
+  ("Call the plumber about the leak", "home", &["phone"], "H", None, 5, None),
+  ("Plan weekend hike", "home.fun", &["outdoor"], "L", Some(4), 3, None),
+  ("Read chapter 4 of the book club novel", "home.fun", &["reading"], "", Some(9), 7, None),
+  ("Learn the taskwarrior-tui keybindings", "", &["next"], "M", None, 0, None),
+  ("Set up backups for the laptop", "home.admin", &[], "M", None, 14, Some(2)),
+  ("Submit expense report", "work.reports", &["email"], "", None, 10, Some(1)),
+  ("Migrate CI to the new runners", "work.dev", &["ci"], "H", None, 20, Some(4)),
+];
+
+fn date(time: DateTime<Utc>) -> String {
+  time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Synthetic tasks with dates relative to `now`, in the format of `task import`.
+pub fn tasks(now: DateTime<Utc>) -> Value {
+  let tasks: Vec<Value> = TASKS
+    .iter()
+    .enumerate()
+    .map(|(i, (description, project, tags, priority, due, entered, completed))| {
+      let mut task = json!({
+        "uuid": Uuid::new_v4().to_string(),
+        "description": description,
+        "entry": date(now - Duration::days(*entered)),
+        "modified": date(now - Duration::days(*entered)),
+        "status": "pending",
+      });
+      if !project.is_empty() {
+        task["project"] = json!(project);
+      }
+      if !tags.is_empty() {
+        task["tags"] = json!(tags);
+      }
+      if !priority.is_empty() {
+        task["priority"] = json!(priority);
+      }
+      if let Some(days) = due {
+        task["due"] = json!(date(now + Duration::days(*days) + Duration::hours(2)));
+      }
+      if let Some(days) = completed {
+        task["status"] = json!("completed");
+        task["end"] = json!(date(now - Duration::days(*days)));
+        task["modified"] = task["end"].clone();
+      }
+      if i == 0 {
+        task["annotations"] = json!([{
+          "entry": date(now - Duration::days(2)),
+          "description": "Use the template from last quarter",
+        }]);
+      }
+      task
+    })
+    .collect();
+  Value::Array(tasks)
+}
+
+/// Most days dates of sample tasks can be spread over, a century on each side of today.
+pub const MAX_SAMPLE_DAYS: i64 = 36500;
+
+/// Shape of the tasks generated by the `sample-data` subcommand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SampleOptions {
+  pub tasks: usize,
+  pub projects: usize,
+  pub tags: usize,
+  /// Dates are spread over this many days before and after today
+  pub days: i64,
+  pub seed: u64,
+}
+
+/// Generate tasks for load testing, the same options always give the same tasks.
+pub fn sample_tasks(options: &SampleOptions, now: DateTime<Utc>) -> Result<Value> {
+  if options.days > MAX_SAMPLE_DAYS {
+    return Err(anyhow!("--days is at most {}, not {}", MAX_SAMPLE_DAYS, options.days));
+  }
+  let mut rng = StdRng::seed_from_u64(options.seed);
+  let days = options.days.max(1);
+  let words = [
+    "review", "write", "fix", "call", "plan", "buy", "update", "clean", "prepare", "send", "read", "schedule",
+  ];
+  let objects = [
+    "report", "invoice", "slides", "garden", "backup", "tests", "budget", "letter", "notes", "release", "car", "docs",
+  ];
+  let tasks: Vec<Value> = (0..options.tasks)
+    .map(|i| {
+      let entered = rng.gen_range(0..=days);
+      let mut task = json!({
+        "uuid": Uuid::from_u128(rng.gen()).to_string(),
+        "description": format!("{} {} {}", words.choose(&mut rng).unwrap(), objects.choose(&mut rng).unwrap(), i + 1),
+        "entry": date(now - Duration::days(entered)),
+        "modified": date(now - Duration::days(entered)),
+        "status": "pending",
+      });
+      if options.projects > 0 && rng.gen_bool(0.8) {
+        task["project"] = json!(format!("project{}", rng.gen_range(1..=options.projects)));
+      }
+      if options.tags > 0 {
+        let tags: Vec<String> = (0..rng.gen_range(0..=2))
+          .map(|_| format!("tag{}", rng.gen_range(1..=options.tags)))
+          .collect();
+        if !tags.is_empty() {
+          task["tags"] = json!(tags);
+        }
+      }
+      if let Some(priority) = ["H", "M", "L", ""].choose(&mut rng).filter(|p| !p.is_empty()) {
+        task["priority"] = json!(priority);
+      }
+      if rng.gen_bool(0.5) {
+        task["due"] = json!(date(now + Duration::days(rng.gen_range(-days..=days))));
+      }
+      if rng.gen_bool(0.2) {
+        task["status"] = json!("completed");
+        task["end"] = json!(date(now - Duration::days(rng.gen_range(0..=entered))));
+        task["modified"] = task["end"].clone();
+      }
+      task
+    })
+    .collect();
+  Ok(Value::Array(tasks))
+}
+
+/// Create a taskrc and a task database with `tasks` in a temporary directory, and point taskwarrior
+/// and taskwarrior-tui to it so that the user's tasks and settings are never touched.
+pub fn setup(tasks: &Value) -> Result<PathBuf> {
+  let dir = env::temp_dir().join(format!("taskwarrior-tui-demo-{}", std::process::id()));
+  let taskdata = dir.join(".task");
+  fs::create_dir_all(&taskdata).with_context(|| format!("Unable to create {:?}", taskdata))?;
+  let taskrc = dir.join("taskrc");
+  fs::write(&taskrc, format!("{}data.location={}\n", TASKRC, taskdata.display()))?;
+  let import = dir.join("tasks.json");
+  fs::write(&import, serde_json::to_string_pretty(tasks)?)?;
+
+  env::set_var("TASKRC", &taskrc);
+  env::set_var("TASKDATA", &taskdata);
+  env::set_var("TASKWARRIOR_TUI_DATA", dir.join("taskwarrior-tui"));
+
+  let output = overrides::task()
+    .arg("rc.verbose=nothing")
+    .arg("import")
+    .arg(&import)
+    .output()
+    .context("Unable to run `task import` for the demo tasks")?;
+  if !output.status.success() {
+    return Err(anyhow!(
+      "Unable to import the demo tasks: {}",
+      String::from_utf8_lossy(&output.stderr).trim()
+    ));
+  }
+  Ok(dir)
+}
+
+pub fn teardown(dir: &Path) -> Result<()> {
+  fs::remove_dir_all(dir)?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_tasks() {
+    let data = serde_json::to_string(&tasks(Utc::now())).unwrap();
+    let tasks: Vec<task_hookrs::task::Task> = task_hookrs::import::import(data.as_bytes()).unwrap();
+    assert_eq!(tasks.len(), TASKS.len());
+    assert_eq!(
+      tasks.iter().filter(|t| *t.status() == task_hookrs::status::TaskStatus::Pending).count(),
+      13
+    );
+    assert_eq!(tasks[0].annotations().map(|a| a.len()), Some(1));
+  }
+
+  #[test]
+  fn test_sample_tasks() {
+    let options = SampleOptions {
+      tasks: 200,
+      projects: 3,
+      tags: 5,
+      days: 10,
+      seed: 1,
+    };
+    let now = Utc::now();
+    let data = sample_tasks(&options, now).unwrap();
+    assert_eq!(data, sample_tasks(&options, now).unwrap());
+    let tasks: Vec<task_hookrs::task::Task> = task_hookrs::import::import(data.to_string().as_bytes()).unwrap();
+    assert_eq!(tasks.len(), 200);
+    assert!(tasks
+      .iter()
+      .filter_map(|t| t.project())
+      .all(|p| ["project1", "project2", "project3"].contains(&p.as_str())));
+
+    let options = SampleOptions { days: i64::MAX, ..options };
+    assert!(sample_tasks(&options, now).is_err());
+  }
+}
@@ -0,0 +1,74 @@
+/// How tightly the task report is laid out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Density {
+  /// No gap under the header and no breadcrumb, to fit as many tasks as possible
+  Compact,
+  #[default]
+  Cozy,
+  /// Wider gaps between columns, and the due date, tags and first annotation on a second line of each task
+  Comfortable,
+}
+
+impl Density {
+  pub const ALL: [Density; 3] = [Density::Compact, Density::Cozy, Density::Comfortable];
+
+  /// Parse the `uda.taskwarrior-tui.density` setting.
+  pub fn parse(s: &str) -> Option<Self> {
+    Self::ALL.iter().copied().find(|d| d.name() == s.trim())
+  }
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      Density::Compact => "compact",
+      Density::Cozy => "cozy",
+      Density::Comfortable => "comfortable",
+    }
+  }
+
+  /// Blank lines between the header and the first task.
+  pub fn header_gap(&self) -> u16 {
+    match self {
+      Density::Compact => 0,
+      Density::Cozy | Density::Comfortable => 1,
+    }
+  }
+
+  /// Lines taken by each task.
+  pub fn row_height(&self) -> u16 {
+    match self {
+      Density::Compact | Density::Cozy => 1,
+      Density::Comfortable => 2,
+    }
+  }
+
+  pub fn column_spacing(&self) -> u16 {
+    match self {
+      Density::Compact | Density::Cozy => 1,
+      Density::Comfortable => 2,
+    }
+  }
+
+  /// The breadcrumb of the projects in the filter is shown above the report.
+  pub fn breadcrumb(&self) -> bool {
+    *self != Density::Compact
+  }
+
+  /// Secondary information of each task is shown on its second line.
+  pub fn details(&self) -> bool {
+    *self == Density::Comfortable
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    assert_eq!(Density::parse("compact"), Some(Density::Compact));
+    assert_eq!(Density::parse(" comfortable"), Some(Density::Comfortable));
+    assert_eq!(Density::parse("tight"), None);
+    assert_eq!(Density::default().row_height(), 1);
+    assert!(Density::Comfortable.details());
+  }
+}
@@ -0,0 +1,158 @@
+use task_hookrs::task::Task;
+use unicode_truncate::UnicodeTruncateStr;
+use uuid::Uuid;
+
+use crate::task_report::format_date_time;
+
+/// Annotations shown at first in the details of a task, and loaded each time the end of those
+/// shown is scrolled past.
+pub const ANNOTATIONS_PAGE: usize = 20;
+/// Characters of a description shown in the details of a task.
+pub const MAX_DESCRIPTION: usize = 4000;
+/// Width a cell of the report is cut at, wider than any terminal.
+pub const MAX_CELL_WIDTH: usize = 512;
+
+/// `s` cut at `MAX_CELL_WIDTH` columns, with an ellipsis when it was.
+pub fn truncate_cell(s: String) -> String {
+  if s.len() <= MAX_CELL_WIDTH {
+    return s;
+  }
+  let (cut, _) = s.unicode_truncate(MAX_CELL_WIDTH - 1);
+  if cut.len() == s.len() {
+    s
+  } else {
+    format!("{}\u{2026}", cut)
+  }
+}
+
+/// Whether the details of `task` are too long to be drawn as `task info` prints them.
+pub fn enormous(task: &Task) -> bool {
+  task.description().len() > MAX_DESCRIPTION || task.annotations().is_some_and(|a| a.len() > ANNOTATIONS_PAGE)
+}
+
+/// `info`, the output of `task <uuid>`, with the description and the annotations of `task`
+/// replaced by the description cut short and the first `shown` annotations, wrapped at `width`.
+/// Returns whether annotations are left.
+pub fn compact(info: &str, task: &Task, shown: usize, width: u16) -> (String, bool) {
+  let lines: Vec<&str> = info.lines().collect();
+  let start = match lines.iter().position(|l| l.starts_with("Description ")) {
+    Some(start) => start,
+    None => return (info.to_string(), false),
+  };
+  // the description and the annotations are on the lines indented after its label
+  let end = lines[start + 1..]
+    .iter()
+    .position(|l| !l.starts_with(' ') || l.trim().is_empty())
+    .map_or(lines.len(), |i| start + 1 + i);
+  let indent = "Description".len() + lines[start]["Description".len()..].chars().take_while(|c| *c == ' ').count();
+  let width = (width as usize).saturating_sub(indent).max(20);
+
+  let mut block = vec![];
+  let description: Vec<char> = task.description().chars().collect();
+  let cut = description.len().min(MAX_DESCRIPTION);
+  for (i, chunk) in description[..cut].chunks(width).enumerate() {
+    let label = if i == 0 { "Description" } else { "" };
+    block.push(format!("{:<indent$}{}", label, chunk.iter().collect::<String>(), indent = indent));
+  }
+  if cut < description.len() {
+    block.push(format!(
+      "{:indent$}\u{2026} {} more characters",
+      "",
+      description.len() - cut,
+      indent = indent
+    ));
+  }
+  let annotations = task.annotations().map(Vec::as_slice).unwrap_or_default();
+  for annotation in annotations.iter().take(shown) {
+    let line = format!("{} {}", format_date_time(**annotation.entry()), annotation.description());
+    let (line, _) = line.unicode_truncate(width.saturating_sub(2));
+    block.push(format!("{:indent$}  {}", "", line, indent = indent));
+  }
+  let left = annotations.len().saturating_sub(shown);
+  if left > 0 {
+    block.push(format!(
+      "{:indent$}  \u{2026} {} more annotations, scroll down to load {} more",
+      "",
+      left,
+      left.min(ANNOTATIONS_PAGE),
+      indent = indent
+    ));
+  }
+
+  let mut compacted: Vec<String> = lines[..start].iter().map(ToString::to_string).collect();
+  compacted.extend(block);
+  compacted.extend(lines[end..].iter().map(ToString::to_string));
+  (compacted.join("\n"), left > 0)
+}
+
+/// How many annotations of the selected task are shown, with its compacted details kept until it
+/// changes.
+#[derive(Debug, Clone, Default)]
+pub struct Pages {
+  uuid: Option<Uuid>,
+  shown: usize,
+  /// Annotations are left to load
+  pub more: bool,
+  /// Details for the task, the number of annotations shown, the width and the length of the output
+  /// of `task info` they were compacted for
+  cache: Option<((Uuid, usize, u16, usize), String)>,
+}
+
+impl Pages {
+  /// Compacted details of `task`, the first page of annotations when another task was shown last.
+  pub fn text(&mut self, info: &str, task: &Task, width: u16) -> &str {
+    if self.uuid != Some(*task.uuid()) {
+      self.uuid = Some(*task.uuid());
+      self.shown = ANNOTATIONS_PAGE;
+    }
+    let key = (*task.uuid(), self.shown, width, info.len());
+    if self.cache.as_ref().map(|(k, _)| *k) != Some(key) {
+      let (text, more) = compact(info, task, self.shown, width);
+      self.more = more;
+      self.cache = Some((key, text));
+    }
+    self.cache.as_ref().map(|(_, text)| text.as_str()).unwrap_or_default()
+  }
+
+  pub fn load_more(&mut self) {
+    self.shown += ANNOTATIONS_PAGE;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  #[test]
+  fn test_compact() {
+    let annotations: Vec<String> = (0..45)
+      .map(|i| format!(r#"{{"entry":"20261001T1000{:02}Z","description":"note {}"}}"#, i, i))
+      .collect();
+    let json = format!(
+      r#"[{{"uuid":"00000000-0000-0000-0000-000000000001","description":"{}","status":"pending","entry":"20261001T090000Z","annotations":[{}]}}]"#,
+      "x".repeat(MAX_DESCRIPTION + 10),
+      annotations.join(",")
+    );
+    let task = import(json.as_bytes()).unwrap().remove(0);
+    assert!(enormous(&task));
+    let info = "\nName          Value\nID            1\nDescription   xxxxxxxx\n                2026-10-01 note 0\n              xxxx\nStatus        Pending\n";
+    let mut pages = Pages::default();
+    let text = pages.text(info, &task, 114).to_string();
+    assert!(pages.more);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[3], format!("Description   {}", "x".repeat(100)));
+    assert_eq!(lines[3 + MAX_DESCRIPTION / 100], "              \u{2026} 10 more characters");
+    assert!(lines.contains(&"                \u{2026} 25 more annotations, scroll down to load 20 more"));
+    assert_eq!(lines.last(), Some(&"Status        Pending"));
+    pages.load_more();
+    pages.load_more();
+    let text = pages.text(info, &task, 114).to_string();
+    assert!(!pages.more);
+    assert_eq!(text.lines().filter(|l| l.contains(" note ")).count(), 45);
+
+    assert_eq!(truncate_cell("short".to_string()), "short");
+    assert_eq!(truncate_cell("y".repeat(1000)).chars().count(), MAX_CELL_WIDTH);
+  }
+}
@@ -0,0 +1,189 @@
+use std::collections::BTreeSet;
+
+use ratatui::{
+  style::{Color, Modifier, Style},
+  text::{Line, Span},
+};
+use serde_json::{Map, Value};
+use task_hookrs::task::Task;
+
+// attributes that change without user intervention and would only add noise to the diff
+const IGNORED_ATTRIBUTES: [&str; 4] = ["id", "modified", "urgency", "uuid"];
+
+/// An attribute of a task that was added, removed or changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeDiff {
+  pub name: String,
+  pub before: Option<String>,
+  pub after: Option<String>,
+}
+
+/// The attributes that differ between two versions of a task, by name.
+pub fn diff(before: &Map<String, Value>, after: &Map<String, Value>) -> Vec<AttributeDiff> {
+  let keys: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+  keys
+    .into_iter()
+    .filter(|k| !IGNORED_ATTRIBUTES.contains(&k.as_str()))
+    .filter(|k| before.get(*k) != after.get(*k))
+    .map(|k| AttributeDiff {
+      name: k.clone(),
+      before: before.get(k).map(format_value),
+      after: after.get(k).map(format_value),
+    })
+    .collect()
+}
+
+fn attributes(task: &Task) -> Map<String, Value> {
+  match serde_json::to_value(task) {
+    Ok(Value::Object(map)) => map,
+    _ => Map::new(),
+  }
+}
+
+pub fn task_diff(before: &Task, after: &Task) -> Vec<AttributeDiff> {
+  diff(&attributes(before), &attributes(after))
+}
+
+fn format_value(value: &Value) -> String {
+  match value {
+    Value::String(s) => s.clone(),
+    Value::Array(values) => values.iter().map(format_value).collect::<Vec<_>>().join(" "),
+    Value::Object(map) => map.get("description").map(format_value).unwrap_or_else(|| value.to_string()),
+    _ => value.to_string(),
+  }
+}
+
+/// What `task <uuid> modify <args>` would change in `task`, with values as they are typed, e.g.
+/// `due:eom` before taskwarrior resolves it. Words that are neither attributes nor tags replace
+/// the description.
+pub fn preview_modify(task: &Task, args: &[String]) -> Vec<AttributeDiff> {
+  let before = attributes(task);
+  let mut after = before.clone();
+  let mut tags: Vec<Value> = before.get("tags").and_then(Value::as_array).cloned().unwrap_or_default();
+  let mut description = vec![];
+  for arg in args {
+    if let Some(tag) = arg.strip_prefix('+') {
+      if !tags.iter().any(|t| t == tag) {
+        tags.push(Value::String(tag.to_string()));
+      }
+    } else if let Some(tag) = arg.strip_prefix('-') {
+      tags.retain(|t| t != tag);
+    } else if let Some((name, value)) = arg.split_once(':').filter(|(n, _)| is_attribute_name(n)) {
+      if value.is_empty() {
+        after.remove(name);
+      } else {
+        after.insert(name.to_string(), Value::String(value.to_string()));
+      }
+    } else {
+      description.push(arg.as_str());
+    }
+  }
+  if !description.is_empty() {
+    after.insert("description".to_string(), Value::String(description.join(" ")));
+  }
+  if tags.is_empty() {
+    after.remove("tags");
+  } else {
+    after.insert("tags".to_string(), Value::Array(tags));
+  }
+  diff(&before, &after)
+}
+
+fn is_attribute_name(name: &str) -> bool {
+  !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+/// Versions of the tasks before and after the last transaction of `undo.data`, the undo log of
+/// taskwarrior 2, i.e. what `task undo` reverts. The last transaction has a `new` line for every
+/// task it touched and an `old` line for those that existed before.
+pub fn last_undo_transaction(data: &str) -> Vec<(Map<String, Value>, Map<String, Value>)> {
+  let transaction = data.trim_end().rsplit("---").find(|t| !t.trim().is_empty()).unwrap_or_default();
+  let mut old = None;
+  let mut changes = vec![];
+  for line in transaction.lines() {
+    if let Some(rest) = line.strip_prefix("old ") {
+      old = Some(parse_ff4(rest));
+    } else if let Some(rest) = line.strip_prefix("new ") {
+      changes.push((old.take().unwrap_or_default(), parse_ff4(rest)));
+    }
+  }
+  changes
+}
+
+/// Attributes of a task in the `[name:"value" ...]` format of the taskwarrior 2 data files.
+fn parse_ff4(line: &str) -> Map<String, Value> {
+  let mut map = Map::new();
+  let line = line.trim().trim_start_matches('[').trim_end_matches(']');
+  let mut rest = line;
+  while let Some((name, value)) = rest.split_once(":\"") {
+    let mut end = None;
+    let mut escaped = false;
+    for (i, c) in value.char_indices() {
+      match c {
+        '\\' if !escaped => escaped = true,
+        '"' if !escaped => {
+          end = Some(i);
+          break;
+        }
+        _ => escaped = false,
+      }
+    }
+    let end = match end {
+      Some(end) => end,
+      None => break,
+    };
+    let text = value[..end].replace("\\\"", "\"").replace("&open;", "[").replace("&close;", "]");
+    map.insert(name.trim().to_string(), Value::String(text));
+    rest = &value[end + 1..];
+  }
+  map
+}
+
+/// Lines of a diff: removed values with `-` and added values with `+`, a changed value has both.
+pub fn lines(diffs: &[AttributeDiff]) -> Vec<Line<'static>> {
+  let removed = Style::default().fg(Color::Red);
+  let added = Style::default().fg(Color::Green);
+  let mut lines = vec![];
+  for d in diffs {
+    if let Some(before) = &d.before {
+      lines.push(Line::from(vec![
+        Span::styled(format!("  - {}: ", d.name), removed.add_modifier(Modifier::BOLD)),
+        Span::styled(before.clone(), removed),
+      ]));
+    }
+    if let Some(after) = &d.after {
+      lines.push(Line::from(vec![
+        Span::styled(format!("  + {}: ", d.name), added.add_modifier(Modifier::BOLD)),
+        Span::styled(after.clone(), added),
+      ]));
+    }
+  }
+  lines
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_diff() {
+    let data = r#"[{"uuid":"6ef1e5b0-a0c0-4f4c-9c4e-3c1a4c2b1f10","description":"Write report","status":"pending","entry":"20240101T000000Z","project":"work","tags":["today","mail"]}]"#;
+    let tasks: Vec<Task> = task_hookrs::import::import(data.as_bytes()).unwrap();
+    let args: Vec<String> = ["project:", "+next", "-mail", "due:eom"].iter().map(ToString::to_string).collect();
+    let diffs = preview_modify(&tasks[0], &args);
+    let names: Vec<&str> = diffs.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, ["due", "project", "tags"]);
+    assert_eq!(diffs[1].after, None);
+    assert_eq!(diffs[2].before.as_deref(), Some("today mail"));
+    assert_eq!(diffs[2].after.as_deref(), Some("today next"));
+    assert_eq!(lines(&diffs).len(), 4);
+
+    let undo = "time 1704067200\nnew [description:\"a\" status:\"pending\"]\n---\ntime 1704070800\nold [description:\"say \\\"hi\\\"\" status:\"pending\"]\nnew [description:\"b &open;x&close;\" status:\"completed\"]\n---\n";
+    let transaction = last_undo_transaction(undo);
+    assert_eq!(transaction.len(), 1);
+    let diffs = diff(&transaction[0].0, &transaction[0].1);
+    assert_eq!(diffs[0].before.as_deref(), Some("say \"hi\""));
+    assert_eq!(diffs[0].after.as_deref(), Some("b [x]"));
+    assert_eq!(diffs[1].name, "status");
+  }
+}
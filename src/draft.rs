@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::action::Action;
+
+/// Text of a prompt that was dismissed before being submitted.
+#[derive(Debug, Clone)]
+pub struct Draft {
+  pub text: String,
+  /// Tasks the prompt applied to, empty for prompts that are not about existing tasks.
+  pub task_uuids: Vec<Uuid>,
+}
+
+/// Drafts of the input prompts, at most one per prompt.
+#[derive(Debug, Default)]
+pub struct Drafts {
+  drafts: HashMap<Action, Draft>,
+  /// Text the open prompts were filled with before any draft was restored
+  prefills: HashMap<Action, String>,
+}
+
+impl Drafts {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Keep `text` as the draft of a prompt being dismissed, unless it is still what the prompt was
+  /// filled with.
+  pub fn save(&mut self, action: Action, task_uuids: Vec<Uuid>, text: &str) {
+    let prefill = self.prefills.remove(&action).unwrap_or_default();
+    // an empty prompt, possibly with the automatically inserted quotes, is not worth keeping
+    if text.trim().is_empty() || text.trim() == r#""""# || text == prefill {
+      self.drafts.remove(&action);
+    } else {
+      self.drafts.insert(
+        action,
+        Draft {
+          text: text.to_string(),
+          task_uuids,
+        },
+      );
+    }
+  }
+
+  /// Remove and return the draft of a prompt being opened with `prefill`, if it was written for the
+  /// same tasks.
+  pub fn take(&mut self, action: Action, task_uuids: &[Uuid], prefill: &str) -> Option<String> {
+    self.prefills.insert(action, prefill.to_string());
+    match self.drafts.get(&action) {
+      Some(draft) if draft.task_uuids == task_uuids => self.drafts.remove(&action).map(|d| d.text),
+      _ => None,
+    }
+  }
+
+  /// Forget the draft of an open prompt, returning the text the prompt was filled with.
+  pub fn discard(&mut self, action: Action) -> String {
+    self.drafts.remove(&action);
+    self.prefills.get(&action).cloned().unwrap_or_default()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_drafts() {
+    let uuids = vec![Uuid::from_u128(1)];
+    let prefill = "Pay rent project:home ";
+    let mut drafts = Drafts::new();
+
+    // dismissing an untouched prompt keeps no draft
+    assert_eq!(drafts.take(Action::Modify, &uuids, prefill), None);
+    drafts.save(Action::Modify, uuids.clone(), prefill);
+    assert_eq!(drafts.take(Action::Modify, &uuids, prefill), None);
+
+    drafts.save(Action::Modify, uuids.clone(), "Pay rent project:flat");
+    assert_eq!(drafts.take(Action::Modify, &[Uuid::from_u128(2)], prefill), None);
+    assert_eq!(drafts.take(Action::Modify, &uuids, prefill).as_deref(), Some("Pay rent project:flat"));
+    // taken drafts are restored only once
+    assert_eq!(drafts.take(Action::Modify, &uuids, prefill), None);
+
+    drafts.save(Action::Modify, uuids.clone(), "Pay rent project:flat");
+    drafts.take(Action::Modify, &uuids, prefill);
+    assert_eq!(drafts.discard(Action::Modify), prefill);
+    drafts.save(Action::Modify, uuids.clone(), prefill);
+    assert_eq!(drafts.take(Action::Modify, &uuids, prefill), None);
+
+    drafts.take(Action::Add, &[], "");
+    drafts.save(Action::Add, vec![], r#""""#);
+    assert_eq!(drafts.take(Action::Add, &[], ""), None);
+    drafts.save(Action::Add, vec![], "Call bank");
+    assert_eq!(drafts.take(Action::Add, &[], "").as_deref(), Some("Call bank"));
+  }
+}
@@ -0,0 +1,223 @@
+use unicode_width::UnicodeWidthChar;
+
+use crate::{compose, event::KeyCode};
+
+/// What the text in the editor is saved as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorTarget {
+  /// Each non-empty line is added as an annotation
+  Annotation,
+  /// Lines are joined with spaces into the description
+  Description,
+  /// Encrypted into the secret UDA
+  Secret,
+}
+
+/// A small multi-line text editor, wrapping words when drawn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEditor {
+  lines: Vec<String>,
+  row: usize,
+  /// Cursor position in characters of the current line
+  col: usize,
+}
+
+impl Default for TextEditor {
+  fn default() -> Self {
+    Self::new("")
+  }
+}
+
+fn byte_index(line: &str, col: usize) -> usize {
+  line.char_indices().nth(col).map_or(line.len(), |(i, _)| i)
+}
+
+impl TextEditor {
+  /// Start editing `text` with the cursor at its end.
+  pub fn new(text: &str) -> Self {
+    let lines: Vec<String> = text.split('\n').map(ToString::to_string).collect();
+    let row = lines.len() - 1;
+    let col = lines[row].chars().count();
+    Self { lines, row, col }
+  }
+
+  pub fn text(&self) -> String {
+    self.lines.join("\n")
+  }
+
+  fn line_len(&self) -> usize {
+    self.lines[self.row].chars().count()
+  }
+
+  pub fn insert(&mut self, c: char) {
+    if c == '\n' {
+      let at = byte_index(&self.lines[self.row], self.col);
+      let rest = self.lines[self.row].split_off(at);
+      self.row += 1;
+      self.lines.insert(self.row, rest);
+      self.col = 0;
+    } else {
+      let at = byte_index(&self.lines[self.row], self.col);
+      let previous = self.lines[self.row][..at].chars().next_back();
+      if let Some(composed) = previous.and_then(|p| compose::compose(p, c)) {
+        self.backspace();
+        self.insert(composed);
+        return;
+      }
+      self.lines[self.row].insert(at, c);
+      self.col += 1;
+    }
+  }
+
+  pub fn backspace(&mut self) {
+    if self.col > 0 {
+      self.col -= 1;
+      let at = byte_index(&self.lines[self.row], self.col);
+      self.lines[self.row].remove(at);
+    } else if self.row > 0 {
+      let line = self.lines.remove(self.row);
+      self.row -= 1;
+      self.col = self.line_len();
+      self.lines[self.row].push_str(&line);
+    }
+  }
+
+  pub fn delete(&mut self) {
+    if self.col < self.line_len() {
+      let at = byte_index(&self.lines[self.row], self.col);
+      self.lines[self.row].remove(at);
+    } else if self.row + 1 < self.lines.len() {
+      let line = self.lines.remove(self.row + 1);
+      self.lines[self.row].push_str(&line);
+    }
+  }
+
+  pub fn left(&mut self) {
+    if self.col > 0 {
+      self.col -= 1;
+    } else if self.row > 0 {
+      self.row -= 1;
+      self.col = self.line_len();
+    }
+  }
+
+  pub fn right(&mut self) {
+    if self.col < self.line_len() {
+      self.col += 1;
+    } else if self.row + 1 < self.lines.len() {
+      self.row += 1;
+      self.col = 0;
+    }
+  }
+
+  pub fn up(&mut self) {
+    if self.row > 0 {
+      self.row -= 1;
+      self.col = self.col.min(self.line_len());
+    }
+  }
+
+  pub fn down(&mut self) {
+    if self.row + 1 < self.lines.len() {
+      self.row += 1;
+      self.col = self.col.min(self.line_len());
+    }
+  }
+
+  /// Edit the text with `input`, returns false for keys the editor does not handle.
+  pub fn handle_input(&mut self, input: KeyCode) -> bool {
+    match input {
+      KeyCode::Char(c) => self.insert(c),
+      KeyCode::ShiftEnter | KeyCode::Keypad('\n') => self.insert('\n'),
+      KeyCode::Keypad(c) => self.insert(c),
+      KeyCode::Backspace => self.backspace(),
+      KeyCode::Delete => self.delete(),
+      KeyCode::Left => self.left(),
+      KeyCode::Right => self.right(),
+      KeyCode::Up => self.up(),
+      KeyCode::Down => self.down(),
+      KeyCode::Home | KeyCode::Ctrl('a') => self.col = 0,
+      KeyCode::End | KeyCode::Ctrl('e') => self.col = self.line_len(),
+      _ => return false,
+    }
+    true
+  }
+
+  /// Lines wrapped at word boundaries to fit in `width` columns, and the position of the cursor in
+  /// them as (column, row).
+  pub fn wrap(&self, width: usize) -> (Vec<String>, (usize, usize)) {
+    let width = width.max(1);
+    let mut wrapped = vec![];
+    let mut cursor = (0, 0);
+    for (row, line) in self.lines.iter().enumerate() {
+      let chars: Vec<char> = line.chars().collect();
+      let mut start = 0;
+      loop {
+        // find the end of the visual line, breaking after the last space that fits
+        let mut end = start;
+        let mut columns = 0;
+        let mut last_space = None;
+        while end < chars.len() {
+          let w = chars[end].width().unwrap_or(0);
+          if columns + w > width {
+            break;
+          }
+          columns += w;
+          if chars[end] == ' ' {
+            last_space = Some(end + 1);
+          }
+          end += 1;
+        }
+        if end < chars.len() {
+          if let Some(space) = last_space.filter(|s| *s > start) {
+            end = space;
+          }
+          end = end.max(start + 1);
+        }
+        let is_last = end >= chars.len();
+        if row == self.row && self.col >= start && (self.col < end || is_last) {
+          let x: usize = chars[start..self.col].iter().map(|c| c.width().unwrap_or(0)).sum();
+          cursor = (x, wrapped.len());
+        }
+        wrapped.push(chars[start..end].iter().collect());
+        if is_last {
+          break;
+        }
+        start = end;
+      }
+    }
+    (wrapped, cursor)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_editing() {
+    let mut editor = TextEditor::new("first");
+    editor.insert('\n');
+    for c in "second".chars() {
+      editor.insert(c);
+    }
+    editor.up();
+    editor.backspace();
+    assert_eq!(editor.text(), "firs\nsecond");
+    editor.delete();
+    assert_eq!(editor.text(), "firssecond");
+    editor.left();
+    editor.insert('é');
+    assert_eq!(editor.text(), "firéssecond");
+  }
+
+  #[test]
+  fn test_wrap() {
+    let editor = TextEditor::new("the quick brown fox\njumps");
+    let (lines, cursor) = editor.wrap(10);
+    assert_eq!(lines, vec!["the quick ", "brown fox", "jumps"]);
+    assert_eq!(cursor, (5, 2));
+    let editor = TextEditor::new("abcdefghij");
+    assert_eq!(editor.wrap(4), (vec!["abcd".to_string(), "efgh".to_string(), "ij".to_string()], (2, 2)));
+  }
+}
@@ -1,6 +1,6 @@
 use crossterm::event::{
   KeyCode::{BackTab, Backspace, Char, Delete, Down, End, Enter, Esc, Home, Insert, Left, Null, PageDown, PageUp, Right, Tab, Up, F},
-  KeyEvent, KeyModifiers,
+  KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
 };
 use futures::StreamExt;
 use log::{debug, error, info, log_enabled, trace, warn, Level, LevelFilter};
@@ -10,11 +10,21 @@ use tokio::{
   task::JoinHandle,
 };
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Event<I> {
   Input(I),
   Tick,
   Closed,
+  /// Size of the terminal changed, only sent when replaying a recording
+  Resize(u16, u16),
+  /// The process was asked to terminate, e.g. because its terminal was closed
+  Terminate,
+  /// The mouse moved to a column and row
+  Hover(u16, u16),
+  /// The mouse wheel turned at a column and row, by one notch down when positive or up when negative
+  Scroll(u16, u16, i8),
+  /// The left mouse button was pressed at a column and row
+  Click(u16, u16),
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, PartialOrd, Eq)]
@@ -42,6 +52,137 @@ pub enum KeyCode {
   Null,
   Esc,
   Tab,
+  /// The following are only told apart by terminals with the kitty keyboard protocol
+  CtrlShift(char),
+  CtrlEnter,
+  ShiftEnter,
+  AltEnter,
+  /// Key on the keypad, `\n` for its Enter key
+  Keypad(char),
+  /// A sequence of keys bound in the key config, the index of the sequence in `KeyConfig::chords`
+  Chord(u8),
+}
+
+/// Translate a crossterm key event into the keys taskwarrior-tui handles, `None` for the release of
+/// a key, which some platforms report as well and would type characters twice.
+pub fn convert_key(key: KeyEvent) -> Option<KeyCode> {
+  if key.kind == KeyEventKind::Release {
+    return None;
+  }
+  if key.state.contains(KeyEventState::KEYPAD) {
+    match key.code {
+      Char(c) => return Some(KeyCode::Keypad(c)),
+      Enter => return Some(KeyCode::Keypad('\n')),
+      _ => {}
+    }
+  }
+  let key = match key.code {
+    Backspace => match key.modifiers {
+      KeyModifiers::CONTROL => KeyCode::CtrlBackspace,
+      KeyModifiers::ALT => KeyCode::AltBackspace,
+      _ => KeyCode::Backspace,
+    },
+    Delete => match key.modifiers {
+      KeyModifiers::CONTROL => KeyCode::CtrlDelete,
+      KeyModifiers::ALT => KeyCode::AltDelete,
+      _ => KeyCode::Delete,
+    },
+    Enter => match key.modifiers {
+      KeyModifiers::CONTROL => KeyCode::CtrlEnter,
+      KeyModifiers::SHIFT => KeyCode::ShiftEnter,
+      KeyModifiers::ALT => KeyCode::AltEnter,
+      _ => KeyCode::Char('\n'),
+    },
+    Left => KeyCode::Left,
+    Right => KeyCode::Right,
+    Up => KeyCode::Up,
+    Down => KeyCode::Down,
+    Home => KeyCode::Home,
+    End => KeyCode::End,
+    PageUp => KeyCode::PageUp,
+    PageDown => KeyCode::PageDown,
+    Tab => KeyCode::Tab,
+    BackTab => KeyCode::BackTab,
+    Insert => KeyCode::Insert,
+    F(k) => KeyCode::F(k),
+    Null => KeyCode::Null,
+    Esc => KeyCode::Esc,
+    Char(c) => match key.modifiers {
+      KeyModifiers::NONE | KeyModifiers::SHIFT => KeyCode::Char(c),
+      KeyModifiers::CONTROL => KeyCode::Ctrl(c),
+      KeyModifiers::ALT => KeyCode::Alt(c),
+      m if m == KeyModifiers::CONTROL | KeyModifiers::SHIFT => KeyCode::CtrlShift(c.to_ascii_lowercase()),
+      // characters typed with AltGr, reported with both Control and Alt on some platforms
+      m if m.contains(KeyModifiers::CONTROL | KeyModifiers::ALT) && !c.is_ascii_alphanumeric() => KeyCode::Char(c),
+      _ => KeyCode::Null,
+    },
+    _ => KeyCode::Null,
+  };
+  Some(key)
+}
+
+/// Translate a crossterm event into the events taskwarrior-tui handles: keys, mouse movements and the
+/// mouse wheel.
+fn convert_event(event: crossterm::event::Event) -> Option<Event<KeyCode>> {
+  match event {
+    crossterm::event::Event::Key(key) => convert_key(key).map(Event::Input),
+    crossterm::event::Event::Mouse(MouseEvent {
+      kind: MouseEventKind::Moved,
+      column,
+      row,
+      ..
+    }) => Some(Event::Hover(column, row)),
+    crossterm::event::Event::Mouse(MouseEvent { kind, column, row, .. }) => match kind {
+      MouseEventKind::ScrollDown => Some(Event::Scroll(column, row, 1)),
+      MouseEventKind::ScrollUp => Some(Event::Scroll(column, row, -1)),
+      MouseEventKind::Down(MouseButton::Left) => Some(Event::Click(column, row)),
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+/// Termination requests from the system: SIGTERM, or SIGHUP when the terminal is closed.
+struct TerminationSignals {
+  #[cfg(unix)]
+  term: Option<tokio::signal::unix::Signal>,
+  #[cfg(unix)]
+  hup: Option<tokio::signal::unix::Signal>,
+}
+
+impl TerminationSignals {
+  fn new() -> Self {
+    #[cfg(unix)]
+    {
+      use tokio::signal::unix::{signal, SignalKind};
+      Self {
+        term: signal(SignalKind::terminate()).map_err(|e| warn!("Unable to handle SIGTERM: {}", e)).ok(),
+        hup: signal(SignalKind::hangup()).map_err(|e| warn!("Unable to handle SIGHUP: {}", e)).ok(),
+      }
+    }
+    #[cfg(not(unix))]
+    Self {}
+  }
+
+  async fn recv(&mut self) {
+    #[cfg(unix)]
+    {
+      async fn recv(signal: &mut Option<tokio::signal::unix::Signal>) {
+        match signal {
+          Some(s) => {
+            s.recv().await;
+          }
+          None => std::future::pending().await,
+        }
+      }
+      tokio::select! {
+          _ = recv(&mut self.term) => {},
+          _ = recv(&mut self.hup) => {},
+      }
+    }
+    #[cfg(not(unix))]
+    std::future::pending::<()>().await
+  }
 }
 
 pub struct EventLoop {
@@ -63,6 +204,7 @@ impl EventLoop {
     if init {
       let mut reader = crossterm::event::EventStream::new();
       tokio::spawn(async move {
+        let mut signals = TerminationSignals::new();
         loop {
           let delay = tokio::time::sleep(tick_rate);
           let event = reader.next();
@@ -77,47 +219,12 @@ impl EventLoop {
                   _tx.send(Event::Tick).unwrap_or_else(|_| warn!("Unable to send Tick event"));
               },
               _ = _tx.closed() => break,
+              _ = signals.recv() => {
+                  _tx.send(Event::Terminate).unwrap_or_else(|_| warn!("Unable to send Terminate event"));
+              },
               maybe_event = event => {
-                  if let Some(Ok(crossterm::event::Event::Key(key))) = maybe_event {
-                      let key = match key.code {
-                          Backspace => {
-                              match key.modifiers {
-                                  KeyModifiers::CONTROL => KeyCode::CtrlBackspace,
-                                  KeyModifiers::ALT => KeyCode::AltBackspace,
-                                  _ => KeyCode::Backspace,
-                              }
-                          },
-                          Delete => {
-                              match key.modifiers {
-                                  KeyModifiers::CONTROL => KeyCode::CtrlDelete,
-                                  KeyModifiers::ALT => KeyCode::AltDelete,
-                                  _ => KeyCode::Delete,
-                              }
-                          },
-                          Enter => KeyCode::Char('\n'),
-                          Left => KeyCode::Left,
-                          Right => KeyCode::Right,
-                          Up => KeyCode::Up,
-                          Down => KeyCode::Down,
-                          Home => KeyCode::Home,
-                          End => KeyCode::End,
-                          PageUp => KeyCode::PageUp,
-                          PageDown => KeyCode::PageDown,
-                          Tab => KeyCode::Tab,
-                          BackTab => KeyCode::BackTab,
-                          Insert => KeyCode::Insert,
-                          F(k) => KeyCode::F(k),
-                          Null => KeyCode::Null,
-                          Esc => KeyCode::Esc,
-                          Char(c) => match key.modifiers {
-                              KeyModifiers::NONE | KeyModifiers::SHIFT => KeyCode::Char(c),
-                              KeyModifiers::CONTROL => KeyCode::Ctrl(c),
-                              KeyModifiers::ALT => KeyCode::Alt(c),
-                              _ => KeyCode::Null,
-                          },
-                          _ => KeyCode::Null,
-                      };
-                      _tx.send(Event::Input(key)).unwrap_or_else(|_| warn!("Unable to send {:?} event", key));
+                  if let Some(event) = maybe_event.and_then(|e| e.ok()).and_then(convert_event) {
+                      _tx.send(event).unwrap_or_else(|_| warn!("Unable to send {:?} event", event));
                   }
               }
           }
@@ -127,4 +234,56 @@ impl EventLoop {
 
     Self { tx, rx, tick_rate, abort }
   }
+
+  /// Send recorded events with their original delays, then hand over to the keyboard without ticks
+  /// so that the state reached at the end of the recording can be inspected.
+  pub fn replay(events: Vec<(std::time::Duration, Event<KeyCode>)>) -> Self {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let _tx = tx.clone();
+    let (abort, mut abort_recv) = mpsc::unbounded_channel();
+
+    tokio::spawn(async move {
+      for (delay, event) in events {
+        tokio::select! {
+            _ = abort_recv.recv() => {
+                _tx.send(Event::Closed).unwrap_or_else(|_| warn!("Unable to send Closed event"));
+                _tx.send(Event::Tick).unwrap_or_else(|_| warn!("Unable to send Tick event"));
+                return;
+            },
+            _ = tokio::time::sleep(delay) => {
+                if _tx.send(event).is_err() {
+                    return;
+                }
+            },
+        }
+      }
+      let mut reader = crossterm::event::EventStream::new();
+      let mut signals = TerminationSignals::new();
+      loop {
+        tokio::select! {
+            _ = abort_recv.recv() => {
+                _tx.send(Event::Closed).unwrap_or_else(|_| warn!("Unable to send Closed event"));
+                _tx.send(Event::Tick).unwrap_or_else(|_| warn!("Unable to send Tick event"));
+                break;
+            },
+            _ = _tx.closed() => break,
+            _ = signals.recv() => {
+                _tx.send(Event::Terminate).unwrap_or_else(|_| warn!("Unable to send Terminate event"));
+            },
+            maybe_event = reader.next() => {
+                if let Some(event) = maybe_event.and_then(|e| e.ok()).and_then(convert_event) {
+                    _tx.send(event).unwrap_or_else(|_| warn!("Unable to send {:?} event", event));
+                }
+            }
+        }
+      }
+    });
+
+    Self {
+      tx,
+      rx,
+      tick_rate: std::time::Duration::from_millis(250),
+      abort,
+    }
+  }
 }
@@ -0,0 +1,354 @@
+use std::{fmt, ops::Range};
+
+const MODIFIERS: [&str; 22] = [
+  "before",
+  "under",
+  "below",
+  "after",
+  "over",
+  "above",
+  "by",
+  "none",
+  "any",
+  "is",
+  "equals",
+  "isnt",
+  "not",
+  "has",
+  "contains",
+  "hasnt",
+  "startswith",
+  "left",
+  "endswith",
+  "right",
+  "word",
+  "noword",
+];
+
+const BINARY_OPERATORS: [&str; 3] = ["and", "or", "xor"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+  /// `project:` or `due.before:`, including the colon
+  Attribute,
+  /// Value of an attribute
+  Value,
+  /// `+tag` or `-tag`
+  Tag,
+  /// `and`, `or`, `xor`, `not` and comparison operators
+  Operator,
+  Parenthesis,
+  /// Anything else, e.g. ids, uuids or words of the description
+  Text,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+  pub kind: TokenKind,
+  pub range: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterError {
+  /// Byte offset of the offending part of the filter
+  pub position: usize,
+  pub message: String,
+}
+
+impl FilterError {
+  fn new(position: usize, message: &str) -> Self {
+    Self {
+      position,
+      message: message.to_string(),
+    }
+  }
+
+  /// One based column of the error, counted in characters.
+  pub fn column(&self, filter: &str) -> usize {
+    filter[..self.position.min(filter.len())].chars().count() + 1
+  }
+}
+
+impl fmt::Display for FilterError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.message)
+  }
+}
+
+/// Split a filter into words, keeping quoted strings and parentheses together.
+/// Returns the byte ranges of the words and of the parentheses.
+fn words(filter: &str) -> Result<Vec<Range<usize>>, FilterError> {
+  let mut words = vec![];
+  let mut start = None;
+  let mut quote: Option<(char, usize)> = None;
+  let mut previous = None;
+  for (i, c) in filter.char_indices() {
+    let after = previous.replace(c);
+    if let Some((q, _)) = quote {
+      if c == q {
+        quote = None;
+      }
+      continue;
+    }
+    // a quote within a word, as in `description:don't`, is an apostrophe
+    let opens_quote = start.is_none() || matches!(after, Some(':' | '='));
+    match c {
+      '"' | '\'' if opens_quote => {
+        quote = Some((c, i));
+        start.get_or_insert(i);
+      }
+      '(' | ')' => {
+        if let Some(s) = start.take() {
+          words.push(s..i);
+        }
+        words.push(i..i + 1);
+      }
+      c if c.is_whitespace() => {
+        if let Some(s) = start.take() {
+          words.push(s..i);
+        }
+      }
+      _ => {
+        start.get_or_insert(i);
+      }
+    }
+  }
+  if let Some((_, i)) = quote {
+    return Err(FilterError::new(i, "unterminated quote"));
+  }
+  if let Some(s) = start {
+    words.push(s..filter.len());
+  }
+  Ok(words)
+}
+
+fn is_operator(word: &str) -> bool {
+  BINARY_OPERATORS.contains(&word.to_lowercase().as_str())
+    || word.eq_ignore_ascii_case("not")
+    || ["<", "<=", ">", ">=", "=", "==", "!=", "!==", "~", "!~"].contains(&word)
+}
+
+fn is_binary_operator(word: &str) -> bool {
+  is_operator(word) && !word.eq_ignore_ascii_case("not")
+}
+
+/// Position of the colon of an attribute, e.g. `3` for `due:tomorrow`.
+fn attribute_colon(word: &str) -> Option<usize> {
+  let i = word.find(':')?;
+  if word[..i].chars().all(|c| c.is_alphanumeric() || c == '.' || c == '_' || c == '-') {
+    Some(i)
+  } else {
+    None
+  }
+}
+
+pub fn tokenize(filter: &str) -> Vec<Token> {
+  let words = words(filter).unwrap_or_else(|_| {
+    // highlight what can be highlighted before the unterminated quote
+    let end = filter.rfind(&['"', '\''][..]).unwrap_or(filter.len());
+    words(&filter[..end]).unwrap_or_default()
+  });
+  let mut tokens = vec![];
+  for range in words {
+    let word = &filter[range.clone()];
+    if word == "(" || word == ")" {
+      tokens.push(Token {
+        kind: TokenKind::Parenthesis,
+        range,
+      });
+    } else if is_operator(word) {
+      tokens.push(Token {
+        kind: TokenKind::Operator,
+        range,
+      });
+    } else if (word.starts_with('+') || word.starts_with('-')) && word.len() > 1 {
+      tokens.push(Token { kind: TokenKind::Tag, range });
+    } else if let Some(i) = attribute_colon(word) {
+      let colon = range.start + i + 1;
+      tokens.push(Token {
+        kind: TokenKind::Attribute,
+        range: range.start..colon,
+      });
+      if colon < range.end {
+        tokens.push(Token {
+          kind: TokenKind::Value,
+          range: colon..range.end,
+        });
+      }
+    } else {
+      tokens.push(Token {
+        kind: TokenKind::Text,
+        range,
+      });
+    }
+  }
+  tokens
+}
+
+/// Projects the filter is restricted to, e.g. `work.dev` for `project:work.dev` or `pro.is:work.dev`.
+pub fn projects(filter: &str) -> Vec<String> {
+  let tokens = tokenize(filter);
+  tokens
+    .windows(2)
+    .filter(|pair| pair[0].kind == TokenKind::Attribute && pair[1].kind == TokenKind::Value)
+    .filter(|pair| {
+      let attribute = filter[pair[0].range.clone()].trim_end_matches(':');
+      let (name, modifier) = attribute.split_once('.').unwrap_or((attribute, "is"));
+      name.len() >= 3 && "project".starts_with(name) && ["is", "equals", "startswith", "left"].contains(&modifier)
+    })
+    .map(|pair| filter[pair[1].range.clone()].trim_matches(&['"', '\''][..]).to_string())
+    .filter(|project| !project.is_empty())
+    .collect()
+}
+
+/// Add the filter `args` given on the command line to `filter` as taskwarrior does, so that
+/// `project:work +urgent` narrows the report filter. Filters with `or` are put in parentheses to keep
+/// their meaning.
+pub fn combine(filter: &str, args: &[String]) -> String {
+  let args = args.iter().map(|a| shlex::quote(a)).collect::<Vec<_>>().join(" ");
+  let filter = filter.trim();
+  if args.is_empty() || filter.is_empty() {
+    return format!("{}{}", filter, args);
+  }
+  let group = |f: &str| {
+    let has_or = tokenize(f)
+      .iter()
+      .any(|t| t.kind == TokenKind::Operator && ["or", "xor"].contains(&f[t.range.clone()].to_lowercase().as_str()));
+    if has_or {
+      format!("( {} )", f)
+    } else {
+      f.to_string()
+    }
+  };
+  format!("{} {}", group(filter), group(&args))
+}
+
+/// Check a filter for the mistakes that would make taskwarrior misread it.
+pub fn validate(filter: &str) -> Result<(), FilterError> {
+  let words = words(filter)?;
+  let mut open = vec![];
+  // whether the previous word can be followed by a binary operator
+  let mut after_operand = false;
+  let mut last_binary_operator = None;
+  for range in &words {
+    let word = &filter[range.clone()];
+    match word {
+      "(" => {
+        open.push(range.start);
+        after_operand = false;
+      }
+      ")" => {
+        if open.pop().is_none() {
+          return Err(FilterError::new(range.start, "unbalanced closing parenthesis"));
+        }
+        if !after_operand {
+          return Err(FilterError::new(range.start, "missing operand before closing parenthesis"));
+        }
+        after_operand = true;
+      }
+      w if is_binary_operator(w) => {
+        if !after_operand {
+          return Err(FilterError::new(range.start, &format!("missing operand before `{}`", w)));
+        }
+        after_operand = false;
+        last_binary_operator = Some(range.clone());
+      }
+      w if w.eq_ignore_ascii_case("not") => after_operand = false,
+      w => {
+        if let Some(i) = attribute_colon(w) {
+          let attribute = &w[..i];
+          if attribute.is_empty() {
+            return Err(FilterError::new(range.start, "missing attribute name"));
+          }
+          if let Some((name, modifier)) = attribute.split_once('.') {
+            if name.is_empty() {
+              return Err(FilterError::new(range.start, "missing attribute name"));
+            }
+            // taskwarrior accepts unambiguous abbreviations of modifiers, and `rc.<name>:<value>` overrides
+            let known = name == "rc" || (modifier.len() >= 2 && MODIFIERS.iter().any(|m| m.starts_with(modifier)));
+            if !known {
+              return Err(FilterError::new(
+                range.start + name.len() + 1,
+                &format!("unknown attribute modifier `{}`", modifier),
+              ));
+            }
+          }
+        }
+        after_operand = true;
+      }
+    }
+  }
+  if let Some(position) = open.pop() {
+    return Err(FilterError::new(position, "unbalanced opening parenthesis"));
+  }
+  if let Some(range) = last_binary_operator {
+    if !after_operand {
+      return Err(FilterError::new(range.start, &format!("missing operand after `{}`", &filter[range])));
+    }
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_tokenize() {
+    let filter = r#"(project:work or +urgent) and "some words" due.before:eom"#;
+    let kinds: Vec<(TokenKind, &str)> = tokenize(filter).into_iter().map(|t| (t.kind, &filter[t.range])).collect();
+    assert_eq!(
+      kinds,
+      vec![
+        (TokenKind::Parenthesis, "("),
+        (TokenKind::Attribute, "project:"),
+        (TokenKind::Value, "work"),
+        (TokenKind::Operator, "or"),
+        (TokenKind::Tag, "+urgent"),
+        (TokenKind::Parenthesis, ")"),
+        (TokenKind::Operator, "and"),
+        (TokenKind::Text, r#""some words""#),
+        (TokenKind::Attribute, "due.before:"),
+        (TokenKind::Value, "eom"),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_projects() {
+    assert_eq!(projects("project:work.dev +next"), vec!["work.dev"]);
+    assert_eq!(projects("(pro:home or proj.is:'garden') due.before:eom"), vec!["home", "garden"]);
+    assert!(projects("project.not:work description:project:x").is_empty());
+  }
+
+  #[test]
+  fn test_combine() {
+    let args = vec!["project:work".to_string(), "+urgent".to_string()];
+    assert_eq!(combine("status:pending", &args), "status:pending project:work +urgent");
+    assert_eq!(combine("", &args), "project:work +urgent");
+    assert_eq!(combine("+home or +garden", &args), "( +home or +garden ) project:work +urgent");
+    assert_eq!(combine("status:pending", &["some words".to_string()]), r#"status:pending "some words""#);
+    assert_eq!(combine("status:pending ", &[]), "status:pending");
+  }
+
+  #[test]
+  fn test_validate() {
+    assert!(validate("").is_ok());
+    assert!(validate("status:pending (project:a or project:b) -WAITING").is_ok());
+    assert!(validate(r#"description.contains:"a (b""#).is_ok());
+    assert!(validate("due.bef:eom rc.context:none").is_ok());
+    assert!(validate("description:don't +home").is_ok());
+    assert!(validate("description.has:'some words").is_err());
+
+    let e = validate("(project:a or project:b").unwrap_err();
+    assert_eq!((e.position, e.message.as_str()), (0, "unbalanced opening parenthesis"));
+    let e = validate("project:a)").unwrap_err();
+    assert_eq!((e.position, e.message.as_str()), (9, "unbalanced closing parenthesis"));
+    let e = validate("project:a or").unwrap_err();
+    assert_eq!((e.position, e.message.as_str()), (10, "missing operand after `or`"));
+    let e = validate("due.befro:eom").unwrap_err();
+    assert_eq!((e.position, e.column("due.befro:eom")), (4, 5));
+    let e = validate(r#"project:a "unterminated"#).unwrap_err();
+    assert_eq!((e.position, e.message.as_str()), (10, "unterminated quote"));
+  }
+}
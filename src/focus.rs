@@ -0,0 +1,170 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone, Utc};
+use task_hookrs::task::Task;
+use uuid::Uuid;
+
+use crate::utils;
+
+/// Where the time spent on tasks comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Source {
+  /// Tasks started and stopped from `taskwarrior-tui`
+  #[default]
+  Tui,
+  /// Intervals of timewarrior tagged with the description of the task, as its taskwarrior hook does
+  Timewarrior,
+}
+
+impl Source {
+  pub fn parse(s: &str) -> Option<Self> {
+    match s.trim() {
+      "tui" => Some(Source::Tui),
+      "timewarrior" => Some(Source::Timewarrior),
+      _ => None,
+    }
+  }
+}
+
+/// Time spent with each task active.
+pub struct Focus {
+  pub source: Source,
+  /// Seconds of the sessions that ended, by task uuid, stored in the data directory
+  tracked: HashMap<Uuid, i64>,
+  /// Seconds of timewarrior intervals, by tag
+  timewarrior: HashMap<String, i64>,
+  path: PathBuf,
+}
+
+impl Focus {
+  pub fn new(filename: &str, source: Source) -> Self {
+    Self {
+      source,
+      tracked: HashMap::new(),
+      timewarrior: HashMap::new(),
+      path: utils::get_data_dir().join(filename),
+    }
+  }
+
+  pub fn load(&mut self) -> Result<()> {
+    if self.path.exists() {
+      self.tracked = serde_json::from_str(&fs::read_to_string(&self.path)?)?;
+    }
+    Ok(())
+  }
+
+  fn write(&self) -> Result<()> {
+    fs::write(&self.path, serde_json::to_string(&self.tracked)?)?;
+    Ok(())
+  }
+
+  /// Add the session of `task`, which is being stopped at `now`.
+  pub fn stop(&mut self, task: &Task, now: DateTime<Utc>) -> Result<()> {
+    if self.source != Source::Tui {
+      return Ok(());
+    }
+    if let Some(start) = task.start() {
+      let session = now - Utc.from_utc_datetime(&NaiveDateTime::new(start.date(), start.time()));
+      *self.tracked.entry(*task.uuid()).or_default() += session.num_seconds().max(0);
+      self.write()?;
+    }
+    Ok(())
+  }
+
+  /// Read the intervals of the timewarrior database again.
+  pub fn refresh_timewarrior(&mut self, now: DateTime<Utc>) {
+    if self.source == Source::Timewarrior {
+      self.timewarrior = timewarrior_data().map(|data| timewarrior_totals(&data, now)).unwrap_or_default();
+    }
+  }
+
+  /// Time spent on `task` up to `now`, including the session it is active in.
+  pub fn total(&self, task: &Task, now: DateTime<Utc>) -> Duration {
+    let seconds = match self.source {
+      Source::Tui => {
+        let running = task.start().map_or(0, |start| {
+          (now - Utc.from_utc_datetime(&NaiveDateTime::new(start.date(), start.time())))
+            .num_seconds()
+            .max(0)
+        });
+        self.tracked.get(task.uuid()).copied().unwrap_or_default() + running
+      }
+      Source::Timewarrior => self.timewarrior.get(task.description()).copied().unwrap_or_default(),
+    };
+    Duration::seconds(seconds)
+  }
+}
+
+/// Time as hours and minutes, e.g. `2h05m` or `12m`.
+pub fn format_tracked(tracked: Duration) -> String {
+  let minutes = tracked.num_minutes();
+  if minutes >= 60 {
+    format!("{}h{:02}m", minutes / 60, minutes % 60)
+  } else {
+    format!("{}m", minutes)
+  }
+}
+
+/// Contents of the data files of the timewarrior database, in `$TIMEWARRIORDB` or its default place.
+fn timewarrior_data() -> Option<String> {
+  let dir = match std::env::var("TIMEWARRIORDB") {
+    Ok(dir) => PathBuf::from(dir),
+    Err(_) => {
+      let home = dirs::home_dir()?.join(".timewarrior");
+      if home.exists() {
+        home
+      } else {
+        dirs::data_dir()?.join("timewarrior")
+      }
+    }
+  };
+  let mut data = String::new();
+  for entry in fs::read_dir(dir.join("data")).ok()?.flatten() {
+    if entry.path().extension().is_some_and(|e| e == "data") {
+      data.push_str(&fs::read_to_string(entry.path()).unwrap_or_default());
+    }
+  }
+  Some(data)
+}
+
+/// Seconds of the intervals of timewarrior data by tag, counting an open interval up to `now`.
+pub fn timewarrior_totals(data: &str, now: DateTime<Utc>) -> HashMap<String, i64> {
+  let parse = |s: &str| NaiveDateTime::parse_from_str(s, "%Y%m%dT%H%M%SZ").ok().map(|d| Utc.from_utc_datetime(&d));
+  let mut totals = HashMap::new();
+  for line in data.lines() {
+    let rest = match line.strip_prefix("inc ") {
+      Some(rest) => rest,
+      None => continue,
+    };
+    let (range, tags) = rest.split_once(" # ").unwrap_or((rest, ""));
+    let (start, end) = match range.split_once(" - ") {
+      Some((start, end)) => (parse(start.trim()), parse(end.trim())),
+      None => (parse(range.trim()), Some(now)),
+    };
+    let seconds = match (start, end) {
+      (Some(start), Some(end)) => (end - start).num_seconds().max(0),
+      _ => continue,
+    };
+    for tag in shlex::split(tags).unwrap_or_default() {
+      *totals.entry(tag).or_default() += seconds;
+    }
+  }
+  totals
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_timewarrior_totals() {
+    let data = "inc 20240101T100000Z - 20240101T110000Z # \"Write report\" work\ninc 20240101T120000Z - 20240101T123000Z # \"Write report\"\ninc 20240101T130000Z # work\n";
+    let now = Utc.with_ymd_and_hms(2024, 1, 1, 13, 15, 0).unwrap();
+    let totals = timewarrior_totals(data, now);
+    assert_eq!(totals["Write report"], 5400);
+    assert_eq!(totals["work"], 4500);
+    assert_eq!(format_tracked(Duration::seconds(totals["Write report"])), "1h30m");
+    assert_eq!(format_tracked(Duration::seconds(754)), "12m");
+  }
+}
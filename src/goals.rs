@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use log::warn;
+use task_hookrs::{status::TaskStatus, task::Task, uda::UDAValue};
+
+const PREFIX: &str = "uda.taskwarrior-tui.goal.";
+
+/// UDA linking a task to a goal, e.g. `task add Call the bank goal:house`.
+pub const UDA: &str = "goal";
+
+/// A larger objective that tasks contribute to.
+///
+/// ```plaintext
+/// uda.taskwarrior-tui.goal.house.description=Buy a house
+/// uda.taskwarrior-tui.goal.house.target=2027-06-30
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Goal {
+  pub name: String,
+  pub description: Option<String>,
+  /// Date the goal should be reached by
+  pub target: Option<NaiveDate>,
+}
+
+/// Goals defined in the output of `task show`, in the order they first appear.
+pub fn parse(data: &str) -> Vec<Goal> {
+  let mut goals: Vec<Goal> = vec![];
+  for line in data.lines() {
+    let rest = match line.strip_prefix(PREFIX) {
+      Some(rest) => rest,
+      None => continue,
+    };
+    let (key, value) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let (name, setting) = match key.rsplit_once('.') {
+      Some(split) => split,
+      None => continue,
+    };
+    let index = match goals.iter().position(|g| g.name == name) {
+      Some(i) => i,
+      None => {
+        goals.push(Goal {
+          name: name.to_string(),
+          ..Goal::default()
+        });
+        goals.len() - 1
+      }
+    };
+    let value = value.trim();
+    match setting {
+      "description" => goals[index].description = Some(value.to_string()).filter(|v| !v.is_empty()),
+      "target" => match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        Ok(date) => goals[index].target = Some(date),
+        Err(_) => warn!("Target {} of goal {} is not a YYYY-MM-DD date", value, name),
+      },
+      _ => warn!("Unknown setting {} of goal {}", setting, name),
+    }
+  }
+  goals
+}
+
+/// How far along a goal is, from the tasks linked to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoalProgress {
+  pub goal: Goal,
+  pub completed: usize,
+  /// Linked tasks that are not deleted
+  pub total: usize,
+  /// Latest due date of the linked tasks still to do
+  pub last_due: Option<NaiveDateTime>,
+  /// Descriptions of the linked tasks still to do, soonest due first
+  pub remaining: Vec<String>,
+}
+
+impl GoalProgress {
+  pub fn ratio(&self) -> f64 {
+    if self.total == 0 {
+      0.0
+    } else {
+      self.completed as f64 / self.total as f64
+    }
+  }
+
+  /// When the goal is expected to be reached: its target, or else the due date of its last task.
+  pub fn horizon(&self) -> Option<NaiveDateTime> {
+    self.goal.target.and_then(|d| d.and_hms_opt(0, 0, 0)).or(self.last_due)
+  }
+}
+
+/// Progress of the configured goals, in their order, followed by the goals only named by tasks.
+pub fn progress(goals: &[Goal], tasks: &[Task]) -> Vec<GoalProgress> {
+  let mut linked: BTreeMap<String, Vec<&Task>> = BTreeMap::new();
+  for task in tasks.iter().filter(|t| *t.status() != TaskStatus::Deleted) {
+    if let Some(UDAValue::Str(name)) = task.uda().get(UDA) {
+      linked.entry(name.clone()).or_default().push(task);
+    }
+  }
+  let mut all: Vec<Goal> = goals.to_vec();
+  for name in linked.keys() {
+    if !goals.iter().any(|g| &g.name == name) {
+      all.push(Goal {
+        name: name.clone(),
+        ..Goal::default()
+      });
+    }
+  }
+  all
+    .into_iter()
+    .map(|goal| {
+      let tasks = linked.remove(&goal.name).unwrap_or_default();
+      let completed = tasks.iter().filter(|t| *t.status() == TaskStatus::Completed).count();
+      let mut todo: Vec<&&Task> = tasks.iter().filter(|t| *t.status() != TaskStatus::Completed).collect();
+      todo.sort_by_key(|t| t.due().map(|d| **d).unwrap_or(NaiveDateTime::MAX));
+      GoalProgress {
+        completed,
+        total: tasks.len(),
+        last_due: todo.iter().filter_map(|t| t.due().map(|d| **d)).max(),
+        remaining: todo.iter().map(|t| t.description().clone()).collect(),
+        goal,
+      }
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  #[test]
+  fn test_goals() {
+    let data = "uda.taskwarrior-tui.goal.house.description  Buy a house\nuda.taskwarrior-tui.goal.house.target  2027-06-30\nuda.taskwarrior-tui.goal.fit.description  Run a marathon\n";
+    let goals = parse(data);
+    assert_eq!(goals.len(), 2);
+    assert_eq!(goals[0].target, NaiveDate::from_ymd_opt(2027, 6, 30));
+
+    let tasks = import(
+      r#"[
+        {"uuid":"00000000-0000-0000-0000-000000000001","description":"Save","status":"completed","entry":"20260101T000000Z","goal":"house"},
+        {"uuid":"00000000-0000-0000-0000-000000000002","description":"Visit","status":"pending","entry":"20260101T000000Z","due":"20260301T000000Z","goal":"house"},
+        {"uuid":"00000000-0000-0000-0000-000000000003","description":"Call","status":"deleted","entry":"20260101T000000Z","goal":"house"},
+        {"uuid":"00000000-0000-0000-0000-000000000004","description":"Learn","status":"pending","entry":"20260101T000000Z","goal":"spanish"}
+      ]"#
+        .as_bytes(),
+    )
+    .unwrap();
+    let progress = progress(&goals, &tasks);
+    assert_eq!(progress.len(), 3);
+    assert_eq!((progress[0].completed, progress[0].total), (1, 2));
+    assert_eq!(progress[0].remaining, ["Visit"]);
+    assert_eq!(progress[0].horizon(), NaiveDate::from_ymd_opt(2027, 6, 30).unwrap().and_hms_opt(0, 0, 0));
+    assert_eq!(progress[1].total, 0);
+    assert_eq!(progress[2].goal.name, "spanish");
+    assert!(progress[2].horizon().is_none());
+  }
+}
@@ -0,0 +1,167 @@
+//! Helpers for snapshot tests of components.
+//!
+//! Render a component into a `TestBackend` with [`render`], then compare the text of the result with
+//! the snapshot stored in `src/snapshots/<name>.snap` with [`assert_snapshot`]. A missing or changed
+//! snapshot fails the test, `UPDATE_SNAPSHOTS=1 cargo test` writes the new ones.
+//! Snapshots only contain the text of the buffer, check styles with `Buffer::with_lines` as usual.
+
+use std::{env, fs, path::PathBuf};
+
+use ratatui::{backend::TestBackend, buffer::Buffer, terminal::Frame, Terminal};
+use task_hookrs::{import::import, task::Task};
+
+/// Tasks shared by the snapshot tests, independent of the taskwarrior test data.
+const FIXTURE_TASKS: &str = r#"[
+{"id":1,"description":"Buy groceries","entry":"20200101T090000Z","modified":"20200101T090000Z","project":"home","status":"pending","tags":["errand"],"uuid":"6b6c7e4e-8f6e-4d5c-9a65-5f3a6b0e0c01","urgency":1.8},
+{"id":2,"description":"Write quarterly report","due":"20200115T170000Z","entry":"20200102T090000Z","modified":"20200102T090000Z","priority":"H","project":"work","status":"pending","uuid":"6b6c7e4e-8f6e-4d5c-9a65-5f3a6b0e0c02","urgency":12.1},
+{"id":3,"description":"Call the plumber","entry":"20200103T090000Z","modified":"20200103T090000Z","status":"pending","uuid":"6b6c7e4e-8f6e-4d5c-9a65-5f3a6b0e0c03","urgency":0}
+]"#;
+
+pub fn fixture_tasks() -> Vec<Task> {
+  import(FIXTURE_TASKS.as_bytes()).expect("Invalid fixture tasks")
+}
+
+/// Draw into a `width` x `height` test terminal and return what was drawn.
+pub fn render<F>(width: u16, height: u16, draw: F) -> Buffer
+where
+  F: FnOnce(&mut Frame<TestBackend>),
+{
+  let mut terminal = Terminal::new(TestBackend::new(width, height)).expect("Unable to create test terminal");
+  terminal.draw(draw).expect("Unable to draw");
+  terminal.backend().buffer().clone()
+}
+
+/// Text of a buffer, one line per row, without styles.
+pub fn buffer_text(buffer: &Buffer) -> String {
+  let mut lines = vec![];
+  for row in buffer.content.chunks(buffer.area.width as usize) {
+    let line: String = row.iter().map(|c| c.symbol.as_str()).collect();
+    lines.push(line.trim_end().to_string());
+  }
+  lines.join("\n") + "\n"
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+  PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+    .join("src")
+    .join("snapshots")
+    .join(format!("{}.snap", name))
+}
+
+/// Lines that differ between two texts, including the lines only one of them has.
+fn diff(expected: &str, actual: &str) -> Vec<String> {
+  let (expected, actual): (Vec<&str>, Vec<&str>) = (expected.lines().collect(), actual.lines().collect());
+  (0..expected.len().max(actual.len()))
+    .filter(|&i| expected.get(i) != actual.get(i))
+    .map(|i| {
+      let mut lines = vec![format!("line {}:", i + 1)];
+      lines.extend(expected.get(i).map(|e| format!("  - {:?}", e)));
+      lines.extend(actual.get(i).map(|a| format!("  + {:?}", a)));
+      lines.join("\n")
+    })
+    .collect()
+}
+
+/// Compare the text of `buffer` with the stored snapshot `name`, or store it when
+/// `UPDATE_SNAPSHOTS=1` is set.
+pub fn assert_snapshot(name: &str, buffer: &Buffer) {
+  let actual = buffer_text(buffer);
+  let path = snapshot_path(name);
+  if env::var("UPDATE_SNAPSHOTS").is_ok_and(|v| v == "1") {
+    fs::create_dir_all(path.parent().unwrap()).expect("Unable to create snapshot directory");
+    fs::write(&path, actual).expect("Unable to write snapshot");
+    return;
+  }
+  let expected = match fs::read_to_string(&path) {
+    Ok(expected) => expected,
+    Err(_) => panic!(
+      "Snapshot `{}` is missing, run with UPDATE_SNAPSHOTS=1 to write it.\n\nactual:\n{}",
+      name, actual
+    ),
+  };
+  if expected != actual {
+    panic!(
+      "Snapshot `{}` does not match, run with UPDATE_SNAPSHOTS=1 to accept the changes.\n{}\n\nactual:\n{}",
+      name,
+      diff(&expected, &actual).join("\n"),
+      actual
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::NaiveDate;
+  use ratatui::{
+    layout::Constraint,
+    style::Style,
+    widgets::{Block, Borders, Clear},
+  };
+
+  use super::*;
+  use crate::{
+    app::centered_rect,
+    calendar::Calendar,
+    help::Help,
+    table::{Row, Table, TableState},
+  };
+
+  #[test]
+  fn test_diff() {
+    assert!(diff("a\nb\n", "a\nb\n").is_empty());
+    assert_eq!(diff("a\nb\n", "a\nc\n"), ["line 2:\n  - \"b\"\n  + \"c\""]);
+    // lines added or removed at the end are reported too
+    assert_eq!(diff("a\n", "a\nb\n"), ["line 2:\n  + \"b\""]);
+    assert_eq!(diff("a\nb\n", "a\n"), ["line 2:\n  - \"b\""]);
+  }
+
+  #[test]
+  fn test_snapshot_help_dialog() {
+    let help = Help::new();
+    let buffer = render(60, 14, |f| {
+      let area = centered_rect(80, 80, f.size());
+      f.render_widget(Clear, area);
+      f.render_widget(&help, area);
+    });
+    assert_snapshot("help_dialog", &buffer);
+  }
+
+  #[test]
+  fn test_snapshot_calendar() {
+    let buffer = render(50, 12, |f| {
+      let calendar = Calendar::default()
+        .block(Block::default().borders(Borders::ALL).title("Calendar"))
+        .year(2020)
+        .months_per_row(2)
+        .date_style(vec![(NaiveDate::from_ymd_opt(2020, 1, 15).unwrap(), Style::default())]);
+      f.render_widget(calendar, f.size());
+    });
+    assert_snapshot("calendar", &buffer);
+  }
+
+  #[test]
+  fn test_snapshot_table() {
+    let tasks = fixture_tasks();
+    let buffer = render(50, 6, |f| {
+      let rows = tasks.iter().map(|t| {
+        Row::Data(
+          vec![
+            t.id().unwrap_or_default().to_string(),
+            t.project().cloned().unwrap_or_default(),
+            t.description().to_string(),
+          ]
+          .into_iter(),
+        )
+      });
+      // the first column also holds the selection symbol
+      let widths = [Constraint::Length(3), Constraint::Length(5), Constraint::Min(10)];
+      let table = Table::new(["ID", "Proj", "Description"].iter(), rows)
+        .widths(&widths)
+        .highlight_symbol("• ");
+      let mut state = TableState::default();
+      state.select(Some(1));
+      f.render_stateful_widget(table, f.size(), &mut state);
+    });
+    assert_snapshot("table", &buffer);
+  }
+}
@@ -0,0 +1,194 @@
+use std::{
+  fmt, fs,
+  path::{Path, PathBuf},
+};
+
+use crate::overrides;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+  pub check: &'static str,
+  pub problem: String,
+  pub hint: &'static str,
+  /// taskwarrior-tui cannot start when a fatal issue is found
+  pub fatal: bool,
+}
+
+impl fmt::Display for Issue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "✗ {}: {}\n  {}", self.check, self.problem, self.hint)
+  }
+}
+
+/// Concise report of the issues found, one entry per failed check.
+pub fn summary(issues: &[Issue]) -> String {
+  let mut lines = vec![format!("Health check found {} problem(s):", issues.len())];
+  lines.extend(issues.iter().map(|i| i.to_string()));
+  lines.join("\n")
+}
+
+fn first_line(output: &[u8]) -> String {
+  String::from_utf8_lossy(output)
+    .lines()
+    .map(str::trim)
+    .find(|l| !l.is_empty())
+    .unwrap_or_default()
+    .to_string()
+}
+
+fn expand_home(path: &str) -> PathBuf {
+  match path.strip_prefix("~/") {
+    Some(rest) => dirs::home_dir().map(|h| h.join(rest)).unwrap_or_else(|| PathBuf::from(path)),
+    None => PathBuf::from(path),
+  }
+}
+
+fn task_get(setting: &str) -> Option<String> {
+  let output = overrides::task().arg("rc.verbose=nothing").arg("_get").arg(setting).output().ok()?;
+  let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+  if value.is_empty() {
+    None
+  } else {
+    Some(value)
+  }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+  use std::os::unix::fs::PermissionsExt;
+  fs::metadata(path).map(|m| m.permissions().mode() & 0o111 != 0).unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+  true
+}
+
+/// Hook scripts that taskwarrior would silently skip because they cannot be executed.
+pub fn non_executable_hooks(hooks: &Path) -> Vec<String> {
+  let mut names: Vec<String> = fs::read_dir(hooks)
+    .map(|entries| {
+      entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && !is_executable(p))
+        .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .filter(|n| n.starts_with("on-"))
+        .collect()
+    })
+    .unwrap_or_default();
+  names.sort();
+  names
+}
+
+/// Verify that taskwarrior can be used before starting the interface.
+pub fn check() -> Vec<Issue> {
+  let mut issues = vec![];
+
+  match overrides::task().arg("--version").output() {
+    Ok(output) if output.status.success() => {}
+    Ok(output) => {
+      issues.push(Issue {
+        check: "taskwarrior",
+        problem: format!("`task --version` failed: {}", first_line(&output.stderr)),
+        hint: "Reinstall taskwarrior or check that the `task` in your PATH is taskwarrior.",
+        fatal: true,
+      });
+      return issues;
+    }
+    Err(e) => {
+      issues.push(Issue {
+        check: "taskwarrior",
+        problem: format!("unable to run `task`: {}", e),
+        hint: "Install taskwarrior and make sure `task` is in your PATH.",
+        fatal: true,
+      });
+      return issues;
+    }
+  }
+
+  if let Ok(output) = overrides::task().arg("rc.color=off").arg("show").output() {
+    if !output.status.success() {
+      issues.push(Issue {
+        check: "taskrc",
+        problem: first_line(&output.stderr),
+        hint: "Fix the configuration file reported above, or point TASKRC / --taskrc to a valid file.",
+        fatal: true,
+      });
+      return issues;
+    }
+  }
+
+  // on-launch hooks run for every command, a failing one makes every command fail
+  if let Ok(output) = overrides::task().arg("rc.verbose=nothing").arg("_version").output() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() || stderr.contains("Hook Error") {
+      issues.push(Issue {
+        check: "hooks",
+        problem: first_line(&output.stderr),
+        hint: "A hook script is failing, fix it or remove it from the hooks directory.",
+        fatal: false,
+      });
+    }
+  }
+
+  if let Some(location) = task_get("rc.data.location") {
+    let data = expand_home(&location);
+    if let Err(e) = fs::read_dir(&data) {
+      issues.push(Issue {
+        check: "data",
+        problem: format!("unable to read {}: {}", data.display(), e),
+        hint: "Create the directory or fix `data.location` in your taskrc.",
+        fatal: true,
+      });
+    }
+    let hooks = task_get("rc.hooks.location")
+      .map(|l| expand_home(&l))
+      .unwrap_or_else(|| data.join("hooks"));
+    let hooks = non_executable_hooks(&hooks);
+    if !hooks.is_empty() {
+      issues.push(Issue {
+        check: "hooks",
+        problem: format!("not executable, taskwarrior ignores: {}", hooks.join(", ")),
+        hint: "Make the hook scripts executable with `chmod +x`.",
+        fatal: false,
+      });
+    }
+  }
+
+  issues
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_summary() {
+    let issues = vec![Issue {
+      check: "taskwarrior",
+      problem: "unable to run `task`".to_string(),
+      hint: "Install taskwarrior.",
+      fatal: true,
+    }];
+    assert_eq!(
+      summary(&issues),
+      "Health check found 1 problem(s):\n✗ taskwarrior: unable to run `task`\n  Install taskwarrior."
+    );
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn test_non_executable_hooks() {
+    use std::os::unix::fs::PermissionsExt;
+    let dir = std::env::temp_dir().join(format!("taskwarrior-tui-hooks-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    for (name, mode) in [("on-add.sh", 0o755), ("on-modify.sh", 0o644), ("README", 0o644)].iter().copied() {
+      let path = dir.join(name);
+      fs::write(&path, "").unwrap();
+      fs::set_permissions(&path, fs::Permissions::from_mode(mode)).unwrap();
+    }
+    assert_eq!(non_executable_hooks(&dir), vec!["on-modify.sh".to_string()]);
+    fs::remove_dir_all(&dir).unwrap();
+  }
+}
@@ -0,0 +1,48 @@
+/// Letters of two letter labels, home row first.
+const LETTERS: &str = "asdfghjklqwertyuiopzxcvbnm";
+
+/// Labels for `count` rows: `1` to `9` for the first nine rows, then `aa`, `as`, `ad`, ... so that
+/// every row is selected with at most two keys. No label is a prefix of another.
+pub fn labels(count: usize) -> Vec<String> {
+  let digits = (1..=9).map(|d| d.to_string());
+  let pairs = LETTERS.chars().flat_map(|a| LETTERS.chars().map(move |b| format!("{}{}", a, b)));
+  digits.chain(pairs).take(count).collect()
+}
+
+/// Result of typing `input` while the labels are shown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Hint {
+  /// The label of the row at this index was typed
+  Row(usize),
+  /// Labels start with the input, wait for the next key
+  Partial,
+  /// No label starts with the input
+  None,
+}
+
+pub fn find(labels: &[String], input: &str) -> Hint {
+  if let Some(i) = labels.iter().position(|l| l == input) {
+    Hint::Row(i)
+  } else if labels.iter().any(|l| l.starts_with(input)) {
+    Hint::Partial
+  } else {
+    Hint::None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_labels() {
+    let labels = labels(12);
+    assert_eq!(labels[..3], ["1", "2", "3"]);
+    assert_eq!(labels[9..], ["aa", "as", "ad"]);
+    assert_eq!(find(&labels, "3"), Hint::Row(2));
+    assert_eq!(find(&labels, "a"), Hint::Partial);
+    assert_eq!(find(&labels, "as"), Hint::Row(10));
+    assert_eq!(find(&labels, "z"), Hint::None);
+    assert_eq!(super::labels(1000).len(), 9 + 26 * 26);
+  }
+}
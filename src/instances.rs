@@ -0,0 +1,155 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use log::{debug, warn};
+use tokio::sync::mpsc;
+
+use crate::utils;
+
+/// Messages exchanged between instances of taskwarrior-tui sharing a data directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Message {
+  /// Tasks or state files were changed by the sender
+  Refresh,
+  /// Another instance was started in single instance mode
+  Focus,
+}
+
+impl Message {
+  fn as_str(&self) -> &'static str {
+    match self {
+      Message::Refresh => "refresh",
+      Message::Focus => "focus",
+    }
+  }
+
+  fn parse(s: &str) -> Option<Self> {
+    match s.trim() {
+      "refresh" => Some(Message::Refresh),
+      "focus" => Some(Message::Focus),
+      _ => None,
+    }
+  }
+}
+
+/// Each running instance listens on a socket in `instances/` in the data directory, named after its pid.
+pub struct Instances {
+  dir: PathBuf,
+  path: Option<PathBuf>,
+  pub rx: mpsc::UnboundedReceiver<Message>,
+}
+
+impl Instances {
+  pub fn new() -> Self {
+    let (_, rx) = mpsc::unbounded_channel();
+    Self {
+      dir: utils::get_data_dir().join("instances"),
+      path: None,
+      rx,
+    }
+  }
+
+  /// Sockets of the other instances that are still running, stale sockets are removed.
+  #[cfg(unix)]
+  pub fn others(&self) -> Vec<PathBuf> {
+    let entries = match std::fs::read_dir(&self.dir) {
+      Ok(entries) => entries,
+      Err(_) => return vec![],
+    };
+    entries
+      .filter_map(|e| e.ok().map(|e| e.path()))
+      .filter(|p| Some(p) != self.path.as_ref())
+      .filter(|p| {
+        let alive = std::os::unix::net::UnixStream::connect(p).is_ok();
+        if !alive {
+          debug!("Removing stale instance socket {:?}", p);
+          std::fs::remove_file(p).ok();
+        }
+        alive
+      })
+      .collect()
+  }
+
+  #[cfg(not(unix))]
+  pub fn others(&self) -> Vec<PathBuf> {
+    vec![]
+  }
+
+  /// Start listening for messages from other instances.
+  #[cfg(unix)]
+  pub fn listen(&mut self) -> Result<()> {
+    use tokio::io::AsyncBufReadExt;
+
+    std::fs::create_dir_all(&self.dir)?;
+    let path = self.dir.join(format!("{}.sock", std::process::id()));
+    std::fs::remove_file(&path).ok();
+    let listener = tokio::net::UnixListener::bind(&path)?;
+    self.path = Some(path);
+    let (tx, rx) = mpsc::unbounded_channel();
+    self.rx = rx;
+    tokio::spawn(async move {
+      while let Ok((stream, _)) = listener.accept().await {
+        let tx = tx.clone();
+        tokio::spawn(async move {
+          let mut lines = tokio::io::BufReader::new(stream).lines();
+          while let Ok(Some(line)) = lines.next_line().await {
+            if let Some(message) = Message::parse(&line) {
+              if tx.send(message).is_err() {
+                return;
+              }
+            }
+          }
+        });
+      }
+    });
+    Ok(())
+  }
+
+  #[cfg(not(unix))]
+  pub fn listen(&mut self) -> Result<()> {
+    Ok(())
+  }
+
+  /// Send `message` to every other running instance.
+  #[cfg(unix)]
+  pub fn notify(&self, message: Message) {
+    use std::io::Write;
+    for path in self.others() {
+      let sent = std::os::unix::net::UnixStream::connect(&path).and_then(|mut s| writeln!(s, "{}", message.as_str()));
+      if let Err(e) = sent {
+        warn!("Unable to notify instance {:?}: {}", path, e);
+      }
+    }
+  }
+
+  #[cfg(not(unix))]
+  pub fn notify(&self, _message: Message) {}
+}
+
+impl Default for Instances {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Drop for Instances {
+  fn drop(&mut self) {
+    if let Some(path) = &self.path {
+      std::fs::remove_file(path).ok();
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_message() {
+    for message in [Message::Refresh, Message::Focus].iter().copied() {
+      assert_eq!(Message::parse(message.as_str()), Some(message));
+    }
+    assert_eq!(Message::parse("refresh\n"), Some(Message::Refresh));
+    assert_eq!(Message::parse("quit"), None);
+  }
+}
@@ -0,0 +1,218 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use log::warn;
+use ratatui::widgets::ListState;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::utils;
+
+const MAX_JOURNAL_ENTRIES: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JournalKind {
+  Add,
+  Log,
+  Modify,
+  Annotate,
+  Done,
+  Delete,
+  Start,
+  Stop,
+  Tag,
+  Edit,
+  Undo,
+  Restore,
+  Compensate,
+}
+
+impl JournalKind {
+  pub fn label(&self) -> &'static str {
+    match self {
+      JournalKind::Add => "add",
+      JournalKind::Log => "log",
+      JournalKind::Modify => "modify",
+      JournalKind::Annotate => "annotate",
+      JournalKind::Done => "done",
+      JournalKind::Delete => "delete",
+      JournalKind::Start => "start",
+      JournalKind::Stop => "stop",
+      JournalKind::Tag => "tag",
+      JournalKind::Edit => "edit",
+      JournalKind::Undo => "undo",
+      JournalKind::Restore => "restore",
+      JournalKind::Compensate => "compensate",
+    }
+  }
+}
+
+/// One mutation performed through the TUI.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+  pub time: String,
+  pub kind: JournalKind,
+  /// Equivalent command line, ready to be pasted in a shell.
+  pub command: String,
+  pub uuids: Vec<Uuid>,
+  /// Arguments to `task` that revert this mutation, if there is such a command.
+  pub compensation: Option<Vec<String>>,
+  #[serde(default)]
+  pub compensated: bool,
+}
+
+impl JournalEntry {
+  pub fn local_time(&self) -> String {
+    DateTime::parse_from_rfc3339(&self.time)
+      .map(|t| t.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string())
+      .unwrap_or_else(|_| self.time.clone())
+  }
+
+  pub fn compensation_command(&self) -> Option<String> {
+    self.compensation.as_ref().map(|args| utils::command_line("task", args))
+  }
+}
+
+/// Persistent log of mutations performed through the TUI, most recent first.
+pub struct Journal {
+  pub entries: Vec<JournalEntry>,
+  pub state: ListState,
+  path: PathBuf,
+}
+
+impl Journal {
+  pub fn new(filename: &str) -> Self {
+    Self {
+      entries: vec![],
+      state: ListState::default(),
+      path: utils::get_data_dir().join(filename),
+    }
+  }
+
+  pub fn load(&mut self) -> Result<()> {
+    if self.path.exists() {
+      let data = fs::read_to_string(&self.path)?;
+      self.entries = serde_json::from_str(&data)?;
+    }
+    Ok(())
+  }
+
+  pub fn write(&self) -> Result<()> {
+    fs::write(&self.path, serde_json::to_string_pretty(&self.entries)?)?;
+    Ok(())
+  }
+
+  pub fn record(&mut self, kind: JournalKind, command: String, uuids: Vec<Uuid>, compensation: Option<Vec<String>>) {
+    self.entries.insert(
+      0,
+      JournalEntry {
+        time: Local::now().to_rfc3339(),
+        kind,
+        command,
+        uuids,
+        compensation,
+        compensated: false,
+      },
+    );
+    self.entries.truncate(MAX_JOURNAL_ENTRIES);
+    if let Err(e) = self.write() {
+      warn!("Unable to write journal to {:?}: {}", self.path, e);
+    }
+  }
+
+  pub fn selected(&self) -> Option<&JournalEntry> {
+    self.state.selected().and_then(|i| self.entries.get(i))
+  }
+
+  pub fn mark_selected_compensated(&mut self) {
+    if let Some(i) = self.state.selected() {
+      if let Some(e) = self.entries.get_mut(i) {
+        e.compensated = true;
+      }
+    }
+    if let Err(e) = self.write() {
+      warn!("Unable to write journal to {:?}: {}", self.path, e);
+    }
+  }
+
+  pub fn select_first_if_needed(&mut self) {
+    if self.entries.is_empty() {
+      self.state.select(None);
+    } else {
+      let i = self.state.selected().unwrap_or(0).min(self.entries.len() - 1);
+      self.state.select(Some(i));
+    }
+  }
+
+  pub fn next(&mut self) {
+    if self.entries.is_empty() {
+      return;
+    }
+    let i = match self.state.selected() {
+      Some(i) if i + 1 < self.entries.len() => i + 1,
+      _ => 0,
+    };
+    self.state.select(Some(i));
+  }
+
+  pub fn previous(&mut self) {
+    if self.entries.is_empty() {
+      return;
+    }
+    let i = match self.state.selected() {
+      Some(0) | None => self.entries.len() - 1,
+      Some(i) => i - 1,
+    };
+    self.state.select(Some(i));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_journal() {
+    let path = std::env::temp_dir().join(format!("taskwarrior-tui-journal-{}.json", std::process::id()));
+    let mut journal = Journal {
+      entries: vec![],
+      state: ListState::default(),
+      path: path.clone(),
+    };
+    let uuid = Uuid::from_u128(1);
+    journal.record(JournalKind::Add, "task add Pay rent".to_string(), vec![uuid], None);
+    journal.record(
+      JournalKind::Done,
+      format!("task {} done", uuid),
+      vec![uuid],
+      Some(vec![uuid.to_string(), "modify".to_string(), "status:pending".to_string()]),
+    );
+    assert_eq!(journal.entries[0].kind, JournalKind::Done);
+    assert_eq!(
+      journal.entries[0].compensation_command().as_deref(),
+      Some("task 00000000-0000-0000-0000-000000000001 modify status:pending")
+    );
+    assert!(journal.entries[1].compensation_command().is_none());
+
+    journal.select_first_if_needed();
+    journal.mark_selected_compensated();
+
+    let mut loaded = Journal {
+      entries: vec![],
+      state: ListState::default(),
+      path: path.clone(),
+    };
+    loaded.load().unwrap();
+    assert_eq!(loaded.entries.len(), 2);
+    assert!(loaded.entries[0].compensated);
+    assert!(!loaded.entries[1].compensated);
+    assert_eq!(loaded.entries[1].command, "task add Pay rent");
+
+    for _ in 0..MAX_JOURNAL_ENTRIES {
+      loaded.record(JournalKind::Undo, "task undo".to_string(), vec![], None);
+    }
+    assert_eq!(loaded.entries.len(), MAX_JOURNAL_ENTRIES);
+    fs::remove_file(&path).ok();
+  }
+}
@@ -16,6 +16,9 @@ pub struct KeyConfig {
   pub up: KeyCode,
   pub page_down: KeyCode,
   pub page_up: KeyCode,
+  /// Moves left within a screen, e.g. between the columns of the header, never in the report
+  pub left: KeyCode,
+  pub right: KeyCode,
   pub delete: KeyCode,
   pub done: KeyCode,
   pub start_stop: KeyCode,
@@ -35,6 +38,18 @@ pub struct KeyConfig {
   pub context_menu: KeyCode,
   pub next_tab: KeyCode,
   pub previous_tab: KeyCode,
+  pub what_changed: KeyCode,
+  pub recent_actions: KeyCode,
+  pub action_history: KeyCode,
+  pub header_focus: KeyCode,
+  pub command_palette: KeyCode,
+  pub toggle_ready: KeyCode,
+  pub hint_select: KeyCode,
+  pub macro_record: KeyCode,
+  pub macro_play: KeyCode,
+  pub calculator: KeyCode,
+  pub screenshot: KeyCode,
+  pub copy_link: KeyCode,
   pub shortcut0: KeyCode,
   pub shortcut1: KeyCode,
   pub shortcut2: KeyCode,
@@ -45,6 +60,12 @@ pub struct KeyConfig {
   pub shortcut7: KeyCode,
   pub shortcut8: KeyCode,
   pub shortcut9: KeyCode,
+  /// Keys of the bindings that are sequences of keys, e.g. `gg`, bound as `KeyCode::Chord(index)`
+  #[serde(skip)]
+  pub chords: Vec<Vec<KeyCode>>,
+  /// Keys translated before the bindings apply, from `uda.taskwarrior-tui.keyconfig.remap.<key>`
+  #[serde(skip)]
+  pub remap: Vec<(KeyCode, KeyCode)>,
 }
 
 impl Default for KeyConfig {
@@ -58,6 +79,8 @@ impl Default for KeyConfig {
       up: KeyCode::Char('k'),
       page_down: KeyCode::Char('J'),
       page_up: KeyCode::Char('K'),
+      left: KeyCode::Char('h'),
+      right: KeyCode::Char('l'),
       delete: KeyCode::Char('x'),
       done: KeyCode::Char('d'),
       start_stop: KeyCode::Char('s'),
@@ -77,6 +100,18 @@ impl Default for KeyConfig {
       context_menu: KeyCode::Char('c'),
       next_tab: KeyCode::Char(']'),
       previous_tab: KeyCode::Char('['),
+      what_changed: KeyCode::Char('W'),
+      recent_actions: KeyCode::Char('T'),
+      action_history: KeyCode::Char('H'),
+      header_focus: KeyCode::Char('S'),
+      command_palette: KeyCode::Char('p'),
+      toggle_ready: KeyCode::Char('R'),
+      hint_select: KeyCode::Char('f'),
+      macro_record: KeyCode::Char('Q'),
+      macro_play: KeyCode::Char('@'),
+      calculator: KeyCode::Alt('c'),
+      screenshot: KeyCode::F(12),
+      copy_link: KeyCode::Char('Y'),
       shortcut0: KeyCode::Char('0'),
       shortcut1: KeyCode::Char('1'),
       shortcut2: KeyCode::Char('2'),
@@ -87,6 +122,8 @@ impl Default for KeyConfig {
       shortcut7: KeyCode::Char('7'),
       shortcut8: KeyCode::Char('8'),
       shortcut9: KeyCode::Char('9'),
+      chords: vec![],
+      remap: vec![],
     }
   }
 }
@@ -99,32 +136,47 @@ impl KeyConfig {
   }
 
   pub fn update(&mut self, data: &str) -> Result<()> {
-    let quit = Self::get_config("uda.taskwarrior-tui.keyconfig.quit", data);
-    let refresh = Self::get_config("uda.taskwarrior-tui.keyconfig.refresh", data);
-    let go_to_bottom = Self::get_config("uda.taskwarrior-tui.keyconfig.go-to-bottom", data);
-    let go_to_top = Self::get_config("uda.taskwarrior-tui.keyconfig.go-to-top", data);
-    let down = Self::get_config("uda.taskwarrior-tui.keyconfig.down", data);
-    let up = Self::get_config("uda.taskwarrior-tui.keyconfig.up", data);
-    let page_down = Self::get_config("uda.taskwarrior-tui.keyconfig.page-down", data);
-    let page_up = Self::get_config("uda.taskwarrior-tui.keyconfig.page-up", data);
-    let delete = Self::get_config("uda.taskwarrior-tui.keyconfig.delete", data);
-    let done = Self::get_config("uda.taskwarrior-tui.keyconfig.done", data);
-    let start_stop = Self::get_config("uda.taskwarrior-tui.keyconfig.start-stop", data);
-    let quick_tag = Self::get_config("uda.taskwarrior-tui.keyconfig.quick-tag", data);
-    let select = Self::get_config("uda.taskwarrior-tui.keyconfig.select", data);
-    let select_all = Self::get_config("uda.taskwarrior-tui.keyconfig.select-all", data);
-    let undo = Self::get_config("uda.taskwarrior-tui.keyconfig.undo", data);
-    let edit = Self::get_config("uda.taskwarrior-tui.keyconfig.edit", data);
-    let modify = Self::get_config("uda.taskwarrior-tui.keyconfig.modify", data);
-    let shell = Self::get_config("uda.taskwarrior-tui.keyconfig.shell", data);
-    let log = Self::get_config("uda.taskwarrior-tui.keyconfig.log", data);
-    let add = Self::get_config("uda.taskwarrior-tui.keyconfig.add", data);
-    let annotate = Self::get_config("uda.taskwarrior-tui.keyconfig.annotate", data);
-    let filter = Self::get_config("uda.taskwarrior-tui.keyconfig.filter", data);
-    let zoom = Self::get_config("uda.taskwarrior-tui.keyconfig.zoom", data);
-    let context_menu = Self::get_config("uda.taskwarrior-tui.keyconfig.context-menu", data);
-    let next_tab = Self::get_config("uda.taskwarrior-tui.keyconfig.next-tab", data);
-    let previous_tab = Self::get_config("uda.taskwarrior-tui.keyconfig.previous-tab", data);
+    self.chords.clear();
+    let quit = self.get_config("uda.taskwarrior-tui.keyconfig.quit", data);
+    let refresh = self.get_config("uda.taskwarrior-tui.keyconfig.refresh", data);
+    let go_to_bottom = self.get_config("uda.taskwarrior-tui.keyconfig.go-to-bottom", data);
+    let go_to_top = self.get_config("uda.taskwarrior-tui.keyconfig.go-to-top", data);
+    let down = self.get_config("uda.taskwarrior-tui.keyconfig.down", data);
+    let up = self.get_config("uda.taskwarrior-tui.keyconfig.up", data);
+    let page_down = self.get_config("uda.taskwarrior-tui.keyconfig.page-down", data);
+    let page_up = self.get_config("uda.taskwarrior-tui.keyconfig.page-up", data);
+    let left = self.get_config("uda.taskwarrior-tui.keyconfig.left", data);
+    let right = self.get_config("uda.taskwarrior-tui.keyconfig.right", data);
+    let delete = self.get_config("uda.taskwarrior-tui.keyconfig.delete", data);
+    let done = self.get_config("uda.taskwarrior-tui.keyconfig.done", data);
+    let start_stop = self.get_config("uda.taskwarrior-tui.keyconfig.start-stop", data);
+    let quick_tag = self.get_config("uda.taskwarrior-tui.keyconfig.quick-tag", data);
+    let select = self.get_config("uda.taskwarrior-tui.keyconfig.select", data);
+    let select_all = self.get_config("uda.taskwarrior-tui.keyconfig.select-all", data);
+    let undo = self.get_config("uda.taskwarrior-tui.keyconfig.undo", data);
+    let edit = self.get_config("uda.taskwarrior-tui.keyconfig.edit", data);
+    let modify = self.get_config("uda.taskwarrior-tui.keyconfig.modify", data);
+    let shell = self.get_config("uda.taskwarrior-tui.keyconfig.shell", data);
+    let log = self.get_config("uda.taskwarrior-tui.keyconfig.log", data);
+    let add = self.get_config("uda.taskwarrior-tui.keyconfig.add", data);
+    let annotate = self.get_config("uda.taskwarrior-tui.keyconfig.annotate", data);
+    let filter = self.get_config("uda.taskwarrior-tui.keyconfig.filter", data);
+    let zoom = self.get_config("uda.taskwarrior-tui.keyconfig.zoom", data);
+    let context_menu = self.get_config("uda.taskwarrior-tui.keyconfig.context-menu", data);
+    let next_tab = self.get_config("uda.taskwarrior-tui.keyconfig.next-tab", data);
+    let previous_tab = self.get_config("uda.taskwarrior-tui.keyconfig.previous-tab", data);
+    let what_changed = self.get_config("uda.taskwarrior-tui.keyconfig.what-changed", data);
+    let recent_actions = self.get_config("uda.taskwarrior-tui.keyconfig.recent-actions", data);
+    let action_history = self.get_config("uda.taskwarrior-tui.keyconfig.action-history", data);
+    let header_focus = self.get_config("uda.taskwarrior-tui.keyconfig.header-focus", data);
+    let command_palette = self.get_config("uda.taskwarrior-tui.keyconfig.command-palette", data);
+    let toggle_ready = self.get_config("uda.taskwarrior-tui.keyconfig.toggle-ready", data);
+    let hint_select = self.get_config("uda.taskwarrior-tui.keyconfig.hint-select", data);
+    let macro_record = self.get_config("uda.taskwarrior-tui.keyconfig.macro-record", data);
+    let macro_play = self.get_config("uda.taskwarrior-tui.keyconfig.macro-play", data);
+    let calculator = self.get_config("uda.taskwarrior-tui.keyconfig.calculator", data);
+    let screenshot = self.get_config("uda.taskwarrior-tui.keyconfig.screenshot", data);
+    let copy_link = self.get_config("uda.taskwarrior-tui.keyconfig.copy-link", data);
 
     self.quit = quit.unwrap_or(self.quit);
     self.refresh = refresh.unwrap_or(self.refresh);
@@ -134,6 +186,8 @@ impl KeyConfig {
     self.up = up.unwrap_or(self.up);
     self.page_down = page_down.unwrap_or(self.page_down);
     self.page_up = page_up.unwrap_or(self.page_up);
+    self.left = left.unwrap_or(self.left);
+    self.right = right.unwrap_or(self.right);
     self.delete = delete.unwrap_or(self.delete);
     self.done = done.unwrap_or(self.done);
     self.start_stop = start_stop.unwrap_or(self.start_stop);
@@ -152,6 +206,19 @@ impl KeyConfig {
     self.context_menu = context_menu.unwrap_or(self.context_menu);
     self.next_tab = next_tab.unwrap_or(self.next_tab);
     self.previous_tab = previous_tab.unwrap_or(self.previous_tab);
+    self.what_changed = what_changed.unwrap_or(self.what_changed);
+    self.recent_actions = recent_actions.unwrap_or(self.recent_actions);
+    self.action_history = action_history.unwrap_or(self.action_history);
+    self.header_focus = header_focus.unwrap_or(self.header_focus);
+    self.command_palette = command_palette.unwrap_or(self.command_palette);
+    self.toggle_ready = toggle_ready.unwrap_or(self.toggle_ready);
+    self.hint_select = hint_select.unwrap_or(self.hint_select);
+    self.macro_record = macro_record.unwrap_or(self.macro_record);
+    self.macro_play = macro_play.unwrap_or(self.macro_play);
+    self.calculator = calculator.unwrap_or(self.calculator);
+    self.screenshot = screenshot.unwrap_or(self.screenshot);
+    self.copy_link = copy_link.unwrap_or(self.copy_link);
+    self.remap = Self::get_remap(data);
 
     self.check()
   }
@@ -185,6 +252,18 @@ impl KeyConfig {
       &self.context_menu,
       &self.next_tab,
       &self.previous_tab,
+      &self.what_changed,
+      &self.recent_actions,
+      &self.action_history,
+      &self.header_focus,
+      &self.command_palette,
+      &self.toggle_ready,
+      &self.hint_select,
+      &self.macro_record,
+      &self.macro_play,
+      &self.calculator,
+      &self.screenshot,
+      &self.copy_link,
     ];
     let l = elements.len();
     elements.dedup();
@@ -195,28 +274,165 @@ impl KeyConfig {
     }
   }
 
-  fn get_config(config: &str, data: &str) -> Option<KeyCode> {
-    for line in data.split('\n') {
-      if line.starts_with(config) {
-        let line = line.trim_start_matches(config).trim_start().trim_end().to_string();
-        if has_just_one_char(&line) {
-          return Some(KeyCode::Char(line.chars().next().unwrap()));
-        } else {
-          error!("Found multiple characters in {} for {}", line, config);
+  /// Name of a key, or of the keys of a chord, as written in the key config.
+  pub fn key_label(&self, key: KeyCode) -> String {
+    match key {
+      KeyCode::Chord(i) => self
+        .chords
+        .get(i as usize)
+        .map(|keys| keys.iter().map(|k| key_name(*k)).collect::<Vec<_>>().join(" "))
+        .unwrap_or_default(),
+      key => key_name(key),
+    }
+  }
+
+  /// Names of the key bindings, as in `uda.taskwarrior-tui.keyconfig.<name>`, with their keys.
+  pub fn bindings(&self) -> Vec<(String, KeyCode)> {
+    match serde_json::to_value(self) {
+      Ok(serde_json::Value::Object(map)) => map
+        .into_iter()
+        .filter_map(|(name, key)| serde_json::from_value(key).ok().map(|key| (name.replace('_', "-"), key)))
+        .collect(),
+      _ => vec![],
+    }
+  }
+
+  /// Keys translated into other keys, e.g. `uda.taskwarrior-tui.keyconfig.remap.n=j`.
+  fn get_remap(data: &str) -> Vec<(KeyCode, KeyCode)> {
+    data
+      .lines()
+      .filter_map(|line| line.strip_prefix("uda.taskwarrior-tui.keyconfig.remap."))
+      .filter_map(|line| {
+        let (from, to) = line.split_once(char::is_whitespace)?;
+        match (parse_key(from), parse_key(to.trim())) {
+          (Some(from), Some(to)) => Some((from, to)),
+          _ => {
+            error!("Unknown key in remap of {} to {}", from, to.trim());
+            None
+          }
         }
+      })
+      .collect()
+  }
+
+  fn get_config(&mut self, config: &str, data: &str) -> Option<KeyCode> {
+    for line in data.split('\n') {
+      let value = if line.starts_with(config) {
+        line.trim_start_matches(config)
       } else if line.starts_with(&config.replace('-', "_")) {
-        let line = line.trim_start_matches(&config.replace('-', "_")).trim_start().trim_end().to_string();
-        if has_just_one_char(&line) {
-          return Some(KeyCode::Char(line.chars().next().unwrap()));
-        } else {
-          error!("Found multiple characters in {} for {}", line, config);
+        line.trim_start_matches(&config.replace('-', "_"))
+      } else {
+        continue;
+      };
+      let value = value.trim();
+      if let Some(key) = parse_key(value) {
+        return Some(key);
+      }
+      match parse_sequence(value) {
+        Some(keys) if self.chords.len() < u8::MAX as usize => {
+          self.chords.push(keys);
+          return Some(KeyCode::Chord((self.chords.len() - 1) as u8));
         }
+        _ => error!("Unknown key {} for {}", value, config),
       }
     }
     None
   }
 }
 
+/// Parse a key of the keyconfig: a single character, or a key name with modifiers such as
+/// `ctrl-a`, `ctrl-shift-a`, `alt-enter`, `keypad-5` or `f5`. Combinations other than `ctrl`, `alt`
+/// and a single character need a terminal with the kitty keyboard protocol.
+pub fn parse_key(s: &str) -> Option<KeyCode> {
+  if has_just_one_char(s) {
+    return s.chars().next().map(KeyCode::Char);
+  }
+  let (mut ctrl, mut alt, mut shift, mut keypad) = (false, false, false, false);
+  let mut rest = s;
+  while let Some((modifier, key)) = rest.split_once('-').filter(|(_, key)| !key.is_empty()) {
+    match modifier.to_lowercase().as_str() {
+      "ctrl" | "c" => ctrl = true,
+      "alt" | "a" | "m" => alt = true,
+      "shift" | "s" => shift = true,
+      "keypad" | "kp" => keypad = true,
+      _ => return None,
+    }
+    rest = key;
+  }
+  let name = rest.to_lowercase();
+  let c = if has_just_one_char(rest) { rest.chars().next() } else { None };
+  let key = match (ctrl, alt, shift, keypad, name.as_str(), c) {
+    (false, false, false, true, "enter", _) => KeyCode::Keypad('\n'),
+    (false, false, false, true, _, Some(c)) => KeyCode::Keypad(c),
+    (true, false, false, false, _, Some(c)) => KeyCode::Ctrl(c.to_ascii_lowercase()),
+    (true, false, true, false, _, Some(c)) => KeyCode::CtrlShift(c.to_ascii_lowercase()),
+    (false, true, false, false, _, Some(c)) => KeyCode::Alt(c),
+    (true, false, false, false, "enter", _) => KeyCode::CtrlEnter,
+    (false, false, true, false, "enter", _) => KeyCode::ShiftEnter,
+    (false, true, false, false, "enter", _) => KeyCode::AltEnter,
+    (true, false, false, false, "backspace", _) => KeyCode::CtrlBackspace,
+    (true, false, false, false, "delete", _) => KeyCode::CtrlDelete,
+    (false, true, false, false, "backspace", _) => KeyCode::AltBackspace,
+    (false, true, false, false, "delete", _) => KeyCode::AltDelete,
+    (false, false, true, false, "tab", _) => KeyCode::BackTab,
+    (false, false, false, false, name, _) => match name {
+      "enter" => KeyCode::Char('\n'),
+      "esc" => KeyCode::Esc,
+      "tab" => KeyCode::Tab,
+      "backspace" => KeyCode::Backspace,
+      "delete" => KeyCode::Delete,
+      "insert" => KeyCode::Insert,
+      "home" => KeyCode::Home,
+      "end" => KeyCode::End,
+      "pageup" => KeyCode::PageUp,
+      "pagedown" => KeyCode::PageDown,
+      "up" => KeyCode::Up,
+      "down" => KeyCode::Down,
+      "left" => KeyCode::Left,
+      "right" => KeyCode::Right,
+      "space" => KeyCode::Char(' '),
+      f => KeyCode::F(f.strip_prefix('f')?.parse().ok()?),
+    },
+    _ => return None,
+  };
+  Some(key)
+}
+
+/// Parse a sequence of keys, either characters written together like `gg`, or keys separated by
+/// spaces like `ctrl-x k`.
+pub fn parse_sequence(s: &str) -> Option<Vec<KeyCode>> {
+  let keys: Option<Vec<KeyCode>> = if s.contains(' ') {
+    s.split_whitespace().map(parse_key).collect()
+  } else {
+    Some(s.chars().map(KeyCode::Char).collect())
+  };
+  keys.filter(|k| (2..=4).contains(&k.len()))
+}
+
+/// Name of a key as it is written in the keyconfig, the reverse of [`parse_key`].
+pub fn key_name(key: KeyCode) -> String {
+  match key {
+    KeyCode::Char('\n') => "enter".to_string(),
+    KeyCode::Char(' ') => "space".to_string(),
+    KeyCode::Char(c) => c.to_string(),
+    KeyCode::Ctrl(c) => format!("ctrl-{}", c),
+    KeyCode::CtrlShift(c) => format!("ctrl-shift-{}", c),
+    KeyCode::Alt(c) => format!("alt-{}", c),
+    KeyCode::Keypad('\n') => "keypad-enter".to_string(),
+    KeyCode::Keypad(c) => format!("keypad-{}", c),
+    KeyCode::CtrlEnter => "ctrl-enter".to_string(),
+    KeyCode::ShiftEnter => "shift-enter".to_string(),
+    KeyCode::AltEnter => "alt-enter".to_string(),
+    KeyCode::CtrlBackspace => "ctrl-backspace".to_string(),
+    KeyCode::CtrlDelete => "ctrl-delete".to_string(),
+    KeyCode::AltBackspace => "alt-backspace".to_string(),
+    KeyCode::AltDelete => "alt-delete".to_string(),
+    KeyCode::BackTab => "shift-tab".to_string(),
+    KeyCode::F(n) => format!("f{}", n),
+    k => format!("{:?}", k).to_lowercase(),
+  }
+}
+
 fn has_just_one_char(s: &str) -> bool {
   let mut chars = s.chars();
   chars.next().is_some() && chars.next().is_none()
@@ -225,4 +441,24 @@ fn has_just_one_char(s: &str) -> bool {
 #[cfg(test)]
 mod tests {
   use super::*;
+
+  #[test]
+  fn test_parse_key() {
+    assert_eq!(parse_key("x"), Some(KeyCode::Char('x')));
+    assert_eq!(parse_key("-"), Some(KeyCode::Char('-')));
+    assert_eq!(parse_key("ctrl-shift-A"), Some(KeyCode::CtrlShift('a')));
+    assert_eq!(parse_key("ctrl--"), Some(KeyCode::Ctrl('-')));
+    assert_eq!(parse_key("Ctrl-Enter"), Some(KeyCode::CtrlEnter));
+    assert_eq!(parse_key("keypad-5"), Some(KeyCode::Keypad('5')));
+    assert_eq!(parse_key("f12"), Some(KeyCode::F(12)));
+    assert_eq!(parse_key("hyper-x"), None);
+    assert_eq!(parse_key("xy"), None);
+    assert_eq!(parse_sequence("gg"), Some(vec![KeyCode::Char('g'), KeyCode::Char('g')]));
+    assert_eq!(parse_sequence("ctrl-x k"), Some(vec![KeyCode::Ctrl('x'), KeyCode::Char('k')]));
+    assert_eq!(parse_sequence("ctrl-x"), None);
+    assert_eq!(parse_sequence("abcdef"), None);
+    for key in ["ctrl-shift-k", "alt-enter", "keypad-enter", "shift-tab", "f5", "enter", "q"].iter() {
+      assert_eq!(parse_key(key).map(key_name).as_deref(), Some(*key));
+    }
+  }
 }
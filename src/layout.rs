@@ -0,0 +1,149 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{event::KeyCode, utils};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum View {
+  Tasks,
+  Projects,
+  Calendar,
+  /// The checklist of the weekly review
+  Review,
+}
+
+/// A named arrangement of the interface that can be switched to at once.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayoutPreset {
+  pub name: String,
+  pub view: View,
+  /// Show the task details below the report.
+  pub details: bool,
+  /// Filter applied to the report, the current filter is kept when missing.
+  #[serde(default)]
+  pub filter: Option<String>,
+  /// Key switching to this layout, a single character or `F1` to `F12`.
+  #[serde(default)]
+  pub key: Option<String>,
+}
+
+impl LayoutPreset {
+  pub fn key_code(&self) -> Option<KeyCode> {
+    self.key.as_deref().and_then(parse_key)
+  }
+}
+
+pub fn parse_key(key: &str) -> Option<KeyCode> {
+  let mut chars = key.chars();
+  match (chars.next(), chars.next()) {
+    (Some(c), None) => Some(KeyCode::Char(c)),
+    _ => match key.strip_prefix('F').and_then(|n| n.parse::<u8>().ok()) {
+      Some(n) if (1..=12).contains(&n) => Some(KeyCode::F(n)),
+      _ => None,
+    },
+  }
+}
+
+fn default_presets() -> Vec<LayoutPreset> {
+  vec![
+    LayoutPreset {
+      name: "triage".to_string(),
+      view: View::Tasks,
+      details: true,
+      filter: None,
+      key: Some("F1".to_string()),
+    },
+    LayoutPreset {
+      name: "planning".to_string(),
+      view: View::Calendar,
+      details: false,
+      filter: None,
+      key: Some("F2".to_string()),
+    },
+    LayoutPreset {
+      name: "focus".to_string(),
+      view: View::Tasks,
+      details: false,
+      filter: None,
+      key: Some("F3".to_string()),
+    },
+    LayoutPreset {
+      name: "review".to_string(),
+      view: View::Review,
+      details: true,
+      filter: None,
+      key: Some("F4".to_string()),
+    },
+  ]
+}
+
+/// Layout presets, stored in the data directory.
+pub struct Layouts {
+  pub presets: Vec<LayoutPreset>,
+  path: PathBuf,
+}
+
+impl Layouts {
+  pub fn new(filename: &str) -> Self {
+    Self {
+      presets: default_presets(),
+      path: utils::get_data_dir().join(filename),
+    }
+  }
+
+  pub fn load(&mut self) -> Result<()> {
+    if self.path.exists() {
+      let data = fs::read_to_string(&self.path)?;
+      self.presets = serde_json::from_str(&data)?;
+    }
+    Ok(())
+  }
+
+  pub fn write(&self) -> Result<()> {
+    fs::write(&self.path, serde_json::to_string_pretty(&self.presets)?)?;
+    Ok(())
+  }
+
+  pub fn get(&self, name: &str) -> Option<&LayoutPreset> {
+    self.presets.iter().find(|p| p.name == name)
+  }
+
+  pub fn by_key(&self, key: KeyCode) -> Option<&LayoutPreset> {
+    self.presets.iter().find(|p| p.key_code() == Some(key))
+  }
+
+  /// Add a preset, replacing the one with the same name but keeping its key.
+  pub fn save(&mut self, mut preset: LayoutPreset) -> Result<()> {
+    match self.presets.iter_mut().find(|p| p.name == preset.name) {
+      Some(existing) => {
+        if preset.key.is_none() {
+          preset.key = existing.key.take();
+        }
+        *existing = preset;
+      }
+      None => self.presets.push(preset),
+    }
+    self.write()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_default_presets() {
+    let layouts = Layouts {
+      presets: default_presets(),
+      path: PathBuf::new(),
+    };
+    let names: Vec<&str> = layouts.presets.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["triage", "planning", "focus", "review"]);
+    assert_eq!(layouts.by_key(KeyCode::F(4)).map(|p| p.view), Some(View::Review));
+    assert_eq!(parse_key("x"), Some(KeyCode::Char('x')));
+    assert_eq!(parse_key("F13"), None);
+  }
+}
@@ -0,0 +1,174 @@
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  sync::OnceLock,
+};
+
+use anyhow::{anyhow, Result};
+use log::{warn, LevelFilter};
+use log4rs::{
+  append::rolling_file::{
+    policy::compound::{roll::fixed_window::FixedWindowRoller, trigger::size::SizeTrigger, CompoundPolicy},
+    RollingFileAppender,
+  },
+  config::{Appender, Config, Logger, Root},
+  encode::pattern::PatternEncoder,
+};
+
+use crate::utils;
+
+const LOG_PATTERN: &str = "{d(%Y-%m-%d %H:%M:%S)} | {l} | {M} | {f}:{L} | {m}{n}";
+
+const CRATE: &str = "taskwarrior_tui";
+
+/// The log file is rotated when it grows over this size.
+const MAX_LOG_SIZE: u64 = 5 * 1024 * 1024;
+
+/// Number of rotated log files that are kept.
+const MAX_LOG_FILES: u32 = 3;
+
+static HANDLE: OnceLock<log4rs::Handle> = OnceLock::new();
+
+pub fn log_file() -> PathBuf {
+  utils::get_state_dir().join("taskwarrior-tui.log")
+}
+
+fn parse_level(level: &str) -> Result<LevelFilter> {
+  match level.trim().to_lowercase().as_str() {
+    "off" => Ok(LevelFilter::Off),
+    "error" => Ok(LevelFilter::Error),
+    "warn" => Ok(LevelFilter::Warn),
+    "info" => Ok(LevelFilter::Info),
+    "debug" => Ok(LevelFilter::Debug),
+    "trace" => Ok(LevelFilter::Trace),
+    l => Err(anyhow!("unknown log level `{}`", l)),
+  }
+}
+
+/// Parse a filter in the style of `RUST_LOG`, e.g. `warn,app=debug,event=trace`.
+/// Modules can be given with or without the `taskwarrior_tui::` prefix.
+pub fn parse_filter(spec: &str) -> Result<(LevelFilter, Vec<(String, LevelFilter)>)> {
+  let mut default = LevelFilter::Info;
+  let mut modules = vec![];
+  for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+    match directive.split_once('=') {
+      Some((module, level)) => {
+        let module = module.trim();
+        let module = if module == CRATE || module.starts_with(&format!("{}::", CRATE)) {
+          module.to_string()
+        } else {
+          format!("{}::{}", CRATE, module)
+        };
+        modules.push((module, parse_level(level)?));
+      }
+      None => default = parse_level(directive)?,
+    }
+  }
+  Ok((default, modules))
+}
+
+/// Where the log was written before it moved to the state directory.
+fn old_log_file() -> PathBuf {
+  utils::get_data_dir().join("taskwarrior-tui.log")
+}
+
+/// Move the log from where earlier versions wrote it, unless a log was written in the new place already.
+fn migrate(old: &Path, new: &Path) {
+  if old == new || !old.exists() || new.exists() {
+    return;
+  }
+  if fs::rename(old, new).is_err() {
+    // the directories may be on different file systems
+    if fs::copy(old, new).is_ok() {
+      fs::remove_file(old).ok();
+    }
+  }
+}
+
+fn build_config(spec: &str) -> Config {
+  let path = log_file();
+  let roller = FixedWindowRoller::builder()
+    .build(&path.with_extension("{}.log").to_string_lossy(), MAX_LOG_FILES)
+    .expect("Failed to build log file roller.");
+  let policy = CompoundPolicy::new(Box::new(SizeTrigger::new(MAX_LOG_SIZE)), Box::new(roller));
+  let logfile = RollingFileAppender::builder()
+    .encoder(Box::new(PatternEncoder::new(LOG_PATTERN)))
+    .build(&path, Box::new(policy))
+    .expect("Failed to build log file appender.");
+
+  let (default, modules) = parse_filter(spec).unwrap_or((LevelFilter::Info, vec![]));
+  let mut config = Config::builder()
+    .appender(Appender::builder().build("logfile", Box::new(logfile)))
+    .logger(Logger::builder().build(CRATE, default));
+  for (module, level) in modules {
+    config = config.logger(Logger::builder().build(module, level));
+  }
+  config
+    .build(Root::builder().appender("logfile").build(LevelFilter::Info))
+    .expect("Failed to build logging config.")
+}
+
+/// Log at the level of `TASKWARRIOR_TUI_LOG_LEVEL`, `info` by default until `set_level` is called
+/// with the level of the taskrc.
+pub fn initialize_logging() {
+  migrate(&old_log_file(), &log_file());
+  let spec = std::env::var("TASKWARRIOR_TUI_LOG_LEVEL").unwrap_or_else(|_| "info".to_string());
+  if let Err(e) = parse_filter(&spec) {
+    eprintln!("Ignoring invalid log level `{}`: {}", spec, e);
+  }
+  let handle = log4rs::init_config(build_config(&spec)).expect("Failed to initialize logging.");
+  HANDLE.set(handle).ok();
+}
+
+/// Log at the level set by `uda.taskwarrior-tui.log.level`, unless `TASKWARRIOR_TUI_LOG_LEVEL`
+/// takes precedence.
+pub fn set_level(spec: &str) {
+  if std::env::var("TASKWARRIOR_TUI_LOG_LEVEL").is_ok() {
+    return;
+  }
+  if let Err(e) = parse_filter(spec) {
+    warn!("Ignoring invalid log level `{}`: {}", spec, e);
+    return;
+  }
+  if let Some(handle) = HANDLE.get() {
+    handle.set_config(build_config(spec));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_filter() {
+    assert_eq!(parse_filter("debug").unwrap(), (LevelFilter::Debug, vec![]));
+    assert_eq!(
+      parse_filter("warn, app=trace,taskwarrior_tui::event=off").unwrap(),
+      (
+        LevelFilter::Warn,
+        vec![
+          ("taskwarrior_tui::app".to_string(), LevelFilter::Trace),
+          ("taskwarrior_tui::event".to_string(), LevelFilter::Off),
+        ]
+      )
+    );
+    assert!(parse_filter("app=loud").is_err());
+  }
+
+  #[test]
+  fn test_migrate() {
+    let dir = std::env::temp_dir().join(format!("taskwarrior-tui-logs-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let (old, new) = (dir.join("old.log"), dir.join("new.log"));
+    fs::write(&old, "old").unwrap();
+    migrate(&old, &new);
+    assert!(!old.exists());
+    assert_eq!(fs::read_to_string(&new).unwrap(), "old");
+
+    // a log written in the new place is not overwritten
+    fs::write(&old, "older").unwrap();
+    migrate(&old, &new);
+    assert_eq!(fs::read_to_string(&new).unwrap(), "old");
+    fs::remove_dir_all(&dir).ok();
+  }
+}
@@ -4,21 +4,87 @@
 #![allow(clippy::too_many_arguments)]
 
 mod action;
+mod activity;
+mod aging;
 mod app;
+mod backup;
+mod badges;
+mod bundle;
+mod calc;
 mod calendar;
+mod capabilities;
+mod capacity;
+mod cheatsheet;
+mod chips;
 mod cli;
+mod colorblind;
 mod completion;
+mod compose;
 mod config;
+mod cursor;
+mod datepreview;
+mod deeplink;
+mod demo;
+mod density;
+mod details;
+mod diff;
+mod draft;
+mod editor;
 mod event;
+mod filter;
+mod focus;
+mod goals;
+#[cfg(test)]
+mod harness;
+mod health;
 mod help;
+mod hints;
 mod history;
+mod instances;
+mod journal;
 mod keyconfig;
+mod layout;
+mod logging;
+mod man;
+mod middleware;
+mod nextaction;
+mod notify;
+mod overrides;
+mod palette;
 mod pane;
+mod perf;
+mod prefetch;
+mod preview;
+mod quickadd;
+mod recent;
+mod recording;
+mod replace;
+mod review;
+mod rules;
+mod screenshot;
 mod scrollbar;
+mod secret;
+mod snapshot;
+mod sort;
+mod spellcheck;
+mod startmenu;
+mod status;
+mod stream;
 mod table;
 mod task_report;
+mod team;
+mod ticks;
+mod timezone;
+mod tooltip;
+mod tour;
 mod ui;
+mod update;
+mod urgency;
+mod usage;
 mod utils;
+mod wip;
+mod workspace;
+mod writequeue;
 
 use std::{
   env,
@@ -29,66 +95,28 @@ use std::{
   time::Duration,
 };
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use app::{Mode, TaskwarriorTui};
 use crossterm::{
-  cursor,
+  cursor::{SetCursorStyle, Show},
   event::{DisableMouseCapture, EnableMouseCapture, EventStream},
   execute,
   terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use futures::stream::{FuturesUnordered, StreamExt};
 use log::{debug, error, info, log_enabled, trace, warn, Level, LevelFilter};
-use log4rs::{
-  append::file::FileAppender,
-  config::{Appender, Config, Logger, Root},
-  encode::pattern::PatternEncoder,
-};
 use path_clean::PathClean;
 use ratatui::{backend::CrosstermBackend, Terminal};
 
 use crate::{action::Action, event::Event, keyconfig::KeyConfig};
 
-const LOG_PATTERN: &str = "{d(%Y-%m-%d %H:%M:%S)} | {l} | {f}:{L} | {m}{n}";
-
 pub fn destruct_terminal() {
   disable_raw_mode().unwrap();
+  if let Err(e) = capabilities::disable_kitty_keyboard() {
+    error!("Unable to disable the kitty keyboard protocol: {}", e);
+  }
   execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture).unwrap();
-  execute!(io::stdout(), cursor::Show).unwrap();
-}
-
-pub fn initialize_logging() {
-  let data_local_dir = if let Ok(s) = std::env::var("TASKWARRIOR_TUI_DATA") {
-    PathBuf::from(s)
-  } else {
-    dirs::data_local_dir()
-      .expect("Unable to find data directory for taskwarrior-tui")
-      .join("taskwarrior-tui")
-  };
-
-  std::fs::create_dir_all(&data_local_dir).unwrap_or_else(|_| panic!("Unable to create {:?}", data_local_dir));
-
-  let logfile = FileAppender::builder()
-    .encoder(Box::new(PatternEncoder::new(LOG_PATTERN)))
-    .append(false)
-    .build(data_local_dir.join("taskwarrior-tui.log"))
-    .expect("Failed to build log file appender.");
-
-  let levelfilter = match std::env::var("TASKWARRIOR_TUI_LOG_LEVEL").unwrap_or_else(|_| "info".to_string()).as_str() {
-    "off" => LevelFilter::Off,
-    "warn" => LevelFilter::Warn,
-    "info" => LevelFilter::Info,
-    "debug" => LevelFilter::Debug,
-    "trace" => LevelFilter::Trace,
-    _ => LevelFilter::Info,
-  };
-  let config = Config::builder()
-    .appender(Appender::builder().build("logfile", Box::new(logfile)))
-    .logger(Logger::builder().build("taskwarrior_tui", levelfilter))
-    .build(Root::builder().appender("logfile").build(LevelFilter::Info))
-    .expect("Failed to build logging config.");
-
-  log4rs::init_config(config).expect("Failed to initialize logging.");
+  execute!(io::stdout(), Show, SetCursorStyle::DefaultUserShape).unwrap();
 }
 
 pub fn absolute_path(path: impl AsRef<Path>) -> io::Result<PathBuf> {
@@ -104,19 +132,260 @@ pub fn absolute_path(path: impl AsRef<Path>) -> io::Result<PathBuf> {
   Ok(absolute_path)
 }
 
-async fn tui_main(report: &str) -> Result<()> {
+/// Names printed by a taskwarrior helper command such as `task _reports`, one per line.
+fn task_names(command: &str) -> Vec<String> {
+  match overrides::task().arg(command).output() {
+    Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+      .lines()
+      .map(str::trim)
+      .filter(|l| !l.is_empty())
+      .map(ToString::to_string)
+      .collect(),
+    _ => vec![],
+  }
+}
+
+/// Print completions for `shell`, completing `--report` and `--context` with the reports and
+/// contexts of the taskrc at the time they are generated.
+fn print_completions(shell: clap_complete::Shell) {
+  let mut app = cli::generate_cli_app();
+  let reports = task_names("_reports");
+  if !reports.is_empty() {
+    app = app.mut_arg("report", |a| a.value_parser(clap::builder::PossibleValuesParser::new(reports)));
+  }
+  let mut contexts = task_names("_context");
+  if !contexts.is_empty() {
+    contexts.push("none".to_string());
+    app = app.mut_arg("context", |a| a.value_parser(clap::builder::PossibleValuesParser::new(contexts)));
+  }
+  let name = app.get_name().to_string();
+  clap_complete::generate(shell, &mut app, name, &mut io::stdout());
+}
+
+/// The output of `task show`, without colors or wrapping.
+fn task_show() -> Result<String> {
+  let show = overrides::task()
+    .arg("rc.color=off")
+    .arg("rc._forcecolor=off")
+    .arg("rc.defaultwidth=0")
+    .arg("show")
+    .output()
+    .context("Unable to run `task show`.")?;
+  Ok(String::from_utf8_lossy(&show.stdout).to_string())
+}
+
+/// Write the bundle of the `settings export` subcommand, to `output` or to stdout.
+fn export_settings(output: Option<&String>) -> Result<()> {
+  let mut layouts = layout::Layouts::new("layouts.json");
+  layouts.load()?;
+  let bundle = bundle::Bundle::new(bundle::settings(&task_show()?), layouts.presets);
+  let json = bundle.to_json()?;
+  match output {
+    Some(path) => {
+      std::fs::write(path, json).with_context(|| format!("Unable to write {}", path))?;
+      println!("Settings written to {}", path);
+    }
+    None => println!("{}", json),
+  }
+  Ok(())
+}
+
+/// Ask on the terminal whether to replace `name`, `yes` answers it for all that follow.
+fn confirm_replace(name: &str, current: &str, incoming: &str, yes: &mut bool) -> Result<bool> {
+  if *yes {
+    return Ok(true);
+  }
+  loop {
+    print!(
+      "{}\n  current:  {}\n  incoming: {}\nReplace? [y]es, [n]o, [a]ll, [q]uit: ",
+      name, current, incoming
+    );
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer)? == 0 {
+      return Ok(false);
+    }
+    match answer.trim() {
+      "y" | "Y" => return Ok(true),
+      "n" | "N" | "" => return Ok(false),
+      "a" | "A" => {
+        *yes = true;
+        return Ok(true);
+      }
+      "q" | "Q" => anyhow::bail!("Import aborted, nothing more was changed"),
+      _ => {}
+    }
+  }
+}
+
+/// Ask a yes or no question on the terminal, no when nothing can be read.
+fn ask(question: &str) -> Result<bool> {
+  loop {
+    print!("{} [y]es, [n]o: ", question);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer)? == 0 {
+      return Ok(false);
+    }
+    match answer.trim() {
+      "y" | "Y" => return Ok(true),
+      "n" | "N" | "" => return Ok(false),
+      _ => {}
+    }
+  }
+}
+
+/// Add the settings and layouts of the bundle at `input` once they were shown and confirmed, and
+/// ask before replacing ones that are set differently, both unless `yes`. Settings running a command
+/// are always asked about one by one.
+fn import_settings(input: &str, mut yes: bool) -> Result<()> {
+  let data = std::fs::read_to_string(input).with_context(|| format!("Unable to read {}", input))?;
+  let bundle = bundle::Bundle::parse(&data).with_context(|| format!("Unable to read the bundle {}", input))?;
+  let mut layouts = layout::Layouts::new("layouts.json");
+  layouts.load()?;
+  let plan = bundle.plan(&bundle::settings(&task_show()?), &layouts.presets);
+
+  let mut settings = plan.settings;
+  let mut presets = plan.layouts;
+  if !settings.is_empty() || !presets.is_empty() {
+    println!("The bundle adds:");
+    for (name, value) in &settings {
+      println!("  {} = {}", name, value);
+    }
+    for preset in &presets {
+      println!("  layout {}", preset.name);
+    }
+    if !yes && !ask("Add them?")? {
+      settings.clear();
+      presets.clear();
+    }
+  }
+  for command in plan.commands {
+    println!("{} runs a command", command.name);
+    if !command.current.is_empty() {
+      println!("  current:  {}", command.current);
+    }
+    println!("  incoming: {}", command.incoming);
+    if ask("Run this command from now on?")? {
+      settings.push((command.name, command.incoming));
+    }
+  }
+  for conflict in plan.setting_conflicts {
+    if confirm_replace(&conflict.name, &conflict.current, &conflict.incoming, &mut yes)? {
+      settings.push((conflict.name, conflict.incoming));
+    }
+  }
+  for conflict in plan.layout_conflicts {
+    let describe = |l: &layout::LayoutPreset| serde_json::to_string(l).unwrap_or_default();
+    let name = format!("layout {}", conflict.name);
+    if confirm_replace(&name, &describe(&conflict.current), &describe(&conflict.incoming), &mut yes)? {
+      presets.push(conflict.incoming);
+    }
+  }
+
+  for (name, value) in &settings {
+    let output = overrides::task()
+      .arg("rc.confirmation=off")
+      .arg("config")
+      .arg(name)
+      .arg(value)
+      .output()
+      .context("Unable to run `task config`.")?;
+    if !output.status.success() {
+      anyhow::bail!("Unable to set {}: {}", name, String::from_utf8_lossy(&output.stderr).trim());
+    }
+  }
+  for preset in &presets {
+    match layouts.presets.iter_mut().find(|p| p.name == preset.name) {
+      Some(existing) => *existing = preset.clone(),
+      None => layouts.presets.push(preset.clone()),
+    }
+  }
+  if !presets.is_empty() {
+    layouts.write()?;
+  }
+  println!("Imported {} settings and {} layouts", settings.len(), presets.len());
+  Ok(())
+}
+
+/// Write the cheatsheet of the `cheatsheet` subcommand, to `output` or to stdout.
+fn export_cheatsheet(output: Option<&String>, format: Option<&String>) -> Result<()> {
+  let output_path = output.map(Path::new);
+  let data = task_show()?;
+  let keyconfig = KeyConfig::new(&data)?;
+  let format = cheatsheet::Format::new(format.map(String::as_str), output_path);
+  let text = cheatsheet::render(format, &cheatsheet::bindings(&keyconfig), &cheatsheet::settings(&data));
+  match output_path {
+    Some(path) => {
+      std::fs::write(path, text).with_context(|| format!("Unable to write {}", path.display()))?;
+      println!("Cheatsheet written to {}", path.display());
+    }
+    None => print!("{}", text),
+  }
+  Ok(())
+}
+
+async fn tui_main(
+  report: &str,
+  filter: &[String],
+  uuid: Option<uuid::Uuid>,
+  start_menu: bool,
+  fresh_install: bool,
+  trace_perf: bool,
+  record: Option<&String>,
+  replay: Option<&String>,
+) -> Result<()> {
   panic::set_hook(Box::new(|panic_info| {
     destruct_terminal();
     better_panic::Settings::auto().create_panic_handler()(panic_info);
   }));
 
-  let mut app = app::TaskwarriorTui::new(report, true).await?;
+  let mut app = match replay {
+    Some(path) => {
+      let events = recording::load(Path::new(path))?;
+      let mut app = app::TaskwarriorTui::new(report, false).await?;
+      app.event_loop = event::EventLoop::replay(events);
+      app.replaying = true;
+      app
+    }
+    None => app::TaskwarriorTui::new(report, true).await?,
+  };
+  if !filter.is_empty() {
+    app.add_filter_args(filter).await?;
+  }
+  if let Some(uuid) = uuid {
+    app.jump_to_uuid(uuid).await?;
+  }
+  if replay.is_none() && app.config.uda_tour.unwrap_or(fresh_install) && !app.tour.taken() {
+    app.open_tour();
+  } else if start_menu && app.config.uda_start_menu {
+    app.open_start_menu();
+  }
+  if let Some(path) = record {
+    app.recorder = Some(recording::Recorder::create(Path::new(path))?);
+  }
+  if trace_perf {
+    app.perf = Some(perf::PerfTrace::new());
+  }
 
   let mut terminal = app.start_tui()?;
 
   let r = app.run(&mut terminal).await;
 
-  app.pause_tui().await?;
+  if let Err(e) = app.pause_tui().await {
+    // after SIGHUP or a failed resume there is no interface left to leave
+    if !app.terminated && r.is_ok() {
+      return Err(e);
+    }
+  }
+
+  if let Some(perf) = &app.perf {
+    let path = utils::get_data_dir().join("perf-report.txt");
+    perf.write(&path)?;
+    if !app.terminated {
+      println!("{}\n\nPerformance report written to {}", perf.report(), path.display());
+    }
+  }
 
   r
 }
@@ -124,7 +393,14 @@ async fn tui_main(report: &str) -> Result<()> {
 fn main() -> Result<()> {
   better_panic::install();
 
-  let matches = cli::generate_cli_app().get_matches();
+  let mut app = cli::generate_cli_app();
+  if env::args().skip(1).any(|a| a == "-h" || a == "--help") {
+    let footer = man::help_footer(&task_names("_reports"), &task_names("_context"));
+    if !footer.is_empty() {
+      app = app.after_help(footer);
+    }
+  }
+  let matches = app.get_matches();
 
   let config = matches.get_one::<String>("config");
   let data = matches.get_one::<String>("data");
@@ -132,6 +408,20 @@ fn main() -> Result<()> {
   let taskdata = matches.get_one::<String>("taskdata");
   let binding = String::from("next");
   let report = matches.get_one::<String>("report").unwrap_or(&binding);
+  let report_given = matches.get_one::<String>("report").is_some();
+  let trace_perf = matches.get_flag("trace-perf");
+  let demo = matches.get_flag("demo");
+  let record = matches.get_one::<String>("record");
+  let replay = matches.get_one::<String>("replay");
+  let uuid = matches.get_one::<String>("uuid").map(|link| {
+    deeplink::parse_link(link).unwrap_or_else(|| {
+      eprintln!("\x1b[0;31m[taskwarrior-tui error]\x1b[0m: No task uuid found in `{}`", link);
+      std::process::exit(1);
+    })
+  });
+  let args: Vec<String> = matches.get_many::<String>("filter").map(|f| f.cloned().collect()).unwrap_or_default();
+  let (rc_overrides, filter) = overrides::split(args);
+  overrides::set(rc_overrides);
 
   if let Some(e) = config {
     if env::var("TASKWARRIOR_TUI_CONFIG").is_err() {
@@ -175,16 +465,109 @@ fn main() -> Result<()> {
     }
   }
 
-  initialize_logging();
+  if matches.subcommand_matches("man").is_some() {
+    print!(
+      "{}",
+      man::render(&cli::generate_cli_app(), &task_names("_reports"), &task_names("_context"))
+    );
+    return Ok(());
+  }
+
+  if let Some(m) = matches.subcommand_matches("completions") {
+    print_completions(*m.get_one::<clap_complete::Shell>("shell").unwrap());
+    return Ok(());
+  }
+
+  if let Some(m) = matches.subcommand_matches("cheatsheet") {
+    if let Err(e) = export_cheatsheet(m.get_one::<String>("output"), m.get_one::<String>("format")) {
+      eprintln!("\x1b[0;31m[taskwarrior-tui error]\x1b[0m: {}", e);
+      std::process::exit(1);
+    }
+    return Ok(());
+  }
+
+  if let Some(m) = matches.subcommand_matches("settings") {
+    let result = match m.subcommand() {
+      Some(("export", m)) => export_settings(m.get_one::<String>("output")),
+      Some(("import", m)) => import_settings(m.get_one::<String>("input").unwrap(), m.get_flag("yes")),
+      _ => Ok(()),
+    };
+    if let Err(e) = result {
+      eprintln!("\x1b[0;31m[taskwarrior-tui error]\x1b[0m: {:#}", e);
+      std::process::exit(1);
+    }
+    return Ok(());
+  }
+
+  let demo_tasks = if let Some(m) = matches.subcommand_matches("sample-data") {
+    let options = demo::SampleOptions {
+      tasks: *m.get_one::<usize>("tasks").unwrap(),
+      projects: *m.get_one::<usize>("projects").unwrap(),
+      tags: *m.get_one::<usize>("tags").unwrap(),
+      days: *m.get_one::<i64>("days").unwrap(),
+      seed: *m.get_one::<u64>("seed").unwrap(),
+    };
+    match demo::sample_tasks(&options, chrono::Utc::now()) {
+      Ok(tasks) => Some(tasks),
+      Err(e) => {
+        eprintln!("\x1b[0;31m[taskwarrior-tui error]\x1b[0m: {}", e);
+        std::process::exit(1);
+      }
+    }
+  } else if demo {
+    Some(demo::tasks(chrono::Utc::now()))
+  } else {
+    None
+  };
+
+  let demo_dir = if let Some(tasks) = demo_tasks {
+    match demo::setup(&tasks) {
+      Ok(dir) => Some(dir),
+      Err(e) => {
+        eprintln!("\x1b[0;31m[taskwarrior-tui error]\x1b[0m: {}", e);
+        std::process::exit(1);
+      }
+    }
+  } else {
+    None
+  };
+
+  // as an override rather than `task context`, which would change the taskrc for good
+  if let Some(context) = matches.get_one::<String>("context") {
+    if context == "none" {
+      overrides::push("context", "");
+    } else if task_names("_context").contains(context) {
+      overrides::push("context", context);
+    } else {
+      eprintln!("\x1b[0;31m[taskwarrior-tui error]\x1b[0m: No context named `{}`", context);
+      std::process::exit(1);
+    }
+  }
+
+  // before the log or anything else is written to the data directory
+  let fresh_install = tour::fresh_install(&utils::get_data_dir());
+  logging::initialize_logging();
 
   debug!("getting matches from clap...");
   debug!("report = {:?}", &report);
   debug!("config = {:?}", &config);
 
-  let r = tokio::runtime::Builder::new_multi_thread()
-    .enable_all()
-    .build()?
-    .block_on(async { tui_main(report).await });
+  let r = tokio::runtime::Builder::new_multi_thread().enable_all().build()?.block_on(async {
+    tui_main(
+      report,
+      &filter,
+      uuid,
+      !report_given && filter.is_empty() && uuid.is_none() && replay.is_none(),
+      fresh_install,
+      trace_perf,
+      record,
+      replay,
+    )
+    .await
+  });
+  if let Some(dir) = demo_dir {
+    demo::teardown(&dir).unwrap_or_else(|e| warn!("Unable to remove demo data in {:?}: {}", dir, e));
+  }
   if let Err(err) = r {
     eprintln!("\x1b[0;31m[taskwarrior-tui error]\x1b[0m: {}\n\nIf you need additional help, please report as a github issue on https://github.com/kdheepak/taskwarrior-tui", err);
     std::process::exit(1);
@@ -0,0 +1,134 @@
+use std::fmt::Write;
+
+use clap::{Arg, Command};
+
+/// Escape text for roff: backslashes and dashes, and control characters at the start of a line.
+fn escape(text: &str) -> String {
+  let text = text.replace('\\', "\\e").replace('-', "\\-");
+  if text.starts_with('.') || text.starts_with('\'') {
+    format!("\\&{}", text)
+  } else {
+    text
+  }
+}
+
+fn synopsis(arg: &Arg) -> String {
+  let value = arg
+    .get_value_names()
+    .and_then(|names| names.first())
+    .map(|name| format!(" \\fI{}\\fR", escape(name)))
+    .unwrap_or_default();
+  let names: Vec<String> = arg
+    .get_short()
+    .map(|s| format!("\\fB\\-{}\\fR", s))
+    .into_iter()
+    .chain(arg.get_long().map(|l| format!("\\fB\\-\\-{}\\fR", escape(l))))
+    .map(|name| format!("{}{}", name, value))
+    .collect();
+  if names.is_empty() {
+    let value_name = arg.get_value_names().and_then(|n| n.first()).map(|n| n.as_str());
+    let repeated = if arg.get_num_args().is_some_and(|n| n.max_values() > 1) {
+      "..."
+    } else {
+      ""
+    };
+    format!("\\fI{}\\fR{}", escape(value_name.unwrap_or(arg.get_id().as_str())), repeated)
+  } else {
+    names.join(", ")
+  }
+}
+
+fn list(out: &mut String, title: &str, items: &[String], empty: &str) {
+  writeln!(out, ".SH {}", title).ok();
+  if items.is_empty() {
+    writeln!(out, "{}", escape(empty)).ok();
+  }
+  for item in items {
+    writeln!(out, ".IP \\(bu 2\n{}", escape(item)).ok();
+  }
+}
+
+/// The man page of `app`, listing the reports and contexts of the taskrc it was generated with.
+pub fn render(app: &Command, reports: &[String], contexts: &[String]) -> String {
+  let name = app.get_name();
+  let mut out = String::new();
+  writeln!(
+    out,
+    ".TH {} 1 \"\" \"{} {}\"",
+    name.to_uppercase(),
+    name,
+    app.get_version().unwrap_or_default()
+  )
+  .ok();
+  writeln!(
+    out,
+    ".SH NAME\n{} \\- {}",
+    escape(name),
+    escape(&app.get_about().map(|a| a.to_string()).unwrap_or_default())
+  )
+  .ok();
+  writeln!(
+    out,
+    ".SH SYNOPSIS\n\\fB{}\\fR [\\fIOPTIONS\\fR] [\\fIFILTER\\fR]... [\\fICOMMAND\\fR]",
+    escape(name)
+  )
+  .ok();
+  writeln!(out, ".SH OPTIONS").ok();
+  for arg in app.get_arguments().filter(|a| !a.is_hide_set()) {
+    writeln!(out, ".TP\n{}", synopsis(arg)).ok();
+    writeln!(out, "{}", escape(&arg.get_help().map(|h| h.to_string()).unwrap_or_default())).ok();
+  }
+  writeln!(out, ".SH COMMANDS").ok();
+  for command in app.get_subcommands().filter(|c| !c.is_hide_set()) {
+    writeln!(out, ".TP\n\\fB{}\\fR", escape(command.get_name())).ok();
+    writeln!(out, "{}", escape(&command.get_about().map(|a| a.to_string()).unwrap_or_default())).ok();
+    for arg in command.get_arguments().filter(|a| a.get_id() != "help") {
+      writeln!(
+        out,
+        ".RS\n.TP\n{}\n{}\n.RE",
+        synopsis(arg),
+        escape(&arg.get_help().map(|h| h.to_string()).unwrap_or_default())
+      )
+      .ok();
+    }
+  }
+  list(&mut out, "REPORTS", reports, "No reports were found, `task _reports` did not run.");
+  list(&mut out, "CONTEXTS", contexts, "No contexts are defined in the taskrc.");
+  writeln!(out, ".SH SEE ALSO\n\\fBtask\\fR(1), \\fBtaskrc\\fR(5)").ok();
+  out
+}
+
+/// Reports and contexts listed after the options of `--help`.
+pub fn help_footer(reports: &[String], contexts: &[String]) -> String {
+  let mut out = String::new();
+  if !reports.is_empty() {
+    writeln!(out, "Reports (-r):\n  {}", reports.join(", ")).ok();
+  }
+  if !contexts.is_empty() {
+    writeln!(out, "Contexts (--context):\n  {}", contexts.join(", ")).ok();
+  }
+  out.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_render() {
+    let app = Command::new("tt").about("A test").arg(
+      Arg::new("report")
+        .short('r')
+        .long("report")
+        .value_name("STRING")
+        .help("Sets default report"),
+    );
+    let page = render(&app, &["next".to_string(), "list".to_string()], &[]);
+    assert!(page.starts_with(".TH TT 1"));
+    assert!(page.contains("\\fB\\-r\\fR \\fISTRING\\fR, \\fB\\-\\-report\\fR \\fISTRING\\fR\nSets default report"));
+    assert!(page.contains(".SH REPORTS\n.IP \\(bu 2\nnext\n"));
+    assert!(page.contains(".SH CONTEXTS\nNo contexts are defined in the taskrc."));
+    assert_eq!(escape(".x-y"), "\\&.x\\-y");
+    assert_eq!(help_footer(&["next".to_string()], &[]), "Reports (-r):\n  next");
+  }
+}
@@ -0,0 +1,243 @@
+use std::time::{Duration, Instant};
+
+use crate::{event::KeyCode, keyconfig::KeyConfig};
+
+/// State of the interface the middleware needs to decide what to do with a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Context {
+  /// The key bindings of the report, projects and calendar apply, no prompt is being typed in
+  pub bindings_apply: bool,
+}
+
+/// A stage between the keys read from the terminal and the key handlers, which can translate,
+/// swallow or expand keys.
+pub trait Middleware: Send {
+  /// Keys passed on to the next stage for `key`: none to swallow it, several to expand it.
+  fn handle(&mut self, key: KeyCode, context: &Context) -> Vec<KeyCode>;
+
+  /// Keys held back that are let through once `now` is past the deadline.
+  fn expire(&mut self, _now: Instant) -> Vec<KeyCode> {
+    vec![]
+  }
+
+  /// When keys held back are let through if nothing else happens.
+  fn deadline(&self) -> Option<Instant> {
+    None
+  }
+
+  /// Short label shown next to the context while the stage is in a special state.
+  fn status(&self) -> Option<&'static str> {
+    None
+  }
+}
+
+/// The middleware stages, run in order on every key.
+#[derive(Default)]
+pub struct Pipeline {
+  stages: Vec<Box<dyn Middleware>>,
+}
+
+impl Pipeline {
+  /// Translation layer, key sequences and macros of the key config.
+  pub fn new(keyconfig: &KeyConfig, chord_timeout: Duration) -> Self {
+    let mut pipeline = Self::default();
+    if !keyconfig.remap.is_empty() {
+      pipeline.push(Remap::new(keyconfig.remap.clone()));
+    }
+    if !keyconfig.chords.is_empty() {
+      pipeline.push(Chords::new(keyconfig.chords.clone(), chord_timeout));
+    }
+    pipeline.push(Macro::new(keyconfig.macro_record, keyconfig.macro_play));
+    pipeline
+  }
+
+  pub fn push(&mut self, stage: impl Middleware + 'static) {
+    self.stages.push(Box::new(stage));
+  }
+
+  fn run_from(&mut self, first: usize, keys: Vec<KeyCode>, context: &Context) -> Vec<KeyCode> {
+    self.stages[first..]
+      .iter_mut()
+      .fold(keys, |keys, stage| keys.into_iter().flat_map(|key| stage.handle(key, context)).collect())
+  }
+
+  /// Keys to handle for `key` read from the terminal.
+  pub fn handle(&mut self, key: KeyCode, context: &Context) -> Vec<KeyCode> {
+    self.run_from(0, vec![key], context)
+  }
+
+  /// Keys held back by a stage whose deadline passed, run through the following stages.
+  pub fn expire(&mut self, now: Instant, context: &Context) -> Vec<KeyCode> {
+    let mut keys = vec![];
+    for i in 0..self.stages.len() {
+      let expired = self.stages[i].expire(now);
+      if !expired.is_empty() {
+        keys.extend(self.run_from(i + 1, expired, context));
+      }
+    }
+    keys
+  }
+
+  pub fn deadline(&self) -> Option<Instant> {
+    self.stages.iter().filter_map(|s| s.deadline()).min()
+  }
+
+  pub fn status(&self) -> Vec<&'static str> {
+    self.stages.iter().filter_map(|s| s.status()).collect()
+  }
+}
+
+/// Translates keys before they reach the key bindings, e.g. for another keyboard layout.
+pub struct Remap {
+  keys: Vec<(KeyCode, KeyCode)>,
+}
+
+impl Remap {
+  pub fn new(keys: Vec<(KeyCode, KeyCode)>) -> Self {
+    Self { keys }
+  }
+}
+
+impl Middleware for Remap {
+  fn handle(&mut self, key: KeyCode, context: &Context) -> Vec<KeyCode> {
+    if !context.bindings_apply {
+      return vec![key];
+    }
+    let key = self.keys.iter().find(|(from, _)| *from == key).map_or(key, |(_, to)| *to);
+    vec![key]
+  }
+}
+
+/// Holds back the keys of a sequence bound in the key config until it is complete, it is bound as
+/// `KeyCode::Chord(index)`.
+pub struct Chords {
+  chords: Vec<Vec<KeyCode>>,
+  timeout: Duration,
+  keys: Vec<KeyCode>,
+  started: Option<Instant>,
+}
+
+impl Chords {
+  pub fn new(chords: Vec<Vec<KeyCode>>, timeout: Duration) -> Self {
+    Self {
+      chords,
+      timeout,
+      keys: vec![],
+      started: None,
+    }
+  }
+}
+
+impl Middleware for Chords {
+  fn handle(&mut self, key: KeyCode, context: &Context) -> Vec<KeyCode> {
+    if !context.bindings_apply && self.keys.is_empty() {
+      return vec![key];
+    }
+    self.keys.push(key);
+    if let Some(i) = self.chords.iter().position(|keys| *keys == self.keys) {
+      self.keys.clear();
+      self.started = None;
+      return vec![KeyCode::Chord(i as u8)];
+    }
+    if self.chords.iter().any(|keys| keys.starts_with(&self.keys)) {
+      self.started.get_or_insert_with(Instant::now);
+      return vec![];
+    }
+    // not a sequence, the keys typed so far are single keys
+    self.started = None;
+    std::mem::take(&mut self.keys)
+  }
+
+  fn expire(&mut self, now: Instant) -> Vec<KeyCode> {
+    match self.deadline() {
+      Some(deadline) if deadline <= now => {
+        self.started = None;
+        std::mem::take(&mut self.keys)
+      }
+      _ => vec![],
+    }
+  }
+
+  fn deadline(&self) -> Option<Instant> {
+    self.started.map(|started| started + self.timeout)
+  }
+}
+
+/// Records the keys typed between two presses of the record key, and types them again on the play
+/// key.
+pub struct Macro {
+  record: KeyCode,
+  play: KeyCode,
+  recording: Option<Vec<KeyCode>>,
+  recorded: Vec<KeyCode>,
+}
+
+impl Macro {
+  pub fn new(record: KeyCode, play: KeyCode) -> Self {
+    Self {
+      record,
+      play,
+      recording: None,
+      recorded: vec![],
+    }
+  }
+}
+
+impl Middleware for Macro {
+  fn handle(&mut self, key: KeyCode, context: &Context) -> Vec<KeyCode> {
+    let keys = if context.bindings_apply && key == self.record {
+      match self.recording.take() {
+        Some(keys) => self.recorded = keys,
+        None => self.recording = Some(vec![]),
+      }
+      vec![]
+    } else if context.bindings_apply && key == self.play {
+      self.recorded.clone()
+    } else {
+      vec![key]
+    };
+    if let Some(recording) = self.recording.as_mut() {
+      recording.extend(&keys);
+    }
+    keys
+  }
+
+  fn status(&self) -> Option<&'static str> {
+    self.recording.as_ref().map(|_| "REC")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const BINDINGS: Context = Context { bindings_apply: true };
+  const PROMPT: Context = Context { bindings_apply: false };
+
+  #[test]
+  fn test_pipeline() {
+    let g = KeyCode::Char('g');
+    let mut pipeline = Pipeline::default();
+    pipeline.push(Remap::new(vec![(KeyCode::Char('h'), g)]));
+    pipeline.push(Chords::new(vec![vec![g, g]], Duration::from_millis(500)));
+    pipeline.push(Macro::new(KeyCode::Char('Q'), KeyCode::Char('@')));
+    assert!(pipeline.handle(KeyCode::Char('h'), &BINDINGS).is_empty());
+    assert_eq!(pipeline.handle(g, &BINDINGS), vec![KeyCode::Chord(0)]);
+    assert_eq!(pipeline.handle(KeyCode::Char('h'), &PROMPT), vec![KeyCode::Char('h')]);
+
+    assert!(pipeline.handle(g, &BINDINGS).is_empty());
+    let deadline = pipeline.deadline().unwrap();
+    assert!(pipeline.expire(deadline - Duration::from_millis(1), &BINDINGS).is_empty());
+    assert_eq!(pipeline.expire(deadline, &BINDINGS), vec![g]);
+
+    assert!(pipeline.handle(KeyCode::Char('Q'), &BINDINGS).is_empty());
+    assert_eq!(pipeline.status(), vec!["REC"]);
+    assert_eq!(pipeline.handle(KeyCode::Char('j'), &BINDINGS), vec![KeyCode::Char('j')]);
+    assert_eq!(pipeline.handle(KeyCode::Char('d'), &BINDINGS), vec![KeyCode::Char('d')]);
+    assert!(pipeline.handle(KeyCode::Char('Q'), &BINDINGS).is_empty());
+    assert_eq!(
+      pipeline.handle(KeyCode::Char('@'), &BINDINGS),
+      vec![KeyCode::Char('j'), KeyCode::Char('d')]
+    );
+  }
+}
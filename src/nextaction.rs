@@ -0,0 +1,80 @@
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::NaiveDateTime;
+use task_hookrs::{status::TaskStatus, task::Task};
+use uuid::Uuid;
+
+/// A project with tasks to do, none of which can be worked on next.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StalledProject {
+  pub name: String,
+  /// Pending and waiting tasks of the project
+  pub pending: usize,
+}
+
+/// Whether a task can be started now, as taskwarrior's `+READY`: pending, not waiting, not scheduled
+/// later and not blocked by a task that is still to do.
+fn ready(task: &Task, todo: &HashSet<Uuid>, now: NaiveDateTime) -> bool {
+  *task.status() == TaskStatus::Pending
+    && task.wait().is_none_or(|w| **w <= now)
+    && task.scheduled().is_none_or(|s| **s <= now)
+    && task.depends().is_none_or(|d| d.iter().all(|u| !todo.contains(u)))
+}
+
+/// Projects of `tasks` with pending tasks but no next action, which is a ready task, tagged with
+/// `tag` unless it is empty.
+pub fn stalled_projects(tasks: &[Task], tag: &str, now: NaiveDateTime) -> Vec<StalledProject> {
+  let todo: HashSet<Uuid> = tasks
+    .iter()
+    .filter(|t| matches!(t.status(), TaskStatus::Pending | TaskStatus::Waiting))
+    .map(|t| *t.uuid())
+    .collect();
+  let mut projects: BTreeMap<&str, (usize, bool)> = BTreeMap::new();
+  for task in tasks.iter().filter(|t| todo.contains(t.uuid())) {
+    let project = match task.project() {
+      Some(project) => project.as_str(),
+      None => continue,
+    };
+    let next = ready(task, &todo, now) && (tag.is_empty() || task.tags().is_some_and(|t| t.iter().any(|t| t == tag)));
+    let entry = projects.entry(project).or_default();
+    entry.0 += 1;
+    entry.1 |= next;
+  }
+  projects
+    .into_iter()
+    .filter(|(_, (_, next))| !next)
+    .map(|(name, (pending, _))| StalledProject {
+      name: name.to_string(),
+      pending,
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::NaiveDate;
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  #[test]
+  fn test_stalled_projects() {
+    let tasks = import(
+      r#"[
+        {"uuid":"00000000-0000-0000-0000-000000000001","description":"Plan","status":"pending","entry":"20260101T000000Z","project":"Home","tags":["next"]},
+        {"uuid":"00000000-0000-0000-0000-000000000002","description":"Paint","status":"pending","entry":"20260101T000000Z","project":"Home"},
+        {"uuid":"00000000-0000-0000-0000-000000000003","description":"Book","status":"pending","entry":"20260101T000000Z","project":"Trip","depends":["00000000-0000-0000-0000-000000000004"]},
+        {"uuid":"00000000-0000-0000-0000-000000000004","description":"Save","status":"waiting","entry":"20260101T000000Z","wait":"20270101T000000Z","project":"Trip"},
+        {"uuid":"00000000-0000-0000-0000-000000000005","description":"Write","status":"pending","entry":"20260101T000000Z","project":"Blog"},
+        {"uuid":"00000000-0000-0000-0000-000000000006","description":"Done","status":"completed","entry":"20260101T000000Z","project":"Old","tags":["next"]}
+      ]"#
+        .as_bytes(),
+    )
+    .unwrap();
+    let now = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    let names = |stalled: Vec<StalledProject>| stalled.into_iter().map(|p| p.name).collect::<Vec<_>>();
+    assert_eq!(names(stalled_projects(&tasks, "", now)), ["Trip"]);
+    assert_eq!(names(stalled_projects(&tasks, "next", now)), ["Blog", "Trip"]);
+    assert_eq!(stalled_projects(&tasks, "", now)[0].pending, 2);
+  }
+}
@@ -0,0 +1,195 @@
+use std::collections::{HashMap, HashSet};
+
+use log::warn;
+use tokio::sync::mpsc;
+
+use crate::{
+  event::{Event, KeyCode},
+  overrides,
+};
+
+const PREFIX: &str = "uda.taskwarrior-tui.named-filter.";
+
+/// A filter saved under a name, applied from the command palette, which can alert when tasks start
+/// matching it and show how many tasks match it in the status bar.
+///
+/// ```plaintext
+/// uda.taskwarrior-tui.named-filter.overdue.filter=+OVERDUE
+/// uda.taskwarrior-tui.named-filter.overdue.notify=true
+/// uda.taskwarrior-tui.named-filter.overdue.badge=true
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NamedFilter {
+  pub name: String,
+  pub filter: String,
+  /// Alert when a task newly matches the filter
+  pub notify: bool,
+  /// Show the number of matching tasks in the status bar
+  pub badge: bool,
+}
+
+/// Named filters defined in the output of `task show`, in the order they first appear.
+pub fn parse(data: &str) -> Vec<NamedFilter> {
+  let mut filters: Vec<NamedFilter> = vec![];
+  for line in data.lines() {
+    let rest = match line.strip_prefix(PREFIX) {
+      Some(rest) => rest,
+      None => continue,
+    };
+    let (key, value) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let (name, setting) = match key.rsplit_once('.') {
+      Some(split) => split,
+      None => continue,
+    };
+    let value = value.trim();
+    let index = match filters.iter().position(|f| f.name == name) {
+      Some(i) => i,
+      None => {
+        filters.push(NamedFilter {
+          name: name.to_string(),
+          ..NamedFilter::default()
+        });
+        filters.len() - 1
+      }
+    };
+    let filter = &mut filters[index];
+    match setting {
+      "filter" => filter.filter = value.to_string(),
+      "notify" => filter.notify = matches!(value, "true" | "yes" | "on" | "1" | "y"),
+      "badge" => filter.badge = matches!(value, "true" | "yes" | "on" | "1" | "y"),
+      _ => warn!("Unknown setting {} of named filter {}", setting, name),
+    }
+  }
+  filters
+}
+
+/// Uuids matching each notifying filter, as found by one check.
+type Matches = Vec<(String, HashSet<String>)>;
+
+/// Tasks that matched each notifying filter when it was last checked, checked in the background so
+/// that refreshing the report never waits for `task`.
+pub struct Subscriptions {
+  matching: HashMap<String, HashSet<String>>,
+  /// Bumped on each refresh
+  generation: u64,
+  /// Generation of the last check taken in, the checks that finish after a later one are dropped
+  applied: u64,
+  tx: mpsc::UnboundedSender<(u64, Matches)>,
+  rx: mpsc::UnboundedReceiver<(u64, Matches)>,
+}
+
+impl Subscriptions {
+  pub fn new() -> Self {
+    let (tx, rx) = mpsc::unbounded_channel();
+    Self {
+      matching: HashMap::new(),
+      generation: 0,
+      applied: 0,
+      tx,
+      rx,
+    }
+  }
+
+  /// List the tasks of the filters with `notify` one after the other with `task _uuids`, sending
+  /// `Event::Tick` on `wake` once done.
+  pub fn refresh(&mut self, filters: &[NamedFilter], wake: &mpsc::UnboundedSender<Event<KeyCode>>) {
+    let filters: Vec<NamedFilter> = filters.iter().filter(|f| f.notify).cloned().collect();
+    if filters.is_empty() {
+      return;
+    }
+    self.generation += 1;
+    let generation = self.generation;
+    let tx = self.tx.clone();
+    let wake = wake.clone();
+    tokio::spawn(async move {
+      let mut matches = vec![];
+      for filter in filters {
+        let output = overrides::task_async()
+          .arg("rc.color=off")
+          .arg("rc.verbose=nothing")
+          .args(shlex::split(&filter.filter).unwrap_or_default())
+          .arg("_uuids")
+          .output()
+          .await;
+        match output {
+          Ok(output) if output.status.success() => {
+            let uuids = String::from_utf8_lossy(&output.stdout)
+              .split_whitespace()
+              .map(ToString::to_string)
+              .collect();
+            matches.push((filter.name, uuids));
+          }
+          Ok(output) => warn!(
+            "Unable to check filter {}: {}",
+            filter.name,
+            String::from_utf8_lossy(&output.stderr).trim()
+          ),
+          Err(e) => warn!("Unable to check filter {}: {}", filter.name, e),
+        }
+      }
+      tx.send((generation, matches)).ok();
+      wake.send(Event::Tick).ok();
+    });
+  }
+
+  /// Take in the checks that finished, returning the number of tasks newly matching each filter.
+  pub fn poll(&mut self) -> Vec<(String, usize)> {
+    let mut new = vec![];
+    while let Ok((generation, matches)) = self.rx.try_recv() {
+      if generation <= self.applied {
+        continue;
+      }
+      self.applied = generation;
+      for (name, uuids) in matches {
+        let count = self.newly_matching(&name, uuids);
+        if count > 0 {
+          new.push((name, count));
+        }
+      }
+    }
+    new
+  }
+
+  /// Number of `uuids` that did not match the filter `name` on its previous check. The first check
+  /// only learns what matches, so that starting the interface does not alert about every task.
+  pub fn newly_matching(&mut self, name: &str, uuids: HashSet<String>) -> usize {
+    let new = match self.matching.get(name) {
+      Some(previous) => uuids.difference(previous).count(),
+      None => 0,
+    };
+    self.matching.insert(name.to_string(), uuids);
+    new
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_subscriptions() {
+    let data = "uda.taskwarrior-tui.named-filter.overdue.filter  +OVERDUE\nuda.taskwarrior-tui.named-filter.overdue.notify  yes\nuda.taskwarrior-tui.named-filter.home.filter  project:home\nuda.taskwarrior-tui.named-filter.home.badge  on\n";
+    let filters = parse(data);
+    assert_eq!(filters.len(), 2);
+    assert_eq!(filters[0].filter, "+OVERDUE");
+    assert!(filters[0].notify);
+    assert!(!filters[1].notify);
+    assert!(filters[1].badge && !filters[0].badge);
+
+    let uuids = |list: &[&str]| list.iter().map(ToString::to_string).collect::<HashSet<_>>();
+    let mut subscriptions = Subscriptions::new();
+    assert_eq!(subscriptions.newly_matching("overdue", uuids(&["a", "b"])), 0);
+    assert_eq!(subscriptions.newly_matching("overdue", uuids(&["b", "c", "d"])), 2);
+    assert_eq!(subscriptions.newly_matching("overdue", uuids(&["b"])), 0);
+    assert_eq!(subscriptions.newly_matching("overdue", uuids(&["a", "b"])), 1);
+
+    subscriptions.generation = 3;
+    subscriptions
+      .tx
+      .send((3, vec![("overdue".to_string(), uuids(&["a", "b", "c"]))]))
+      .unwrap();
+    subscriptions.tx.send((2, vec![("overdue".to_string(), uuids(&["x"]))])).unwrap();
+    assert_eq!(subscriptions.poll(), [("overdue".to_string(), 1)]);
+    assert!(subscriptions.poll().is_empty());
+  }
+}
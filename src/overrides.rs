@@ -0,0 +1,102 @@
+use std::sync::RwLock;
+
+/// `rc.<name>=<value>` overrides given on the command line of `taskwarrior-tui`.
+static OVERRIDES: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Taskrc of the current workspace, used instead of the one `TASKRC` points to.
+static TASKRC: RwLock<Option<String>> = RwLock::new(None);
+
+/// Whether `arg` overrides a setting of the taskrc, as `rc.<name>=<value>` or `rc.<name>:<value>`
+/// do for `task`.
+pub fn is_override(arg: &str) -> bool {
+  match arg.strip_prefix("rc.") {
+    Some(rest) => rest.find(['=', ':']).is_some_and(|i| i > 0),
+    None => false,
+  }
+}
+
+/// Overrides among `args`, and the other arguments.
+pub fn split(args: Vec<String>) -> (Vec<String>, Vec<String>) {
+  args.into_iter().partition(|a| is_override(a))
+}
+
+/// Keep `overrides` to pass them on to every `task` command.
+pub fn set(overrides: Vec<String>) {
+  *OVERRIDES.write().unwrap_or_else(|e| e.into_inner()) = overrides;
+}
+
+/// Pass `rc.<name>=<value>` on to every following `task` command, after the ones already kept.
+pub fn push(name: &str, value: &str) {
+  OVERRIDES
+    .write()
+    .unwrap_or_else(|e| e.into_inner())
+    .push(format!("rc.{}={}", name, value));
+}
+
+/// Stop overriding the setting `name`, so that the taskrc has its say again.
+pub fn remove(name: &str) {
+  let prefix = format!("rc.{}", name);
+  OVERRIDES
+    .write()
+    .unwrap_or_else(|e| e.into_inner())
+    .retain(|a| !(a.starts_with(&prefix) && a[prefix.len()..].starts_with(['=', ':'])));
+}
+
+pub fn args() -> Vec<String> {
+  OVERRIDES.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Run every following `task` command with the taskrc at `path`.
+pub fn set_taskrc(path: String) {
+  *TASKRC.write().unwrap_or_else(|e| e.into_inner()) = Some(path);
+}
+
+fn taskrc() -> Option<String> {
+  TASKRC.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// `task` with the overrides, before the arguments added by the caller so that the settings
+/// `taskwarrior-tui` relies on, like `rc.confirmation=off`, still have the last word.
+pub fn task() -> std::process::Command {
+  let mut command = std::process::Command::new("task");
+  if let Some(taskrc) = taskrc() {
+    command.env("TASKRC", taskrc);
+  }
+  command.args(args());
+  command
+}
+
+pub fn task_async() -> tokio::process::Command {
+  let mut command = tokio::process::Command::new("task");
+  if let Some(taskrc) = taskrc() {
+    command.env("TASKRC", taskrc);
+  }
+  command.args(args());
+  command
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_split() {
+    let args: Vec<String> = ["project:work", "rc.context=none", "+urgent", "rc.verbose:nothing", "rc.", "rc.=x"]
+      .iter()
+      .map(ToString::to_string)
+      .collect();
+    let (overrides, filter) = split(args);
+    assert_eq!(overrides, ["rc.context=none", "rc.verbose:nothing"]);
+    assert_eq!(filter, ["project:work", "+urgent", "rc.", "rc.=x"]);
+  }
+
+  #[test]
+  fn test_push_remove() {
+    set(vec!["rc.contexts=1".to_string(), "rc.context:home".to_string()]);
+    push("context", "work");
+    assert_eq!(args(), ["rc.contexts=1", "rc.context:home", "rc.context=work"]);
+    remove("context");
+    assert_eq!(args(), ["rc.contexts=1"]);
+    set(vec![]);
+  }
+}
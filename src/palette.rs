@@ -0,0 +1,154 @@
+use ratatui::widgets::ListState;
+
+use crate::density::Density;
+
+/// Commands that can be run from the command palette.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteCommand {
+  Layout(String),
+  SaveLayout,
+  ToggleDetails,
+  Refresh,
+  Help,
+  ContextMenu,
+  WhatChanged,
+  RecentActions,
+  ActionHistory,
+  SortColumns,
+  PerfReport,
+  ShowLog,
+  UsageStats,
+  EditDescription,
+  AnnotateInEditor,
+  ToggleReady,
+  Rules,
+  Workspace(String),
+  /// Filter of a named filter
+  Filter(String),
+  Density(Density),
+  Screenshot,
+  CopyLink,
+  Sync,
+  ShowOutput,
+  UrgencyTuner,
+  Goals,
+  Review,
+  AbandonReview,
+  StalledProjects,
+  Replace,
+  BackUp,
+  Backups,
+  RevealSecret,
+  HideSecret,
+  EditSecret,
+  ToggleMine,
+  Assign,
+  Activity,
+  Changelog,
+  Tour,
+}
+
+/// Fuzzy searchable list of commands.
+pub struct Palette {
+  pub query: String,
+  pub commands: Vec<(String, PaletteCommand)>,
+  pub state: ListState,
+}
+
+impl Palette {
+  pub fn new() -> Self {
+    Self {
+      query: String::new(),
+      commands: vec![],
+      state: ListState::default(),
+    }
+  }
+
+  pub fn open(&mut self, commands: Vec<(String, PaletteCommand)>) {
+    self.query.clear();
+    self.commands = commands;
+    self.state.select(Some(0));
+  }
+
+  /// Commands whose label contains the characters of the query in order, ignoring case.
+  pub fn matches(&self) -> Vec<&(String, PaletteCommand)> {
+    let query = self.query.to_lowercase();
+    self
+      .commands
+      .iter()
+      .filter(|(label, _)| {
+        let mut label = label.to_lowercase().chars().collect::<Vec<_>>().into_iter();
+        query.chars().all(|q| label.any(|c| c == q))
+      })
+      .collect()
+  }
+
+  pub fn selected(&self) -> Option<PaletteCommand> {
+    let matches = self.matches();
+    self.state.selected().and_then(|i| matches.get(i)).map(|(_, c)| c.clone())
+  }
+
+  pub fn push(&mut self, c: char) {
+    self.query.push(c);
+    self.state.select(Some(0));
+  }
+
+  pub fn pop(&mut self) {
+    self.query.pop();
+    self.state.select(Some(0));
+  }
+
+  pub fn next(&mut self) {
+    let len = self.matches().len();
+    if len == 0 {
+      return;
+    }
+    let i = match self.state.selected() {
+      Some(i) if i + 1 < len => i + 1,
+      _ => 0,
+    };
+    self.state.select(Some(i));
+  }
+
+  pub fn previous(&mut self) {
+    let len = self.matches().len();
+    if len == 0 {
+      return;
+    }
+    let i = match self.state.selected() {
+      Some(0) | None => len - 1,
+      Some(i) => i - 1,
+    };
+    self.state.select(Some(i));
+  }
+}
+
+impl Default for Palette {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_matches() {
+    let mut palette = Palette::new();
+    palette.open(vec![
+      ("Layout: triage".to_string(), PaletteCommand::Layout("triage".to_string())),
+      ("Toggle task details".to_string(), PaletteCommand::ToggleDetails),
+      ("Refresh".to_string(), PaletteCommand::Refresh),
+    ]);
+    for c in "tgl".chars() {
+      palette.push(c);
+    }
+    assert_eq!(palette.selected(), Some(PaletteCommand::ToggleDetails));
+    palette.pop();
+    palette.pop();
+    assert_eq!(palette.matches().len(), 2);
+    palette.next();
+    assert_eq!(palette.selected(), Some(PaletteCommand::ToggleDetails));
+  }
+}
@@ -31,6 +31,7 @@ use crate::{
   action::Action,
   app::{Mode, TaskwarriorTui},
   event::KeyCode,
+  overrides,
   pane::Pane,
   table::TableState,
 };
@@ -86,7 +87,7 @@ impl ContextsState {
   }
 
   pub fn update_data(&mut self) -> Result<()> {
-    let output = Command::new("task").arg("context").output()?;
+    let output = overrides::task().arg("context").output()?;
     let data = String::from_utf8_lossy(&output.stdout);
 
     self.rows = vec![];
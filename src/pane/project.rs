@@ -12,6 +12,8 @@ use std::{
   cmp::min,
   collections::{HashMap, HashSet},
   error::Error,
+  fs,
+  path::PathBuf,
   process::{Command, Output},
 };
 
@@ -31,11 +33,15 @@ use crate::{
   action::Action,
   app::{Mode, TaskwarriorTui},
   event::KeyCode,
+  overrides,
   pane::Pane,
   table::TableState,
-  utils::Changeset,
+  utils::{self, Changeset},
 };
 
+/// Directory in the data directory with the notes of the projects, one `<project>.md` per project.
+const NOTES_DIR: &str = "projects";
+
 pub struct ProjectsState {
   pub(crate) list: Vec<Project>,
   pub table_state: TableState,
@@ -44,6 +50,8 @@ pub struct ProjectsState {
   pub columns: Vec<String>,
   pub rows: Vec<ProjectDetails>,
   pub data: String,
+  /// Line of `data` showing each project of `list`
+  pub lines: Vec<usize>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -69,6 +77,7 @@ impl ProjectsState {
       ],
       data: Default::default(),
       rows: vec![],
+      lines: vec![],
     }
   }
 
@@ -112,22 +121,26 @@ impl ProjectsState {
 
   pub fn last_line(&self, line: &str) -> bool {
     let words = line.trim().split(' ').map(|s| s.trim()).collect::<Vec<&str>>();
-    return words.len() == 2 && words[0].chars().map(|c| c.is_numeric()).all(|b| b) && (words[1] == "project" || words[1] == "projects");
+    words.len() == 2 && words[0].chars().all(|c| c.is_numeric()) && (words[1] == "project" || words[1] == "projects")
   }
 
   pub fn update_data(&mut self) -> Result<()> {
     self.list.clear();
     self.rows.clear();
-    let output = Command::new("task")
-      .arg("summary")
-      .output()
-      .context("Unable to run `task summary`")
-      .unwrap();
+    let output = overrides::task().arg("summary").output().context("Unable to run `task summary`").unwrap();
     let data = String::from_utf8_lossy(&output.stdout);
     self.data = data.into();
+    let (lines, list) = summary_projects(&self.data).into_iter().unzip();
+    self.lines = lines;
+    self.list = list;
+    self.current_selection = self.current_selection.min(self.list.len().saturating_sub(1));
     Ok(())
   }
 
+  pub fn selected(&self) -> Option<&Project> {
+    self.list.get(self.current_selection)
+  }
+
   fn update_table_state(&mut self) {
     self.table_state.select(Some(self.current_selection));
     if self.marked.is_empty() {
@@ -163,6 +176,53 @@ impl Pane for ProjectsState {
   }
 }
 
+/// Lines of the output of `task summary` showing a project, with the full name of the project.
+/// Subprojects are indented under their parent with only the last part of their name.
+pub fn summary_projects(data: &str) -> Vec<(usize, Project)> {
+  let mut parents: Vec<String> = vec![];
+  let mut projects = vec![];
+  for (i, line) in data.lines().enumerate() {
+    let mut words = line.split_whitespace();
+    let name = match words.next() {
+      Some(name) if name != "Project" && !name.chars().all(|c| c == '-') => name,
+      _ => continue,
+    };
+    if !words.any(|w| w.ends_with('%')) {
+      continue;
+    }
+    let depth = (line.len() - line.trim_start().len()) / 2;
+    parents.truncate(depth);
+    let full = if parents.is_empty() {
+      name.to_string()
+    } else {
+      format!("{}.{}", parents.join("."), name)
+    };
+    parents.push(name.to_string());
+    projects.push((i, full));
+  }
+  projects
+}
+
+/// File with the description and goal of a project, e.g. `projects/Home.Garden.md` in the data
+/// directory.
+pub fn notes_path(project: &str) -> PathBuf {
+  utils::get_data_dir().join(NOTES_DIR).join(format!("{}.md", project))
+}
+
+/// Notes of `project`, or of its closest parent project that has notes, with the name of the
+/// project they belong to.
+pub fn read_notes(project: &str) -> Option<(String, String)> {
+  let mut name = project;
+  loop {
+    if let Ok(notes) = fs::read_to_string(notes_path(name)) {
+      if !notes.trim().is_empty() {
+        return Some((name.to_string(), notes));
+      }
+    }
+    name = &name[..name.rfind('.')?];
+  }
+}
+
 fn focus_on_next_project(app: &mut TaskwarriorTui) {
   if app.projects.current_selection < app.projects.list.len().saturating_sub(1) {
     app.projects.current_selection += 1;
@@ -190,3 +250,24 @@ fn update_task_filter_by_selection(app: &mut TaskwarriorTui) -> Result<()> {
   app.filter.update(filter.as_str(), filter.len(), &mut Changeset::default());
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_summary_projects() {
+    let data = "\nProject    Remaining Avg age Complete 0%        100%\n---------- --------- ------- -------- ----------\nHome               3      2w      25% XXX\n  Garden           1      1w       0%\n    Roses          1      3d       0%\nWork               2      5d      50% XXXXX\n(none)             4      1w       0%\n\n3 projects\n";
+    let projects = summary_projects(data);
+    assert_eq!(
+      projects,
+      [
+        (3, "Home".to_string()),
+        (4, "Home.Garden".to_string()),
+        (5, "Home.Garden.Roses".to_string()),
+        (6, "Work".to_string()),
+        (7, "(none)".to_string()),
+      ]
+    );
+  }
+}
@@ -0,0 +1,62 @@
+use ratatui::layout::Rect;
+
+/// Lines scrolled by one notch of the mouse wheel.
+pub const WHEEL_LINES: u16 = 3;
+
+/// A part of the screen that scrolls on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollPane {
+  Report,
+  Details,
+  Help,
+  SnapshotDiff,
+  UsageStats,
+  LogViewer,
+}
+
+/// Where each pane was drawn in the last frame, so that the mouse wheel scrolls the one under the
+/// pointer.
+#[derive(Debug, Default)]
+pub struct PaneAreas {
+  areas: Vec<(ScrollPane, Rect)>,
+}
+
+impl PaneAreas {
+  pub fn clear(&mut self) {
+    self.areas.clear();
+  }
+
+  /// Remember `area` of `pane`, drawn over the panes registered before it.
+  pub fn register(&mut self, pane: ScrollPane, area: Rect) {
+    self.areas.push((pane, area));
+  }
+
+  /// The topmost pane at `column` and `row`.
+  pub fn at(&self, column: u16, row: u16) -> Option<ScrollPane> {
+    self
+      .areas
+      .iter()
+      .rev()
+      .find(|(_, a)| column >= a.x && column < a.x + a.width && row >= a.y && row < a.y + a.height)
+      .map(|(pane, _)| *pane)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_pane_at() {
+    let mut areas = PaneAreas::default();
+    areas.register(ScrollPane::Report, Rect::new(0, 0, 80, 20));
+    areas.register(ScrollPane::Details, Rect::new(0, 20, 80, 20));
+    areas.register(ScrollPane::Help, Rect::new(20, 10, 40, 20));
+    assert_eq!(areas.at(5, 5), Some(ScrollPane::Report));
+    assert_eq!(areas.at(5, 25), Some(ScrollPane::Details));
+    assert_eq!(areas.at(30, 25), Some(ScrollPane::Help));
+    assert_eq!(areas.at(5, 45), None);
+    areas.clear();
+    assert_eq!(areas.at(5, 5), None);
+  }
+}
@@ -0,0 +1,160 @@
+use std::{
+  fmt, fs,
+  path::Path,
+  time::{Duration, Instant},
+};
+
+use anyhow::Result;
+
+/// Only the most recent samples of each metric are kept.
+const MAX_SAMPLES: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+  /// Time spent drawing a frame
+  Draw,
+  /// Time spent running `task export` and parsing its output
+  Export,
+  /// Time from receiving an input or tick event until it is handled
+  Event,
+}
+
+impl fmt::Display for Metric {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let name = match self {
+      Metric::Draw => "draw",
+      Metric::Export => "export",
+      Metric::Event => "event",
+    };
+    write!(f, "{}", name)
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+  pub count: usize,
+  pub mean: Duration,
+  pub p50: Duration,
+  pub p95: Duration,
+  pub max: Duration,
+}
+
+impl Stats {
+  pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+    if samples.is_empty() {
+      return None;
+    }
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+    let percentile = |p: usize| sorted[(sorted.len() - 1) * p / 100];
+    Some(Self {
+      count: sorted.len(),
+      mean: sorted.iter().sum::<Duration>() / sorted.len() as u32,
+      p50: percentile(50),
+      p95: percentile(95),
+      max: sorted[sorted.len() - 1],
+    })
+  }
+}
+
+fn ms(d: Duration) -> String {
+  format!("{:.2}ms", d.as_secs_f64() * 1000.0)
+}
+
+/// Timings recorded with `--trace-perf`.
+pub struct PerfTrace {
+  started: Instant,
+  draw: Vec<Duration>,
+  export: Vec<Duration>,
+  event: Vec<Duration>,
+}
+
+impl PerfTrace {
+  pub fn new() -> Self {
+    Self {
+      started: Instant::now(),
+      draw: vec![],
+      export: vec![],
+      event: vec![],
+    }
+  }
+
+  fn samples_mut(&mut self, metric: Metric) -> &mut Vec<Duration> {
+    match metric {
+      Metric::Draw => &mut self.draw,
+      Metric::Export => &mut self.export,
+      Metric::Event => &mut self.event,
+    }
+  }
+
+  pub fn samples(&self, metric: Metric) -> &[Duration] {
+    match metric {
+      Metric::Draw => &self.draw,
+      Metric::Export => &self.export,
+      Metric::Event => &self.event,
+    }
+  }
+
+  pub fn record(&mut self, metric: Metric, duration: Duration) {
+    let samples = self.samples_mut(metric);
+    if samples.len() == MAX_SAMPLES {
+      samples.remove(0);
+    }
+    samples.push(duration);
+  }
+
+  pub fn report(&self) -> String {
+    let mut lines = vec![
+      format!(
+        "taskwarrior-tui {} performance trace, {:.0}s",
+        env!("CARGO_PKG_VERSION"),
+        self.started.elapsed().as_secs_f64()
+      ),
+      String::new(),
+      format!("{:<8}{:>8}{:>12}{:>12}{:>12}{:>12}", "metric", "count", "mean", "p50", "p95", "max"),
+    ];
+    for metric in [Metric::Draw, Metric::Export, Metric::Event].iter().copied() {
+      match Stats::from_samples(self.samples(metric)) {
+        Some(s) => lines.push(format!(
+          "{:<8}{:>8}{:>12}{:>12}{:>12}{:>12}",
+          metric.to_string(),
+          s.count,
+          ms(s.mean),
+          ms(s.p50),
+          ms(s.p95),
+          ms(s.max)
+        )),
+        None => lines.push(format!("{:<8}{:>8}", metric.to_string(), 0)),
+      }
+    }
+    lines.join("\n")
+  }
+
+  pub fn write(&self, path: &Path) -> Result<()> {
+    fs::write(path, self.report() + "\n")?;
+    Ok(())
+  }
+}
+
+impl Default for PerfTrace {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_stats() {
+    let samples: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+    let stats = Stats::from_samples(&samples).unwrap();
+    assert_eq!(stats.count, 100);
+    assert_eq!(stats.p50, Duration::from_millis(50));
+    assert_eq!(stats.p95, Duration::from_millis(95));
+    assert_eq!(stats.max, Duration::from_millis(100));
+    assert_eq!(stats.mean, Duration::from_micros(50_500));
+    assert_eq!(Stats::from_samples(&[]), None);
+  }
+}
@@ -0,0 +1,174 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use log::warn;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::{
+  event::{Event, KeyCode},
+  overrides,
+};
+
+/// Details of tasks, i.e. the output of `task <uuid>`, fetched in the background a few at a time
+/// so that moving through a long report does not start dozens of processes at once.
+pub struct Prefetch {
+  /// Tasks waiting for their details, the closest to the selected task first
+  queue: VecDeque<Uuid>,
+  running: HashSet<Uuid>,
+  /// Processes started before the tasks changed that did not finish yet, their details are dropped
+  /// but they still count against `concurrency`
+  stale: usize,
+  /// Tasks `task <uuid>` failed for, not fetched again until the tasks change
+  failed: HashSet<Uuid>,
+  /// Processes running at once
+  pub concurrency: usize,
+  /// Bumped when the tasks change, the details fetched before are then dropped
+  generation: u64,
+  tx: mpsc::UnboundedSender<(u64, Uuid, Option<String>)>,
+  rx: mpsc::UnboundedReceiver<(u64, Uuid, Option<String>)>,
+}
+
+impl Prefetch {
+  pub fn new(concurrency: usize) -> Self {
+    let (tx, rx) = mpsc::unbounded_channel();
+    Self {
+      queue: VecDeque::new(),
+      running: HashSet::new(),
+      stale: 0,
+      failed: HashSet::new(),
+      concurrency,
+      generation: 0,
+      tx,
+      rx,
+    }
+  }
+
+  /// Fetch the details of `uuids` next, in this order, instead of those queued before, skipping
+  /// those already fetched, being fetched or that failed.
+  pub fn schedule(&mut self, uuids: impl IntoIterator<Item = Uuid>, fetched: &HashMap<Uuid, String>) {
+    self.queue.clear();
+    for uuid in uuids {
+      let skip = fetched.contains_key(&uuid) || self.running.contains(&uuid) || self.failed.contains(&uuid);
+      if !skip && !self.queue.contains(&uuid) {
+        self.queue.push_back(uuid);
+      }
+    }
+  }
+
+  /// Forget the queue and the details being fetched, the tasks changed.
+  pub fn invalidate(&mut self) {
+    self.queue.clear();
+    self.stale += self.running.len();
+    self.running.clear();
+    self.failed.clear();
+    self.generation += 1;
+  }
+
+  /// Tasks to fetch now so that at most `concurrency` are fetched at once.
+  fn next_batch(&mut self) -> Vec<Uuid> {
+    let mut batch = vec![];
+    while self.running.len() + self.stale < self.concurrency.max(1) {
+      match self.queue.pop_front() {
+        Some(uuid) => {
+          self.running.insert(uuid);
+          batch.push(uuid);
+        }
+        None => break,
+      }
+    }
+    batch
+  }
+
+  /// Run `task <uuid>` for the next tasks in the background, sending `Event::Tick` on `wake` once
+  /// each is done.
+  pub fn start(&mut self, width: u16, wake: &mpsc::UnboundedSender<Event<KeyCode>>) {
+    for uuid in self.next_batch() {
+      let tx = self.tx.clone();
+      let wake = wake.clone();
+      let generation = self.generation;
+      tokio::spawn(async move {
+        let output = overrides::task_async()
+          .arg("rc.color=off")
+          .arg("rc._forcecolor=off")
+          .arg(format!("rc.defaultwidth={}", width))
+          .arg(uuid.to_string())
+          .output()
+          .await;
+        let data = match output {
+          Ok(output) if output.status.success() => Some(String::from_utf8_lossy(&output.stdout).to_string()),
+          Ok(output) => {
+            warn!(
+              "Unable to fetch the details of {}: {}",
+              uuid,
+              String::from_utf8_lossy(&output.stderr).trim()
+            );
+            None
+          }
+          Err(e) => {
+            warn!("Unable to run `task {}`: {}", uuid, e);
+            None
+          }
+        };
+        tx.send((generation, uuid, data)).ok();
+        wake.send(Event::Tick).ok();
+      });
+    }
+  }
+
+  /// Details fetched since the last call, for the current tasks. Those that failed are left out, to
+  /// be fetched again when the task is shown.
+  pub fn poll(&mut self) -> Vec<(Uuid, String)> {
+    let mut fetched = vec![];
+    while let Ok((generation, uuid, data)) = self.rx.try_recv() {
+      if generation != self.generation {
+        self.stale = self.stale.saturating_sub(1);
+        continue;
+      }
+      self.running.remove(&uuid);
+      match data {
+        Some(data) => fetched.push((uuid, data)),
+        None => {
+          self.failed.insert(uuid);
+        }
+      }
+    }
+    fetched
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_prefetch() {
+    let uuids: Vec<Uuid> = (1..=5).map(Uuid::from_u128).collect();
+    let mut prefetch = Prefetch::new(2);
+    let fetched = HashMap::from([(uuids[0], String::new())]);
+    prefetch.schedule(uuids.clone(), &fetched);
+    assert_eq!(prefetch.next_batch(), uuids[1..3]);
+    assert!(prefetch.next_batch().is_empty());
+
+    // the selection moved, tasks being fetched are not queued again
+    prefetch.schedule(uuids.iter().rev().copied(), &fetched);
+    assert_eq!(prefetch.queue, uuids[3..].iter().rev().copied().collect::<Vec<_>>());
+    prefetch.tx.send((0, uuids[1], Some("details".to_string()))).unwrap();
+    assert_eq!(prefetch.poll(), [(uuids[1], "details".to_string())]);
+    assert_eq!(prefetch.next_batch(), [uuids[4]]);
+
+    // processes started before the tasks changed still count until they finish
+    prefetch.invalidate();
+    prefetch.schedule(uuids.clone(), &fetched);
+    assert!(prefetch.next_batch().is_empty());
+    prefetch.tx.send((0, uuids[2], Some("stale".to_string()))).unwrap();
+    assert!(prefetch.poll().is_empty());
+    assert_eq!(prefetch.next_batch(), [uuids[1]]);
+
+    // a failed task is not fetched again until the tasks change
+    prefetch.tx.send((0, uuids[4], None)).unwrap();
+    prefetch.tx.send((1, uuids[1], None)).unwrap();
+    assert!(prefetch.poll().is_empty());
+    prefetch.schedule(uuids.clone(), &fetched);
+    assert_eq!(prefetch.next_batch(), uuids[2..4]);
+  }
+}
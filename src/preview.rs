@@ -0,0 +1,222 @@
+use std::{
+  env, fs,
+  io::{self, Read, Write},
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+use crossterm::{
+  cursor::{MoveTo, RestorePosition, SavePosition},
+  queue,
+};
+use log::{debug, warn};
+use ratatui::layout::Rect;
+
+use crate::utils;
+
+const IMAGE_EXTENSIONS: [&str; 6] = ["png", "jpg", "jpeg", "gif", "bmp", "webp"];
+
+/// Cell size in pixels when the terminal does not report it.
+const DEFAULT_CELL_SIZE: (u16, u16) = (8, 16);
+
+/// How images are drawn in the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Graphics {
+  /// The kitty graphics protocol, which reads PNG files directly
+  Kitty,
+  /// Sixel graphics, encoded by `img2sixel` from libsixel
+  Sixel,
+  /// Images are described in text only
+  Off,
+}
+
+impl Graphics {
+  /// Graphics for the `uda.taskwarrior-tui.image-preview` setting: `auto`, `kitty`, `sixel` or `off`.
+  pub fn detect(setting: &str) -> Self {
+    if cfg!(not(feature = "graphics")) {
+      return Graphics::Off;
+    }
+    match setting {
+      "kitty" => return Graphics::Kitty,
+      "sixel" => return Graphics::Sixel,
+      "off" | "false" | "no" => return Graphics::Off,
+      _ => {}
+    }
+    let term = env::var("TERM").unwrap_or_default();
+    let program = env::var("TERM_PROGRAM").unwrap_or_default();
+    if env::var("KITTY_WINDOW_ID").is_ok() || term.contains("kitty") || program == "WezTerm" || program == "ghostty" {
+      Graphics::Kitty
+    } else if (term.contains("sixel") || term.starts_with("foot") || term.starts_with("mlterm")) && has_img2sixel() {
+      Graphics::Sixel
+    } else {
+      Graphics::Off
+    }
+  }
+}
+
+fn has_img2sixel() -> bool {
+  Command::new("img2sixel").arg("--version").output().is_ok()
+}
+
+/// First existing image file referenced in `text`, e.g. an annotation `diagram ~/notes/flow.png`.
+pub fn image_path(text: &str) -> Option<PathBuf> {
+  text.split_whitespace().find_map(|word| {
+    let word = word.trim_start_matches("file://");
+    let extension = Path::new(word).extension()?.to_str()?.to_lowercase();
+    if !IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+      return None;
+    }
+    let path = match word.strip_prefix("~/") {
+      Some(rest) => dirs::home_dir()?.join(rest),
+      None => PathBuf::from(word),
+    };
+    if path.is_file() {
+      Some(path)
+    } else {
+      None
+    }
+  })
+}
+
+/// Width and height of a PNG image from its header.
+pub fn png_size(header: &[u8]) -> Option<(u32, u32)> {
+  if header.len() < 24 || &header[..8] != b"\x89PNG\r\n\x1a\n" || &header[12..16] != b"IHDR" {
+    return None;
+  }
+  let width = u32::from_be_bytes([header[16], header[17], header[18], header[19]]);
+  let height = u32::from_be_bytes([header[20], header[21], header[22], header[23]]);
+  Some((width, height))
+}
+
+fn read_png_size(path: &Path) -> Option<(u32, u32)> {
+  let mut header = [0u8; 24];
+  fs::File::open(path).and_then(|mut f| f.read_exact(&mut header)).ok()?;
+  png_size(&header)
+}
+
+/// One line describing the image, shown when it cannot be drawn.
+pub fn describe(path: &Path) -> String {
+  match read_png_size(path) {
+    Some((width, height)) => format!("Image: {} ({}x{})", path.display(), width, height),
+    None => format!("Image: {}", path.display()),
+  }
+}
+
+/// The image shown in the task details, drawn over the cells of its area after each frame.
+pub struct ImagePreview {
+  pub graphics: Graphics,
+  shown: Option<(PathBuf, Rect)>,
+  requested: Option<(PathBuf, Rect)>,
+}
+
+impl ImagePreview {
+  pub fn new(graphics: Graphics) -> Self {
+    Self {
+      graphics,
+      shown: None,
+      requested: None,
+    }
+  }
+
+  /// Whether `path` can be drawn, kitty only reads PNG files.
+  pub fn can_show(&self, path: &Path) -> bool {
+    match self.graphics {
+      Graphics::Kitty => read_png_size(path).is_some(),
+      Graphics::Sixel => true,
+      Graphics::Off => false,
+    }
+  }
+
+  /// Show `path` in `area` after the current frame, the preview is hidden when nothing is requested.
+  pub fn request(&mut self, path: PathBuf, area: Rect) {
+    self.requested = Some((path, area));
+  }
+
+  /// Draw or remove the image after a frame, returns true when the frame has to be redrawn to remove
+  /// a sixel image.
+  pub fn flush(&mut self) -> io::Result<bool> {
+    let requested = self.requested.take();
+    if requested == self.shown {
+      return Ok(false);
+    }
+    let mut stdout = io::stdout();
+    if self.shown.is_some() {
+      match self.graphics {
+        // delete all placements visible on the screen
+        Graphics::Kitty => write!(stdout, "\x1b_Ga=d,d=a,q=2\x1b\\")?,
+        // sixel images are part of the screen, only a full redraw removes them, the requested image
+        // is drawn after the next frame
+        Graphics::Sixel => {
+          self.shown = None;
+          return Ok(true);
+        }
+        Graphics::Off => {}
+      }
+    }
+    if let Some((path, area)) = &requested {
+      if let Err(e) = self.draw(&mut stdout, path, *area) {
+        warn!("Unable to show image {:?}: {}", path, e);
+      }
+    }
+    stdout.flush()?;
+    self.shown = requested;
+    Ok(false)
+  }
+
+  /// Forget the shown image after the screen was cleared, so that it is drawn again.
+  pub fn invalidate(&mut self) {
+    self.shown = None;
+  }
+
+  fn draw(&self, stdout: &mut io::Stdout, path: &Path, area: Rect) -> io::Result<()> {
+    debug!("Showing image {:?} in {:?}", path, area);
+    let path = fs::canonicalize(path)?;
+    queue!(stdout, SavePosition, MoveTo(area.x, area.y))?;
+    match self.graphics {
+      Graphics::Kitty => {
+        // the terminal reads the file, and scales the image to the area
+        write!(
+          stdout,
+          "\x1b_Ga=T,t=f,f=100,q=2,c={},r={};{}\x1b\\",
+          area.width,
+          area.height,
+          utils::base64_encode(path.to_string_lossy().as_bytes())
+        )?;
+      }
+      Graphics::Sixel => {
+        let cell = crossterm::terminal::window_size()
+          .ok()
+          .filter(|s| s.width > 0 && s.columns > 0)
+          .map(|s| (s.width / s.columns, s.height / s.rows))
+          .unwrap_or(DEFAULT_CELL_SIZE);
+        let (max_width, max_height) = (area.width as u32 * cell.0 as u32, area.height as u32 * cell.1 as u32);
+        let mut command = Command::new("img2sixel");
+        match read_png_size(&path) {
+          Some((width, height)) if width as u64 * max_height as u64 > height as u64 * max_width as u64 => {
+            command.arg(format!("--width={}", max_width))
+          }
+          _ => command.arg(format!("--height={}", max_height)),
+        };
+        let output = command.arg(&path).output()?;
+        stdout.write_all(&output.stdout)?;
+      }
+      Graphics::Off => {}
+    }
+    queue!(stdout, RestorePosition)?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_png_size() {
+    let mut header = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    header.extend_from_slice(&640u32.to_be_bytes());
+    header.extend_from_slice(&480u32.to_be_bytes());
+    assert_eq!(png_size(&header), Some((640, 480)));
+    assert_eq!(png_size(b"GIF89a"), None);
+  }
+}
@@ -0,0 +1,197 @@
+/// A task typed in the add prompt with inline syntax, e.g. `pay rent tomorrow 5pm #home @errands p:H`.
+///
+/// `#name` sets the project, `@name` adds a tag, `p:H` or `p1` to `p3` set the priority, and a day
+/// (`today`, `tomorrow`, a weekday, `in 3 days`) and a time (`5pm`, `17:30`) set the due date. Words
+/// in quotes and taskwarrior attributes such as `due:eom` or `+tag` are kept as they are.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuickAdd {
+  pub description: Vec<String>,
+  pub attributes: Vec<String>,
+}
+
+const DAYS: [&str; 10] = [
+  "today",
+  "tomorrow",
+  "monday",
+  "tuesday",
+  "wednesday",
+  "thursday",
+  "friday",
+  "saturday",
+  "sunday",
+  "eow",
+];
+
+/// Time of day as hours and minutes, e.g. `5pm`, `5:30pm`, `17:30` or `9am`.
+fn parse_time(word: &str) -> Option<(u32, u32)> {
+  let word = word.to_lowercase();
+  let (clock, offset) = if let Some(clock) = word.strip_suffix("am") {
+    (clock, Some(0))
+  } else if let Some(clock) = word.strip_suffix("pm") {
+    (clock, Some(12))
+  } else {
+    (word.as_str(), None)
+  };
+  let (hours, minutes) = match clock.split_once(':') {
+    Some((h, m)) if m.len() == 2 => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+    // a bare number is only a time with am or pm
+    None if offset.is_some() => (clock.parse::<u32>().ok()?, 0),
+    _ => return None,
+  };
+  let hours = match offset {
+    Some(_) if hours == 0 || hours > 12 => return None,
+    Some(offset) => hours % 12 + offset,
+    None => hours,
+  };
+  (hours < 24 && minutes < 60).then_some((hours, minutes))
+}
+
+/// Unit of `in 3 days` in a taskwarrior duration.
+fn duration_unit(word: &str) -> Option<&'static str> {
+  match word.trim_end_matches('s') {
+    "day" => Some("d"),
+    "week" => Some("w"),
+    "month" => Some("mo"),
+    _ => None,
+  }
+}
+
+fn priority(value: &str) -> Option<&'static str> {
+  match value.to_uppercase().as_str() {
+    "H" | "1" => Some("H"),
+    "M" | "2" => Some("M"),
+    "L" | "3" => Some("L"),
+    _ => None,
+  }
+}
+
+/// An attribute taskwarrior understands as it is, e.g. `due:eom`, `+tag` or `-tag`.
+fn is_attribute(word: &str) -> bool {
+  let tag = (word.starts_with('+') || word.starts_with('-')) && word.len() > 1 && !word[1..].starts_with(char::is_numeric);
+  let attribute = word.split_once(':').is_some_and(|(name, value)| {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_') && !value.starts_with("//")
+  });
+  tag || attribute
+}
+
+/// Words of `text` split at whitespace outside of quotes, with whether they were quoted. Unlike
+/// shell quoting, `#` does not start a comment.
+fn words(text: &str) -> Option<Vec<(String, bool)>> {
+  let mut words = vec![];
+  let mut word = String::new();
+  let mut quoted = false;
+  let mut quote = None;
+  for c in text.chars() {
+    match quote {
+      Some(q) if c == q => quote = None,
+      Some(_) => word.push(c),
+      None if c == '"' || c == '\'' => {
+        quote = Some(c);
+        quoted = true;
+      }
+      None if c.is_whitespace() => {
+        if !word.is_empty() || quoted {
+          words.push((std::mem::take(&mut word), quoted));
+        }
+        quoted = false;
+      }
+      None => word.push(c),
+    }
+  }
+  if quote.is_some() {
+    return None;
+  }
+  if !word.is_empty() || quoted {
+    words.push((word, quoted));
+  }
+  Some(words)
+}
+
+impl QuickAdd {
+  /// Parse the text of the add prompt, `None` when its quotes are unbalanced.
+  pub fn parse(text: &str) -> Option<Self> {
+    let (words, quoted): (Vec<String>, Vec<bool>) = words(text)?.into_iter().unzip();
+    let mut quick = QuickAdd::default();
+    let mut day: Option<String> = None;
+    let mut time: Option<(u32, u32)> = None;
+    let mut i = 0;
+    while i < words.len() {
+      let word = &words[i];
+      let lower = word.to_lowercase();
+      let count = words.get(i + 1).and_then(|w| w.parse::<u32>().ok());
+      let unit = words.get(i + 2).map(|w| w.to_lowercase());
+      if quoted[i] {
+        quick.description.push(word.clone());
+      } else if let Some(project) = word.strip_prefix('#').filter(|p| !p.is_empty()) {
+        quick.attributes.push(format!("project:{}", project));
+      } else if let Some(tag) = word.strip_prefix('@').filter(|t| !t.is_empty()) {
+        quick.attributes.push(format!("+{}", tag));
+      } else if let Some(p) = lower
+        .strip_prefix("p:")
+        .or_else(|| lower.strip_prefix('p').filter(|n| n.len() == 1 && n.starts_with(char::is_numeric)))
+        .and_then(priority)
+      {
+        quick.attributes.push(format!("priority:{}", p));
+      } else if day.is_none() && DAYS.contains(&lower.as_str()) {
+        day = Some(lower);
+      } else if let (Some(n), Some(unit), true) = (count, unit.as_deref().and_then(duration_unit), day.is_none() && lower == "in") {
+        day = Some(format!("today+{}{}", n, unit));
+        i += 3;
+        continue;
+      } else if time.is_none() && parse_time(word).is_some() {
+        time = parse_time(word);
+      } else if is_attribute(word) {
+        quick.attributes.push(word.clone());
+      } else {
+        quick.description.push(word.clone());
+      }
+      i += 1;
+    }
+    let due = match (day, time) {
+      (day, Some((hours, 0))) => Some(format!("{}+{}h", day.unwrap_or_else(|| "today".to_string()), hours)),
+      (day, Some((hours, minutes))) => Some(format!("{}+PT{}H{}M", day.unwrap_or_else(|| "today".to_string()), hours, minutes)),
+      (Some(day), None) => Some(day),
+      (None, None) => None,
+    };
+    if let Some(due) = due {
+      quick.attributes.insert(0, format!("due:{}", due));
+    }
+    Some(quick)
+  }
+
+  /// Arguments of `task add`.
+  pub fn args(&self) -> Vec<String> {
+    let mut args = vec![];
+    if !self.description.is_empty() {
+      args.push(self.description.join(" "));
+    }
+    args.extend(self.attributes.iter().cloned());
+    args
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    let quick = QuickAdd::parse("pay rent tomorrow 5pm #home @errands p:H").unwrap();
+    assert_eq!(quick.description, vec!["pay", "rent"]);
+    assert_eq!(quick.attributes, vec!["due:tomorrow+17h", "project:home", "+errands", "priority:H"]);
+    assert_eq!(quick.args()[0], "pay rent");
+
+    let quick = QuickAdd::parse(r#""meet at 5pm today" 9:30am p2 due.before:eom"#).unwrap();
+    assert_eq!(quick.description, vec!["meet at 5pm today"]);
+    assert_eq!(quick.attributes, vec!["due:today+PT9H30M", "priority:M", "due.before:eom"]);
+
+    let quick = QuickAdd::parse("call back in 3 days +phone").unwrap();
+    assert_eq!(quick.attributes, vec!["due:today+3d", "+phone"]);
+    assert_eq!(quick.description, vec!["call", "back"]);
+
+    assert_eq!(parse_time("12am"), Some((0, 0)));
+    assert_eq!(parse_time("12pm"), Some((12, 0)));
+    assert_eq!(parse_time("5"), None);
+    assert_eq!(QuickAdd::parse("\"unbalanced"), None);
+  }
+}
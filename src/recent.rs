@@ -0,0 +1,146 @@
+use chrono::{DateTime, Local};
+use ratatui::widgets::ListState;
+use uuid::Uuid;
+
+const MAX_RECENT_ACTIONS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecentActionKind {
+  Done,
+  Delete,
+}
+
+impl RecentActionKind {
+  pub fn label(&self) -> &'static str {
+    match self {
+      RecentActionKind::Done => "completed",
+      RecentActionKind::Delete => "deleted",
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecentAction {
+  pub kind: RecentActionKind,
+  pub uuid: Uuid,
+  pub description: String,
+  pub time: DateTime<Local>,
+}
+
+/// Tasks completed or deleted through the TUI during this session, most recent first.
+pub struct RecentActions {
+  pub items: Vec<RecentAction>,
+  pub state: ListState,
+}
+
+impl RecentActions {
+  pub fn new() -> Self {
+    Self {
+      items: vec![],
+      state: ListState::default(),
+    }
+  }
+
+  pub fn push(&mut self, kind: RecentActionKind, uuid: Uuid, description: String) {
+    self.items.retain(|a| a.uuid != uuid);
+    self.items.insert(
+      0,
+      RecentAction {
+        kind,
+        uuid,
+        description,
+        time: Local::now(),
+      },
+    );
+    self.items.truncate(MAX_RECENT_ACTIONS);
+  }
+
+  pub fn selected(&self) -> Option<&RecentAction> {
+    self.state.selected().and_then(|i| self.items.get(i))
+  }
+
+  pub fn remove_selected(&mut self) -> Option<RecentAction> {
+    let i = self.state.selected()?;
+    if i >= self.items.len() {
+      return None;
+    }
+    let action = self.items.remove(i);
+    self.select_first_if_needed();
+    Some(action)
+  }
+
+  pub fn select_first_if_needed(&mut self) {
+    if self.items.is_empty() {
+      self.state.select(None);
+    } else {
+      let i = self.state.selected().unwrap_or(0).min(self.items.len() - 1);
+      self.state.select(Some(i));
+    }
+  }
+
+  pub fn next(&mut self) {
+    if self.items.is_empty() {
+      return;
+    }
+    let i = match self.state.selected() {
+      Some(i) if i + 1 < self.items.len() => i + 1,
+      _ => 0,
+    };
+    self.state.select(Some(i));
+  }
+
+  pub fn previous(&mut self) {
+    if self.items.is_empty() {
+      return;
+    }
+    let i = match self.state.selected() {
+      Some(0) | None => self.items.len() - 1,
+      Some(i) => i - 1,
+    };
+    self.state.select(Some(i));
+  }
+}
+
+impl Default for RecentActions {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_recent_actions() {
+    let uuid = |n: u128| Uuid::from_u128(n);
+    let mut recent = RecentActions::new();
+    recent.push(RecentActionKind::Done, uuid(1), "Pay rent".to_string());
+    recent.push(RecentActionKind::Delete, uuid(2), "Call bank".to_string());
+    // acting on a task again moves it to the top instead of listing it twice
+    recent.push(RecentActionKind::Delete, uuid(1), "Pay rent".to_string());
+    assert_eq!(recent.items.len(), 2);
+    assert_eq!((recent.items[0].uuid, recent.items[0].kind), (uuid(1), RecentActionKind::Delete));
+
+    assert!(recent.selected().is_none());
+    recent.select_first_if_needed();
+    assert_eq!(recent.selected().map(|a| a.uuid), Some(uuid(1)));
+    recent.previous();
+    assert_eq!(recent.state.selected(), Some(1));
+    recent.next();
+    assert_eq!(recent.state.selected(), Some(0));
+
+    recent.next();
+    assert_eq!(recent.remove_selected().map(|a| a.uuid), Some(uuid(2)));
+    assert_eq!(recent.state.selected(), Some(0));
+    assert_eq!(recent.remove_selected().map(|a| a.uuid), Some(uuid(1)));
+    assert!(recent.state.selected().is_none());
+    assert!(recent.remove_selected().is_none());
+
+    for n in 0..150 {
+      recent.push(RecentActionKind::Done, uuid(n), String::new());
+    }
+    assert_eq!(recent.items.len(), MAX_RECENT_ACTIONS);
+    assert_eq!(recent.items[0].uuid, uuid(149));
+  }
+}
@@ -0,0 +1,112 @@
+use std::{
+  fs::{self, File},
+  io::{BufWriter, Write},
+  path::Path,
+  time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::event::{Event, KeyCode};
+
+/// One line of a session recording.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Entry {
+  /// Event delivered to the interface, replayed with `--replay`
+  Event { delay_ms: u64, event: Event<KeyCode> },
+  /// Taskwarrior command run in response to the previous event, only informative
+  Command { command: String },
+}
+
+/// Writes the events of a session to a file, one JSON entry per line.
+pub struct Recorder {
+  file: BufWriter<File>,
+  last: Instant,
+}
+
+impl Recorder {
+  pub fn create(path: &Path) -> Result<Self> {
+    let file = File::create(path).with_context(|| format!("Unable to create recording {:?}", path))?;
+    Ok(Self {
+      file: BufWriter::new(file),
+      last: Instant::now(),
+    })
+  }
+
+  fn write(&mut self, entry: &Entry) -> Result<()> {
+    writeln!(self.file, "{}", serde_json::to_string(entry)?)?;
+    // keep the recording usable when taskwarrior-tui crashes
+    self.file.flush()?;
+    Ok(())
+  }
+
+  pub fn event(&mut self, event: Event<KeyCode>) -> Result<()> {
+    let delay_ms = self.last.elapsed().as_millis() as u64;
+    self.last = Instant::now();
+    self.write(&Entry::Event { delay_ms, event })
+  }
+
+  pub fn command(&mut self, command: &str) -> Result<()> {
+    self.write(&Entry::Command {
+      command: command.to_string(),
+    })
+  }
+}
+
+/// Events of a recording with the delay before each of them.
+pub fn parse(recording: &str) -> Result<Vec<(Duration, Event<KeyCode>)>> {
+  let mut events = vec![];
+  for (i, line) in recording.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+    let entry: Entry = serde_json::from_str(line).with_context(|| format!("Invalid entry on line {} of the recording", i + 1))?;
+    if let Entry::Event { delay_ms, event } = entry {
+      events.push((Duration::from_millis(delay_ms), event));
+    }
+  }
+  Ok(events)
+}
+
+pub fn load(path: &Path) -> Result<Vec<(Duration, Event<KeyCode>)>> {
+  let recording = fs::read_to_string(path).with_context(|| format!("Unable to read recording {:?}", path))?;
+  parse(&recording)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    let recording = [
+      Entry::Event {
+        delay_ms: 0,
+        event: Event::Resize(80, 24),
+      },
+      Entry::Event {
+        delay_ms: 120,
+        event: Event::Input(KeyCode::Char('d')),
+      },
+      Entry::Command {
+        command: "task rc.confirmation=off 1 done".to_string(),
+      },
+      Entry::Event {
+        delay_ms: 250,
+        event: Event::Tick,
+      },
+    ]
+    .iter()
+    .map(|e| serde_json::to_string(e).unwrap())
+    .collect::<Vec<_>>()
+    .join("\n");
+    assert_eq!(
+      parse(&recording).unwrap(),
+      vec![
+        (Duration::from_millis(0), Event::Resize(80, 24)),
+        (Duration::from_millis(120), Event::Input(KeyCode::Char('d'))),
+        (Duration::from_millis(250), Event::Tick),
+      ]
+    );
+    assert!(parse("not json").is_err());
+  }
+}
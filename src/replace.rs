@@ -0,0 +1,161 @@
+use regex::{Regex, RegexBuilder};
+use task_hookrs::task::Task;
+use uuid::Uuid;
+
+/// A `/pattern/replacement/flags` expression, as in sed: any character not in the pattern can
+/// delimit it instead of `/`. Only the first match is replaced unless the `g` flag is given, and
+/// `i` makes the pattern case insensitive. `$1` or `${name}` in the replacement insert groups.
+#[derive(Debug, Clone)]
+pub struct Replacement {
+  pub regex: Regex,
+  pub replacement: String,
+  pub global: bool,
+}
+
+impl Replacement {
+  pub fn parse(expression: &str) -> Result<Self, String> {
+    let mut chars = expression.trim().chars();
+    let delimiter = chars
+      .next()
+      .filter(|c| !c.is_alphanumeric() && !c.is_whitespace() && *c != '\\')
+      .ok_or("Start with a delimiter, e.g. `/pattern/replacement/`")?;
+    let parts: Vec<&str> = chars.as_str().splitn(3, delimiter).collect();
+    let (pattern, replacement, flags) = match parts.as_slice() {
+      [pattern, replacement] => (*pattern, *replacement, ""),
+      [pattern, replacement, flags] => (*pattern, *replacement, *flags),
+      _ => return Err(format!("Missing the replacement, e.g. `{0}pattern{0}replacement{0}`", delimiter)),
+    };
+    if pattern.is_empty() {
+      return Err("The pattern is empty".to_string());
+    }
+    if let Some(flag) = flags.chars().find(|c| *c != 'g' && *c != 'i') {
+      return Err(format!("Unknown flag `{}`, use `g` and `i`", flag));
+    }
+    let regex = RegexBuilder::new(pattern)
+      .case_insensitive(flags.contains('i'))
+      .build()
+      .map_err(|e| e.to_string())?;
+    Ok(Self {
+      regex,
+      replacement: replacement.to_string(),
+      global: flags.contains('g'),
+    })
+  }
+
+  /// `text` with the replacement made, `None` when the pattern does not match.
+  pub fn apply(&self, text: &str) -> Option<String> {
+    if !self.regex.is_match(text) {
+      return None;
+    }
+    let replaced = if self.global {
+      self.regex.replace_all(text, self.replacement.as_str())
+    } else {
+      self.regex.replace(text, self.replacement.as_str())
+    };
+    Some(replaced.into_owned())
+  }
+}
+
+/// A description to change, reviewed before the change is made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+  pub uuid: Uuid,
+  pub before: String,
+  pub after: String,
+  pub accepted: bool,
+}
+
+/// Changes to the descriptions of `tasks`, one per task the replacement changes.
+pub fn changes(replacement: &Replacement, tasks: &[Task]) -> Vec<Change> {
+  tasks
+    .iter()
+    .filter_map(|task| {
+      let after = replacement.apply(task.description())?;
+      let after = after.split_whitespace().collect::<Vec<_>>().join(" ");
+      (!after.is_empty() && after != *task.description()).then(|| Change {
+        uuid: *task.uuid(),
+        before: task.description().clone(),
+        after,
+        accepted: false,
+      })
+    })
+    .collect()
+}
+
+/// Going through the changes one by one, accepting or skipping each.
+#[derive(Debug, Clone, Default)]
+pub struct ReplaceReview {
+  pub changes: Vec<Change>,
+  /// Change being reviewed, all were once it is the number of changes
+  pub index: usize,
+}
+
+impl ReplaceReview {
+  pub fn new(changes: Vec<Change>) -> Self {
+    Self { changes, index: 0 }
+  }
+
+  pub fn current(&self) -> Option<&Change> {
+    self.changes.get(self.index)
+  }
+
+  pub fn done(&self) -> bool {
+    self.index >= self.changes.len()
+  }
+
+  /// Accept or skip the current change and move to the next one.
+  pub fn decide(&mut self, accept: bool) {
+    if let Some(change) = self.changes.get_mut(self.index) {
+      change.accepted = accept;
+      self.index += 1;
+    }
+  }
+
+  /// Accept the current change and all those after it.
+  pub fn accept_rest(&mut self) {
+    while !self.done() {
+      self.decide(true);
+    }
+  }
+
+  pub fn accepted(&self) -> impl Iterator<Item = &Change> {
+    self.changes.iter().filter(|c| c.accepted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  #[test]
+  fn test_replace() {
+    assert!(Replacement::parse("abc").is_err());
+    assert!(Replacement::parse("/abc").is_err());
+    assert!(Replacement::parse("/a(/b/").is_err());
+    assert!(Replacement::parse("/a/b/x").is_err());
+    let r = Replacement::parse("/colou?r/hue/").unwrap();
+    assert_eq!(r.apply("color and colour").as_deref(), Some("hue and colour"));
+    let r = Replacement::parse("|(\\w+)@home|$1@work|gi").unwrap();
+    assert_eq!(r.apply("Mail bob@Home and ann@home").as_deref(), Some("Mail bob@work and ann@work"));
+    assert_eq!(r.apply("nothing"), None);
+
+    let tasks = import(
+      r#"[
+        {"uuid":"00000000-0000-0000-0000-000000000001","description":"Call Bob","status":"pending","entry":"20260101T000000Z"},
+        {"uuid":"00000000-0000-0000-0000-000000000002","description":"Email Ann","status":"pending","entry":"20260101T000000Z"},
+        {"uuid":"00000000-0000-0000-0000-000000000003","description":"Call Ann","status":"pending","entry":"20260101T000000Z"}
+      ]"#
+        .as_bytes(),
+    )
+    .unwrap();
+    let mut review = ReplaceReview::new(changes(&Replacement::parse("/^Call /Phone /").unwrap(), &tasks));
+    assert_eq!(review.changes.len(), 2);
+    assert_eq!(review.current().unwrap().after, "Phone Bob");
+    review.decide(false);
+    review.accept_rest();
+    assert!(review.done());
+    assert_eq!(review.accepted().map(|c| c.after.as_str()).collect::<Vec<_>>(), ["Phone Ann"]);
+  }
+}
@@ -0,0 +1,216 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Result;
+use chrono::Local;
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::utils;
+
+const PREFIX: &str = "uda.taskwarrior-tui.review.";
+
+/// A step of the weekly review, loading a filter in the report.
+///
+/// ```plaintext
+/// uda.taskwarrior-tui.review.steps=inbox,waiting,projects,someday,goals
+/// uda.taskwarrior-tui.review.step.goals.label=Check the goals
+/// uda.taskwarrior-tui.review.step.goals.filter=status:pending goal.any:
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+  pub name: String,
+  pub label: String,
+  pub filter: String,
+}
+
+fn default_step(name: &str) -> Step {
+  let (label, filter) = match name {
+    "inbox" => ("Process the inbox", "status:pending +inbox"),
+    "waiting" => ("Review waiting tasks", "+WAITING"),
+    "projects" => ("Review projects without next actions", "status:pending project.any: -next"),
+    "someday" => ("Review the someday list", "status:pending +someday"),
+    _ => (name, "status:pending"),
+  };
+  Step {
+    name: name.to_string(),
+    label: label.to_string(),
+    filter: filter.to_string(),
+  }
+}
+
+/// Steps of the weekly review in the output of `task show`, in the order of the `steps` setting.
+pub fn parse(data: &str) -> Vec<Step> {
+  let mut names = "inbox,waiting,projects,someday".to_string();
+  let mut settings = vec![];
+  for line in data.lines() {
+    let rest = match line.strip_prefix(PREFIX) {
+      Some(rest) => rest,
+      None => continue,
+    };
+    let (key, value) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let value = value.trim().to_string();
+    if key == "steps" {
+      names = value;
+    } else if let Some((name, setting)) = key.strip_prefix("step.").and_then(|k| k.rsplit_once('.')) {
+      settings.push((name.to_string(), setting.to_string(), value));
+    }
+  }
+  let mut steps: Vec<Step> = names.split(',').map(str::trim).filter(|n| !n.is_empty()).map(default_step).collect();
+  for (name, setting, value) in settings {
+    let step = match steps.iter_mut().find(|s| s.name == name) {
+      Some(step) => step,
+      None => {
+        warn!("Review step {} is not in uda.taskwarrior-tui.review.steps", name);
+        continue;
+      }
+    };
+    match setting.as_str() {
+      "label" => step.label = value,
+      "filter" => step.filter = value,
+      _ => warn!("Unknown setting {} of review step {}", setting, name),
+    }
+  }
+  steps
+}
+
+/// Progress of the weekly review, kept in the data directory so that a review can be finished
+/// another day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewState {
+  /// Names of the steps done in the review in progress
+  pub done: Vec<String>,
+  /// Step whose tasks are shown in the report, done once the checklist is opened again
+  #[serde(default)]
+  pub current: Option<String>,
+  /// When the review in progress was started, in RFC 3339
+  pub started: Option<String>,
+  /// When the last review was completed, in RFC 3339
+  pub completed: Option<String>,
+  /// Filter of the report before the review, restored once it is over
+  pub filter: Option<String>,
+}
+
+pub struct Review {
+  pub state: ReviewState,
+  path: PathBuf,
+}
+
+impl Review {
+  pub fn new(filename: &str) -> Self {
+    Self {
+      state: ReviewState::default(),
+      path: utils::get_data_dir().join(filename),
+    }
+  }
+
+  pub fn load(&mut self) -> Result<()> {
+    if self.path.exists() {
+      self.state = serde_json::from_str(&fs::read_to_string(&self.path)?)?;
+    }
+    Ok(())
+  }
+
+  fn write(&self) -> Result<()> {
+    fs::write(&self.path, serde_json::to_string_pretty(&self.state)?)?;
+    Ok(())
+  }
+
+  pub fn in_progress(&self) -> bool {
+    self.state.started.is_some()
+  }
+
+  /// Start a review, unless one is in progress, remembering the filter to restore afterwards.
+  pub fn start(&mut self, filter: &str) -> Result<()> {
+    if !self.in_progress() {
+      self.state.done.clear();
+      self.state.current = None;
+      self.state.started = Some(Local::now().to_rfc3339());
+      self.state.filter = Some(filter.to_string());
+      self.write()?;
+    }
+    Ok(())
+  }
+
+  pub fn is_done(&self, step: &Step) -> bool {
+    self.state.done.contains(&step.name)
+  }
+
+  /// Mark a step done or not. Returns the filter to restore when this completed the review.
+  pub fn set_done(&mut self, step: &Step, done: bool, steps: &[Step]) -> Result<Option<String>> {
+    self.state.done.retain(|n| n != &step.name);
+    if done {
+      self.state.done.push(step.name.clone());
+    }
+    let restore = if steps.iter().all(|s| self.is_done(s)) {
+      self.state.completed = Some(Local::now().to_rfc3339());
+      self.state.started = None;
+      self.state.done.clear();
+      self.state.current = None;
+      Some(self.state.filter.take().unwrap_or_default())
+    } else {
+      None
+    };
+    self.write()?;
+    Ok(restore)
+  }
+
+  /// Show the tasks of `step` in the report.
+  pub fn load_step(&mut self, step: &Step) -> Result<()> {
+    self.state.current = Some(step.name.clone());
+    self.write()
+  }
+
+  /// Mark the step whose tasks were shown done. Returns the filter to restore when this completed
+  /// the review.
+  pub fn finish_current(&mut self, steps: &[Step]) -> Result<Option<String>> {
+    match self.state.current.take().and_then(|name| steps.iter().find(|s| s.name == name)) {
+      Some(step) => self.set_done(step, true, steps),
+      None => Ok(None),
+    }
+  }
+
+  /// Give up on the review in progress. Returns the filter to restore.
+  pub fn abandon(&mut self) -> Result<String> {
+    self.state.started = None;
+    self.state.done.clear();
+    self.state.current = None;
+    let filter = self.state.filter.take().unwrap_or_default();
+    self.write()?;
+    Ok(filter)
+  }
+
+  /// Index of the first step not done yet.
+  pub fn next_step(&self, steps: &[Step]) -> usize {
+    steps.iter().position(|s| !self.is_done(s)).unwrap_or_default()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_review() {
+    assert_eq!(parse("").len(), 4);
+    let data = "uda.taskwarrior-tui.review.step.goals.filter  goal.any:\nuda.taskwarrior-tui.review.steps  inbox, goals\n";
+    let steps = parse(data);
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[0].filter, "status:pending +inbox");
+    assert_eq!(steps[1].label, "goals");
+    assert_eq!(steps[1].filter, "goal.any:");
+
+    let mut review = Review {
+      state: ReviewState::default(),
+      path: std::env::temp_dir().join(format!("taskwarrior-tui-review-{}.json", std::process::id())),
+    };
+    review.start("+work").unwrap();
+    assert!(review.in_progress());
+    assert_eq!(review.set_done(&steps[1], true, &steps).unwrap(), None);
+    assert_eq!(review.next_step(&steps), 0);
+    review.load_step(&steps[0]).unwrap();
+    assert_eq!(review.finish_current(&steps).unwrap().as_deref(), Some("+work"));
+    assert!(!review.in_progress());
+    assert!(review.state.completed.is_some());
+    fs::remove_file(&review.path).ok();
+  }
+}
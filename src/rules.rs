@@ -0,0 +1,275 @@
+use log::warn;
+use task_hookrs::{import::import, task::Task};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::{
+  event::{Event, KeyCode},
+  overrides,
+};
+
+const PREFIX: &str = "uda.taskwarrior-tui.rule.";
+
+/// What a rule does to the tasks matching its filter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleAction {
+  /// Delete the tasks and remove them from the data files
+  Purge,
+  Delete,
+  Done,
+  /// Modify the tasks with these arguments, e.g. `wait:now+30d`
+  Modify(Vec<String>),
+}
+
+impl RuleAction {
+  pub fn parse(s: &str) -> Option<Self> {
+    match s.trim() {
+      "" => None,
+      "purge" => Some(RuleAction::Purge),
+      "delete" => Some(RuleAction::Delete),
+      "done" => Some(RuleAction::Done),
+      args => shlex::split(args).filter(|a| !a.is_empty()).map(RuleAction::Modify),
+    }
+  }
+
+  pub fn label(&self) -> String {
+    match self {
+      RuleAction::Purge => "purge".to_string(),
+      RuleAction::Delete => "delete".to_string(),
+      RuleAction::Done => "done".to_string(),
+      RuleAction::Modify(args) => format!("modify {}", args.join(" ")),
+    }
+  }
+
+  /// Arguments of the taskwarrior commands that apply the action to `uuids`, run in order.
+  pub fn commands(&self, uuids: &[Uuid]) -> Vec<Vec<String>> {
+    let with = |args: &[&str]| {
+      let mut command: Vec<String> = uuids.iter().map(ToString::to_string).collect();
+      command.extend(args.iter().map(ToString::to_string));
+      command
+    };
+    match self {
+      // only deleted tasks can be purged
+      RuleAction::Purge => vec![with(&["delete"]), with(&["purge"])],
+      RuleAction::Delete => vec![with(&["delete"])],
+      RuleAction::Done => vec![with(&["done"])],
+      RuleAction::Modify(args) => {
+        let mut command = with(&["modify"]);
+        command.extend(args.iter().cloned());
+        vec![command]
+      }
+    }
+  }
+}
+
+/// A rule that keeps the task database tidy, reviewed and applied from the rules screen.
+///
+/// ```plaintext
+/// uda.taskwarrior-tui.rule.old-inbox.filter=status:completed end.before:now-90d project:Inbox
+/// uda.taskwarrior-tui.rule.old-inbox.action=purge
+/// uda.taskwarrior-tui.rule.someday.filter=status:pending +someday modified.before:now-180d
+/// uda.taskwarrior-tui.rule.someday.action=wait:now+30d
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+  pub name: String,
+  pub filter: String,
+  pub action: RuleAction,
+}
+
+impl Rule {
+  /// Filter of the tasks among `uuids` that still match the rule, e.g.
+  /// `( status:completed project:Inbox ) <uuid>`.
+  pub fn recheck_filter(&self, uuids: &[Uuid]) -> Vec<String> {
+    let mut filter = vec!["(".to_string()];
+    filter.extend(shlex::split(&self.filter).unwrap_or_default());
+    filter.push(")".to_string());
+    filter.extend(uuids.iter().map(ToString::to_string));
+    filter
+  }
+}
+
+/// Rules defined in the output of `task show`, in the order they first appear. Rules without a
+/// filter or an action are left out, so that a typo cannot apply an action to every task.
+pub fn parse(data: &str) -> Vec<Rule> {
+  let mut settings: Vec<(String, Option<String>, Option<String>)> = vec![];
+  for line in data.lines() {
+    let rest = match line.strip_prefix(PREFIX) {
+      Some(rest) => rest,
+      None => continue,
+    };
+    let (key, value) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let (name, setting) = match key.rsplit_once('.') {
+      Some(split) => split,
+      None => continue,
+    };
+    let index = match settings.iter().position(|(n, _, _)| n == name) {
+      Some(i) => i,
+      None => {
+        settings.push((name.to_string(), None, None));
+        settings.len() - 1
+      }
+    };
+    let value = Some(value.trim().to_string()).filter(|v| !v.is_empty());
+    match setting {
+      "filter" => settings[index].1 = value,
+      "action" => settings[index].2 = value,
+      _ => warn!("Unknown setting {} of rule {}", setting, name),
+    }
+  }
+  settings
+    .into_iter()
+    .filter_map(|(name, filter, action)| match (filter, action.as_deref().and_then(RuleAction::parse)) {
+      (Some(filter), Some(action)) => Some(Rule { name, filter, action }),
+      _ => {
+        warn!("Rule {} needs both a filter and an action", name);
+        None
+      }
+    })
+    .collect()
+}
+
+/// The tasks a rule applies to at the last refresh, with their descriptions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleMatches {
+  pub rule: Rule,
+  pub tasks: Vec<(Uuid, String)>,
+}
+
+impl RuleMatches {
+  pub fn uuids(&self) -> Vec<Uuid> {
+    self.tasks.iter().map(|(uuid, _)| *uuid).collect()
+  }
+
+  /// Tasks shown in the preview that `current`, the tasks matching the filter of the rule now, still
+  /// has. Tasks changed since the preview so that the rule no longer applies to them are left alone.
+  pub fn still_matching(&self, current: &[Task]) -> Vec<Uuid> {
+    self
+      .tasks
+      .iter()
+      .map(|(uuid, _)| *uuid)
+      .filter(|uuid| current.iter().any(|t| t.uuid() == uuid))
+      .collect()
+  }
+}
+
+/// The tasks each rule applies to, found in the background so that a refresh never waits
+/// for one `task export` per rule.
+pub struct Evaluations {
+  /// Bumped on each refresh, the matches of an earlier one are then dropped
+  generation: u64,
+  tx: mpsc::UnboundedSender<(u64, Vec<RuleMatches>)>,
+  rx: mpsc::UnboundedReceiver<(u64, Vec<RuleMatches>)>,
+}
+
+impl Evaluations {
+  pub fn new() -> Self {
+    let (tx, rx) = mpsc::unbounded_channel();
+    Self { generation: 0, tx, rx }
+  }
+
+  /// Export the tasks of the rules one after the other, sending `Event::Tick` on `wake` once done.
+  pub fn refresh(&mut self, rules: &[Rule], wake: &mpsc::UnboundedSender<Event<KeyCode>>) {
+    if rules.is_empty() {
+      return;
+    }
+    self.generation += 1;
+    let generation = self.generation;
+    let rules = rules.to_vec();
+    let tx = self.tx.clone();
+    let wake = wake.clone();
+    tokio::spawn(async move {
+      let mut matches = vec![];
+      for rule in rules {
+        let output = overrides::task_async()
+          .arg("rc.json.array=on")
+          .arg("rc.color=off")
+          .arg("rc.verbose=nothing")
+          .args(shlex::split(&rule.filter).unwrap_or_default())
+          .arg("export")
+          .output()
+          .await;
+        let tasks = match output {
+          Ok(o) if o.status.success() => import(o.stdout.as_slice()).unwrap_or_default(),
+          _ => {
+            warn!("Unable to find the tasks of rule {}", rule.name);
+            continue;
+          }
+        };
+        let tasks: Vec<(Uuid, String)> = tasks.iter().map(|t: &Task| (*t.uuid(), t.description().clone())).collect();
+        if !tasks.is_empty() {
+          matches.push(RuleMatches { rule, tasks });
+        }
+      }
+      tx.send((generation, matches)).ok();
+      wake.send(Event::Tick).ok();
+    });
+  }
+
+  /// The matches of the last refresh if they arrived since the last call.
+  pub fn poll(&mut self) -> Option<Vec<RuleMatches>> {
+    let mut arrived = None;
+    while let Ok((generation, matches)) = self.rx.try_recv() {
+      if generation == self.generation {
+        arrived = Some(matches);
+      }
+    }
+    arrived
+  }
+}
+
+impl Default for Evaluations {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    let data = "uda.taskwarrior-tui.rule.old-inbox.filter  status:completed project:Inbox\nuda.taskwarrior-tui.rule.old-inbox.action  purge\nuda.taskwarrior-tui.rule.someday.filter  +someday\nuda.taskwarrior-tui.rule.someday.action  wait:now+30d +review\nuda.taskwarrior-tui.rule.broken.action  delete\n";
+    let rules = parse(data);
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules[0].action, RuleAction::Purge);
+    assert_eq!(rules[1].action.label(), "modify wait:now+30d +review");
+
+    let uuid = Uuid::nil();
+    let commands = rules[0].action.commands(&[uuid]);
+    assert_eq!(commands.len(), 2);
+    assert_eq!(commands[1], vec![uuid.to_string(), "purge".to_string()]);
+    assert_eq!(rules[1].action.commands(&[uuid])[0][1..], ["modify", "wait:now+30d", "+review"]);
+    assert_eq!(
+      rules[0].recheck_filter(&[uuid])[..5],
+      ["(", "status:completed", "project:Inbox", ")", &uuid.to_string()]
+    );
+  }
+
+  #[test]
+  fn test_apply_after_change() {
+    let tasks = import(
+      r#"[
+        {"uuid":"00000000-0000-0000-0000-000000000001","description":"old","status":"completed","entry":"20260101T090000Z"},
+        {"uuid":"00000000-0000-0000-0000-000000000002","description":"reopened","status":"completed","entry":"20260101T090000Z"}
+      ]"#
+        .as_bytes(),
+    )
+    .unwrap();
+    let matches = RuleMatches {
+      rule: parse("uda.taskwarrior-tui.rule.old.filter  status:completed\nuda.taskwarrior-tui.rule.old.action  purge\n").remove(0),
+      tasks: tasks.iter().map(|t| (*t.uuid(), t.description().clone())).collect(),
+    };
+    // the second task was reopened after the preview, the filter run again no longer finds it
+    let current = &tasks[..1];
+    let uuids = matches.still_matching(current);
+    assert_eq!(uuids, [Uuid::from_u128(1)]);
+    assert_eq!(
+      matches.rule.action.commands(&uuids)[0],
+      [Uuid::from_u128(1).to_string(), "delete".to_string()]
+    );
+    // tasks that match the filter now but were not previewed are not touched either
+    assert!(RuleMatches { tasks: vec![], ..matches }.still_matching(current).is_empty());
+  }
+}
@@ -0,0 +1,131 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Result;
+use chrono::Local;
+use ratatui::{
+  buffer::{Buffer, Cell},
+  style::{Color, Modifier},
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Cells of each row of `buffer`, leaving out the cells covered by the wide character before them.
+fn rows(buffer: &Buffer) -> Vec<Vec<&Cell>> {
+  let mut rows = vec![];
+  for row in buffer.content.chunks(buffer.area.width as usize) {
+    let mut cells = vec![];
+    let mut skip = 0;
+    for cell in row {
+      if skip > 0 {
+        skip -= 1;
+        continue;
+      }
+      skip = cell.symbol.width().saturating_sub(1);
+      cells.push(cell);
+    }
+    rows.push(cells);
+  }
+  rows
+}
+
+/// Text of a frame, one line per row.
+pub fn plain(buffer: &Buffer) -> String {
+  let lines: Vec<String> = rows(buffer)
+    .into_iter()
+    .map(|row| row.iter().map(|c| c.symbol.as_str()).collect::<String>().trim_end().to_string())
+    .collect();
+  lines.join("\n") + "\n"
+}
+
+fn color_code(color: Color, background: bool) -> String {
+  let base = if background { 40 } else { 30 };
+  match color {
+    Color::Reset => (base + 9).to_string(),
+    Color::Black => base.to_string(),
+    Color::Red => (base + 1).to_string(),
+    Color::Green => (base + 2).to_string(),
+    Color::Yellow => (base + 3).to_string(),
+    Color::Blue => (base + 4).to_string(),
+    Color::Magenta => (base + 5).to_string(),
+    Color::Cyan => (base + 6).to_string(),
+    Color::Gray => (base + 7).to_string(),
+    Color::DarkGray => (base + 60).to_string(),
+    Color::LightRed => (base + 61).to_string(),
+    Color::LightGreen => (base + 62).to_string(),
+    Color::LightYellow => (base + 63).to_string(),
+    Color::LightBlue => (base + 64).to_string(),
+    Color::LightMagenta => (base + 65).to_string(),
+    Color::LightCyan => (base + 66).to_string(),
+    Color::White => (base + 67).to_string(),
+    Color::Indexed(i) => format!("{};5;{}", base + 8, i),
+    Color::Rgb(r, g, b) => format!("{};2;{};{};{}", base + 8, r, g, b),
+  }
+}
+
+fn sgr(cell: &Cell) -> String {
+  let mut codes = vec!["0".to_string()];
+  for (modifier, code) in [
+    (Modifier::BOLD, "1"),
+    (Modifier::DIM, "2"),
+    (Modifier::ITALIC, "3"),
+    (Modifier::UNDERLINED, "4"),
+    (Modifier::SLOW_BLINK, "5"),
+    (Modifier::RAPID_BLINK, "6"),
+    (Modifier::REVERSED, "7"),
+    (Modifier::HIDDEN, "8"),
+    (Modifier::CROSSED_OUT, "9"),
+  ] {
+    if cell.modifier.contains(modifier) {
+      codes.push(code.to_string());
+    }
+  }
+  codes.push(color_code(cell.fg, false));
+  codes.push(color_code(cell.bg, true));
+  format!("\x1b[{}m", codes.join(";"))
+}
+
+/// Text of a frame with the escape sequences of its colors and styles, e.g. for `cat` or `less -R`.
+pub fn ansi(buffer: &Buffer) -> String {
+  let mut out = String::new();
+  for row in rows(buffer) {
+    let mut current = None;
+    for cell in row {
+      let style = sgr(cell);
+      if current.as_ref() != Some(&style) {
+        out.push_str(&style);
+        current = Some(style);
+      }
+      out.push_str(&cell.symbol);
+    }
+    out.push_str("\x1b[0m\n");
+  }
+  out
+}
+
+/// Write `buffer` to `screenshot-<time>.txt` and `screenshot-<time>.ansi` in `dir`, returning the
+/// path of the plain text file.
+pub fn save(buffer: &Buffer, dir: PathBuf) -> Result<PathBuf> {
+  fs::create_dir_all(&dir)?;
+  let name = format!("screenshot-{}", Local::now().format("%Y%m%d-%H%M%S"));
+  let path = dir.join(format!("{}.txt", name));
+  fs::write(&path, plain(buffer))?;
+  fs::write(dir.join(format!("{}.ansi", name)), ansi(buffer))?;
+  Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+  use ratatui::{layout::Rect, style::Style};
+
+  use super::*;
+
+  #[test]
+  fn test_screenshot() {
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 2));
+    buffer.set_string(0, 0, "ab", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+    buffer.set_string(0, 1, "日x", Style::default().bg(Color::Indexed(4)));
+    assert_eq!(plain(&buffer), "ab\n日x\n");
+    let ansi = ansi(&buffer);
+    assert!(ansi.starts_with("\x1b[0;1;31;49mab\x1b[0;39;49m    \x1b[0m\n"));
+    assert!(ansi.contains("\x1b[0;39;48;5;4m日x"));
+  }
+}
@@ -0,0 +1,111 @@
+use std::{
+  io::Write,
+  process::{Command, Stdio},
+  thread,
+};
+
+use anyhow::{anyhow, Result};
+use task_hookrs::{task::Task, uda::UDAValue};
+
+use crate::utils;
+
+/// Run `command` with `input` on its standard input, returning its standard output.
+fn run(command: &[String], input: &[u8]) -> Result<Vec<u8>> {
+  let line = command.join(" ");
+  let mut child = Command::new(&command[0])
+    .args(&command[1..])
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .map_err(|e| anyhow!("Unable to run `{}`: {}", line, e))?;
+  // written from another thread while the output is read, so that a command writing as it reads
+  // never waits for its output to be read while its input waits to be written
+  let mut stdin = child.stdin.take().expect("stdin is piped");
+  let input = input.to_vec();
+  let writer = thread::spawn(move || stdin.write_all(&input));
+  let output = child.wait_with_output()?;
+  let written = writer.join();
+  // a command failing before reading all of its input breaks the pipe, its error says why
+  if !output.status.success() {
+    return Err(anyhow!("`{}` failed. {}", line, String::from_utf8_lossy(&output.stderr).trim()));
+  }
+  written
+    .map_err(|_| anyhow!("Unable to write to `{}`", line))?
+    .map_err(|e| anyhow!("Unable to write to `{}`: {}", line, e))?;
+  Ok(output.stdout)
+}
+
+/// A UDA holding encrypted text, e.g. sensitive details of a task, with the external commands
+/// encrypting it and decrypting it. Both read their input on the standard input and write the
+/// result to the standard output, and the ciphertext is stored in base64.
+///
+/// ```plaintext
+/// uda.taskwarrior-tui.secret.uda=secret
+/// uda.taskwarrior-tui.secret.encrypt=age -r age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8p
+/// uda.taskwarrior-tui.secret.decrypt=age -d -i ~/.config/age/key.txt
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Secret {
+  pub uda: String,
+  encrypt: Vec<String>,
+  decrypt: Vec<String>,
+}
+
+impl Secret {
+  /// `None` unless the UDA and both commands are set.
+  pub fn parse(uda: &str, encrypt: &str, decrypt: &str) -> Option<Self> {
+    let words = |command: &str| -> Option<Vec<String>> {
+      let words: Vec<String> = shlex::split(command)?.iter().map(|w| shellexpand::tilde(w).into_owned()).collect();
+      (!words.is_empty()).then_some(words)
+    };
+    if uda.trim().is_empty() {
+      return None;
+    }
+    Some(Self {
+      uda: uda.trim().to_string(),
+      encrypt: words(encrypt)?,
+      decrypt: words(decrypt)?,
+    })
+  }
+
+  /// Encrypted value of `task`, if it has one.
+  pub fn value<'a>(&self, task: &'a Task) -> Option<&'a str> {
+    match task.uda().get(&self.uda) {
+      Some(UDAValue::Str(value)) if !value.is_empty() => Some(value),
+      _ => None,
+    }
+  }
+
+  pub fn encrypt(&self, text: &str) -> Result<String> {
+    Ok(utils::base64_encode(&run(&self.encrypt, text.as_bytes())?))
+  }
+
+  pub fn decrypt(&self, value: &str) -> Result<String> {
+    let ciphertext = utils::base64_decode(value).ok_or_else(|| anyhow!("The value of `{}` is not base64", self.uda))?;
+    Ok(String::from_utf8_lossy(&run(&self.decrypt, &ciphertext)?).into_owned())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_secret() {
+    assert_eq!(Secret::parse("", "cat", "cat"), None);
+    assert_eq!(Secret::parse("secret", "", "cat"), None);
+    // `cat` makes a toy cipher, and an input larger than a pipe holds needs it to be read while it
+    // is written
+    let secret = Secret::parse("secret", "cat", "cat").unwrap();
+    let text = "pin 1234\n".repeat(20_000);
+    let value = secret.encrypt(&text).unwrap();
+    assert_eq!(utils::base64_decode(&value).unwrap(), text.as_bytes());
+    assert_eq!(secret.decrypt(&value).unwrap(), text);
+
+    // the error of a command quitting without reading its input, rather than the broken pipe
+    let command = ["sh", "-c", "echo denied >&2; exit 1"].map(ToString::to_string);
+    let error = run(&command, text.as_bytes()).unwrap_err().to_string();
+    assert!(error.ends_with("failed. denied"), "{}", error);
+  }
+}
@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use log::{debug, info, warn};
+use task_hookrs::{import::import, status::TaskStatus, task::Task};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::{
+  diff::{self, AttributeDiff},
+  event::{Event, KeyCode},
+  overrides,
+};
+
+/// Tasks modified since a time, exported in the background, and whether to show them once found.
+type Export = (DateTime<Utc>, bool, Result<Vec<Task>, String>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+  Added,
+  Completed,
+  Deleted,
+  Modified,
+}
+
+impl ChangeKind {
+  pub fn symbol(&self) -> &'static str {
+    match self {
+      ChangeKind::Added => "+",
+      ChangeKind::Completed => "\u{2714}",
+      ChangeKind::Deleted => "\u{2718}",
+      ChangeKind::Modified => "~",
+    }
+  }
+
+  pub fn label(&self) -> &'static str {
+    match self {
+      ChangeKind::Added => "added",
+      ChangeKind::Completed => "completed",
+      ChangeKind::Deleted => "deleted",
+      ChangeKind::Modified => "modified",
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct TaskChange {
+  pub kind: ChangeKind,
+  pub before: Option<Task>,
+  pub after: Task,
+}
+
+impl TaskChange {
+  /// Attributes that differ between the previous and the current version of the task.
+  /// Returns an empty list when the previous version was never seen by this session.
+  pub fn diff(&self) -> Vec<AttributeDiff> {
+    match &self.before {
+      Some(before) => diff::task_diff(before, &self.after),
+      None => vec![],
+    }
+  }
+
+  /// Names of the attributes that differ between the previous and the current version of the task.
+  pub fn changed_attributes(&self) -> Vec<String> {
+    self.diff().into_iter().map(|d| d.name).collect()
+  }
+}
+
+pub struct Snapshot {
+  pub taken_at: Option<DateTime<Utc>>,
+  pub known: HashMap<Uuid, Task>,
+  pub changes: Vec<TaskChange>,
+  pub changes_since: Option<DateTime<Utc>>,
+  pub scroll: u16,
+  tx: mpsc::UnboundedSender<Export>,
+  rx: mpsc::UnboundedReceiver<Export>,
+}
+
+impl Snapshot {
+  pub fn new() -> Self {
+    let (tx, rx) = mpsc::unbounded_channel();
+    Self {
+      taken_at: None,
+      known: HashMap::new(),
+      changes: vec![],
+      changes_since: None,
+      scroll: 0,
+      tx,
+      rx,
+    }
+  }
+
+  /// Remember the current version of the tasks so that later changes can be compared against them.
+  pub fn remember(&mut self, tasks: &[Task]) {
+    for task in tasks {
+      self.known.insert(*task.uuid(), task.clone());
+    }
+  }
+
+  /// Export every task modified since the last snapshot in the background, sending `Event::Tick` on
+  /// `wake` once done. `show` is handed back by `poll` when changes are found.
+  pub fn refresh(&mut self, show: bool, wake: &mpsc::UnboundedSender<Event<KeyCode>>) {
+    let now = Utc::now();
+    let since = match self.taken_at.replace(now) {
+      Some(since) => since,
+      None => return,
+    };
+
+    // taskwarrior stores timestamps with a one second resolution
+    let after = since - Duration::seconds(1);
+
+    let mut task = overrides::task_async();
+    task
+      .arg("rc.json.array=on")
+      .arg("rc.confirmation=off")
+      .arg("rc.json.depends.array=on")
+      .arg("rc.color=off")
+      .arg("rc._forcecolor=off")
+      .arg(format!("modified.after:{}", after.format("%Y-%m-%dT%H:%M:%SZ")))
+      .arg("export");
+
+    info!("Running `{:?}` in the background", task);
+    let tx = self.tx.clone();
+    let wake = wake.clone();
+    tokio::spawn(async move {
+      let modified = match task.output().await {
+        Ok(output) if output.status.success() => {
+          import(output.stdout.as_slice()).map_err(|e| format!("Unable to parse the tasks modified since {}: {}", since, e))
+        }
+        Ok(output) => Err(format!(
+          "Unable to export the tasks modified since {}: {}",
+          since,
+          String::from_utf8_lossy(&output.stderr).trim()
+        )),
+        Err(e) => Err(format!("Unable to export the tasks modified since {}: {}", since, e)),
+      };
+      tx.send((since, show, modified)).ok();
+      wake.send(Event::Tick).ok();
+    });
+  }
+
+  /// Classify the changes exported since the last call. Returns `Some(show)` when any were found,
+  /// with `show` as passed to `refresh`.
+  pub fn poll(&mut self) -> Option<bool> {
+    let mut found = None;
+    while let Ok((since, show, modified)) = self.rx.try_recv() {
+      let modified = match modified {
+        Ok(modified) => modified,
+        Err(e) => {
+          warn!("Unable to compute changes since last snapshot: {}", e);
+          continue;
+        }
+      };
+      let changes = self.classify(since, modified);
+      debug!("Found {} changes since {}", changes.len(), since);
+      if changes.is_empty() {
+        continue;
+      }
+      self.changes = changes;
+      self.changes_since = Some(since);
+      self.scroll = 0;
+      found = Some(found.unwrap_or(false) || show);
+    }
+    found
+  }
+
+  fn classify(&mut self, since: DateTime<Utc>, modified: Vec<Task>) -> Vec<TaskChange> {
+    let mut changes = vec![];
+    for task in modified {
+      let before = self.known.insert(*task.uuid(), task.clone());
+      let kind = if Utc.from_utc_datetime(task.entry()) > since {
+        ChangeKind::Added
+      } else if task.status() == &TaskStatus::Completed && before.as_ref().map(|t| t.status()) != Some(&TaskStatus::Completed) {
+        ChangeKind::Completed
+      } else if task.status() == &TaskStatus::Deleted && before.as_ref().map(|t| t.status()) != Some(&TaskStatus::Deleted) {
+        ChangeKind::Deleted
+      } else {
+        ChangeKind::Modified
+      };
+      let change = TaskChange { kind, before, after: task };
+      // skip tasks caught only because of the one second safety margin
+      if change.kind == ChangeKind::Modified && change.before.is_some() && change.changed_attributes().is_empty() {
+        continue;
+      }
+      changes.push(change);
+    }
+    changes.sort_by_key(|c| match c.kind {
+      ChangeKind::Added => 0,
+      ChangeKind::Completed => 1,
+      ChangeKind::Deleted => 2,
+      ChangeKind::Modified => 3,
+    });
+    changes
+  }
+}
+
+impl Default for Snapshot {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_classify() {
+    let before = import(
+      r#"[
+        {"uuid":"00000000-0000-0000-0000-000000000001","description":"Pay rent","status":"pending","entry":"20261001T090000Z","project":"home"},
+        {"uuid":"00000000-0000-0000-0000-000000000002","description":"Call bank","status":"pending","entry":"20261001T090000Z"},
+        {"uuid":"00000000-0000-0000-0000-000000000003","description":"Old idea","status":"pending","entry":"20261001T090000Z"},
+        {"uuid":"00000000-0000-0000-0000-000000000004","description":"Unchanged","status":"pending","entry":"20261001T090000Z"}
+      ]"#
+        .as_bytes(),
+    )
+    .unwrap();
+    let modified = import(
+      r#"[
+        {"uuid":"00000000-0000-0000-0000-000000000001","description":"Pay the rent","status":"pending","entry":"20261001T090000Z","project":"flat"},
+        {"uuid":"00000000-0000-0000-0000-000000000002","description":"Call bank","status":"completed","entry":"20261001T090000Z","end":"20261010T090000Z"},
+        {"uuid":"00000000-0000-0000-0000-000000000003","description":"Old idea","status":"deleted","entry":"20261001T090000Z","end":"20261010T090000Z"},
+        {"uuid":"00000000-0000-0000-0000-000000000004","description":"Unchanged","status":"pending","entry":"20261001T090000Z"},
+        {"uuid":"00000000-0000-0000-0000-000000000005","description":"New","status":"pending","entry":"20261010T100000Z"}
+      ]"#
+      .as_bytes(),
+    )
+    .unwrap();
+    let mut snapshot = Snapshot::new();
+    snapshot.remember(&before);
+    let since = Utc.with_ymd_and_hms(2026, 10, 5, 0, 0, 0).unwrap();
+    let changes = snapshot.classify(since, modified);
+    let kinds: Vec<ChangeKind> = changes.iter().map(|c| c.kind).collect();
+    assert_eq!(
+      kinds,
+      [ChangeKind::Added, ChangeKind::Completed, ChangeKind::Deleted, ChangeKind::Modified]
+    );
+    assert_eq!(changes[3].changed_attributes(), ["description", "project"]);
+    assert!(changes[0].changed_attributes().is_empty());
+    assert_eq!(snapshot.known.len(), 5);
+  }
+}
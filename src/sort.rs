@@ -0,0 +1,280 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use chrono::NaiveDateTime;
+use log::warn;
+use task_hookrs::{task::Task, uda::UDAValue};
+
+const PREFIX: &str = "uda.taskwarrior-tui.sort.";
+
+/// Letters with accents, and the letters they are sorted as when collating. This is an
+/// approximation of collation, not the rules of any language: Swedish sorts `å`, `ä` and `ö` after
+/// `z` for instance, where they are sorted here as `a` and `o`.
+const ACCENTED: &str = "ÀÁÂÃÄÅàáâãäåÇçÈÉÊËèéêëÌÍÎÏìíîïÑñÒÓÔÕÖØòóôõöøÙÚÛÜùúûüÝýÿŠšŽžČčĆćĐđŁłŃńŚśŹźŻżĄąĘęŘřŤťŮůĚěŇňĎďĽľĹĺŔŕŐőŰűĞğŞşİı";
+const UNACCENTED: &str = "AAAAAAaaaaaaCcEEEEeeeeIIIIiiiiNnOOOOOOooooooUUUUuuuuYyySsZzCcCcDdLlNnSsZzZzAaEeRrTtUuEeNnDdLlLlRrOoUuGgSsIi";
+
+/// How two texts are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparator {
+  /// Character by character, as taskwarrior does
+  Default,
+  /// Numbers in the text by their value, so that `file2` comes before `file10`
+  Natural,
+  /// Ignoring case and accents, so that `éclair` comes between `Eclair` and `fig`, see [`ACCENTED`]
+  Collate,
+}
+
+impl Comparator {
+  fn parse(s: &str) -> Option<Self> {
+    match s {
+      "default" => Some(Self::Default),
+      "natural" => Some(Self::Natural),
+      "collate" => Some(Self::Collate),
+      _ => None,
+    }
+  }
+
+  pub fn compare(&self, a: &str, b: &str) -> Ordering {
+    match self {
+      Self::Default => a.cmp(b),
+      Self::Natural => natural(a, b).then_with(|| a.cmp(b)),
+      Self::Collate => fold(a).cmp(&fold(b)).then_with(|| a.cmp(b)),
+    }
+  }
+}
+
+/// `s` in lower case without the accents of [`ACCENTED`].
+fn fold(s: &str) -> String {
+  s.chars()
+    .map(|c| match ACCENTED.chars().position(|a| a == c) {
+      Some(i) => UNACCENTED.chars().nth(i).unwrap_or(c),
+      None => c,
+    })
+    .flat_map(char::to_lowercase)
+    .collect()
+}
+
+/// Compare runs of digits by their value and the other characters one by one.
+fn natural(a: &str, b: &str) -> Ordering {
+  let (mut a, mut b) = (a.chars().peekable(), b.chars().peekable());
+  loop {
+    match (a.peek().copied(), b.peek().copied()) {
+      (None, None) => return Ordering::Equal,
+      (None, Some(_)) => return Ordering::Less,
+      (Some(_), None) => return Ordering::Greater,
+      (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+        let number = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+          let mut digits = String::new();
+          while let Some(d) = chars.next_if(char::is_ascii_digit) {
+            digits.push(d);
+          }
+          digits.trim_start_matches('0').to_string()
+        };
+        let (x, y) = (number(&mut a), number(&mut b));
+        // without leading zeros, a longer number is a larger one
+        let ordering = x.len().cmp(&y.len()).then_with(|| x.cmp(&y));
+        if ordering != Ordering::Equal {
+          return ordering;
+        }
+      }
+      (Some(x), Some(y)) => {
+        if x != y {
+          return x.cmp(&y);
+        }
+        a.next();
+        b.next();
+      }
+    }
+  }
+}
+
+/// One key of a sort, e.g. `description:natural+`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKey {
+  pub attribute: String,
+  pub comparator: Comparator,
+  pub ascending: bool,
+}
+
+impl SortKey {
+  pub fn parse(s: &str) -> Option<Self> {
+    let s = s.trim();
+    let (s, ascending) = if let Some(s) = s.strip_suffix('-') {
+      (s, false)
+    } else {
+      (s.strip_suffix('+').unwrap_or(s), true)
+    };
+    let (attribute, comparator) = match s.split_once(':') {
+      Some((attribute, comparator)) => (attribute, Comparator::parse(comparator)?),
+      None => (s, Comparator::Default),
+    };
+    (!attribute.is_empty()).then(|| Self {
+      attribute: attribute.to_string(),
+      comparator,
+      ascending,
+    })
+  }
+}
+
+/// Sorts of reports defined in the output of `task show`, by report.
+///
+/// ```plaintext
+/// uda.taskwarrior-tui.sort.next=project:collate+,description:natural+,due+
+/// ```
+pub fn parse(data: &str) -> HashMap<String, Vec<SortKey>> {
+  let mut sorts = HashMap::new();
+  for line in data.lines() {
+    let rest = match line.strip_prefix(PREFIX) {
+      Some(rest) => rest,
+      None => continue,
+    };
+    let (report, value) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let keys: Option<Vec<SortKey>> = value.split(',').filter(|k| !k.trim().is_empty()).map(SortKey::parse).collect();
+    match keys {
+      Some(keys) if !keys.is_empty() => {
+        sorts.insert(report.to_string(), keys);
+      }
+      _ => warn!("Invalid sort `{}` of report {}", value.trim(), report),
+    }
+  }
+  sorts
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+enum Value {
+  Number(f64),
+  Date(NaiveDateTime),
+  Text(String),
+  /// Folded text, then the text itself
+  Collated(String, String),
+}
+
+fn value(task: &Task, attribute: &str) -> Option<Value> {
+  let date = |d: Option<&task_hookrs::date::Date>| d.map(|d| Value::Date(**d));
+  match attribute {
+    "id" => task.id().map(|id| Value::Number(id as f64)),
+    "urgency" => task.urgency().map(|u| Value::Number(*u)),
+    "description" => Some(Value::Text(task.description().clone())),
+    "project" => task.project().map(|p| Value::Text(p.clone())),
+    "tags" => task.tags().filter(|t| !t.is_empty()).map(|t| Value::Text(t.join(" "))),
+    "status" => Some(Value::Text(format!("{:?}", task.status()).to_lowercase())),
+    "entry" => Some(Value::Date(**task.entry())),
+    "modified" => date(task.modified()),
+    "due" => date(task.due()),
+    "scheduled" => date(task.scheduled()),
+    "wait" => date(task.wait()),
+    "until" => date(task.until()),
+    "start" => date(task.start()),
+    "end" => date(task.end()),
+    uda => match task.uda().get(uda)? {
+      UDAValue::Str(s) => Some(Value::Text(s.clone())),
+      UDAValue::F64(f) => Some(Value::Number(*f)),
+      UDAValue::U64(u) => Some(Value::Number(*u as f64)),
+    },
+  }
+}
+
+/// Value of `task` for `key` as it is compared, text being folded once here when collating.
+fn prepare(task: &Task, key: &SortKey) -> Option<Value> {
+  match value(task, &key.attribute)? {
+    Value::Text(text) if key.comparator == Comparator::Collate => Some(Value::Collated(fold(&text), text)),
+    value => Some(value),
+  }
+}
+
+fn compare(a: &Option<Value>, b: &Option<Value>, key: &SortKey) -> Ordering {
+  let ordering = match (a, b) {
+    (Some(Value::Text(x)), Some(Value::Text(y))) => key.comparator.compare(x, y),
+    (Some(x), Some(y)) => x.partial_cmp(y).unwrap_or(Ordering::Equal),
+    // tasks without a value come last whichever the direction
+    (Some(_), None) => return Ordering::Less,
+    (None, Some(_)) => return Ordering::Greater,
+    (None, None) => Ordering::Equal,
+  };
+  if key.ascending {
+    ordering
+  } else {
+    ordering.reverse()
+  }
+}
+
+/// Values of a task for each key of a sort, taken once per task rather than at each comparison.
+struct Prepared<'a> {
+  values: Vec<Option<Value>>,
+  keys: &'a [SortKey],
+}
+
+impl Ord for Prepared<'_> {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self
+      .keys
+      .iter()
+      .zip(self.values.iter().zip(&other.values))
+      .map(|(k, (a, b))| compare(a, b, k))
+      .find(|o| *o != Ordering::Equal)
+      .unwrap_or(Ordering::Equal)
+  }
+}
+
+impl PartialOrd for Prepared<'_> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl PartialEq for Prepared<'_> {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
+
+impl Eq for Prepared<'_> {}
+
+/// Sort `tasks` by each key in turn, keeping the order of tasks equal for all of them.
+pub fn sort(tasks: &mut [Task], keys: &[SortKey]) {
+  tasks.sort_by_cached_key(|task| Prepared {
+    values: keys.iter().map(|k| prepare(task, k)).collect(),
+    keys,
+  });
+}
+
+#[cfg(test)]
+mod tests {
+  use task_hookrs::import::import;
+
+  use super::*;
+
+  #[test]
+  fn test_sort() {
+    assert_eq!(natural("file2", "file10"), Ordering::Less);
+    assert_eq!(natural("file010", "file10"), Ordering::Equal);
+    assert_eq!(natural("v1.10", "v1.9"), Ordering::Greater);
+    assert_eq!(Comparator::Collate.compare("éclair", "Eclair"), Ordering::Greater);
+    assert_eq!(Comparator::Collate.compare("éclair", "fig"), Ordering::Less);
+    assert_eq!(Comparator::Default.compare("éclair", "fig"), Ordering::Greater);
+
+    let sorts = parse("uda.taskwarrior-tui.sort.next project:collate+,description:natural+,due-\nuda.taskwarrior-tui.sort.bad x:fancy+");
+    assert_eq!(sorts.len(), 1);
+    assert_eq!(
+      sorts["next"][2],
+      SortKey {
+        attribute: "due".to_string(),
+        comparator: Comparator::Default,
+        ascending: false,
+      }
+    );
+
+    let mut tasks = import(
+      r#"[
+        {"uuid":"00000000-0000-0000-0000-000000000001","description":"file10","status":"pending","entry":"20261001T090000Z","project":"Écoles"},
+        {"uuid":"00000000-0000-0000-0000-000000000002","description":"file2","status":"pending","entry":"20261001T090000Z","project":"ecoles"},
+        {"uuid":"00000000-0000-0000-0000-000000000003","description":"file1","status":"pending","entry":"20261001T090000Z"},
+        {"uuid":"00000000-0000-0000-0000-000000000004","description":"file3","status":"pending","entry":"20261001T090000Z","project":"Zoo"}
+      ]"#
+        .as_bytes(),
+    )
+    .unwrap();
+    sort(&mut tasks, &sorts["next"][..2]);
+    let descriptions: Vec<&str> = tasks.iter().map(|t| t.description().as_str()).collect();
+    assert_eq!(descriptions, ["file2", "file10", "file3", "file1"]);
+  }
+}
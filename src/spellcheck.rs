@@ -0,0 +1,148 @@
+use std::{collections::HashSet, fs, ops::Range};
+
+use anyhow::Result;
+use ratatui::widgets::ListState;
+
+const MAX_SUGGESTIONS: usize = 10;
+
+// hunspell affix rules are not interpreted, strip the most common english suffixes instead
+const SUFFIXES: [&str; 6] = ["'s", "s", "es", "ed", "ing", "ly"];
+
+/// Word list read from a hunspell `.dic` file.
+pub struct SpellChecker {
+  words: HashSet<String>,
+}
+
+impl SpellChecker {
+  pub fn from_dic(path: &str) -> Result<Self> {
+    let path = shellexpand::tilde(path).into_owned();
+    let data = fs::read_to_string(path)?;
+    Ok(Self::from_words(data.lines().skip(1)))
+  }
+
+  pub fn from_words<'a>(lines: impl Iterator<Item = &'a str>) -> Self {
+    let words = lines
+      .filter_map(|line| line.split(|c: char| c == '/' || c.is_whitespace()).next())
+      .filter(|w| !w.is_empty())
+      .map(str::to_lowercase)
+      .collect();
+    Self { words }
+  }
+
+  pub fn is_correct(&self, word: &str) -> bool {
+    let word = word.to_lowercase();
+    if self.words.contains(&word) {
+      return true;
+    }
+    SUFFIXES
+      .iter()
+      .filter_map(|suffix| word.strip_suffix(suffix))
+      .any(|stem| self.words.contains(stem) || self.words.contains(&format!("{}e", stem)))
+  }
+
+  /// Byte ranges of the misspelled words of a prompt.
+  /// Attributes (`due:...`), tags (`+tag`) and words containing digits are left alone.
+  pub fn misspelled(&self, text: &str) -> Vec<Range<usize>> {
+    words(text).into_iter().filter(|r| !self.is_correct(&text[r.clone()])).collect()
+  }
+
+  pub fn suggestions(&self, word: &str) -> Vec<String> {
+    let word = word.to_lowercase();
+    let mut candidates: Vec<(usize, &String)> = self
+      .words
+      .iter()
+      .filter(|w| w.len().abs_diff(word.len()) <= 2)
+      .map(|w| (strsim::damerau_levenshtein(&word, w), w))
+      .filter(|(distance, _)| *distance <= 2)
+      .collect();
+    candidates.sort();
+    candidates.into_iter().take(MAX_SUGGESTIONS).map(|(_, w)| w.clone()).collect()
+  }
+}
+
+/// Byte ranges of the words of a prompt that should be spellchecked.
+pub fn words(text: &str) -> Vec<Range<usize>> {
+  let mut ranges = vec![];
+  let mut offset = 0;
+  for token in text.split_inclusive(char::is_whitespace) {
+    let start = offset;
+    offset += token.len();
+    let token = token.trim_end();
+    if token.contains(':') || token.starts_with('+') || token.starts_with('-') || token.chars().any(|c| c.is_ascii_digit()) {
+      continue;
+    }
+    let mut word_start = None;
+    for (i, c) in token.char_indices().chain(std::iter::once((token.len(), ' '))) {
+      let is_word = c.is_alphabetic() || (c == '\'' && word_start.is_some());
+      match (word_start, is_word) {
+        (None, true) => word_start = Some(i),
+        (Some(s), false) => {
+          let word = token[s..i].trim_end_matches('\'');
+          if word.chars().count() > 1 {
+            ranges.push(start + s..start + s + word.len());
+          }
+          word_start = None;
+        }
+        _ => {}
+      }
+    }
+  }
+  ranges
+}
+
+/// Suggestions for the word at the cursor of a prompt.
+pub struct SpellSuggestions {
+  pub word: Range<usize>,
+  pub items: Vec<String>,
+  pub state: ListState,
+}
+
+impl SpellSuggestions {
+  pub fn new(word: Range<usize>, items: Vec<String>) -> Self {
+    let mut state = ListState::default();
+    if !items.is_empty() {
+      state.select(Some(0));
+    }
+    Self { word, items, state }
+  }
+
+  pub fn selected(&self) -> Option<&String> {
+    self.state.selected().and_then(|i| self.items.get(i))
+  }
+
+  pub fn next(&mut self) {
+    if self.items.is_empty() {
+      return;
+    }
+    let i = match self.state.selected() {
+      Some(i) if i + 1 < self.items.len() => i + 1,
+      _ => 0,
+    };
+    self.state.select(Some(i));
+  }
+
+  pub fn previous(&mut self) {
+    if self.items.is_empty() {
+      return;
+    }
+    let i = match self.state.selected() {
+      Some(0) | None => self.items.len() - 1,
+      Some(i) => i - 1,
+    };
+    self.state.select(Some(i));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_misspelled() {
+    let checker = SpellChecker::from_words(["meeting/S", "with", "team", "the", "call/SG"].iter().copied());
+    let text = r#""meting with the team calls" project:work +urgent due:2d"#;
+    let misspelled: Vec<&str> = checker.misspelled(text).into_iter().map(|r| &text[r]).collect();
+    assert_eq!(misspelled, vec!["meting"]);
+    assert_eq!(checker.suggestions("meting").first().map(String::as_str), Some("meeting"));
+  }
+}
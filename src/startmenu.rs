@@ -0,0 +1,136 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Result;
+use ratatui::widgets::ListState;
+
+use crate::utils;
+
+/// Workspaces remembered as recently used.
+const RECENT_WORKSPACES: usize = 5;
+
+/// Something to start with, picked from the start menu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StartItem {
+  Report(String),
+  Workspace(String),
+  AddTask,
+  CommandPalette,
+  Calendar,
+  Help,
+}
+
+/// Names of the reports defined in the output of `task show report`, which the task report is able
+/// to show as they have columns.
+pub fn list_reports(data: &str) -> Vec<String> {
+  let mut reports: Vec<String> = data
+    .lines()
+    .filter_map(|l| l.strip_prefix("report."))
+    .filter_map(|l| l.split_whitespace().next())
+    .filter_map(|key| key.strip_suffix(".columns"))
+    .map(ToString::to_string)
+    .collect();
+  reports.sort();
+  reports.dedup();
+  reports
+}
+
+/// Names of the workspaces switched to lately, most recent first, kept in the data directory.
+pub struct RecentWorkspaces {
+  pub names: Vec<String>,
+  path: PathBuf,
+}
+
+impl RecentWorkspaces {
+  pub fn new(filename: &str) -> Self {
+    Self {
+      names: vec![],
+      path: utils::get_data_dir().join(filename),
+    }
+  }
+
+  pub fn load(&mut self) -> Result<()> {
+    if self.path.exists() {
+      self.names = serde_json::from_str(&fs::read_to_string(&self.path)?)?;
+    }
+    Ok(())
+  }
+
+  pub fn push(&mut self, name: &str) -> Result<()> {
+    self.names.retain(|n| n != name);
+    self.names.insert(0, name.to_string());
+    self.names.truncate(RECENT_WORKSPACES);
+    fs::write(&self.path, serde_json::to_string(&self.names)?)?;
+    Ok(())
+  }
+}
+
+/// The screen shown when `taskwarrior-tui` starts without a report.
+#[derive(Default)]
+pub struct StartMenu {
+  pub items: Vec<(String, StartItem)>,
+  pub state: ListState,
+}
+
+impl StartMenu {
+  /// Items of the menu: the `default` report first, the other reports, the workspaces with the
+  /// recently used ones first, and the quick actions.
+  pub fn new(default: &str, reports: &[String], workspaces: &[String], recent: &[String]) -> Self {
+    let mut items = vec![(format!("Report: {} (default)", default), StartItem::Report(default.to_string()))];
+    for report in reports.iter().filter(|r| *r != default) {
+      items.push((format!("Report: {}", report), StartItem::Report(report.clone())));
+    }
+    let mut ordered: Vec<&String> = recent.iter().filter(|r| workspaces.contains(r)).collect();
+    ordered.extend(workspaces.iter().filter(|w| !recent.contains(w)));
+    for workspace in ordered {
+      let label = if recent.contains(workspace) {
+        format!("Workspace: {} (recent)", workspace)
+      } else {
+        format!("Workspace: {}", workspace)
+      };
+      items.push((label, StartItem::Workspace(workspace.clone())));
+    }
+    items.push(("Add a task".to_string(), StartItem::AddTask));
+    items.push(("Command palette".to_string(), StartItem::CommandPalette));
+    items.push(("Calendar".to_string(), StartItem::Calendar));
+    items.push(("Help".to_string(), StartItem::Help));
+    let mut state = ListState::default();
+    state.select(Some(0));
+    Self { items, state }
+  }
+
+  pub fn next(&mut self) {
+    let i = self.state.selected().map_or(0, |i| (i + 1) % self.items.len().max(1));
+    self.state.select(Some(i));
+  }
+
+  pub fn previous(&mut self) {
+    let len = self.items.len().max(1);
+    let i = self.state.selected().map_or(0, |i| (i + len - 1) % len);
+    self.state.select(Some(i));
+  }
+
+  pub fn selected(&self) -> Option<StartItem> {
+    self.state.selected().and_then(|i| self.items.get(i)).map(|(_, item)| item.clone())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_start_menu() {
+    let data = "report.next.columns       id,description\nreport.next.filter        status:pending\nreport.list.columns       id,description\nreport.list.labels        ID,Description\n";
+    let reports = list_reports(data);
+    assert_eq!(reports, ["list", "next"]);
+
+    let workspaces = vec!["home".to_string(), "work".to_string()];
+    let mut menu = StartMenu::new("next", &reports, &workspaces, &["work".to_string(), "gone".to_string()]);
+    assert_eq!(menu.items[0].1, StartItem::Report("next".to_string()));
+    assert_eq!(menu.items[1].1, StartItem::Report("list".to_string()));
+    assert_eq!(menu.items[2].0, "Workspace: work (recent)");
+    assert_eq!(menu.items[3].1, StartItem::Workspace("home".to_string()));
+    menu.previous();
+    assert_eq!(menu.selected(), Some(StartItem::Help));
+  }
+}
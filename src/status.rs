@@ -0,0 +1,48 @@
+use chrono::{Duration, NaiveDateTime};
+use task_hookrs::{status::TaskStatus, task::Task};
+
+/// Due date of the pending task that is due next after `now`.
+pub fn next_due(tasks: &[Task], now: NaiveDateTime) -> Option<NaiveDateTime> {
+  tasks
+    .iter()
+    .filter(|t| *t.status() == TaskStatus::Pending)
+    .filter_map(|t| t.due())
+    .map(|due| NaiveDateTime::new(due.date(), due.time()))
+    .filter(|due| *due > now)
+    .min()
+}
+
+/// Time left as `hh:mm:ss`, with the number of days in front when there is more than a day left.
+pub fn countdown(left: Duration) -> String {
+  let seconds = left.num_seconds().max(0);
+  let (days, seconds) = (seconds / 86400, seconds % 86400);
+  let time = format!("{:02}:{:02}:{:02}", seconds / 3600, seconds % 3600 / 60, seconds % 60);
+  if days > 0 {
+    format!("{}d {}", days, time)
+  } else {
+    time
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::NaiveDate;
+
+  use super::*;
+
+  #[test]
+  fn test_next_due() {
+    let data = r#"[
+      {"uuid":"6ef1e5b0-a0c0-4f4c-9c4e-3c1a4c2b1f10","description":"past","status":"pending","entry":"20240101T000000Z","due":"20240101T080000Z"},
+      {"uuid":"6ef1e5b0-a0c0-4f4c-9c4e-3c1a4c2b1f11","description":"later","status":"pending","entry":"20240101T000000Z","due":"20240103T080000Z"},
+      {"uuid":"6ef1e5b0-a0c0-4f4c-9c4e-3c1a4c2b1f12","description":"next","status":"pending","entry":"20240101T000000Z","due":"20240102T080000Z"},
+      {"uuid":"6ef1e5b0-a0c0-4f4c-9c4e-3c1a4c2b1f13","description":"done","status":"completed","entry":"20240101T000000Z","end":"20240101T000000Z","due":"20240101T130000Z"}
+    ]"#;
+    let tasks: Vec<Task> = task_hookrs::import::import(data.as_bytes()).unwrap();
+    let now = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(12, 0, 0).unwrap();
+    let next = next_due(&tasks, now).unwrap();
+    assert_eq!(next, NaiveDate::from_ymd_opt(2024, 1, 2).unwrap().and_hms_opt(8, 0, 0).unwrap());
+    assert_eq!(countdown(next - now), "20:00:00");
+    assert_eq!(countdown(Duration::seconds(90061)), "1d 01:01:01");
+  }
+}
@@ -0,0 +1,227 @@
+use std::{
+  process::Stdio,
+  time::{Duration, Instant},
+};
+
+use tokio::{
+  io::{AsyncBufReadExt, BufReader},
+  process::Command,
+  sync::{mpsc, oneshot},
+};
+
+use crate::event::{Event, KeyCode};
+
+/// Lines kept of the output of a command, the oldest are dropped first.
+const MAX_LINES: usize = 5000;
+
+/// Time a cancelled command has to exit after SIGTERM before it is killed.
+const GRACE: Duration = Duration::from_secs(3);
+
+#[derive(Debug)]
+enum Message {
+  /// A line of the output, `true` when it was printed to stderr
+  Line(bool, String),
+  /// The command exited with this code, `None` when it was killed or did not start
+  Exit(Option<i32>),
+}
+
+/// Output of a slow command, e.g. `task sync`, read while it runs so that the interface stays
+/// responsive and the command can be cancelled.
+pub struct OutputStream {
+  pub title: String,
+  /// Lines printed so far, `true` for those printed to stderr
+  pub lines: Vec<(bool, String)>,
+  pub started: Instant,
+  /// Time the command took and its exit code, once it exited
+  pub finished: Option<(Duration, Option<i32>)>,
+  pub cancelled: bool,
+  rx: mpsc::UnboundedReceiver<Message>,
+  kill: Option<oneshot::Sender<()>>,
+}
+
+impl OutputStream {
+  /// Run `program` with `args`, sending `Event::Tick` on `wake` whenever there is new output.
+  pub fn spawn(title: &str, program: &str, args: &[String], wake: mpsc::UnboundedSender<Event<KeyCode>>) -> Self {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let (kill, killed) = oneshot::channel();
+    let mut command = Command::new(program);
+    command
+      .args(args)
+      .stdin(Stdio::null())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped())
+      .kill_on_drop(true);
+    match command.spawn() {
+      Ok(child) => {
+        tokio::spawn(run(child, tx, killed, wake));
+      }
+      Err(e) => {
+        tx.send(Message::Line(true, format!("Unable to run `{}`: {}", program, e))).ok();
+        tx.send(Message::Exit(None)).ok();
+        wake.send(Event::Tick).ok();
+      }
+    }
+    Self {
+      title: title.to_string(),
+      lines: vec![],
+      started: Instant::now(),
+      finished: None,
+      cancelled: false,
+      rx,
+      kill: Some(kill),
+    }
+  }
+
+  /// Take the output that arrived since the last call. Returns whether there was any.
+  pub fn poll(&mut self) -> bool {
+    let mut changed = false;
+    while let Ok(message) = self.rx.try_recv() {
+      changed = true;
+      match message {
+        Message::Line(stderr, line) => {
+          self.lines.push((stderr, line));
+          if self.lines.len() > MAX_LINES {
+            self.lines.remove(0);
+          }
+        }
+        Message::Exit(code) => self.finished = Some((self.started.elapsed(), code)),
+      }
+    }
+    changed
+  }
+
+  pub fn running(&self) -> bool {
+    self.finished.is_none()
+  }
+
+  pub fn success(&self) -> bool {
+    matches!(self.finished, Some((_, Some(0)))) && !self.cancelled
+  }
+
+  pub fn elapsed(&self) -> Duration {
+    self.finished.map_or_else(|| self.started.elapsed(), |(elapsed, _)| elapsed)
+  }
+
+  /// Ask the command to stop, killing it if it is still running after `GRACE`. The output printed
+  /// so far is kept.
+  pub fn cancel(&mut self) {
+    if let Some(kill) = self.kill.take() {
+      self.cancelled = self.running();
+      kill.send(()).ok();
+    }
+  }
+
+  /// Status line, e.g. `running for 12s` or `failed with code 1 after 3s`.
+  pub fn status(&self) -> String {
+    let seconds = self.elapsed().as_secs();
+    match self.finished {
+      None => format!("running for {}s", seconds),
+      Some(_) if self.cancelled => format!("cancelled after {}s", seconds),
+      Some((_, Some(0))) => format!("finished in {}s", seconds),
+      Some((_, Some(code))) => format!("failed with code {} after {}s", code, seconds),
+      Some((_, None)) => format!("stopped after {}s", seconds),
+    }
+  }
+}
+
+/// Send SIGTERM to `child`, so that it can clean up before exiting, e.g. release the lock of the
+/// task database. Without a `kill` to send it, the child is killed right away.
+async fn terminate(child: &mut tokio::process::Child) {
+  let pid = match child.id() {
+    Some(pid) => pid,
+    None => return,
+  };
+  let sent = Command::new("kill")
+    .args(["-TERM", &pid.to_string()])
+    .stdout(Stdio::null())
+    .stderr(Stdio::null())
+    .status()
+    .await;
+  if !sent.is_ok_and(|s| s.success()) {
+    child.start_kill().ok();
+  }
+}
+
+async fn run(
+  mut child: tokio::process::Child,
+  tx: mpsc::UnboundedSender<Message>,
+  mut killed: oneshot::Receiver<()>,
+  wake: mpsc::UnboundedSender<Event<KeyCode>>,
+) {
+  let mut stdout = child.stdout.take().map(|s| BufReader::new(s).lines());
+  let mut stderr = child.stderr.take().map(|s| BufReader::new(s).lines());
+  // when the command is killed if it did not exit by then, once it was cancelled
+  let mut deadline: Option<tokio::time::Instant> = None;
+  while stdout.is_some() || stderr.is_some() {
+    let (is_stderr, line) = tokio::select! {
+      line = async { stdout.as_mut().unwrap().next_line().await }, if stdout.is_some() => (false, line),
+      line = async { stderr.as_mut().unwrap().next_line().await }, if stderr.is_some() => (true, line),
+      _ = &mut killed, if deadline.is_none() => {
+        terminate(&mut child).await;
+        deadline = Some(tokio::time::Instant::now() + GRACE);
+        continue;
+      }
+      _ = tokio::time::sleep_until(deadline.unwrap_or_else(tokio::time::Instant::now)), if deadline.is_some() => {
+        child.kill().await.ok();
+        break;
+      }
+    };
+    match line {
+      Ok(Some(line)) => {
+        tx.send(Message::Line(is_stderr, line)).ok();
+        wake.send(Event::Tick).ok();
+      }
+      _ if is_stderr => stderr = None,
+      _ => stdout = None,
+    }
+  }
+  if let Some(deadline) = deadline {
+    if tokio::time::timeout_at(deadline, child.wait()).await.is_err() {
+      child.kill().await.ok();
+    }
+  }
+  let code = child.wait().await.ok().and_then(|status| status.code());
+  tx.send(Message::Exit(code)).ok();
+  wake.send(Event::Tick).ok();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn test_stream() {
+    let (wake, mut woken) = mpsc::unbounded_channel();
+    let args = vec!["-c".to_string(), "echo out; echo err >&2; exit 3".to_string()];
+    let mut stream = OutputStream::spawn("test", "sh", &args, wake);
+    while stream.running() {
+      woken.recv().await;
+      stream.poll();
+    }
+    assert!(stream.lines.contains(&(false, "out".to_string())));
+    assert!(stream.lines.contains(&(true, "err".to_string())));
+    assert!(!stream.success());
+    assert!(stream.status().starts_with("failed with code 3"));
+  }
+
+  #[tokio::test]
+  async fn test_cancel() {
+    let (wake, mut woken) = mpsc::unbounded_channel();
+    let script = "trap 'echo cleaning up; exit 4' TERM; echo started; while true; do sleep 0.1; done";
+    let mut stream = OutputStream::spawn("test", "sh", &["-c".to_string(), script.to_string()], wake);
+    while stream.lines.is_empty() {
+      woken.recv().await;
+      stream.poll();
+    }
+    stream.cancel();
+    while stream.running() {
+      woken.recv().await;
+      stream.poll();
+    }
+    // the command exited by itself on SIGTERM, printing what it did meanwhile
+    assert!(stream.lines.contains(&(false, "cleaning up".to_string())));
+    assert_eq!(stream.finished.map(|(_, code)| code), Some(Some(4)));
+    assert!(stream.elapsed() < GRACE);
+    assert!(stream.status().starts_with("cancelled after"));
+  }
+}
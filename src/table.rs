@@ -12,7 +12,7 @@ use cassowary::{
 use ratatui::{
   buffer::Buffer,
   layout::{Constraint, Rect},
-  style::Style,
+  style::{Modifier, Style},
   widgets::{Block, StatefulWidget, Widget},
 };
 use unicode_segmentation::{Graphemes, UnicodeSegmentation};
@@ -56,6 +56,11 @@ impl TableState {
     self.mode = TableMode::SingleSelection;
   }
 
+  /// Index of the first visible row
+  pub fn offset(&self) -> usize {
+    self.offset
+  }
+
   pub fn current_selection(&self) -> Option<usize> {
     self.current_selection
   }
@@ -87,7 +92,7 @@ impl TableState {
     }
   }
 
-  pub fn marked(&self) -> std::collections::hash_set::Iter<usize> {
+  pub fn marked(&self) -> std::collections::hash_set::Iter<'_, usize> {
     self.marked.iter()
   }
 
@@ -141,12 +146,20 @@ pub struct Table<'a, H, R> {
   header: H,
   /// Style for the header
   header_style: Style,
+  /// Index of the header that has focus, if any
+  focused_header: Option<usize>,
+  /// Style used to render the focused header
+  focused_header_style: Style,
   /// Width constraints for each column
   widths: &'a [Constraint],
   /// Space between each column
   column_spacing: u16,
   /// Space between the header and the rows
   header_gap: u16,
+  /// Lines taken by each row
+  row_height: u16,
+  /// Text on the second line of each row, starting at the given column
+  details: Option<(usize, &'a [String])>,
   /// Style used to render the selected row
   highlight_style: Style,
   /// Symbol in front of the selected row
@@ -159,6 +172,10 @@ pub struct Table<'a, H, R> {
   mark_highlight_symbol: Option<&'a str>,
   /// Symbol in front of the unmarked and selected row
   unmark_highlight_symbol: Option<&'a str>,
+  /// Column whose comma or space separated words are styled, with the style of each word
+  word_styles: Option<(usize, &'a HashMap<String, Style>)>,
+  /// Columns whose cells are styled, with the style of the cell of each row
+  cell_styles: Vec<(usize, &'a [Style])>,
   /// Data to display in each row
   rows: R,
 }
@@ -174,15 +191,21 @@ where
       style: Style::default(),
       header: H::default(),
       header_style: Style::default(),
+      focused_header: None,
+      focused_header_style: Style::default(),
       widths: &[],
       column_spacing: 1,
       header_gap: 1,
+      row_height: 1,
+      details: None,
       highlight_style: Style::default(),
       highlight_symbol: None,
       mark_symbol: None,
       unmark_symbol: None,
       mark_highlight_symbol: None,
       unmark_highlight_symbol: None,
+      word_styles: None,
+      cell_styles: vec![],
       rows: R::default(),
     }
   }
@@ -200,15 +223,21 @@ where
       style: Style::default(),
       header,
       header_style: Style::default(),
+      focused_header: None,
+      focused_header_style: Style::default(),
       widths: &[],
       column_spacing: 1,
       header_gap: 1,
+      row_height: 1,
+      details: None,
       highlight_style: Style::default(),
       highlight_symbol: None,
       mark_symbol: None,
       unmark_symbol: None,
       mark_highlight_symbol: None,
       unmark_highlight_symbol: None,
+      word_styles: None,
+      cell_styles: vec![],
       rows,
     }
   }
@@ -230,6 +259,16 @@ where
     self
   }
 
+  pub fn focused_header(mut self, index: Option<usize>) -> Table<'a, H, R> {
+    self.focused_header = index;
+    self
+  }
+
+  pub fn focused_header_style(mut self, style: Style) -> Table<'a, H, R> {
+    self.focused_header_style = style;
+    self
+  }
+
   pub fn widths(mut self, widths: &'a [Constraint]) -> Table<'a, H, R> {
     let between_0_and_100 = |&w| match w {
       Constraint::Percentage(p) => p <= 100,
@@ -286,6 +325,17 @@ where
     self
   }
 
+  pub fn word_styles(mut self, column: usize, styles: &'a HashMap<String, Style>) -> Table<'a, H, R> {
+    self.word_styles = Some((column, styles));
+    self
+  }
+
+  /// Patch the text of the cell of row `i` in `column` with `styles[i]`.
+  pub fn cell_styles(mut self, column: usize, styles: &'a [Style]) -> Table<'a, H, R> {
+    self.cell_styles.push((column, styles));
+    self
+  }
+
   pub fn column_spacing(mut self, spacing: u16) -> Table<'a, H, R> {
     self.column_spacing = spacing;
     self
@@ -295,6 +345,17 @@ where
     self.header_gap = gap;
     self
   }
+
+  pub fn row_height(mut self, height: u16) -> Table<'a, H, R> {
+    self.row_height = height.max(1);
+    self
+  }
+
+  /// Show `details[i]` on the second line of row `i` from `column` on, when rows are higher than one line.
+  pub fn details(mut self, column: usize, details: &'a [String]) -> Table<'a, H, R> {
+    self.details = Some((column, details));
+    self
+  }
 }
 
 impl<'a, H, D, R> StatefulWidget for Table<'a, H, R>
@@ -362,24 +423,30 @@ where
     let mut index = 0;
     if y < table_area.bottom() {
       for (w, t) in solved_widths.iter().zip(self.header.by_ref()) {
+        let header_style = if self.focused_header == Some(index) {
+          self.header_style.patch(self.focused_header_style)
+        } else {
+          self.header_style
+        };
         buf.set_stringn(
           x,
           y,
           format!("{symbol:>width$}", symbol = " ", width = *w as usize),
           *w as usize,
-          self.header_style,
+          header_style,
         );
-        if t.to_string() == "ID" {
+        let t = t.to_string();
+        if t.split(' ').next() == Some("ID") {
           buf.set_stringn(
             x,
             y,
             format!("{symbol:>width$}", symbol = t, width = *w as usize),
             *w as usize,
-            self.header_style,
+            header_style,
           );
           header_index = index;
         } else {
-          buf.set_stringn(x, y, format!("{}", t), *w as usize, self.header_style);
+          buf.set_stringn(x, y, &t, *w as usize, header_style);
         }
         x += *w + self.column_spacing;
         index += 1;
@@ -431,7 +498,8 @@ where
     // Draw rows
     let default_style = Style::default();
     if y < table_area.bottom() {
-      let remaining = (table_area.bottom() - y) as usize;
+      let (row_height, details, spacing) = (self.row_height, self.details, self.column_spacing);
+      let remaining = ((table_area.bottom() - y) / row_height).max(1) as usize;
 
       // Make sure the table shows the selected item
       state.offset = selected.map_or(0, |s| {
@@ -470,12 +538,23 @@ where
             }
           }
         };
+        let y = y + i as u16 * row_height;
         x = table_area.left();
+        for line in 1..row_height.min(table_area.bottom() - y) {
+          buf.set_stringn(x, y + line, " ".repeat(table_area.width as usize), table_area.width as usize, style);
+        }
+        if let Some((column, details)) = details.filter(|_| row_height > 1 && y + 1 < table_area.bottom()) {
+          let start: u16 = solved_widths.iter().take(column).map(|w| w + spacing).sum();
+          if let Some(text) = details.get(i + state.offset).filter(|_| start < table_area.width) {
+            let width = (table_area.width - start) as usize;
+            buf.set_stringn(x + start, y + 1, text, width, style.add_modifier(Modifier::DIM));
+          }
+        }
         for (c, (w, elt)) in solved_widths.iter().zip(data).enumerate() {
           let s = if c == 0 {
             buf.set_stringn(
               x,
-              y + i as u16,
+              y,
               format!("{symbol:^width$}", symbol = "", width = area.width as usize),
               *w as usize,
               style,
@@ -500,10 +579,10 @@ where
             }
           } else {
             buf.set_stringn(
-              x - 1,
-              y + i as u16,
+              x - spacing,
+              y,
               format!("{symbol:^width$}", symbol = "", width = area.width as usize),
-              *w as usize + 1,
+              (*w + spacing) as usize,
               style,
             );
             if c == header_index {
@@ -512,7 +591,27 @@ where
               format!("{elt:<width$}", elt = elt, width = *w as usize)
             }
           };
-          buf.set_stringn(x, y + i as u16, s, *w as usize, style);
+          buf.set_stringn(x, y, &s, *w as usize, style);
+          if let Some((_, styles)) = self.word_styles.filter(|(column, _)| *column == c) {
+            let mut offset = 0;
+            for word in s.split_inclusive([',', ' ']) {
+              let width = word.width() as u16;
+              let name = word.trim_end_matches([',', ' ']);
+              if let Some(word_style) = styles.get(name) {
+                let visible = (name.width() as u16).min(w.saturating_sub(offset));
+                buf.set_style(Rect::new(x + offset, y, visible, 1), *word_style);
+              }
+              offset += width;
+              if offset >= *w {
+                break;
+              }
+            }
+          }
+          for (_, styles) in self.cell_styles.iter().filter(|(column, _)| *column == c) {
+            if let Some(cell_style) = styles.get(i + state.offset) {
+              buf.set_style(Rect::new(x, y, (s.trim_end().width() as u16).min(*w), 1), *cell_style);
+            }
+          }
           x += *w + self.column_spacing;
         }
       }
@@ -1,14 +1,17 @@
-use std::{error::Error, process::Command};
+use std::{collections::HashMap, error::Error, process::Command};
 
 use anyhow::Result;
-use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, TimeZone};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike};
 use itertools::join;
 use task_hookrs::{task::Task, uda::UDAValue};
 use unicode_truncate::UnicodeTruncateStr;
 use unicode_width::UnicodeWidthStr;
+use uuid::Uuid;
+
+use crate::{details, focus::format_tracked, overrides, team::Team};
 
 pub fn format_date_time(dt: NaiveDateTime) -> String {
-  let dt = Local.from_local_datetime(&dt).unwrap();
+  let dt = Local.from_utc_datetime(&dt);
   dt.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
@@ -18,9 +21,19 @@ pub fn format_date(dt: NaiveDateTime) -> String {
   dt.format("%Y-%m-%d").to_string()
 }
 
+/// Date, and the time of day when it is not midnight, e.g. for a task due at the end of a meeting.
+pub fn format_date_and_time(dt: NaiveDateTime) -> String {
+  let dt = Local.from_utc_datetime(&dt);
+  if dt.num_seconds_from_midnight() == 0 {
+    dt.format("%Y-%m-%d").to_string()
+  } else {
+    dt.format("%Y-%m-%d %H:%M").to_string()
+  }
+}
+
 pub fn vague_format_date_time(from_dt: NaiveDateTime, to_dt: NaiveDateTime, with_remainder: bool) -> String {
-  let to_dt = Local.from_local_datetime(&to_dt).unwrap();
-  let from_dt = Local.from_local_datetime(&from_dt).unwrap();
+  let to_dt = Local.from_utc_datetime(&to_dt);
+  let from_dt = Local.from_utc_datetime(&from_dt);
   let mut seconds = (to_dt - from_dt).num_seconds();
   let minus = if seconds < 0 {
     seconds *= -1;
@@ -76,13 +89,107 @@ pub fn vague_format_date_time(from_dt: NaiveDateTime, to_dt: NaiveDateTime, with
   format!("{}{}s", minus, seconds)
 }
 
+/// One key of a report's `sort` setting, e.g. `project+/` or `urgency-`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortColumn {
+  pub attribute: String,
+  pub ascending: bool,
+  /// Trailing `/`, which makes taskwarrior insert a break between groups.
+  pub separator: bool,
+}
+
+impl SortColumn {
+  pub fn parse(s: &str) -> Option<Self> {
+    let s = s.trim();
+    let (s, separator) = match s.strip_suffix('/') {
+      Some(s) => (s, true),
+      None => (s, false),
+    };
+    let (attribute, ascending) = if let Some(a) = s.strip_suffix('+') {
+      (a, true)
+    } else if let Some(a) = s.strip_suffix('-') {
+      (a, false)
+    } else {
+      (s, true)
+    };
+    if attribute.is_empty() {
+      return None;
+    }
+    Some(Self {
+      attribute: attribute.to_string(),
+      ascending,
+      separator,
+    })
+  }
+}
+
+impl std::fmt::Display for SortColumn {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "{}{}{}",
+      self.attribute,
+      if self.ascending { "+" } else { "-" },
+      if self.separator { "/" } else { "" }
+    )
+  }
+}
+
+/// Which columns are hidden when they are empty for every task in the report.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum AutoColumns {
+  /// Every empty column is hidden
+  #[default]
+  All,
+  /// All columns of the report are shown
+  Off,
+  /// Only these columns are hidden when empty, e.g. `recur,until`
+  Only(Vec<String>),
+}
+
+impl AutoColumns {
+  /// Parse the `uda.taskwarrior-tui.task-report.auto-columns` setting.
+  pub fn parse(s: &str) -> Self {
+    match s.trim() {
+      "" | "on" | "yes" | "true" | "1" => AutoColumns::All,
+      "off" | "no" | "false" | "0" => AutoColumns::Off,
+      s => AutoColumns::Only(s.split(',').map(|c| c.trim().to_string()).filter(|c| !c.is_empty()).collect()),
+    }
+  }
+
+  /// Whether `column` (e.g. `due.relative`) may be hidden, a listed column covers all of its formats.
+  pub fn hides(&self, column: &str) -> bool {
+    match self {
+      AutoColumns::All => true,
+      AutoColumns::Off => false,
+      AutoColumns::Only(columns) => {
+        let name = column.split('.').next().unwrap_or(column);
+        columns.iter().any(|c| c == column || c == name)
+      }
+    }
+  }
+}
+
 pub struct TaskReportTable {
   pub labels: Vec<String>,
   pub columns: Vec<String>,
+  /// Columns left after `simplify_table` dropped the empty ones.
+  pub visible_columns: Vec<String>,
+  pub auto_columns: AutoColumns,
+  pub sort: Vec<SortColumn>,
+  /// Set once the sort order was changed from the TUI, so that it is no longer read from the report.
+  pub sort_overridden: bool,
   pub tasks: Vec<Vec<String>>,
   pub virtual_tags: Vec<String>,
   pub description_width: usize,
   pub date_time_vague_precise: bool,
+  /// Add a `tracked` column with the time spent on each task
+  pub show_tracked: bool,
+  pub tracked: HashMap<Uuid, Duration>,
+  /// Add an `aging` column with the age of each task, colored by the report
+  pub show_aging: bool,
+  /// Show the column of the assignee UDA as initials, adding it when the report does not have it
+  pub team: Option<Team>,
 }
 
 impl TaskReportTable {
@@ -126,10 +233,18 @@ impl TaskReportTable {
     let mut task_report_table = Self {
       labels: vec![],
       columns: vec![],
+      visible_columns: vec![],
+      auto_columns: AutoColumns::default(),
+      sort: vec![],
+      sort_overridden: false,
       tasks: vec![vec![]],
       virtual_tags: virtual_tags.iter().map(ToString::to_string).collect::<Vec<_>>(),
       description_width: 100,
       date_time_vague_precise: false,
+      show_tracked: false,
+      tracked: HashMap::new(),
+      show_aging: false,
+      team: None,
     };
     task_report_table.export_headers(Some(data), report)?;
     Ok(task_report_table)
@@ -139,40 +254,35 @@ impl TaskReportTable {
     self.columns = vec![];
     self.labels = vec![];
 
-    let data = if let Some(s) = data {
-      s.to_string()
-    } else {
-      let output = Command::new("task")
-        .arg("show")
-        .arg("rc.defaultwidth=0")
-        .arg(format!("report.{}.columns", report))
-        .output()?;
-      String::from_utf8_lossy(&output.stdout).into_owned()
+    // the columns, labels and sort order of the report, read at once
+    let data = match data {
+      Some(s) => s.to_string(),
+      None => {
+        let output = overrides::task()
+          .arg("show")
+          .arg("rc.defaultwidth=0")
+          .arg(format!("report.{}.", report))
+          .output()?;
+        String::from_utf8_lossy(&output.stdout).into_owned()
+      }
     };
 
-    for line in data.split('\n') {
-      if line.starts_with(format!("report.{}.columns", report).as_str()) {
-        let column_names = line.split_once(' ').unwrap().1;
-        for column in column_names.split(',') {
-          self.columns.push(column.to_string());
-        }
-      }
+    let setting = |name: &str| {
+      let key = format!("report.{}.{}", report, name);
+      data
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v.trim().to_string())
+    };
+    if let Some(columns) = setting("columns") {
+      self.columns = columns.split(',').map(ToString::to_string).collect();
     }
-
-    let output = Command::new("task")
-      .arg("show")
-      .arg("rc.defaultwidth=0")
-      .arg(format!("report.{}.labels", report))
-      .output()?;
-    let data = String::from_utf8_lossy(&output.stdout);
-
-    for line in data.split('\n') {
-      if line.starts_with(format!("report.{}.labels", report).as_str()) {
-        let label_names = line.split_once(' ').unwrap().1;
-        for label in label_names.split(',') {
-          self.labels.push(label.to_string());
-        }
-      }
+    if let Some(labels) = setting("labels") {
+      self.labels = labels.split(',').map(ToString::to_string).collect();
+    }
+    if !self.sort_overridden {
+      self.sort = Self::parse_sort(&data, report);
     }
 
     if self.labels.is_empty() {
@@ -193,9 +303,68 @@ impl TaskReportTable {
     let num_columns = self.columns.len();
     assert!(num_labels == num_columns, "Must have the same number of labels (currently {}) and columns (currently {}). Compare their values as shown by \"task show report.{}.\" and fix your taskwarrior config.", num_labels, num_columns, report);
 
+    if self.show_tracked {
+      self.columns.push("tracked".to_string());
+      self.labels.push("Tracked".to_string());
+    }
+    if self.show_aging {
+      self.columns.push("aging".to_string());
+      self.labels.push("Age".to_string());
+    }
+    if let Some(team) = &self.team {
+      if !self.columns.contains(&team.uda) {
+        self.columns.push(team.uda.clone());
+        self.labels.push("Who".to_string());
+      }
+    }
+
     Ok(())
   }
 
+  fn parse_sort(data: &str, report: &str) -> Vec<SortColumn> {
+    let key = format!("report.{}.sort", report);
+    for line in data.split('\n') {
+      if let Some((k, v)) = line.split_once(' ') {
+        if k == key {
+          return v.trim().split(',').filter_map(SortColumn::parse).collect();
+        }
+      }
+    }
+    vec![]
+  }
+
+  /// Value for `rc.report.<report>.sort` matching the current sort order.
+  pub fn sort_string(&self) -> String {
+    self.sort.iter().map(ToString::to_string).collect::<Vec<_>>().join(",")
+  }
+
+  /// Sort direction of a column, `Some(true)` when ascending.
+  pub fn sort_direction(&self, column: &str) -> Option<bool> {
+    let attribute = column.split('.').next().unwrap_or_default();
+    self.sort.iter().find(|s| s.attribute == attribute).map(|s| s.ascending)
+  }
+
+  /// Cycle the sort of a column: unsorted -> descending -> ascending -> unsorted.
+  /// A column that was not sorted on becomes the primary sort key.
+  pub fn cycle_sort(&mut self, column: &str) {
+    let attribute = column.split('.').next().unwrap_or_default().to_string();
+    match self.sort.iter().position(|s| s.attribute == attribute) {
+      Some(i) if !self.sort[i].ascending => self.sort[i].ascending = true,
+      Some(i) => {
+        self.sort.remove(i);
+      }
+      None => self.sort.insert(
+        0,
+        SortColumn {
+          attribute,
+          ascending: false,
+          separator: false,
+        },
+      ),
+    }
+    self.sort_overridden = true;
+  }
+
   pub fn generate_table(&mut self, tasks: &[Task]) {
     self.tasks = vec![];
 
@@ -206,8 +375,8 @@ impl TaskReportTable {
       }
       let mut item = vec![];
       for name in &self.columns {
-        let s = self.get_string_attribute(name, task, tasks);
-        item.push(s);
+        // a description of a megabyte would be measured on every frame otherwise
+        item.push(details::truncate_cell(self.get_string_attribute(name, task, tasks)));
       }
       self.tasks.push(item);
     }
@@ -227,6 +396,13 @@ impl TaskReportTable {
       }
     }
 
+    // keep empty columns that are not hidden automatically
+    for (i, column) in self.columns.iter().enumerate().take(null_columns.len()) {
+      if !self.auto_columns.hides(column) {
+        null_columns[i] += 1;
+      }
+    }
+
     // filter out columns where everything is empty
     let mut tasks = vec![];
     for task in &self.tasks {
@@ -240,6 +416,14 @@ impl TaskReportTable {
       tasks.push(t);
     }
 
+    self.visible_columns = self
+      .columns
+      .iter()
+      .enumerate()
+      .filter(|&(i, _)| null_columns[i] != 0)
+      .map(|(_, e)| e.clone())
+      .collect();
+
     // filter out header where all columns are empty
     let headers: Vec<String> = self
       .labels
@@ -272,7 +456,11 @@ impl TaskReportTable {
         None => "".to_string(),
       },
       "due" => match task.due() {
-        Some(v) => format_date(NaiveDateTime::new(v.date(), v.time())),
+        Some(v) => format_date_and_time(NaiveDateTime::new(v.date(), v.time())),
+        None => "".to_string(),
+      },
+      "scheduled" => match task.scheduled() {
+        Some(v) => format_date_and_time(NaiveDateTime::new(v.date(), v.time())),
         None => "".to_string(),
       },
       "until.remaining" => match task.until() {
@@ -284,7 +472,7 @@ impl TaskReportTable {
         None => "".to_string(),
       },
       "until" => match task.until() {
-        Some(v) => format_date(NaiveDateTime::new(v.date(), v.time())),
+        Some(v) => format_date_and_time(NaiveDateTime::new(v.date(), v.time())),
         None => "".to_string(),
       },
       "entry.age" => vague_format_date_time(
@@ -425,6 +613,16 @@ impl TaskReportTable {
         d
       }
       "description.desc" | "description" => task.description().to_string(),
+      // a UDA of the same name is shown when the column is not added
+      "tracked" if self.show_tracked => match self.tracked.get(task.uuid()) {
+        Some(tracked) if tracked.num_minutes() > 0 => format_tracked(*tracked),
+        _ => "".to_string(),
+      },
+      "aging" if self.show_aging => vague_format_date_time(**task.entry(), Local::now().naive_utc(), false),
+      s if self.team.as_ref().is_some_and(|t| t.uda == s) => match self.team.as_ref().and_then(|t| t.assignee(task).map(|l| t.user(l))) {
+        Some(user) => format!(" {} ", user.initials()),
+        None => "".to_string(),
+      },
       "urgency" => match &task.urgency() {
         Some(f) => format!("{:.2}", *f),
         None => "0.00".to_string(),
@@ -444,3 +642,52 @@ impl TaskReportTable {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_auto_columns() {
+    assert_eq!(AutoColumns::parse("true"), AutoColumns::All);
+    assert!(!AutoColumns::parse("false").hides("recur"));
+    let auto_columns = AutoColumns::parse("recur, until");
+    assert!(auto_columns.hides("recur"));
+    assert!(auto_columns.hides("until.remaining"));
+    assert!(!auto_columns.hides("due"));
+  }
+
+  #[test]
+  fn test_sort() {
+    let data = "report.next.columns id,project,description.count,urgency\nreport.next.labels ID,Proj,Description,Urg\nreport.next.sort    urgency-,project+/\nreport.list.sort  due+\n";
+    let mut table = TaskReportTable::new(data, "next").unwrap();
+    assert_eq!(table.columns, ["id", "project", "description.count", "urgency"]);
+    assert_eq!(table.labels, ["ID", "Proj", "Description", "Urg"]);
+    assert_eq!(table.sort_string(), "urgency-,project+/");
+    assert_eq!(TaskReportTable::parse_sort(data, "list")[0].attribute, "due");
+    assert!(TaskReportTable::parse_sort(data, "all").is_empty());
+    assert_eq!(table.sort_direction("urgency"), Some(false));
+    assert_eq!(table.sort_direction("description.count"), None);
+
+    table.cycle_sort("description.count");
+    assert_eq!(table.sort_string(), "description-,urgency-,project+/");
+    assert!(table.sort_overridden);
+    table.cycle_sort("description.count");
+    assert_eq!(table.sort_direction("description"), Some(true));
+    table.cycle_sort("description.count");
+    assert_eq!(table.sort_string(), "urgency-,project+/");
+
+    // a sort changed from the TUI is kept when the report is read again
+    table.cycle_sort("project");
+    table.export_headers(Some(data), "next").unwrap();
+    assert_eq!(table.sort_string(), "urgency-");
+  }
+
+  #[test]
+  fn test_format_date_and_time() {
+    let midnight = Local.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap().naive_utc();
+    assert_eq!(format_date_and_time(midnight), "2024-01-02");
+    let evening = Local.with_ymd_and_hms(2024, 1, 2, 17, 30, 0).unwrap().naive_utc();
+    assert_eq!(format_date_and_time(evening), "2024-01-02 17:30");
+  }
+}
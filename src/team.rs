@@ -0,0 +1,116 @@
+use ratatui::style::Color;
+use task_hookrs::{task::Task, uda::UDAValue};
+
+/// Background colors of the initials, picked from the login so that each user keeps theirs.
+const COLORS: [Color; 8] = [
+  Color::Rgb(230, 124, 115),
+  Color::Rgb(247, 181, 0),
+  Color::Rgb(129, 199, 132),
+  Color::Rgb(77, 182, 172),
+  Color::Rgb(100, 181, 246),
+  Color::Rgb(149, 117, 205),
+  Color::Rgb(240, 98, 146),
+  Color::Rgb(161, 136, 127),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct User {
+  /// Value of the UDA for the tasks assigned to the user
+  pub login: String,
+  pub name: String,
+}
+
+impl User {
+  /// Up to two letters: the first of the first and last words of the name, or the first two of a
+  /// name of one word.
+  pub fn initials(&self) -> String {
+    let words: Vec<&str> = self.name.split_whitespace().collect();
+    let initials: String = match words.as_slice() {
+      [] => String::new(),
+      [word] => word.chars().take(2).collect(),
+      [first, .., last] => first.chars().take(1).chain(last.chars().take(1)).collect(),
+    };
+    initials.to_uppercase()
+  }
+
+  pub fn color(&self) -> Color {
+    let hash = self.login.bytes().fold(0u32, |h, b| h.wrapping_mul(31).wrapping_add(b as u32));
+    COLORS[hash as usize % COLORS.len()]
+  }
+}
+
+/// People sharing the task database, with the UDA holding who each task is assigned to.
+///
+/// ```plaintext
+/// uda.assignee.type=string
+/// uda.taskwarrior-tui.assignee.uda=assignee
+/// uda.taskwarrior-tui.assignee.users=alice:Alice Smith,bob:Bob Jones,carol
+/// uda.taskwarrior-tui.assignee.me=alice
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Team {
+  pub uda: String,
+  pub users: Vec<User>,
+  /// Login of the user running `taskwarrior-tui`
+  pub me: String,
+}
+
+impl Team {
+  /// Users are separated by commas, each a login optionally followed by a colon and a name.
+  /// `None` when no UDA is set.
+  pub fn parse(uda: &str, users: &str, me: &str) -> Option<Self> {
+    let uda = uda.trim();
+    if uda.is_empty() {
+      return None;
+    }
+    let users = users
+      .split(',')
+      .filter_map(|u| {
+        let (login, name) = u.split_once(':').unwrap_or((u, u));
+        let login = login.trim();
+        (!login.is_empty()).then(|| User {
+          login: login.to_string(),
+          name: Some(name.trim()).filter(|n| !n.is_empty()).unwrap_or(login).to_string(),
+        })
+      })
+      .collect();
+    Some(Self {
+      uda: uda.to_string(),
+      users,
+      me: me.trim().to_string(),
+    })
+  }
+
+  /// Login of the user `task` is assigned to.
+  pub fn assignee<'a>(&self, task: &'a Task) -> Option<&'a str> {
+    match task.uda().get(&self.uda) {
+      Some(UDAValue::Str(login)) if !login.is_empty() => Some(login),
+      _ => None,
+    }
+  }
+
+  /// The user with `login`, one named after the login when they are not in the list.
+  pub fn user(&self, login: &str) -> User {
+    self.users.iter().find(|u| u.login == login).cloned().unwrap_or_else(|| User {
+      login: login.to_string(),
+      name: login.to_string(),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_team() {
+    assert_eq!(Team::parse(" ", "alice", "alice"), None);
+    let team = Team::parse("assignee", "alice:Alice Mary Smith, bob:, carol:Carol,,", "bob").unwrap();
+    assert_eq!(team.users.len(), 3);
+    assert_eq!(team.users[1].name, "bob");
+    let initials: Vec<String> = team.users.iter().map(User::initials).collect();
+    assert_eq!(initials, ["AS", "BO", "CA"]);
+    assert_eq!(team.user("dave").initials(), "DA");
+    assert_eq!(team.user("alice").color(), team.users[0].color());
+  }
+}
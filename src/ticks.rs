@@ -0,0 +1,104 @@
+use std::time::{Duration, Instant};
+
+/// Parts of the interface that do work on their own cadence when the event loop ticks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Component {
+  /// Check whether the task data changed on disk, and export the report again when it did
+  Report,
+  /// Redraw the screen, so that relative dates and the status line move on
+  Display,
+  /// Redraw the clock and the countdown to the next due task in the status bar
+  Clock,
+  /// Check the named filters with `notify` for tasks that newly match them
+  Notify,
+}
+
+impl Component {
+  pub const ALL: [Component; 4] = [Component::Report, Component::Display, Component::Clock, Component::Notify];
+
+  /// Name of the component in `uda.taskwarrior-tui.tick.<name>`.
+  pub fn name(&self) -> &'static str {
+    match self {
+      Component::Report => "report",
+      Component::Display => "display",
+      Component::Clock => "clock",
+      Component::Notify => "notify",
+    }
+  }
+
+  /// Interval in milliseconds when none is configured.
+  pub fn default_interval(&self, tick_rate: u64) -> u64 {
+    match self {
+      Component::Report | Component::Display => tick_rate,
+      Component::Clock => 1000,
+      // filters such as `+OVERDUE` start matching as time passes, without the data changing
+      Component::Notify => 60000,
+    }
+  }
+}
+
+struct Schedule {
+  component: Component,
+  /// `None` when the component is only updated on demand, e.g. after a key press
+  interval: Option<Duration>,
+  next: Instant,
+}
+
+/// When each component does its periodic work next.
+pub struct Ticks {
+  schedules: Vec<Schedule>,
+}
+
+impl Ticks {
+  pub fn new(intervals: &[(Component, Option<Duration>)]) -> Self {
+    let mut ticks = Self { schedules: vec![] };
+    for (component, interval) in intervals {
+      ticks.request(*component, *interval);
+    }
+    ticks
+  }
+
+  /// Update `component` every `interval`, or only on demand when it is `None`.
+  pub fn request(&mut self, component: Component, interval: Option<Duration>) {
+    let next = Instant::now() + interval.unwrap_or_default();
+    match self.schedules.iter_mut().find(|s| s.component == component) {
+      Some(schedule) => {
+        schedule.interval = interval;
+        schedule.next = next;
+      }
+      None => self.schedules.push(Schedule { component, interval, next }),
+    }
+  }
+
+  /// Components whose interval elapsed at `now`, each scheduled again for its next interval.
+  pub fn due(&mut self, now: Instant) -> Vec<Component> {
+    let mut due = vec![];
+    for schedule in &mut self.schedules {
+      if let Some(interval) = schedule.interval {
+        if schedule.next <= now {
+          due.push(schedule.component);
+          // skip the ticks that were missed rather than catching up on them
+          schedule.next = now + interval;
+        }
+      }
+    }
+    due
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_due() {
+    let start = Instant::now();
+    let mut ticks = Ticks::new(&[(Component::Report, None), (Component::Display, Some(Duration::from_secs(1)))]);
+    assert!(ticks.due(start).is_empty());
+    let later = start + Duration::from_millis(1500);
+    assert_eq!(ticks.due(later), vec![Component::Display]);
+    assert!(ticks.due(later + Duration::from_millis(500)).is_empty());
+    ticks.request(Component::Report, Some(Duration::ZERO));
+    assert_eq!(ticks.due(later + Duration::from_secs(1)), vec![Component::Report, Component::Display]);
+  }
+}
@@ -0,0 +1,98 @@
+use chrono::{FixedOffset, NaiveDateTime, TimeZone};
+use task_hookrs::task::Task;
+
+/// A second timezone the dates of the selected task are shown in, e.g. of home while travelling.
+///
+/// It has a fixed offset from UTC, daylight saving time is not followed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlternateZone {
+  pub name: String,
+  pub offset: FixedOffset,
+}
+
+/// Offset such as `UTC`, `UTC+9`, `+09:00`, `-0530` or `+5`.
+fn parse_offset(s: &str) -> Option<FixedOffset> {
+  let s = s.trim();
+  let s = s.strip_prefix("UTC").or_else(|| s.strip_prefix("GMT")).unwrap_or(s);
+  if s.is_empty() || s == "Z" {
+    return FixedOffset::east_opt(0);
+  }
+  let (sign, rest) = match s.chars().next()? {
+    '+' => (1, &s[1..]),
+    '-' => (-1, &s[1..]),
+    _ => return None,
+  };
+  let (hours, minutes) = match rest.split_once(':') {
+    Some((h, m)) => (h, m),
+    None if rest.len() == 4 => rest.split_at(2),
+    None => (rest, "0"),
+  };
+  let hours: i32 = hours.parse().ok()?;
+  let minutes: i32 = minutes.parse().ok()?;
+  if hours > 14 || minutes >= 60 {
+    return None;
+  }
+  FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+impl AlternateZone {
+  /// Parse the `uda.taskwarrior-tui.timezone.alternate` setting, an offset with an optional name in
+  /// front, e.g. `Tokyo +09:00`.
+  pub fn parse(s: &str) -> Option<Self> {
+    let s = s.trim();
+    let (name, offset) = match s.rsplit_once(char::is_whitespace) {
+      Some((name, offset)) => (name.trim(), offset),
+      None => (s, s),
+    };
+    Some(Self {
+      name: name.to_string(),
+      offset: parse_offset(offset)?,
+    })
+  }
+
+  /// `utc`, a date as stored by taskwarrior, in this timezone.
+  pub fn format(&self, utc: NaiveDateTime) -> String {
+    self.offset.from_utc_datetime(&utc).format("%Y-%m-%d %H:%M").to_string()
+  }
+
+  /// The dates of `task` in this timezone, one per line.
+  pub fn describe(&self, task: &Task) -> String {
+    let dates = [
+      ("Due", task.due()),
+      ("Scheduled", task.scheduled()),
+      ("Wait", task.wait()),
+      ("Until", task.until()),
+      ("Start", task.start()),
+    ];
+    dates
+      .iter()
+      .filter_map(|(label, date)| {
+        let date = (*date)?;
+        let utc = NaiveDateTime::new(date.date(), date.time());
+        Some(format!("{:<10} {} ({})", label, self.format(utc), self.name))
+      })
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::NaiveDate;
+
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    let zone = AlternateZone::parse("Tokyo +09:00").unwrap();
+    assert_eq!(zone.name, "Tokyo");
+    assert_eq!(zone.offset.local_minus_utc(), 9 * 3600);
+    assert_eq!(AlternateZone::parse("-0530").unwrap().offset.local_minus_utc(), -(5 * 3600 + 30 * 60));
+    assert_eq!(AlternateZone::parse("UTC").unwrap().offset.local_minus_utc(), 0);
+    assert_eq!(AlternateZone::parse("home UTC+2").unwrap().offset.local_minus_utc(), 2 * 3600);
+    assert_eq!(AlternateZone::parse("Europe/Paris"), None);
+
+    let utc = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(20, 0, 0).unwrap();
+    assert_eq!(zone.format(utc), "2024-01-02 05:00");
+  }
+}
@@ -0,0 +1,70 @@
+use std::time::{Duration, Instant};
+
+use chrono::NaiveDateTime;
+use task_hookrs::task::Task;
+
+use crate::task_report::format_date_and_time;
+
+/// Where the mouse rests over the interface, and since when.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hover {
+  pub column: u16,
+  pub row: u16,
+  pub since: Instant,
+}
+
+impl Hover {
+  pub fn new(column: u16, row: u16) -> Self {
+    Self {
+      column,
+      row,
+      since: Instant::now(),
+    }
+  }
+
+  /// The mouse rested long enough at `now` for the tooltip to show.
+  pub fn ready(&self, delay: Duration, now: Instant) -> bool {
+    now >= self.since + delay
+  }
+}
+
+/// Lines of the tooltip of `task`: its due date, tags and first annotation.
+pub fn lines(task: &Task) -> Vec<String> {
+  let mut lines = details(task);
+  if lines.is_empty() {
+    lines.push("No due date, tags or annotations".to_string());
+  }
+  lines
+}
+
+/// Due date, tags and first annotation of `task`, the ones it has.
+pub fn details(task: &Task) -> Vec<String> {
+  let mut lines = vec![];
+  if let Some(due) = task.due() {
+    lines.push(format!("Due {}", format_date_and_time(NaiveDateTime::new(due.date(), due.time()))));
+  }
+  if let Some(tags) = task.tags().filter(|t| !t.is_empty()) {
+    let tags: Vec<String> = tags.iter().map(|t| format!("+{}", t)).collect();
+    lines.push(tags.join(" "));
+  }
+  if let Some(annotation) = task.annotations().and_then(|a| a.first()) {
+    lines.push(annotation.description().to_string());
+  }
+  lines
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_lines() {
+    let data = r#"[{"uuid":"6ef1e5b0-a0c0-4f4c-9c4e-3c1a4c2b1f10","description":"Call","status":"pending","entry":"20240101T000000Z","tags":["home","phone"],"annotations":[{"entry":"20240101T000000Z","description":"number in contacts"},{"entry":"20240102T000000Z","description":"second"}]}]"#;
+    let tasks: Vec<Task> = task_hookrs::import::import(data.as_bytes()).unwrap();
+    assert_eq!(lines(&tasks[0]), vec!["+home +phone", "number in contacts"]);
+
+    let hover = Hover::new(3, 4);
+    assert!(!hover.ready(Duration::from_millis(500), hover.since));
+    assert!(hover.ready(Duration::from_millis(500), hover.since + Duration::from_millis(500)));
+  }
+}
@@ -0,0 +1,166 @@
+use std::{
+  fs,
+  path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use chrono::Local;
+use ratatui::{buffer::Buffer, layout::Rect, style::Modifier, widgets::Widget};
+
+use crate::{keyconfig::KeyConfig, utils};
+
+/// Part of the screen a step of the tour points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+  /// The tabs, with the status bar on their right
+  Tabs,
+  Report,
+  /// The two lines at the bottom, where filters and prompts are typed
+  CommandLine,
+}
+
+impl Region {
+  /// Area of the region on a screen of `size` showing the report.
+  pub fn area(&self, size: Rect) -> Rect {
+    match self {
+      Region::Tabs => Rect::new(size.x, size.y, size.width, size.height.min(1)),
+      Region::Report => Rect::new(size.x, size.y + 1, size.width, size.height.saturating_sub(3)),
+      Region::CommandLine => Rect::new(size.x, size.y + size.height.saturating_sub(2), size.width, size.height.min(2)),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+  pub region: Region,
+  pub title: &'static str,
+  pub text: String,
+}
+
+/// Steps of the tour, naming the keys as they are bound.
+pub fn steps(keys: &KeyConfig) -> Vec<Step> {
+  let key = |k| keys.key_label(k);
+  vec![
+    Step {
+      region: Region::Report,
+      title: "The report",
+      text: format!(
+        "Tasks of the current report. Move with {} and {}, add a task with {}, modify the selected one with {} and mark it done with {}.",
+        key(keys.down),
+        key(keys.up),
+        key(keys.add),
+        key(keys.modify),
+        key(keys.done)
+      ),
+    },
+    Step {
+      region: Region::Tabs,
+      title: "Tabs and status bar",
+      text: format!(
+        "Switch between tasks, projects and the calendar with {} and {}. On the right, the status bar shows the context, which {} switches, and what taskwarrior-tui is up to.",
+        key(keys.previous_tab),
+        key(keys.next_tab),
+        key(keys.context_menu)
+      ),
+    },
+    Step {
+      region: Region::CommandLine,
+      title: "Filters and prompts",
+      text: format!(
+        "Press {} to filter the report. Prompts to add and modify tasks open here too, with <Tab> completing projects, tags and dates.",
+        key(keys.filter)
+      ),
+    },
+    Step {
+      region: Region::Report,
+      title: "Help and commands",
+      text: format!(
+        "Press {} for all the key bindings and {} for the command palette, where \"Take the tour\" starts this tour again.",
+        key(keys.help),
+        key(keys.command_palette)
+      ),
+    },
+  ]
+}
+
+/// Dims every cell outside `except`, so that the region a step points at stands out.
+pub struct Dim {
+  pub except: Rect,
+}
+
+impl Widget for Dim {
+  fn render(self, area: Rect, buf: &mut Buffer) {
+    for y in area.top()..area.bottom() {
+      for x in area.left()..area.right() {
+        let inside = (self.except.left()..self.except.right()).contains(&x) && (self.except.top()..self.except.bottom()).contains(&y);
+        if !inside {
+          buf.get_mut(x, y).modifier.insert(Modifier::DIM);
+        }
+      }
+    }
+  }
+}
+
+/// Where the tour is at, and whether it was taken, remembered by a file in the data directory so
+/// that it only starts by itself the first time.
+pub struct Tour {
+  pub step: usize,
+  path: PathBuf,
+}
+
+/// Whether `data_dir` holds nothing yet, i.e. `taskwarrior-tui` never ran with it. Checked before
+/// anything is written there.
+pub fn fresh_install(data_dir: &Path) -> bool {
+  fs::read_dir(data_dir).map_or(true, |mut entries| entries.next().is_none())
+}
+
+impl Tour {
+  pub fn new(filename: &str) -> Self {
+    Self {
+      step: 0,
+      path: utils::get_data_dir().join(filename),
+    }
+  }
+
+  pub fn taken(&self) -> bool {
+    self.path.exists()
+  }
+
+  pub fn mark(&self) -> Result<()> {
+    fs::write(&self.path, Local::now().to_rfc3339())?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_tour() {
+    let steps = steps(&KeyConfig::default());
+    assert_eq!(steps.len(), 4);
+    assert!(steps[3].text.starts_with("Press ? for all the key bindings"));
+
+    let size = Rect::new(0, 0, 80, 24);
+    assert_eq!(Region::Tabs.area(size), Rect::new(0, 0, 80, 1));
+    assert_eq!(Region::Report.area(size), Rect::new(0, 1, 80, 21));
+    assert_eq!(Region::CommandLine.area(size), Rect::new(0, 22, 80, 2));
+
+    let mut buf = Buffer::empty(size);
+    Dim {
+      except: Region::CommandLine.area(size),
+    }
+    .render(size, &mut buf);
+    assert!(buf.get(0, 21).modifier.contains(Modifier::DIM));
+    assert!(!buf.get(0, 22).modifier.contains(Modifier::DIM));
+
+    let dir = std::env::temp_dir().join(format!("taskwarrior-tui-tour-{}", std::process::id()));
+    assert!(fresh_install(&dir));
+    fs::create_dir_all(&dir).unwrap();
+    assert!(fresh_install(&dir));
+    fs::write(dir.join("history.txt"), "").unwrap();
+    assert!(!fresh_install(&dir));
+    fs::remove_dir_all(&dir).unwrap();
+  }
+}
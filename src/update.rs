@@ -0,0 +1,262 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::mpsc;
+use versions::Versioning;
+
+use crate::{
+  event::{Event, KeyCode},
+  utils,
+};
+
+pub const CURRENT: &str = env!("CARGO_PKG_VERSION");
+const RELEASES: &str = "https://api.github.com/repos/kdheepak/taskwarrior-tui/releases?per_page=30";
+const CRATE: &str = "https://crates.io/api/v1/crates/taskwarrior-tui";
+
+/// Where the latest version is looked up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+  GitHub,
+  CratesIo,
+}
+
+impl Source {
+  pub fn parse(s: &str) -> Option<Self> {
+    match s.trim() {
+      "github" => Some(Self::GitHub),
+      "crates.io" => Some(Self::CratesIo),
+      _ => None,
+    }
+  }
+
+  fn url(&self) -> &'static str {
+    match self {
+      Self::GitHub => RELEASES,
+      Self::CratesIo => CRATE,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+  pub version: String,
+  /// `YYYY-MM-DD`
+  pub date: String,
+  /// In Markdown
+  pub notes: String,
+}
+
+/// Published releases in the answer of the GitHub API, the latest first.
+pub fn releases(json: &str) -> Vec<Release> {
+  let releases: Vec<Value> = serde_json::from_str(json).unwrap_or_default();
+  releases
+    .iter()
+    .filter(|r| !r["draft"].as_bool().unwrap_or(false) && !r["prerelease"].as_bool().unwrap_or(false))
+    .filter_map(|r| {
+      Some(Release {
+        version: r["tag_name"].as_str()?.trim_start_matches('v').to_string(),
+        date: r["published_at"].as_str().unwrap_or_default().chars().take(10).collect(),
+        notes: r["body"].as_str().unwrap_or_default().replace("\r\n", "\n"),
+      })
+    })
+    .collect()
+}
+
+/// Latest version in the answer of `source`.
+pub fn latest(source: Source, json: &str) -> Option<String> {
+  match source {
+    Source::GitHub => releases(json).into_iter().next().map(|r| r.version),
+    Source::CratesIo => {
+      let answer: Value = serde_json::from_str(json).ok()?;
+      answer["crate"]["max_stable_version"].as_str().map(ToString::to_string)
+    }
+  }
+}
+
+/// Whether `version` comes after `current`.
+pub fn newer(version: &str, current: &str) -> bool {
+  match (Versioning::new(version), Versioning::new(current)) {
+    (Some(version), Some(current)) => version > current,
+    _ => false,
+  }
+}
+
+/// Lines of the changelog of `releases`, the releases after `current` marked as new.
+pub fn changelog(releases: &[Release], current: &str) -> Vec<String> {
+  let mut lines = vec![];
+  for release in releases {
+    let new = if newer(&release.version, current) { " (new)" } else { "" };
+    let installed = if release.version == current { " (installed)" } else { "" };
+    lines.push(format!("{} - {}{}{}", release.version, release.date, new, installed));
+    lines.push(String::new());
+    lines.extend(release.notes.lines().map(ToString::to_string));
+    lines.push(String::new());
+  }
+  lines
+}
+
+/// Body of `url`, fetched with `curl` so that no HTTP client is built in.
+async fn fetch(url: &str) -> Result<String, String> {
+  if cfg!(not(feature = "network")) {
+    return Err("taskwarrior-tui was built without the `network` feature".to_string());
+  }
+  let output = tokio::process::Command::new("curl")
+    .args(["--silent", "--show-error", "--fail", "--location", "--max-time", "15"])
+    .args(["--user-agent", &format!("taskwarrior-tui/{}", CURRENT)])
+    .arg(url)
+    .output()
+    .await
+    .map_err(|e| format!("Unable to run `curl`: {}", e))?;
+  if output.status.success() {
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+  } else {
+    Err(format!("Unable to fetch {}: {}", url, String::from_utf8_lossy(&output.stderr).trim()))
+  }
+}
+
+enum Message {
+  Latest(Result<String, String>),
+  Releases(Result<Vec<Release>, String>),
+}
+
+/// When the latest version was last looked up, and what it was.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct State {
+  /// In RFC 3339
+  checked: Option<String>,
+  latest: Option<String>,
+}
+
+/// Looks up the latest version at most once a day, in the background, and fetches the changelog
+/// when it is asked for.
+pub struct Updates {
+  state: State,
+  path: PathBuf,
+  /// The changelog once fetched, or why it could not be
+  pub changelog: Option<Result<Vec<Release>, String>>,
+  pub scroll: u16,
+  tx: mpsc::UnboundedSender<Message>,
+  rx: mpsc::UnboundedReceiver<Message>,
+}
+
+impl Updates {
+  pub fn new(filename: &str) -> Self {
+    let (tx, rx) = mpsc::unbounded_channel();
+    Self {
+      state: State::default(),
+      path: utils::get_data_dir().join(filename),
+      changelog: None,
+      scroll: 0,
+      tx,
+      rx,
+    }
+  }
+
+  pub fn load(&mut self) -> Result<()> {
+    if self.path.exists() {
+      self.state = serde_json::from_str(&fs::read_to_string(&self.path)?)?;
+    }
+    Ok(())
+  }
+
+  /// The latest version when it is newer than this one.
+  pub fn available(&self) -> Option<&str> {
+    self.state.latest.as_deref().filter(|v| newer(v, CURRENT))
+  }
+
+  /// Whether the latest version was looked up more than a day before `now`.
+  pub fn due(&self, now: DateTime<Utc>) -> bool {
+    let checked = self.state.checked.as_ref().and_then(|c| DateTime::parse_from_rfc3339(c).ok());
+    checked.is_none_or(|checked| now.signed_duration_since(checked) > Duration::days(1))
+  }
+
+  /// Look up the latest version on `source`, sending `Event::Tick` on `wake` once done.
+  pub fn check(&mut self, source: Source, wake: &mpsc::UnboundedSender<Event<KeyCode>>) {
+    // a failed lookup is not tried again before the next day either
+    self.state.checked = Some(Utc::now().to_rfc3339());
+    let tx = self.tx.clone();
+    let wake = wake.clone();
+    tokio::spawn(async move {
+      let latest = fetch(source.url())
+        .await
+        .and_then(|json| latest(source, &json).ok_or_else(|| "No version found".to_string()));
+      tx.send(Message::Latest(latest)).ok();
+      wake.send(Event::Tick).ok();
+    });
+  }
+
+  /// Fetch the changelog unless it was fetched already.
+  pub fn fetch_changelog(&mut self, wake: &mpsc::UnboundedSender<Event<KeyCode>>) {
+    self.scroll = 0;
+    if matches!(self.changelog, Some(Ok(_))) {
+      return;
+    }
+    self.changelog = None;
+    let tx = self.tx.clone();
+    let wake = wake.clone();
+    tokio::spawn(async move {
+      let releases = fetch(RELEASES).await.map(|json| releases(&json));
+      tx.send(Message::Releases(releases)).ok();
+      wake.send(Event::Tick).ok();
+    });
+  }
+
+  /// Take in the lookups done since the last call, returning whether any was. A failed check only
+  /// shows in the log, it is not worth interrupting for.
+  pub fn poll(&mut self) -> bool {
+    let mut any = false;
+    while let Ok(message) = self.rx.try_recv() {
+      any = true;
+      match message {
+        Message::Latest(latest) => {
+          match latest {
+            Ok(version) => self.state.latest = Some(version),
+            Err(e) => warn!("{}", e),
+          }
+          if let Err(e) = self.save() {
+            warn!("Unable to save {}: {}", self.path.display(), e);
+          }
+        }
+        Message::Releases(releases) => self.changelog = Some(releases),
+      }
+    }
+    any
+  }
+
+  fn save(&self) -> Result<()> {
+    fs::write(&self.path, serde_json::to_string(&self.state)?)?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_releases() {
+    let json = r#"[
+      {"tag_name":"v0.27.0-rc1","published_at":"2026-10-10T00:00:00Z","body":"Beta","draft":false,"prerelease":true},
+      {"tag_name":"v0.26.0","published_at":"2026-09-01T12:00:00Z","body":"- Sorts\r\n- Activity feed","draft":false,"prerelease":false},
+      {"tag_name":"v0.25.4","published_at":"2026-06-01T12:00:00Z","body":"- Fixes","draft":false,"prerelease":false}
+    ]"#;
+    let releases = releases(json);
+    assert_eq!(releases.len(), 2);
+    assert_eq!(latest(Source::GitHub, json).as_deref(), Some("0.26.0"));
+    assert_eq!(
+      latest(Source::CratesIo, r#"{"crate":{"max_stable_version":"0.26.1"}}"#).as_deref(),
+      Some("0.26.1")
+    );
+    assert!(newer("0.26.0", "0.25.4"));
+    assert!(newer("0.25.10", "0.25.4"));
+    assert!(!newer("0.25.4", "0.25.4"));
+    let lines = changelog(&releases, "0.25.4");
+    assert_eq!(lines[0], "0.26.0 - 2026-09-01 (new)");
+    assert_eq!(lines[3], "- Activity feed");
+    assert_eq!(lines[5], "0.25.4 - 2026-06-01 (installed)");
+  }
+}
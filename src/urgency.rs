@@ -0,0 +1,266 @@
+use std::{
+  collections::HashMap,
+  sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+  },
+  time::Duration,
+};
+
+use log::warn;
+use ratatui::widgets::ListState;
+use task_hookrs::{import::import, task::Task};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::{
+  event::{Event, KeyCode},
+  overrides,
+};
+
+/// Tasks of the report sent to `task export` for the preview, to keep the command line short.
+pub const PREVIEW_TASKS: usize = 500;
+
+/// How long adjusting has to pause before the tasks are ranked again.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// An urgency coefficient of the taskrc being tuned, e.g. `urgency.due.coefficient`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coefficient {
+  /// Name of the setting, e.g. `urgency.due.coefficient`
+  pub name: String,
+  /// Value in the taskrc
+  pub original: f64,
+  pub value: f64,
+}
+
+impl Coefficient {
+  pub fn changed(&self) -> bool {
+    (self.value - self.original).abs() > f64::EPSILON
+  }
+
+  /// Change the value by `step`, rounded to tenths so that repeated steps do not drift.
+  pub fn adjust(&mut self, step: f64) {
+    self.value = ((self.value + step) * 10.0).round() / 10.0;
+  }
+
+  /// The value as taskwarrior writes it, without trailing zeros.
+  pub fn formatted(&self) -> String {
+    format_value(self.value)
+  }
+}
+
+fn format_value(value: f64) -> String {
+  let s = format!("{:.2}", value);
+  s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// State of the urgency tuner screen.
+#[derive(Default)]
+pub struct Tuner {
+  pub coefficients: Vec<Coefficient>,
+  pub state: ListState,
+  /// Tasks of the report ranked with the coefficients of the taskrc
+  pub baseline: Vec<(Uuid, f64)>,
+  /// The same tasks ranked with the coefficients being tuned
+  pub preview: Vec<(Uuid, f64)>,
+  pub descriptions: HashMap<Uuid, String>,
+}
+
+impl Tuner {
+  pub fn new(coefficients: Vec<Coefficient>, tasks: &[Task]) -> Self {
+    let tasks = &tasks[..tasks.len().min(PREVIEW_TASKS)];
+    let mut state = ListState::default();
+    state.select((!coefficients.is_empty()).then_some(0));
+    let baseline = ranking(tasks);
+    Self {
+      coefficients,
+      state,
+      preview: baseline.clone(),
+      baseline,
+      descriptions: tasks.iter().map(|t| (*t.uuid(), t.description().clone())).collect(),
+    }
+  }
+
+  pub fn selected_mut(&mut self) -> Option<&mut Coefficient> {
+    let i = self.state.selected()?;
+    self.coefficients.get_mut(i)
+  }
+
+  pub fn next(&mut self) {
+    if !self.coefficients.is_empty() {
+      let i = self.state.selected().map_or(0, |i| (i + 1) % self.coefficients.len());
+      self.state.select(Some(i));
+    }
+  }
+
+  pub fn previous(&mut self) {
+    let len = self.coefficients.len();
+    if len > 0 {
+      let i = self.state.selected().map_or(0, |i| (i + len - 1) % len);
+      self.state.select(Some(i));
+    }
+  }
+}
+
+/// Urgency coefficients in the output of `task show`, in the order they appear.
+pub fn parse(data: &str) -> Vec<Coefficient> {
+  data
+    .lines()
+    .filter(|l| l.starts_with("urgency."))
+    .filter_map(|l| {
+      let (name, value) = l.split_once(char::is_whitespace)?;
+      let value: f64 = value.trim().parse().ok()?;
+      name.ends_with(".coefficient").then(|| Coefficient {
+        name: name.to_string(),
+        original: value,
+        value,
+      })
+    })
+    .collect()
+}
+
+/// `rc.` overrides of the coefficients that were changed, to compute urgencies with them.
+pub fn overrides(coefficients: &[Coefficient]) -> Vec<String> {
+  coefficients
+    .iter()
+    .filter(|c| c.changed())
+    .map(|c| format!("rc.{}={}", c.name, c.formatted()))
+    .collect()
+}
+
+/// Tasks from the most to the least urgent.
+pub fn ranking(tasks: &[Task]) -> Vec<(Uuid, f64)> {
+  let mut ranking: Vec<(Uuid, f64)> = tasks.iter().map(|t| (*t.uuid(), t.urgency().copied().unwrap_or_default())).collect();
+  ranking.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+  ranking
+}
+
+/// Rankings of the preview, computed by taskwarrior in the background once adjusting pauses so
+/// that holding an arrow key does not run `task export` for each step.
+pub struct Rankings {
+  /// Bumped on each request, only the ranking of the last one is kept
+  requests: Arc<AtomicU64>,
+  tx: mpsc::UnboundedSender<(u64, Vec<(Uuid, f64)>)>,
+  rx: mpsc::UnboundedReceiver<(u64, Vec<(Uuid, f64)>)>,
+}
+
+impl Rankings {
+  pub fn new() -> Self {
+    let (tx, rx) = mpsc::unbounded_channel();
+    Self {
+      requests: Arc::new(AtomicU64::new(0)),
+      tx,
+      rx,
+    }
+  }
+
+  /// Rank `uuids` with the coefficient `overrides` once no other ranking is requested for a while,
+  /// sending `Event::Tick` on `wake` once done.
+  pub fn request(&mut self, overrides: Vec<String>, uuids: Vec<Uuid>, wake: &mpsc::UnboundedSender<Event<KeyCode>>) {
+    let request = self.requests.fetch_add(1, Ordering::SeqCst) + 1;
+    let requests = self.requests.clone();
+    let tx = self.tx.clone();
+    let wake = wake.clone();
+    tokio::spawn(async move {
+      tokio::time::sleep(DEBOUNCE).await;
+      if requests.load(Ordering::SeqCst) != request {
+        return;
+      }
+      let output = overrides::task_async()
+        .arg("rc.json.array=on")
+        .arg("rc.confirmation=off")
+        .arg("rc.json.depends.array=on")
+        .arg("rc.color=off")
+        .arg("rc._forcecolor=off")
+        .args(&overrides)
+        .args(uuids.iter().map(ToString::to_string))
+        .arg("export")
+        .output()
+        .await;
+      match output.map(|o| import(o.stdout.as_slice())) {
+        Ok(Ok(tasks)) => {
+          tx.send((request, ranking(&tasks))).ok();
+          wake.send(Event::Tick).ok();
+        }
+        Ok(Err(e)) => warn!("Unable to parse the urgency preview: {}", e),
+        Err(e) => warn!("Unable to run `task export` for the urgency preview: {}", e),
+      }
+    });
+  }
+
+  /// Drop the rankings still being computed.
+  pub fn cancel(&mut self) {
+    self.requests.fetch_add(1, Ordering::SeqCst);
+  }
+
+  /// The ranking of the last request, if it arrived since the last call.
+  pub fn poll(&mut self) -> Option<Vec<(Uuid, f64)>> {
+    let latest = self.requests.load(Ordering::SeqCst);
+    let mut arrived = None;
+    while let Ok((request, ranking)) = self.rx.try_recv() {
+      if request == latest {
+        arrived = Some(ranking);
+      }
+    }
+    arrived
+  }
+}
+
+impl Default for Rankings {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Places `uuid` moved up in `after` compared to `before`, negative when it moved down.
+pub fn movement(before: &[(Uuid, f64)], after: &[(Uuid, f64)], uuid: &Uuid) -> i64 {
+  let position = |ranking: &[(Uuid, f64)]| ranking.iter().position(|(u, _)| u == uuid).map(|p| p as i64);
+  match (position(before), position(after)) {
+    (Some(b), Some(a)) => b - a,
+    _ => 0,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_coefficients() {
+    let data = "urgency.active.coefficient         4.0\nurgency.age.max                    365\nurgency.due.coefficient            12.0\nurgency.user.tag.next.coefficient  15.0\n";
+    let mut coefficients = parse(data);
+    assert_eq!(coefficients.len(), 3);
+    assert!(overrides(&coefficients).is_empty());
+    coefficients[1].adjust(-0.5);
+    coefficients[1].adjust(0.1);
+    assert_eq!(overrides(&coefficients), ["rc.urgency.due.coefficient=11.6"]);
+    coefficients[1].adjust(0.4);
+    assert!(!coefficients[1].changed());
+    assert_eq!(coefficients[2].formatted(), "15");
+
+    let a = Uuid::from_u128(1);
+    let b = Uuid::from_u128(2);
+    let before = vec![(a, 10.0), (b, 5.0)];
+    let after = vec![(b, 12.0), (a, 10.0)];
+    assert_eq!(movement(&before, &after, &b), 1);
+    assert_eq!(movement(&before, &after, &a), -1);
+  }
+
+  #[tokio::test]
+  async fn test_rankings() {
+    let (wake, _) = mpsc::unbounded_channel();
+    let mut rankings = Rankings::new();
+    rankings.request(vec!["rc.urgency.due.coefficient=11".to_string()], vec![], &wake);
+    rankings.request(vec!["rc.urgency.due.coefficient=10".to_string()], vec![], &wake);
+    assert_eq!(rankings.requests.load(Ordering::SeqCst), 2);
+    // only the ranking of the last request is kept
+    rankings.tx.send((1, vec![(Uuid::from_u128(1), 1.0)])).unwrap();
+    assert!(rankings.poll().is_none());
+    rankings.tx.send((2, vec![(Uuid::from_u128(2), 2.0)])).unwrap();
+    assert_eq!(rankings.poll(), Some(vec![(Uuid::from_u128(2), 2.0)]));
+    rankings.tx.send((2, vec![])).unwrap();
+    rankings.cancel();
+    assert!(rankings.poll().is_none());
+  }
+}
@@ -0,0 +1,87 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use anyhow::Result;
+
+use crate::utils;
+
+/// Unsaved counts are written after this many increments, and when quitting.
+const WRITE_EVERY: u64 = 50;
+
+/// Counts of the keys pressed, views opened and commands run, kept in the data directory and never
+/// sent anywhere.
+pub struct Usage {
+  pub counts: BTreeMap<String, u64>,
+  path: PathBuf,
+  unsaved: u64,
+}
+
+impl Usage {
+  pub fn new(filename: &str) -> Self {
+    Self {
+      counts: BTreeMap::new(),
+      path: utils::get_data_dir().join(filename),
+      unsaved: 0,
+    }
+  }
+
+  pub fn load(&mut self) -> Result<()> {
+    if self.path.exists() {
+      let data = fs::read_to_string(&self.path)?;
+      self.counts = serde_json::from_str(&data)?;
+    }
+    Ok(())
+  }
+
+  pub fn write(&mut self) -> Result<()> {
+    fs::write(&self.path, serde_json::to_string_pretty(&self.counts)?)?;
+    self.unsaved = 0;
+    Ok(())
+  }
+
+  /// Count one use of `name` in `category`, e.g. `key` and `done`.
+  pub fn count(&mut self, category: &str, name: &str) -> Result<()> {
+    *self.counts.entry(format!("{}.{}", category, name)).or_insert(0) += 1;
+    self.unsaved += 1;
+    if self.unsaved >= WRITE_EVERY {
+      self.write()?;
+    }
+    Ok(())
+  }
+
+  pub fn get(&self, category: &str, name: &str) -> u64 {
+    self.counts.get(&format!("{}.{}", category, name)).copied().unwrap_or(0)
+  }
+
+  /// Names and counts in `category`, most used first.
+  pub fn ranked(&self, category: &str) -> Vec<(&str, u64)> {
+    let prefix = format!("{}.", category);
+    let mut ranked: Vec<(&str, u64)> = self
+      .counts
+      .iter()
+      .filter_map(|(k, v)| k.strip_prefix(&prefix).map(|name| (name, *v)))
+      .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    ranked
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_ranked() {
+    let mut usage = Usage {
+      counts: BTreeMap::new(),
+      path: PathBuf::new(),
+      unsaved: 0,
+    };
+    for name in ["done", "add", "done", "filter", "done", "add"].iter() {
+      *usage.counts.entry(format!("key.{}", name)).or_insert(0) += 1;
+    }
+    usage.counts.insert("view.calendar".to_string(), 7);
+    assert_eq!(usage.ranked("key"), vec![("done", 3), ("add", 2), ("filter", 1)]);
+    assert_eq!(usage.get("view", "calendar"), 7);
+    assert_eq!(usage.get("view", "projects"), 0);
+  }
+}
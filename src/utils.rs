@@ -1,3 +1,8 @@
+use std::{
+  io::{self, Write},
+  path::PathBuf,
+};
+
 use rustyline::line_buffer::{ChangeListener, DeleteListener, Direction};
 
 /// Undo manager
@@ -15,3 +20,109 @@ impl ChangeListener for Changeset {
 
   fn replace(&mut self, idx: usize, old: &str, new: &str) {}
 }
+
+/// Directory where taskwarrior-tui keeps its own state (history, logs, ...).
+pub fn get_data_dir() -> PathBuf {
+  let data_path = if let Ok(s) = std::env::var("TASKWARRIOR_TUI_DATA") {
+    PathBuf::from(s)
+  } else {
+    dirs::data_local_dir()
+      .map(|d| d.join("taskwarrior-tui"))
+      .expect("Unable to create data directory for taskwarrior-tui")
+  };
+  std::fs::create_dir_all(&data_path).unwrap_or_else(|_| panic!("Unable to create data directory in {:?}", &data_path));
+  data_path
+}
+
+/// Directory for files taskwarrior-tui writes but does not need to keep, such as logs.
+pub fn get_state_dir() -> PathBuf {
+  if std::env::var("TASKWARRIOR_TUI_DATA").is_ok() {
+    return get_data_dir();
+  }
+  match dirs::state_dir() {
+    Some(d) => {
+      let state_path = d.join("taskwarrior-tui");
+      std::fs::create_dir_all(&state_path).unwrap_or_else(|_| panic!("Unable to create state directory in {:?}", &state_path));
+      state_path
+    }
+    None => get_data_dir(),
+  }
+}
+
+/// Render a program and its arguments as a line that can be pasted in a shell.
+pub fn command_line<S: AsRef<str>>(program: &str, args: &[S]) -> String {
+  let mut line = program.to_string();
+  for arg in args {
+    line.push(' ');
+    line.push_str(&shlex::quote(arg.as_ref()));
+  }
+  line
+}
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub fn base64_encode(data: &[u8]) -> String {
+  let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+  for chunk in data.chunks(3) {
+    let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+    let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+    for i in 0..4 {
+      if i <= chunk.len() {
+        encoded.push(BASE64_ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+      } else {
+        encoded.push('=');
+      }
+    }
+  }
+  encoded
+}
+
+/// Bytes of a base64 string, `None` when it is not one.
+pub fn base64_decode(s: &str) -> Option<Vec<u8>> {
+  let s = s.trim().trim_end_matches('=');
+  let mut decoded = Vec::with_capacity(s.len() * 3 / 4);
+  let (mut n, mut bits) = (0u32, 0);
+  for c in s.bytes() {
+    n = (n << 6) | BASE64_ALPHABET.iter().position(|a| *a == c)? as u32;
+    bits += 6;
+    if bits >= 8 {
+      bits -= 8;
+      decoded.push((n >> bits) as u8);
+    }
+  }
+  Some(decoded)
+}
+
+/// Copy text to the system clipboard using the OSC 52 terminal escape sequence.
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+  let mut stdout = io::stdout();
+  write!(stdout, "\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))?;
+  stdout.flush()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_command_line() {
+    assert_eq!(command_line("task", &["1", "modify", "project:home"]), "task 1 modify project:home");
+    assert_eq!(
+      command_line("task", &["annotate", "call Bob's bank", ""]),
+      r#"task annotate "call Bob's bank" """#
+    );
+  }
+
+  #[test]
+  fn test_base64() {
+    let decoded = ["", "f", "fo", "foo", "foob", "fooba", "foobar"];
+    let encoded = ["", "Zg==", "Zm8=", "Zm9v", "Zm9vYg==", "Zm9vYmE=", "Zm9vYmFy"];
+    for (decoded, encoded) in decoded.iter().zip(encoded) {
+      assert_eq!(base64_encode(decoded.as_bytes()), encoded);
+      assert_eq!(base64_decode(encoded).as_deref(), Some(decoded.as_bytes()));
+    }
+    assert_eq!(base64_encode(&[0xfb, 0xff]), "+/8=");
+    assert_eq!(base64_decode("+/8=\n"), Some(vec![0xfb, 0xff]));
+    assert_eq!(base64_decode("Zm9v!"), None);
+  }
+}
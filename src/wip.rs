@@ -0,0 +1,199 @@
+use log::warn;
+use task_hookrs::{status::TaskStatus, task::Task};
+use tokio::sync::mpsc;
+
+use crate::{
+  event::{Event, KeyCode},
+  overrides,
+};
+
+const PREFIX: &str = "uda.taskwarrior-tui.wip-limit.";
+
+/// What a work in progress limit counts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Scope {
+  /// Started tasks
+  Active,
+  /// Started tasks of a project and its subprojects
+  Project(String),
+  /// Pending tasks with a tag
+  Tag(String),
+}
+
+/// The most tasks that should be in progress at once, e.g.
+///
+/// ```plaintext
+/// uda.taskwarrior-tui.wip-limit.active=3
+/// uda.taskwarrior-tui.wip-limit.project.work=2
+/// uda.taskwarrior-tui.wip-limit.tag.today=5
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WipLimit {
+  pub scope: Scope,
+  pub max: usize,
+}
+
+/// Limits defined in the output of `task show`.
+pub fn parse(data: &str) -> Vec<WipLimit> {
+  let mut limits = vec![];
+  for line in data.lines() {
+    let rest = match line.strip_prefix(PREFIX) {
+      Some(rest) => rest,
+      None => continue,
+    };
+    let (key, value) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let scope = match key.split_once('.') {
+      None if key == "active" => Scope::Active,
+      None if key == "confirm" => continue,
+      Some(("project", name)) => Scope::Project(name.to_string()),
+      Some(("tag", name)) => Scope::Tag(name.to_string()),
+      _ => {
+        warn!("Unknown work in progress limit {}", key);
+        continue;
+      }
+    };
+    match value.trim().parse::<usize>() {
+      Ok(max) => limits.push(WipLimit { scope, max }),
+      Err(_) => warn!("Work in progress limit {} is not a number: {}", key, value.trim()),
+    }
+  }
+  limits
+}
+
+impl WipLimit {
+  /// Name of the limit in the status bar.
+  pub fn label(&self) -> String {
+    match &self.scope {
+      Scope::Active => "active".to_string(),
+      Scope::Project(name) => format!("project:{}", name),
+      Scope::Tag(name) => format!("+{}", name),
+    }
+  }
+
+  /// Taskwarrior filter of the tasks the limit counts.
+  pub fn filter(&self) -> String {
+    match &self.scope {
+      Scope::Active => "+ACTIVE".to_string(),
+      Scope::Project(name) => format!("+ACTIVE project:{}", name),
+      Scope::Tag(name) => format!("status:pending +{}", name),
+    }
+  }
+
+  /// Starting `task` adds it to the tasks the limit counts.
+  pub fn counts_start(&self, task: &Task) -> bool {
+    if task.start().is_some() || *task.status() != TaskStatus::Pending {
+      return false;
+    }
+    match &self.scope {
+      Scope::Active => true,
+      Scope::Project(name) => task.project().is_some_and(|p| p == name || p.starts_with(&format!("{}.", name))),
+      Scope::Tag(_) => false,
+    }
+  }
+}
+
+/// Number of tasks each limit counts, counted in the background so that refreshing the report never
+/// waits for `task`.
+pub struct Counts {
+  /// Counts in the order of the limits
+  pub counts: Vec<usize>,
+  /// Bumped on each refresh, the counts of an earlier one are then dropped
+  generation: u64,
+  tx: mpsc::UnboundedSender<(u64, Vec<usize>)>,
+  rx: mpsc::UnboundedReceiver<(u64, Vec<usize>)>,
+}
+
+impl Counts {
+  pub fn new() -> Self {
+    let (tx, rx) = mpsc::unbounded_channel();
+    Self {
+      counts: vec![],
+      generation: 0,
+      tx,
+      rx,
+    }
+  }
+
+  /// Count the tasks of `limits` one after the other with `task count`, sending `Event::Tick` on
+  /// `wake` once done.
+  pub fn refresh(&mut self, limits: &[WipLimit], wake: &mpsc::UnboundedSender<Event<KeyCode>>) {
+    if limits.is_empty() {
+      return;
+    }
+    self.generation += 1;
+    let generation = self.generation;
+    let limits = limits.to_vec();
+    let tx = self.tx.clone();
+    let wake = wake.clone();
+    tokio::spawn(async move {
+      let mut counts = vec![];
+      for limit in limits {
+        let output = overrides::task_async()
+          .arg("rc.color=off")
+          .arg("rc.verbose=nothing")
+          .args(shlex::split(&limit.filter()).unwrap_or_default())
+          .arg("count")
+          .output()
+          .await;
+        counts.push(match output {
+          Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout).trim().parse().unwrap_or(0),
+          _ => {
+            warn!("Unable to count the tasks of work in progress limit {}", limit.label());
+            0
+          }
+        });
+      }
+      tx.send((generation, counts)).ok();
+      wake.send(Event::Tick).ok();
+    });
+  }
+
+  /// Take in the counts of the last refresh, returning whether they arrived.
+  pub fn poll(&mut self) -> bool {
+    let mut arrived = false;
+    while let Ok((generation, counts)) = self.rx.try_recv() {
+      if generation == self.generation {
+        self.counts = counts;
+        arrived = true;
+      }
+    }
+    arrived
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_limits() {
+    let data = "uda.taskwarrior-tui.wip-limit.active  3\nuda.taskwarrior-tui.wip-limit.project.work  2\nuda.taskwarrior-tui.wip-limit.tag.today  5\nuda.taskwarrior-tui.wip-limit.confirm  yes\n";
+    let limits = parse(data);
+    assert_eq!(limits.len(), 3);
+    assert_eq!(limits[1].filter(), "+ACTIVE project:work");
+    assert_eq!(limits[2].label(), "+today");
+
+    let data = r#"[
+      {"uuid":"6ef1e5b0-a0c0-4f4c-9c4e-3c1a4c2b1f10","description":"a","status":"pending","entry":"20240101T000000Z","project":"work.reports"},
+      {"uuid":"6ef1e5b0-a0c0-4f4c-9c4e-3c1a4c2b1f11","description":"b","status":"pending","entry":"20240101T000000Z","project":"workshop"},
+      {"uuid":"6ef1e5b0-a0c0-4f4c-9c4e-3c1a4c2b1f12","description":"c","status":"pending","entry":"20240101T000000Z","project":"work","start":"20240101T000000Z"}
+    ]"#;
+    let tasks: Vec<Task> = task_hookrs::import::import(data.as_bytes()).unwrap();
+    assert!(limits[0].counts_start(&tasks[0]));
+    assert!(limits[1].counts_start(&tasks[0]));
+    assert!(!limits[1].counts_start(&tasks[1]));
+    assert!(!limits[0].counts_start(&tasks[2]));
+    assert!(!limits[2].counts_start(&tasks[0]));
+  }
+
+  #[test]
+  fn test_counts() {
+    let mut counts = Counts::new();
+    counts.generation = 2;
+    counts.tx.send((1, vec![7])).unwrap();
+    assert!(!counts.poll());
+    counts.tx.send((2, vec![1, 3])).unwrap();
+    assert!(counts.poll());
+    assert_eq!(counts.counts, [1, 3]);
+  }
+}
@@ -0,0 +1,77 @@
+use log::warn;
+
+const PREFIX: &str = "uda.taskwarrior-tui.workspace.";
+
+/// A named set of taskrc, context, report and layout, switched to at once from the command palette.
+///
+/// ```plaintext
+/// uda.taskwarrior-tui.workspace.work.context=work
+/// uda.taskwarrior-tui.workspace.work.report=next
+/// uda.taskwarrior-tui.workspace.work.layout=triage
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Workspace {
+  pub name: String,
+  /// Taskrc file the tasks and settings are read from, e.g. a second taskrc for another role
+  pub taskrc: Option<String>,
+  /// Taskwarrior context, `none` to clear it
+  pub context: Option<String>,
+  pub report: Option<String>,
+  /// Name of a layout preset
+  pub layout: Option<String>,
+  pub filter: Option<String>,
+}
+
+/// Workspaces defined in the output of `task show`, in the order they first appear.
+pub fn parse(data: &str) -> Vec<Workspace> {
+  let mut workspaces: Vec<Workspace> = vec![];
+  for line in data.lines() {
+    let rest = match line.strip_prefix(PREFIX) {
+      Some(rest) => rest,
+      None => continue,
+    };
+    let (key, value) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let (name, setting) = match key.rsplit_once('.') {
+      Some(split) => split,
+      None => continue,
+    };
+    let value = value.trim().to_string();
+    let index = match workspaces.iter().position(|w| w.name == name) {
+      Some(i) => i,
+      None => {
+        workspaces.push(Workspace {
+          name: name.to_string(),
+          ..Workspace::default()
+        });
+        workspaces.len() - 1
+      }
+    };
+    let workspace = &mut workspaces[index];
+    match setting {
+      "taskrc" => workspace.taskrc = Some(value),
+      "context" => workspace.context = Some(value),
+      "report" => workspace.report = Some(value),
+      "layout" => workspace.layout = Some(value),
+      "filter" => workspace.filter = Some(value),
+      _ => warn!("Unknown setting {} of workspace {}", setting, name),
+    }
+  }
+  workspaces
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    let data = "uda.taskwarrior-tui.workspace.work.context  work\nuda.taskwarrior-tui.workspace.home.report  list\nuda.taskwarrior-tui.workspace.work.layout  triage\nweekstart  monday\n";
+    let workspaces = parse(data);
+    assert_eq!(workspaces.len(), 2);
+    assert_eq!(workspaces[0].name, "work");
+    assert_eq!(workspaces[0].context.as_deref(), Some("work"));
+    assert_eq!(workspaces[0].layout.as_deref(), Some("triage"));
+    assert_eq!(workspaces[0].report, None);
+    assert_eq!(workspaces[1].report.as_deref(), Some("list"));
+  }
+}
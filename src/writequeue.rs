@@ -0,0 +1,145 @@
+use std::{
+  collections::VecDeque,
+  process::Output,
+  time::{Duration, Instant},
+};
+
+use uuid::Uuid;
+
+use crate::journal::JournalKind;
+
+/// Messages of taskwarrior when another process holds the lock of the task database, e.g. a hook
+/// or a sync in another client.
+const LOCK_ERRORS: [&str; 4] = ["database is locked", "unable to lock", "cannot lock", "could not lock"];
+
+/// The command failed only because the task database was locked.
+pub fn is_lock_error(output: &Output) -> bool {
+  let text = format!("{}{}", String::from_utf8_lossy(&output.stderr), String::from_utf8_lossy(&output.stdout)).to_lowercase();
+  !output.status.success() && LOCK_ERRORS.iter().any(|e| text.contains(e))
+}
+
+/// Time to wait before the attempt after `attempts` failed ones, doubling up to five seconds.
+pub fn backoff(attempts: u32) -> Duration {
+  Duration::from_millis(100u64.saturating_mul(1 << attempts.min(6))).min(Duration::from_secs(5))
+}
+
+/// What became of a change to the tasks.
+#[derive(Debug)]
+pub enum Written {
+  /// The command ran, successfully or not
+  Ran(Output),
+  /// The task database was locked, the change waits in the queue
+  Queued,
+}
+
+/// Journal entry of a queued change, recorded once the change is made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingRecord {
+  pub kind: JournalKind,
+  pub uuids: Vec<Uuid>,
+  pub compensation: Option<Vec<String>>,
+}
+
+/// Longest time quitting waits for the queued changes before dropping them.
+pub const FLUSH_LIMIT: Duration = Duration::from_secs(10);
+
+/// A change to the tasks waiting for the lock of the task database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingWrite {
+  pub args: Vec<String>,
+  pub attempts: u32,
+  pub next: Instant,
+  pub record: Option<PendingRecord>,
+}
+
+/// Changes to the tasks that found the task database locked, retried in order so that later changes
+/// never overtake earlier ones.
+#[derive(Debug, Default)]
+pub struct WriteQueue {
+  pub pending: VecDeque<PendingWrite>,
+}
+
+impl WriteQueue {
+  pub fn push(&mut self, args: Vec<String>, attempts: u32) {
+    self.pending.push_back(PendingWrite {
+      args,
+      attempts,
+      next: Instant::now() + backoff(attempts),
+      record: None,
+    });
+  }
+
+  /// Journal the change queued last with `record` once it is made.
+  pub fn record_last(&mut self, record: PendingRecord) {
+    if let Some(write) = self.pending.back_mut() {
+      write.record = Some(record);
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.pending.is_empty()
+  }
+
+  /// The first change is due for its next attempt at `now`.
+  pub fn due(&self, now: Instant) -> Option<&PendingWrite> {
+    self.pending.front().filter(|w| w.next <= now)
+  }
+
+  /// The first change found the database locked again.
+  pub fn retry_later(&mut self) {
+    if let Some(write) = self.pending.front_mut() {
+      write.attempts += 1;
+      write.next = Instant::now() + backoff(write.attempts);
+    }
+  }
+
+  /// When the first change is tried again.
+  pub fn deadline(&self) -> Option<Instant> {
+    self.pending.front().map(|w| w.next)
+  }
+
+  /// Status bar text while changes are waiting, e.g. `2 writes waiting (retry 3)`.
+  pub fn status(&self) -> Option<String> {
+    let first = self.pending.front()?;
+    let writes = if self.pending.len() == 1 { "write" } else { "writes" };
+    Some(format!("{} {} waiting (retry {})", self.pending.len(), writes, first.attempts))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::process::ExitStatus;
+
+  use super::*;
+
+  #[test]
+  fn test_queue() {
+    assert_eq!(backoff(0), Duration::from_millis(100));
+    assert_eq!(backoff(3), Duration::from_millis(800));
+    assert_eq!(backoff(20), Duration::from_secs(5));
+
+    let output = Output {
+      status: ExitStatus::default(),
+      stdout: vec![],
+      stderr: b"Database is locked".to_vec(),
+    };
+    // a successful command is never retried
+    assert!(!is_lock_error(&output));
+
+    let mut queue = WriteQueue::default();
+    assert_eq!(queue.status(), None);
+    queue.push(vec!["1".to_string(), "done".to_string()], 1);
+    queue.push(vec!["2".to_string(), "done".to_string()], 0);
+    assert!(queue.due(Instant::now()).is_none());
+    assert!(queue.due(Instant::now() + Duration::from_secs(1)).is_some());
+    queue.retry_later();
+    assert_eq!(queue.status().as_deref(), Some("2 writes waiting (retry 2)"));
+    queue.record_last(PendingRecord {
+      kind: JournalKind::Done,
+      uuids: vec![],
+      compensation: None,
+    });
+    assert!(queue.pending[0].record.is_none());
+    assert_eq!(queue.pending[1].record.as_ref().map(|r| &r.kind), Some(&JournalKind::Done));
+  }
+}